@@ -0,0 +1,187 @@
+//! Definitions for checking the ABI compatibility of ELF files.
+
+use core::{error, fmt};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    flags::{ArmFloatAbi, MachineFlags, MipsAbi, RiscVFloatAbi},
+    header::{ElfHeader, Machine},
+    ident::{Class, Encoding},
+};
+
+/// Description of the ABI to which an ELF file conforms.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AbiDescription {
+    /// The [`Class`] of the ELF file.
+    pub class: Class,
+    /// The [`Encoding`] of the ELF file.
+    pub encoding: Encoding,
+    /// The [`Machine`] targeted by the ELF file.
+    pub machine: Machine,
+    /// The processor specific flags of the ELF file.
+    pub flags: MachineFlags,
+}
+
+impl AbiDescription {
+    /// Returns the [`AbiDescription`] of the ELF file described by `header`.
+    pub fn from_header<C: ClassParse, E: EncodingParse>(header: &ElfHeader<'_, C, E>) -> Self {
+        Self {
+            class: header.ident().class(),
+            encoding: header.ident().encoding(),
+            machine: header.machine(),
+            flags: header.machine_flags(),
+        }
+    }
+
+    /// Checks whether code conforming to this [`AbiDescription`] can be combined with code
+    /// conforming to `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`AbiIncompatibility`] found between this [`AbiDescription`] and
+    /// `other`.
+    pub fn check_compatible(&self, other: &Self) -> Result<(), AbiIncompatibility> {
+        if self.class != other.class {
+            return Err(AbiIncompatibility::Class(self.class, other.class));
+        }
+        if self.encoding != other.encoding {
+            return Err(AbiIncompatibility::Encoding(self.encoding, other.encoding));
+        }
+        if self.machine != other.machine {
+            return Err(AbiIncompatibility::Machine(self.machine, other.machine));
+        }
+
+        match (self.flags, other.flags) {
+            (MachineFlags::Arm(a), MachineFlags::Arm(b)) => {
+                if a.eabi_version() != 0
+                    && b.eabi_version() != 0
+                    && a.eabi_version() != b.eabi_version()
+                {
+                    return Err(AbiIncompatibility::ArmEabiVersion(
+                        a.eabi_version(),
+                        b.eabi_version(),
+                    ));
+                }
+
+                match (a.float_abi(), b.float_abi()) {
+                    (ArmFloatAbi::Hard, ArmFloatAbi::Soft)
+                    | (ArmFloatAbi::Soft, ArmFloatAbi::Hard)
+                    | (ArmFloatAbi::Conflicting, _)
+                    | (_, ArmFloatAbi::Conflicting) => {
+                        return Err(AbiIncompatibility::ArmFloatAbi(
+                            a.float_abi(),
+                            b.float_abi(),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+            (MachineFlags::RiscV(a), MachineFlags::RiscV(b)) => {
+                if a.float_abi() != b.float_abi() {
+                    return Err(AbiIncompatibility::RiscVFloatAbi(
+                        a.float_abi(),
+                        b.float_abi(),
+                    ));
+                }
+                if a.rve() != b.rve() {
+                    return Err(AbiIncompatibility::RiscVRve);
+                }
+            }
+            (MachineFlags::Mips(a), MachineFlags::Mips(b)) => {
+                if a.abi() != b.abi() {
+                    return Err(AbiIncompatibility::MipsAbi(a.abi(), b.abi()));
+                }
+                if a.is_n32() != b.is_n32() {
+                    return Err(AbiIncompatibility::MipsN32);
+                }
+                if a.is_fp64() != b.is_fp64() {
+                    return Err(AbiIncompatibility::MipsFp64);
+                }
+                if a.is_nan2008() != b.is_nan2008() {
+                    return Err(AbiIncompatibility::MipsNan2008);
+                }
+            }
+            (MachineFlags::PowerPc64(a), MachineFlags::PowerPc64(b))
+                if a.abi_version() != 0
+                    && b.abi_version() != 0
+                    && a.abi_version() != b.abi_version() =>
+            {
+                return Err(AbiIncompatibility::PowerPc64AbiVersion(
+                    a.abi_version(),
+                    b.abi_version(),
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> ElfHeader<'_, C, E> {
+    /// Checks whether the ELF file described by this [`ElfHeader`] can be combined with the ELF
+    /// file described by `other`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`AbiIncompatibility`] found between the two ELF files.
+    pub fn check_abi_compatible<C2: ClassParse, E2: EncodingParse>(
+        &self,
+        other: &ElfHeader<'_, C2, E2>,
+    ) -> Result<(), AbiIncompatibility> {
+        AbiDescription::from_header(self).check_compatible(&AbiDescription::from_header(other))
+    }
+}
+
+/// Various ABI incompatibilities that can exist between two ELF files.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AbiIncompatibility {
+    /// The [`Class`]es of the ELF files differ.
+    Class(Class, Class),
+    /// The [`Encoding`]s of the ELF files differ.
+    Encoding(Encoding, Encoding),
+    /// The [`Machine`]s of the ELF files differ.
+    Machine(Machine, Machine),
+    /// The ARM EABI versions of the ELF files differ.
+    ArmEabiVersion(u8, u8),
+    /// The ARM floating point calling conventions of the ELF files conflict.
+    ArmFloatAbi(ArmFloatAbi, ArmFloatAbi),
+    /// The RISC-V floating point calling conventions of the ELF files differ.
+    RiscVFloatAbi(RiscVFloatAbi, RiscVFloatAbi),
+    /// Only one of the ELF files targets the RV32E base integer instruction set.
+    RiscVRve,
+    /// The MIPS ABIs of the ELF files differ.
+    MipsAbi(MipsAbi, MipsAbi),
+    /// Only one of the ELF files uses the MIPS N32 ABI.
+    MipsN32,
+    /// Only one of the ELF files uses 64-bit MIPS floating point registers.
+    MipsFp64,
+    /// Only one of the ELF files uses the IEEE 754-2008 NaN encoding.
+    MipsNan2008,
+    /// The 64-bit PowerPC ABI versions of the ELF files differ.
+    PowerPc64AbiVersion(u8, u8),
+}
+
+impl fmt::Display for AbiIncompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Class(a, b) => write!(f, "class mismatch: {a:?} and {b:?}"),
+            Self::Encoding(a, b) => write!(f, "encoding mismatch: {a:?} and {b:?}"),
+            Self::Machine(a, b) => write!(f, "machine mismatch: {a:?} and {b:?}"),
+            Self::ArmEabiVersion(a, b) => write!(f, "ARM EABI version mismatch: {a} and {b}"),
+            Self::ArmFloatAbi(a, b) => write!(f, "ARM float ABI mismatch: {a:?} and {b:?}"),
+            Self::RiscVFloatAbi(a, b) => write!(f, "RISC-V float ABI mismatch: {a:?} and {b:?}"),
+            Self::RiscVRve => write!(f, "RISC-V RVE mismatch"),
+            Self::MipsAbi(a, b) => write!(f, "MIPS ABI mismatch: {a:?} and {b:?}"),
+            Self::MipsN32 => write!(f, "MIPS N32 ABI mismatch"),
+            Self::MipsFp64 => write!(f, "MIPS floating point register width mismatch"),
+            Self::MipsNan2008 => write!(f, "MIPS NaN encoding mismatch"),
+            Self::PowerPc64AbiVersion(a, b) => {
+                write!(f, "PowerPC64 ABI version mismatch: {a} and {b}")
+            }
+        }
+    }
+}
+
+impl error::Error for AbiIncompatibility {}
@@ -0,0 +1,240 @@
+//! Definitions for comparing the symbols exported by a shared object against a recorded
+//! baseline.
+//!
+//! The ABI surface of a shared object is the set of symbols it exports, each with the version
+//! it is bound to by the symbol version table. A symbol may be exported under several versions,
+//! such as `memcpy@GLIBC_2.2.5` and `memcpy@@GLIBC_2.14`, in which case each version is a
+//! separate entry of the surface.
+//!
+//! The comparison does not allocate, and compares every entry of the surface against every
+//! entry of the baseline, so its cost grows with the product of their sizes.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    symbol::{self, SymbolBinding, SymbolVisibility},
+    versym::{Versym, VersymTable},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`AbiSurface`] over the symbols exported by the
+    /// [`ElfFile::dynamic_symbol_table()`] of this [`ElfFile`], along with their versions.
+    pub fn abi_surface(&self) -> Option<AbiSurface<'slice, C, E>> {
+        let symbols = self.dynamic_symbol_table()?;
+
+        Some(AbiSurface {
+            file: *self,
+            symbols: symbols.into_iter(),
+            next: 0,
+            versyms: self.versym_table(symbols.count()),
+        })
+    }
+
+    /// Returns an [`AbiChanges`] over the differences between the [`ElfFile::abi_surface()`] of
+    /// this [`ElfFile`] and `baseline`, a previously recorded surface.
+    ///
+    /// Entries exported under the same name and version as an entry of `baseline` are unchanged.
+    /// An entry whose name appears in `baseline` only with versions that are no longer exported
+    /// is reported as an [`AbiChange::VersionChanged`]; other entries are reported as
+    /// [`AbiChange::Added`], followed by the entries of `baseline` that are no longer exported
+    /// under any version, reported as [`AbiChange::Removed`].
+    pub fn compare_abi<'baseline>(
+        &self,
+        baseline: &'baseline [AbiEntry<'baseline>],
+    ) -> Option<AbiChanges<'slice, 'baseline, C, E>> {
+        let surface = self.abi_surface()?;
+
+        Some(AbiChanges {
+            surface,
+            entries: surface,
+            baseline,
+            next_baseline: 0,
+        })
+    }
+}
+
+/// An entry of an ABI surface: the name of an exported symbol and its version.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AbiEntry<'slice> {
+    /// The name of the symbol.
+    pub name: &'slice [u8],
+    /// The name of the version the symbol is bound to, or `None` if it is unversioned or bound to
+    /// the base version.
+    pub version: Option<&'slice [u8]>,
+}
+
+/// An [`Iterator`] over the [`AbiEntry`]s of the symbols exported by an [`ElfFile`].
+///
+/// A symbol is exported if it is defined, named, not [`SymbolBinding::LOCAL`] and of
+/// [`SymbolVisibility::DEFAULT`] or [`SymbolVisibility::PROTECTED`] visibility. The symbols the
+/// linker emits to name each version are skipped.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct AbiSurface<'slice, C, E> {
+    /// The [`ElfFile`] whose symbols are exported.
+    file: ElfFile<'slice, C, E>,
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// The index of the next symbol.
+    next: usize,
+    /// The symbol version table, if present.
+    versyms: Option<VersymTable<'slice, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> AbiSurface<'slice, C, E> {
+    /// Returns the next [`AbiEntry`], skipping those not named `name` if it is given.
+    ///
+    /// Filtering by name before looking up versions avoids searching the version definition
+    /// table for every symbol.
+    fn next_named(&mut self, name: Option<&[u8]>) -> Option<AbiEntry<'slice>> {
+        for symbol in self.symbols.by_ref() {
+            let index = self.next;
+            self.next += 1;
+
+            let visibility = symbol.visibility();
+            if !symbol.is_defined()
+                || symbol.binding() == SymbolBinding::LOCAL
+                || (visibility != SymbolVisibility::DEFAULT
+                    && visibility != SymbolVisibility::PROTECTED)
+            {
+                continue;
+            }
+            let Some(symbol_name) = symbol.name().filter(|symbol_name| {
+                !symbol_name.is_empty() && name.is_none_or(|name| name == *symbol_name)
+            }) else {
+                continue;
+            };
+
+            let versym = self
+                .versyms
+                .and_then(|versyms| versyms.get(index))
+                .map_or(Versym::GLOBAL.0, Versym::index);
+            let version = (versym > Versym::GLOBAL.0)
+                .then(|| self.file.defined_version(versym))
+                .flatten();
+            if version == Some(symbol_name) {
+                continue;
+            }
+
+            return Some(AbiEntry {
+                name: symbol_name,
+                version,
+            });
+        }
+
+        None
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for AbiSurface<'slice, C, E> {
+    type Item = AbiEntry<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_named(None)
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for AbiSurface<'_, C, E> {}
+
+/// A difference between the ABI surface of an [`ElfFile`] and a baseline.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum AbiChange<'slice, 'baseline> {
+    /// The entry is exported, but not present in the baseline.
+    Added(AbiEntry<'slice>),
+    /// The entry is present in the baseline, but its symbol is no longer exported.
+    Removed(AbiEntry<'baseline>),
+    /// The symbol is exported under a version that is not present in the baseline, while a
+    /// version present in the baseline is no longer exported.
+    VersionChanged {
+        /// The name of the symbol.
+        name: &'slice [u8],
+        /// The version present in the baseline.
+        baseline: Option<&'baseline [u8]>,
+        /// The version now exported.
+        current: Option<&'slice [u8]>,
+    },
+}
+
+/// An [`Iterator`] over the [`AbiChange`]s between the ABI surface of an [`ElfFile`] and a
+/// baseline.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct AbiChanges<'slice, 'baseline, C, E> {
+    /// The full ABI surface, searched for the entries of the baseline.
+    surface: AbiSurface<'slice, C, E>,
+    /// The entries of the ABI surface that have not yet been visited.
+    entries: AbiSurface<'slice, C, E>,
+    /// The baseline.
+    baseline: &'baseline [AbiEntry<'baseline>],
+    /// The index of the next entry of the baseline.
+    next_baseline: usize,
+}
+
+impl<'slice, 'baseline, C: ClassParse, E: EncodingParse> AbiChanges<'slice, 'baseline, C, E> {
+    /// Returns `true` if the ABI surface contains an entry named `name` with version `version`.
+    fn exports(&self, name: &[u8], version: Option<&[u8]>) -> bool {
+        let mut surface = self.surface;
+        while let Some(entry) = surface.next_named(Some(name)) {
+            if entry.version == version {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns `true` if the baseline contains an entry named `name` with version `version`.
+    fn recorded(&self, name: &[u8], version: Option<&[u8]>) -> bool {
+        self.baseline
+            .iter()
+            .any(|entry| entry.name == name && entry.version == version)
+    }
+}
+
+impl<'slice, 'baseline, C: ClassParse, E: EncodingParse> Iterator
+    for AbiChanges<'slice, 'baseline, C, E>
+{
+    type Item = AbiChange<'slice, 'baseline>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.entries.next() {
+            if self.recorded(entry.name, entry.version) {
+                continue;
+            }
+
+            let replaced = self.baseline.iter().find(|recorded| {
+                recorded.name == entry.name && !self.exports(recorded.name, recorded.version)
+            });
+            return Some(match replaced {
+                Some(recorded) => AbiChange::VersionChanged {
+                    name: entry.name,
+                    baseline: recorded.version,
+                    current: entry.version,
+                },
+                None => AbiChange::Added(entry),
+            });
+        }
+
+        while let Some(&recorded) = self.baseline.get(self.next_baseline) {
+            self.next_baseline += 1;
+            if self.exports(recorded.name, recorded.version) {
+                continue;
+            }
+
+            // A version of a symbol that is still exported was reported as changed above.
+            let mut surface = self.surface;
+            let mut replaced = false;
+            while let Some(entry) = surface.next_named(Some(recorded.name)) {
+                replaced |= !self.recorded(entry.name, entry.version);
+            }
+            if !replaced {
+                return Some(AbiChange::Removed(recorded));
+            }
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for AbiChanges<'_, '_, C, E> {}
@@ -0,0 +1,122 @@
+//! Definitions for checking the alignment of [`SegmentType::LOAD`] segments.
+//!
+//! [`ElfFile::new()`][new] rejects a file containing a misaligned segment with the first
+//! [`ValidateProgramHeaderSpecError`][e] encountered. The findings reported here cover every
+//! [`SegmentType::LOAD`] segment of a [`ProgramHeaderTable`] and carry the offending values, which
+//! helps diagnose files that fail to parse.
+//!
+//! [new]: crate::ElfFile::new
+//! [e]: crate::program_header::ValidateProgramHeaderSpecError
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{ProgramHeaderTable, SegmentType},
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ProgramHeaderTable<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`LoadAlignmentFinding`]s of the [`SegmentType::LOAD`]
+    /// segments in this [`ProgramHeaderTable`].
+    ///
+    /// A segment is reported if its alignment is not zero or a power of two, or if its virtual
+    /// address and file offset are not congruent modulo its alignment.
+    pub fn load_alignment_findings(self) -> LoadAlignmentFindings<'slice, C, E> {
+        LoadAlignmentFindings {
+            table: self,
+            next: 0,
+        }
+    }
+}
+
+/// The alignment problem of a [`SegmentType::LOAD`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoadAlignmentIssue {
+    /// The alignment is neither zero nor a power of two.
+    NotPowerOfTwo,
+    /// The virtual address and file offset are not congruent modulo the alignment.
+    Incongruent,
+}
+
+/// A [`SegmentType::LOAD`] segment whose alignment is invalid.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LoadAlignmentFinding {
+    /// The index of the program header describing the segment.
+    pub index: u16,
+    /// The [`LoadAlignmentIssue`] of the segment.
+    pub issue: LoadAlignmentIssue,
+    /// The virtual address of the segment.
+    pub virtual_address: u64,
+    /// The file offset of the segment.
+    pub file_offset: u64,
+    /// The alignment of the segment.
+    pub alignment: u64,
+}
+
+impl fmt::Display for LoadAlignmentFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.issue {
+            LoadAlignmentIssue::NotPowerOfTwo => write!(
+                f,
+                "load segment {} has alignment {:#x}, which is not a power of two",
+                self.index, self.alignment
+            ),
+            LoadAlignmentIssue::Incongruent => write!(
+                f,
+                "load segment {} has virtual address {:#x} and file offset {:#x}, which are not \
+                 congruent modulo {:#x}",
+                self.index, self.virtual_address, self.file_offset, self.alignment
+            ),
+        }
+    }
+}
+
+/// An [`Iterator`] over the [`LoadAlignmentFinding`]s of a [`ProgramHeaderTable`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LoadAlignmentFindings<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ProgramHeaderTable`] being checked.
+    table: ProgramHeaderTable<'slice, C, E>,
+    /// The index of the next [`ProgramHeader`][ph] to check.
+    ///
+    /// [ph]: crate::program_header::ProgramHeader
+    next: u16,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for LoadAlignmentFindings<'_, C, E> {
+    type Item = LoadAlignmentFinding;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(program_header) = self.table.get(self.next) {
+            let index = self.next;
+            self.next += 1;
+            if program_header.segment_type() != SegmentType::LOAD {
+                continue;
+            }
+
+            let virtual_address: u64 = program_header.virtual_address().into();
+            let file_offset: u64 = program_header.file_offset().into();
+            let alignment: u64 = program_header.alignment().into();
+
+            let issue = if alignment != 0 && !alignment.is_power_of_two() {
+                LoadAlignmentIssue::NotPowerOfTwo
+            } else if alignment > 1 && virtual_address % alignment != file_offset % alignment {
+                LoadAlignmentIssue::Incongruent
+            } else {
+                continue;
+            };
+
+            return Some(LoadAlignmentFinding {
+                index,
+                issue,
+                virtual_address,
+                file_offset,
+                alignment,
+            });
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for LoadAlignmentFindings<'_, C, E> {}
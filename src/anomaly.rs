@@ -0,0 +1,316 @@
+//! Definitions for detecting structural anomalies commonly produced by packers and obfuscators.
+//!
+//! None of the anomalies reported here prevent an [`ElfFile`] from being parsed, but they rarely
+//! occur in ELF files produced by standard toolchains.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{ProgramHeader, SegmentType},
+    ElfFile,
+};
+
+/// Alignments of [`SegmentType::LOAD`] segments greater than this value are reported as
+/// [`Anomaly::LargeAlignment`].
+pub const LARGE_ALIGNMENT: u64 = 0x100_0000;
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`Anomaly`]s present in this [`ElfFile`].
+    pub fn anomalies(&self) -> Anomalies<'slice, C, E> {
+        Anomalies {
+            file: *self,
+            stage: Stage::SectionHeaderCount,
+            index: 0,
+            other: 0,
+        }
+    }
+}
+
+/// A structural anomaly in an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Anomaly {
+    /// The section header table offset is zero, but the section header count is not.
+    SectionHeaderCountWithoutOffset,
+    /// The section header table extends past the end of the file.
+    SectionHeaderTableOutOfBounds,
+    /// The entry point is not contained in any [`SegmentType::LOAD`] segment.
+    EntryOutsideLoadSegments,
+    /// The file data of a segment extends past the end of the file.
+    SegmentOutOfBounds {
+        /// The index of the [`ProgramHeader`] describing the segment.
+        index: u16,
+    },
+    /// The file data of a segment other than [`SegmentType::LOAD`] or [`SegmentType::PHDR`]
+    /// overlaps the ELF header or the program header table.
+    SegmentOverlapsHeaders {
+        /// The index of the [`ProgramHeader`] describing the segment.
+        index: u16,
+    },
+    /// A [`SegmentType::LOAD`] segment has a lower virtual address than the preceding
+    /// [`SegmentType::LOAD`] segment.
+    MisorderedLoadSegment {
+        /// The index of the [`ProgramHeader`] describing the segment.
+        index: u16,
+    },
+    /// A [`SegmentType::LOAD`] segment has an alignment greater than [`LARGE_ALIGNMENT`].
+    LargeAlignment {
+        /// The index of the [`ProgramHeader`] describing the segment.
+        index: u16,
+        /// The alignment of the segment.
+        alignment: u64,
+    },
+    /// Two [`SegmentType::LOAD`] segments occupy overlapping virtual address ranges.
+    OverlappingLoadSegments {
+        /// The index of the [`ProgramHeader`] describing the first segment.
+        first: u16,
+        /// The index of the [`ProgramHeader`] describing the second segment.
+        second: u16,
+    },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SectionHeaderCountWithoutOffset => {
+                write!(f, "section header count is nonzero but offset is zero")
+            }
+            Self::SectionHeaderTableOutOfBounds => {
+                write!(f, "section header table located out of bounds")
+            }
+            Self::EntryOutsideLoadSegments => {
+                write!(f, "entry point is outside of all load segments")
+            }
+            Self::SegmentOutOfBounds { index } => {
+                write!(f, "segment {index} located out of bounds")
+            }
+            Self::SegmentOverlapsHeaders { index } => {
+                write!(f, "segment {index} overlaps the ELF or program headers")
+            }
+            Self::MisorderedLoadSegment { index } => {
+                write!(f, "load segment {index} is not in ascending address order")
+            }
+            Self::LargeAlignment { index, alignment } => {
+                write!(f, "load segment {index} has large alignment {alignment:#x}")
+            }
+            Self::OverlappingLoadSegments { first, second } => {
+                write!(f, "load segments {first} and {second} overlap")
+            }
+        }
+    }
+}
+
+/// The check an [`Anomalies`] iterator performs next.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Stage {
+    /// Checking for [`Anomaly::SectionHeaderCountWithoutOffset`].
+    SectionHeaderCount,
+    /// Checking for [`Anomaly::SectionHeaderTableOutOfBounds`].
+    SectionHeaderBounds,
+    /// Checking for [`Anomaly::EntryOutsideLoadSegments`].
+    Entry,
+    /// Checking each segment individually.
+    Segments,
+    /// Checking each pair of segments.
+    Pairs,
+    /// All checks have been performed.
+    Done,
+}
+
+/// An [`Iterator`] over the [`Anomaly`]s present in an [`ElfFile`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Anomalies<'slice, C, E> {
+    /// The [`ElfFile`] being checked.
+    file: ElfFile<'slice, C, E>,
+    /// The check to perform next.
+    stage: Stage,
+    /// The index of the [`ProgramHeader`] being checked.
+    index: u16,
+    /// The index of the second [`ProgramHeader`] or per-segment check being performed.
+    other: u16,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Anomalies<'slice, C, E> {
+    /// Returns the [`Anomaly`] produced by the per-segment `check` of `program_header`, if any.
+    fn check_segment(
+        &self,
+        index: u16,
+        program_header: ProgramHeader<'slice, C, E>,
+        check: u16,
+    ) -> Option<Anomaly> {
+        let header = self.file.header();
+        let segment_type = program_header.segment_type();
+        let file_offset: u64 = program_header.file_offset().into();
+        let file_size: u64 = program_header.file_size().into();
+
+        match check {
+            0 => file_offset
+                .checked_add(file_size)
+                .is_none_or(|end| end > self.file.bytes.len() as u64)
+                .then_some(Anomaly::SegmentOutOfBounds { index }),
+            1 => {
+                if file_size == 0
+                    || segment_type == SegmentType::LOAD
+                    || segment_type == SegmentType::PHDR
+                {
+                    return None;
+                }
+
+                let table_start: u64 = header.program_header_offset().into();
+                let table_end = table_start.saturating_add(
+                    u64::from(header.program_header_count())
+                        * u64::from(header.program_header_size()),
+                );
+                let end = file_offset.saturating_add(file_size);
+
+                let overlaps_header = file_offset < u64::from(header.header_size());
+                let overlaps_table = file_offset < table_end && table_start < end;
+                (overlaps_header || overlaps_table)
+                    .then_some(Anomaly::SegmentOverlapsHeaders { index })
+            }
+            2 => {
+                if segment_type != SegmentType::LOAD {
+                    return None;
+                }
+
+                let table = self.file.program_header_table()?;
+                let previous = (0..index)
+                    .rev()
+                    .filter_map(|index| table.get(index))
+                    .find(|program_header| program_header.segment_type() == SegmentType::LOAD)?;
+                let address: u64 = program_header.virtual_address().into();
+
+                (address < previous.virtual_address().into())
+                    .then_some(Anomaly::MisorderedLoadSegment { index })
+            }
+            3 => {
+                let alignment: u64 = program_header.alignment().into();
+
+                (segment_type == SegmentType::LOAD && alignment > LARGE_ALIGNMENT)
+                    .then_some(Anomaly::LargeAlignment { index, alignment })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The number of per-segment checks performed by [`Anomalies::check_segment()`].
+const SEGMENT_CHECKS: u16 = 4;
+
+/// Returns the start and end virtual addresses of the [`SegmentType::LOAD`] segment described by
+/// `program_header`.
+fn load_range<C: ClassParse, E: EncodingParse>(
+    program_header: ProgramHeader<'_, C, E>,
+) -> Option<(u64, u64)> {
+    if program_header.segment_type() != SegmentType::LOAD {
+        return None;
+    }
+
+    let start: u64 = program_header.virtual_address().into();
+    let end = start.saturating_add(program_header.memory_size().into());
+
+    Some((start, end))
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for Anomalies<'_, C, E> {
+    type Item = Anomaly;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let header = self.file.header();
+        let count = header.program_header_count();
+
+        loop {
+            match self.stage {
+                Stage::SectionHeaderCount => {
+                    self.stage = Stage::SectionHeaderBounds;
+
+                    let offset: u64 = header.section_header_offset().into();
+                    if offset == 0 && header.section_header_count() != 0 {
+                        return Some(Anomaly::SectionHeaderCountWithoutOffset);
+                    }
+                }
+                Stage::SectionHeaderBounds => {
+                    self.stage = Stage::Entry;
+
+                    let offset: u64 = header.section_header_offset().into();
+                    let size = u64::from(header.section_header_count())
+                        * u64::from(header.section_header_size());
+                    if offset != 0
+                        && offset
+                            .checked_add(size)
+                            .is_none_or(|end| end > self.file.bytes.len() as u64)
+                    {
+                        return Some(Anomaly::SectionHeaderTableOutOfBounds);
+                    }
+                }
+                Stage::Entry => {
+                    self.stage = Stage::Segments;
+
+                    let entry: u64 = header.entry().into();
+                    if entry != 0 && self.file.entry_segment().is_none() {
+                        return Some(Anomaly::EntryOutsideLoadSegments);
+                    }
+                }
+                Stage::Segments => {
+                    if self.index >= count {
+                        self.stage = Stage::Pairs;
+                        self.index = 0;
+                        self.other = 1;
+                        continue;
+                    }
+
+                    let index = self.index;
+                    let check = self.other;
+                    self.other += 1;
+                    if self.other >= SEGMENT_CHECKS {
+                        self.index += 1;
+                        self.other = 0;
+                    }
+
+                    let program_header = self.file.program_header_table()?.get(index)?;
+                    if let Some(anomaly) = self.check_segment(index, program_header, check) {
+                        return Some(anomaly);
+                    }
+                }
+                Stage::Pairs => {
+                    if self.other >= count {
+                        self.index += 1;
+                        self.other = self.index + 1;
+                    }
+                    if self.index >= count {
+                        self.stage = Stage::Done;
+                        continue;
+                    }
+
+                    let (first, second) = (self.index, self.other);
+                    self.other += 1;
+
+                    let table = self.file.program_header_table()?;
+                    let Some((first_start, first_end)) = table.get(first).and_then(load_range)
+                    else {
+                        self.other = count;
+                        continue;
+                    };
+                    let Some((second_start, second_end)) = table.get(second).and_then(load_range)
+                    else {
+                        continue;
+                    };
+
+                    if first_start < second_end && second_start < first_end {
+                        return Some(Anomaly::OverlappingLoadSegments { first, second });
+                    }
+                }
+                Stage::Done => return None,
+            }
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for Anomalies<'_, C, E> {}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for Anomalies<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
@@ -0,0 +1,534 @@
+//! Definitions for parsing `ar` archives, such as static libraries.
+//!
+//! Both the GNU/System V and BSD variants of the format are supported, including the extended
+//! filename table, the System V symbol index, the BSD `__.SYMDEF` ranlib symbol index, and thin
+//! archives. The data of each member can be passed to [`ElfFile::new()`][new] to parse the ELF
+//! files contained in the archive.
+//!
+//! [new]: crate::ElfFile::new
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    instrument,
+    search::{find_nul, until_nul},
+};
+
+/// The magic bytes at the start of an archive.
+pub const ARCHIVE_MAGIC: &[u8; 8] = b"!<arch>\n";
+/// The magic bytes at the start of a thin archive.
+pub const THIN_ARCHIVE_MAGIC: &[u8; 8] = b"!<thin>\n";
+
+/// The size of the header of an archive member.
+const HEADER_SIZE: usize = 60;
+/// The magic bytes at the end of the header of an archive member.
+const HEADER_MAGIC: &[u8; 2] = b"`\n";
+
+/// An `ar` archive.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Archive<'slice> {
+    /// The underlying bytes of the archive.
+    bytes: &'slice [u8],
+    /// The data of the extended filename table, if present.
+    names: Option<&'slice [u8]>,
+    /// The data and format of the symbol index, if present.
+    symbols: Option<(&'slice [u8], SymbolIndexFormat)>,
+    /// Whether this [`Archive`] is a thin archive.
+    thin: bool,
+}
+
+impl<'slice> Archive<'slice> {
+    /// Creates a new [`Archive`] from the given `slice`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseArchiveError::InvalidMagic`]: Returned if `slice` does not start with
+    ///   [`ARCHIVE_MAGIC`] or [`THIN_ARCHIVE_MAGIC`].
+    /// - [`ParseArchiveError::InvalidMemberHeader`]: Returned if the header of a special member
+    ///   preceding the first regular member is malformed.
+    pub fn new(slice: &'slice [u8]) -> Result<Self, ParseArchiveError> {
+        let thin = if slice.starts_with(ARCHIVE_MAGIC) {
+            false
+        } else if slice.starts_with(THIN_ARCHIVE_MAGIC) {
+            true
+        } else {
+            return Err(ParseArchiveError::InvalidMagic);
+        };
+
+        let mut archive = Self {
+            bytes: slice,
+            names: None,
+            symbols: None,
+            thin,
+        };
+
+        let mut offset = ARCHIVE_MAGIC.len();
+        while offset < slice.len() {
+            let raw = RawMember::parse(slice, offset, thin)?;
+            match raw.name {
+                b"/" => archive.symbols = Some((raw.data, SymbolIndexFormat::SysV32)),
+                b"/SYM64/" => archive.symbols = Some((raw.data, SymbolIndexFormat::SysV64)),
+                b"__.SYMDEF" | b"__.SYMDEF SORTED" => {
+                    archive.symbols = Some((raw.data, SymbolIndexFormat::Ranlib32));
+                }
+                b"__.SYMDEF_64" | b"__.SYMDEF_64 SORTED" => {
+                    archive.symbols = Some((raw.data, SymbolIndexFormat::Ranlib64));
+                }
+                b"//" => archive.names = Some(raw.data),
+                _ if raw.is_special() => {}
+                _ => break,
+            }
+
+            offset = raw.next;
+        }
+        instrument::debug!(
+            "parsed archive header: first member at {offset:#x}, symbol index {}, thin {thin}",
+            archive.symbols.is_some(),
+        );
+
+        Ok(archive)
+    }
+
+    /// Returns `true` if this [`Archive`] is a thin archive.
+    ///
+    /// The members of a thin archive are not stored in the archive. Their names are paths,
+    /// relative to the archive, of the files holding their data.
+    pub fn is_thin(&self) -> bool {
+        self.thin
+    }
+
+    /// Returns an [`Iterator`] over the regular [`ArchiveMember`]s of this [`Archive`].
+    ///
+    /// Special members, such as the symbol index and the extended filename table, are skipped.
+    pub fn members(&self) -> Members<'slice> {
+        Members {
+            archive: *self,
+            offset: ARCHIVE_MAGIC.len(),
+        }
+    }
+
+    /// Returns the [`ArchiveMember`] whose header starts at `offset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseArchiveError::InvalidMemberHeader`] if no valid member header is located
+    /// at `offset`.
+    pub fn member_at(&self, offset: usize) -> Result<ArchiveMember<'slice>, ParseArchiveError> {
+        let raw = RawMember::parse(self.bytes, offset, self.thin)?;
+        self.resolve(raw)
+    }
+
+    /// Returns an [`Iterator`] over the entries of the symbol index of this [`Archive`].
+    ///
+    /// Returns `None` if this [`Archive`] has no symbol index or it is malformed.
+    pub fn symbol_index(&self) -> Option<SymbolIndex<'slice>> {
+        let (data, format) = self.symbols?;
+        let width = format.width();
+
+        let (entries, names, big_endian) = match format {
+            SymbolIndexFormat::SysV32 | SymbolIndexFormat::SysV64 => {
+                let count = read_uint(data.get(..width)?, true);
+                let size = usize::try_from(count).ok()?.checked_mul(width)?;
+                let entries = data.get(width..)?.get(..size)?;
+
+                (entries, &data[width + size..], true)
+            }
+            SymbolIndexFormat::Ranlib32 | SymbolIndexFormat::Ranlib64 => {
+                // The byte order of a ranlib symbol index is that of the archived objects, so
+                // pick the one under which the index fits in the member.
+                let big_endian = !ranlib_fits(data, width, false);
+                let size = usize::try_from(read_uint(data.get(..width)?, big_endian)).ok()?;
+                let entries = data.get(width..)?.get(..size)?;
+                let strings = &data[width + size..];
+                let strings_size =
+                    usize::try_from(read_uint(strings.get(..width)?, big_endian)).ok()?;
+                let strings = strings.get(width..)?.get(..strings_size)?;
+
+                (entries, strings, big_endian)
+            }
+        };
+
+        Some(SymbolIndex {
+            entries,
+            names,
+            format,
+            big_endian,
+        })
+    }
+
+    /// Returns the [`ArchiveMember`] that defines the symbol `name`, according to the symbol
+    /// index of this [`Archive`].
+    ///
+    /// Returns `None` if this [`Archive`] has no symbol index or `name` is not in it.
+    pub fn find_symbol(
+        &self,
+        name: &[u8],
+    ) -> Option<Result<ArchiveMember<'slice>, ParseArchiveError>> {
+        let entry = self.symbol_index()?.find(|entry| entry.name == name)?;
+        let offset = usize::try_from(entry.member_offset).ok()?;
+
+        Some(self.member_at(offset))
+    }
+
+    /// Converts a [`RawMember`] into an [`ArchiveMember`], resolving its name.
+    fn resolve(&self, raw: RawMember<'slice>) -> Result<ArchiveMember<'slice>, ParseArchiveError> {
+        let mut name = raw.name;
+
+        if let Some(offset) = name.strip_prefix(b"/").filter(|offset| !offset.is_empty()) {
+            let offset = parse_decimal(offset)
+                .and_then(|offset| usize::try_from(offset).ok())
+                .ok_or(ParseArchiveError::InvalidMemberName)?;
+            let names = self
+                .names
+                .and_then(|names| names.get(offset..))
+                .ok_or(ParseArchiveError::InvalidMemberName)?;
+            let length = names
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .unwrap_or(names.len());
+            name = &names[..length];
+            name = name.strip_suffix(b"/").unwrap_or(name);
+        } else {
+            name = name.strip_suffix(b"/").unwrap_or(name);
+        }
+
+        Ok(ArchiveMember {
+            name,
+            header_offset: raw.offset,
+            modification_time: raw.modification_time,
+            user_id: raw.user_id,
+            group_id: raw.group_id,
+            mode: raw.mode,
+            size: raw.size,
+            data: raw.data,
+        })
+    }
+}
+
+impl fmt::Debug for Archive<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.members()).finish()
+    }
+}
+
+/// A regular member of an [`Archive`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ArchiveMember<'slice> {
+    /// The name of this [`ArchiveMember`].
+    pub name: &'slice [u8],
+    /// The offset of the header of this [`ArchiveMember`] from the start of the [`Archive`].
+    pub header_offset: usize,
+    /// The modification time of this [`ArchiveMember`], in seconds since the Unix epoch.
+    pub modification_time: u64,
+    /// The user ID of the owner of this [`ArchiveMember`].
+    pub user_id: u64,
+    /// The group ID of the owner of this [`ArchiveMember`].
+    pub group_id: u64,
+    /// The file mode of this [`ArchiveMember`].
+    pub mode: u64,
+    /// The size of this [`ArchiveMember`] in bytes.
+    pub size: u64,
+    /// The data of this [`ArchiveMember`].
+    ///
+    /// This is empty for the members of a thin archive, whose data is stored in the file named
+    /// by [`ArchiveMember::name`].
+    pub data: &'slice [u8],
+}
+
+/// The raw contents of the header and data of an archive member.
+#[derive(Clone, Copy, Debug)]
+struct RawMember<'slice> {
+    /// The offset of the header of the member.
+    offset: usize,
+    /// The raw name of the member, without trailing padding.
+    name: &'slice [u8],
+    /// The modification time of the member.
+    modification_time: u64,
+    /// The user ID of the owner of the member.
+    user_id: u64,
+    /// The group ID of the owner of the member.
+    group_id: u64,
+    /// The file mode of the member.
+    mode: u64,
+    /// The size of the member.
+    size: u64,
+    /// The data of the member.
+    data: &'slice [u8],
+    /// The offset of the header of the next member.
+    next: usize,
+}
+
+impl<'slice> RawMember<'slice> {
+    /// Parses the archive member whose header starts at `offset` in `bytes`.
+    ///
+    /// If `thin` is `true`, the data of regular members is not stored in `bytes`.
+    fn parse(bytes: &'slice [u8], offset: usize, thin: bool) -> Result<Self, ParseArchiveError> {
+        let header = offset
+            .checked_add(HEADER_SIZE)
+            .and_then(|end| bytes.get(offset..end))
+            .ok_or(ParseArchiveError::InvalidMemberHeader)?;
+        if &header[58..60] != HEADER_MAGIC {
+            return Err(ParseArchiveError::InvalidMemberHeader);
+        }
+
+        let field = |start: usize, end: usize| {
+            let field = &header[start..end];
+            let length = field
+                .iter()
+                .rposition(|&byte| byte != b' ')
+                .map_or(0, |i| i + 1);
+            &field[..length]
+        };
+        let number = |start: usize, end: usize, radix: u32| {
+            parse_number(field(start, end), radix).ok_or(ParseArchiveError::InvalidMemberHeader)
+        };
+
+        let mut name = field(0, 16);
+        let mut size = number(48, 58, 10)?;
+
+        // Thin archives only store the data of the symbol index and the extended filename table.
+        let stored = !thin || matches!(name, b"/" | b"//" | b"/SYM64/");
+        let stored_size = match stored {
+            true => usize::try_from(size).map_err(|_| ParseArchiveError::InvalidMemberHeader)?,
+            false => 0,
+        };
+
+        let data_start = offset + HEADER_SIZE;
+        let mut data = data_start
+            .checked_add(stored_size)
+            .and_then(|end| bytes.get(data_start..end))
+            .ok_or(ParseArchiveError::MemberOutOfBounds)?;
+        let next = data_start + stored_size + (stored_size & 1);
+
+        if let Some(length) = name.strip_prefix(b"#1/") {
+            // BSD long names are stored at the start of the member data.
+            let length = parse_decimal(length)
+                .and_then(|length| usize::try_from(length).ok())
+                .filter(|&length| length <= data.len())
+                .ok_or(ParseArchiveError::InvalidMemberName)?;
+            let (long_name, remaining) = data.split_at(length);
+            name = until_nul(long_name);
+            data = remaining;
+            size -= length as u64;
+        }
+
+        Ok(Self {
+            offset,
+            name,
+            modification_time: number(16, 28, 10)?,
+            user_id: number(28, 34, 10)?,
+            group_id: number(34, 40, 10)?,
+            mode: number(40, 48, 8)?,
+            size,
+            data,
+            next,
+        })
+    }
+
+    /// Returns `true` if this [`RawMember`] is a special member rather than a regular file.
+    fn is_special(&self) -> bool {
+        matches!(
+            self.name,
+            b"/" | b"//"
+                | b"/SYM64/"
+                | b"__.SYMDEF"
+                | b"__.SYMDEF SORTED"
+                | b"__.SYMDEF_64"
+                | b"__.SYMDEF_64 SORTED"
+        )
+    }
+}
+
+/// An [`Iterator`] over the regular [`ArchiveMember`]s of an [`Archive`].
+///
+/// Iteration ends after the first malformed member.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Members<'slice> {
+    /// The [`Archive`] being iterated.
+    archive: Archive<'slice>,
+    /// The offset of the header of the next member.
+    offset: usize,
+}
+
+impl<'slice> Iterator for Members<'slice> {
+    type Item = Result<ArchiveMember<'slice>, ParseArchiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.offset >= self.archive.bytes.len() {
+                return None;
+            }
+
+            let raw = match RawMember::parse(self.archive.bytes, self.offset, self.archive.thin) {
+                Ok(raw) => raw,
+                Err(error) => {
+                    self.offset = self.archive.bytes.len();
+                    return Some(Err(error));
+                }
+            };
+            self.offset = raw.next;
+
+            if !raw.is_special() {
+                return Some(self.archive.resolve(raw));
+            }
+        }
+    }
+}
+
+impl FusedIterator for Members<'_> {}
+
+/// The format of the symbol index of an [`Archive`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum SymbolIndexFormat {
+    /// A System V symbol index with 32-bit offsets.
+    SysV32,
+    /// A System V symbol index with 64-bit offsets.
+    SysV64,
+    /// A BSD ranlib symbol index with 32-bit fields.
+    Ranlib32,
+    /// A BSD ranlib symbol index with 64-bit fields.
+    Ranlib64,
+}
+
+impl SymbolIndexFormat {
+    /// Returns the size of a single field of the symbol index in bytes.
+    fn width(self) -> usize {
+        match self {
+            Self::SysV32 | Self::Ranlib32 => 4,
+            Self::SysV64 | Self::Ranlib64 => 8,
+        }
+    }
+}
+
+/// An entry of the symbol index of an [`Archive`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SymbolIndexEntry<'slice> {
+    /// The name of the symbol, without the terminating null byte.
+    pub name: &'slice [u8],
+    /// The offset of the header of the [`ArchiveMember`] defining the symbol.
+    pub member_offset: u64,
+}
+
+/// An [`Iterator`] over the entries of the symbol index of an [`Archive`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SymbolIndex<'slice> {
+    /// The entries that have not been visited.
+    entries: &'slice [u8],
+    /// The symbol names that have not been visited for a System V symbol index, or the string
+    /// table of a ranlib symbol index.
+    names: &'slice [u8],
+    /// The format of the symbol index.
+    format: SymbolIndexFormat,
+    /// Whether the fields of the symbol index are big-endian.
+    big_endian: bool,
+}
+
+impl<'slice> Iterator for SymbolIndex<'slice> {
+    type Item = SymbolIndexEntry<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.format.width();
+        match self.format {
+            SymbolIndexFormat::SysV32 | SymbolIndexFormat::SysV64 => {
+                let (offset, entries) = self.entries.split_at_checked(width)?;
+                let length = find_nul(self.names)?;
+
+                let entry = SymbolIndexEntry {
+                    name: &self.names[..length],
+                    member_offset: read_uint(offset, self.big_endian),
+                };
+                self.entries = entries;
+                self.names = &self.names[length + 1..];
+
+                Some(entry)
+            }
+            SymbolIndexFormat::Ranlib32 | SymbolIndexFormat::Ranlib64 => {
+                let (entry, entries) = self.entries.split_at_checked(2 * width)?;
+                self.entries = entries;
+
+                let (name_offset, member_offset) = entry.split_at(width);
+                let name = usize::try_from(read_uint(name_offset, self.big_endian))
+                    .ok()
+                    .and_then(|offset| self.names.get(offset..))
+                    .map(until_nul)
+                    .unwrap_or_default();
+
+                Some(SymbolIndexEntry {
+                    name,
+                    member_offset: read_uint(member_offset, self.big_endian),
+                })
+            }
+        }
+    }
+}
+
+impl FusedIterator for SymbolIndex<'_> {}
+
+/// Various errors that can occur while parsing an [`Archive`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParseArchiveError {
+    /// The archive does not start with [`ARCHIVE_MAGIC`] or [`THIN_ARCHIVE_MAGIC`].
+    InvalidMagic,
+    /// The header of a member is malformed.
+    InvalidMemberHeader,
+    /// The data of a member is located out of bounds.
+    MemberOutOfBounds,
+    /// The name of a member cannot be resolved.
+    InvalidMemberName,
+}
+
+impl fmt::Display for ParseArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "invalid archive magic"),
+            Self::InvalidMemberHeader => write!(f, "malformed archive member header"),
+            Self::MemberOutOfBounds => write!(f, "archive member located out of bounds"),
+            Self::InvalidMemberName => write!(f, "archive member name cannot be resolved"),
+        }
+    }
+}
+
+impl error::Error for ParseArchiveError {}
+
+/// Parses an unsigned ASCII decimal number.
+fn parse_decimal(bytes: &[u8]) -> Option<u64> {
+    parse_number(bytes, 10)
+}
+
+/// Parses an unsigned ASCII number in the given `radix`.
+///
+/// Empty fields are treated as zero.
+fn parse_number(bytes: &[u8], radix: u32) -> Option<u64> {
+    bytes.iter().try_fold(0u64, |value, &byte| {
+        let digit = char::from(byte).to_digit(radix)?;
+        value
+            .checked_mul(u64::from(radix))?
+            .checked_add(u64::from(digit))
+    })
+}
+
+/// Reads an unsigned integer from `bytes` in the given byte order.
+fn read_uint(bytes: &[u8], big_endian: bool) -> u64 {
+    let fold = |value: u64, &byte: &u8| (value << 8) | u64::from(byte);
+    if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    }
+}
+
+/// Returns `true` if the ranlib symbol index in `data`, with fields of `width` bytes, fits in
+/// `data` when read in the given byte order.
+fn ranlib_fits(data: &[u8], width: usize, big_endian: bool) -> bool {
+    let Some(size) = data.get(..width).map(|size| read_uint(size, big_endian)) else {
+        return false;
+    };
+
+    usize::try_from(size)
+        .ok()
+        .and_then(|size| width.checked_add(size))
+        .and_then(|end| data.get(end..)?.get(..width))
+        .map(|strings_size| read_uint(strings_size, big_endian))
+        .and_then(|strings_size| usize::try_from(strings_size).ok())
+        .is_some_and(|strings_size| strings_size <= data.len())
+}
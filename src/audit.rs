@@ -0,0 +1,145 @@
+//! Definitions for auditing the memory protection properties of an ELF file.
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::{ConstDynamicTag, DynamicFlags, DynamicTable},
+    encoding::EncodingParse,
+    program_header::{SegmentFlags, SegmentType},
+    relocation::{RelTable, RelaTable},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Audits this [`ElfFile`] for violations of the W^X policy, returning a [`WxAudit`]
+    /// describing the violations found.
+    ///
+    /// Relocation tables that are located out of bounds are skipped.
+    pub fn audit_wx(&self) -> WxAudit {
+        let writable_executable_segments = self.program_header_table().map_or(0, |table| {
+            table
+                .segments_of_type(SegmentType::LOAD)
+                .filter(|program_header| {
+                    program_header
+                        .flags()
+                        .contains(SegmentFlags::WRITE | SegmentFlags::EXECUTE)
+                })
+                .count()
+        });
+
+        let Some(dynamic) = self.dynamic_table() else {
+            return WxAudit {
+                writable_executable_segments,
+                text_relocations: false,
+                executable_relocations: 0,
+            };
+        };
+
+        let text_relocations = dynamic.find(ConstDynamicTag::TEXT_REL).is_some()
+            || dynamic
+                .find(ConstDynamicTag::FLAGS)
+                .is_some_and(|flags| DynamicFlags(flags.into()).contains(DynamicFlags::TEXT_REL));
+
+        let mut executable_relocations = 0;
+        if let Some(table) = self
+            .relocation_data(
+                dynamic,
+                ConstDynamicTag::RELA_TABLE,
+                ConstDynamicTag::RELA_SIZE,
+            )
+            .and_then(|data| {
+                RelaTable::new(
+                    self.class,
+                    self.encoding,
+                    data,
+                    data.len() / self.class.expected_rela_size(),
+                )
+            })
+        {
+            executable_relocations += table
+                .into_iter()
+                .filter(|rela| self.is_executable_address(rela.offset.into()))
+                .count();
+        }
+        if let Some(table) = self
+            .relocation_data(
+                dynamic,
+                ConstDynamicTag::REL_TABLE,
+                ConstDynamicTag::REL_SIZE,
+            )
+            .and_then(|data| {
+                RelTable::new(
+                    self.class,
+                    self.encoding,
+                    data,
+                    data.len() / self.class.expected_rel_size(),
+                )
+            })
+        {
+            executable_relocations += table
+                .into_iter()
+                .filter(|rel| self.is_executable_address(rel.offset.into()))
+                .count();
+        }
+        if let Some(table) = self.plt_table() {
+            executable_relocations += table
+                .into_iter()
+                .filter(|entry| self.is_executable_address(entry.got_slot.into()))
+                .count();
+        }
+
+        WxAudit {
+            writable_executable_segments,
+            text_relocations,
+            executable_relocations,
+        }
+    }
+
+    /// Returns the bytes of the relocation table located by the `address` and `size` entries of
+    /// `dynamic`.
+    pub(crate) fn relocation_data(
+        &self,
+        dynamic: DynamicTable<'slice, C, E>,
+        address: ConstDynamicTag,
+        size: ConstDynamicTag,
+    ) -> Option<&'slice [u8]> {
+        let size = to_len(dynamic.find(size)?).ok()?;
+
+        self.virtual_address_data(dynamic.find(address)?)?
+            .get(..size)
+    }
+
+    /// Returns `true` if `address` lies within an executable [`SegmentType::LOAD`] segment.
+    fn is_executable_address(&self, address: u64) -> bool {
+        self.program_header_table().is_some_and(|table| {
+            table
+                .segments_of_type(SegmentType::LOAD)
+                .filter(|program_header| program_header.flags().contains(SegmentFlags::EXECUTE))
+                .any(|program_header| {
+                    let start: u64 = program_header.virtual_address().into();
+                    address
+                        .checked_sub(start)
+                        .is_some_and(|offset| offset < program_header.memory_size().into())
+                })
+        })
+    }
+}
+
+/// The result of auditing an [`ElfFile`] for violations of the W^X policy.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WxAudit {
+    /// The number of [`SegmentType::LOAD`] segments that are both writable and executable.
+    pub writable_executable_segments: usize,
+    /// Whether the dynamic array indicates that relocations may modify non-writable segments.
+    pub text_relocations: bool,
+    /// The number of dynamic relocations that target an executable [`SegmentType::LOAD`] segment.
+    pub executable_relocations: usize,
+}
+
+impl WxAudit {
+    /// Returns `true` if no violations of the W^X policy were found.
+    pub const fn is_clean(&self) -> bool {
+        self.writable_executable_segments == 0
+            && !self.text_relocations
+            && self.executable_relocations == 0
+    }
+}
@@ -0,0 +1,218 @@
+//! Definitions for synthesizing the auxiliary vector passed to a program by its loader.
+
+use core::{error, fmt};
+
+use crate::{
+    biased::apply_bias,
+    class::ClassParse,
+    encoding::EncodingParse,
+    ident::{Class, Encoding},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`AuxiliaryVector`] a loader must pass to this [`ElfFile`] when it is loaded
+    /// according to `options`.
+    ///
+    /// Returns `None` if the address of the program header table cannot be determined.
+    pub fn auxiliary_vector(&self, options: AuxvOptions) -> Option<AuxiliaryVector> {
+        let header = self.header();
+        let class = header.ident().class();
+        let program_headers = apply_bias(class, self.program_header_table_address()?, options.bias);
+        let entry = apply_bias(class, header.entry().into(), options.bias);
+
+        let entries = [
+            AuxvEntry::new(AuxvType::PHDR, program_headers),
+            AuxvEntry::new(AuxvType::PHENT, header.program_header_size().into()),
+            AuxvEntry::new(AuxvType::PHNUM, header.program_header_count().into()),
+            AuxvEntry::new(AuxvType::ENTRY, entry),
+            AuxvEntry::new(AuxvType::BASE, options.interpreter_base.unwrap_or(0)),
+            AuxvEntry::new(AuxvType::PAGE_SIZE, options.page_size),
+            AuxvEntry::new(AuxvType::RANDOM, options.random_address),
+            AuxvEntry::new(AuxvType::NULL, 0),
+        ];
+
+        let vector = AuxiliaryVector {
+            entries,
+            class: header.ident().class(),
+            encoding: header.ident().encoding(),
+        };
+
+        Some(vector)
+    }
+}
+
+/// The parameters describing how an [`ElfFile`] is loaded, used to synthesize its
+/// [`AuxiliaryVector`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AuxvOptions {
+    /// The load bias of the [`ElfFile`].
+    pub bias: i64,
+    /// The address at which the program interpreter was loaded, if any.
+    pub interpreter_base: Option<u64>,
+    /// The size of a page on the target system.
+    pub page_size: u64,
+    /// The address of the 16 random bytes provided to the program.
+    pub random_address: u64,
+}
+
+/// The type of an [`AuxvEntry`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AuxvType(pub u64);
+
+impl AuxvType {
+    /// Marks the end of the auxiliary vector.
+    pub const NULL: Self = Self(0);
+    /// The address of the program header table of the program.
+    pub const PHDR: Self = Self(3);
+    /// The size of a program header of the program.
+    pub const PHENT: Self = Self(4);
+    /// The number of program headers of the program.
+    pub const PHNUM: Self = Self(5);
+    /// The size of a page on the system.
+    pub const PAGE_SIZE: Self = Self(6);
+    /// The address at which the program interpreter was loaded.
+    pub const BASE: Self = Self(7);
+    /// The entry point of the program.
+    pub const ENTRY: Self = Self(9);
+    /// The address of 16 random bytes.
+    pub const RANDOM: Self = Self(25);
+}
+
+impl fmt::Debug for AuxvType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NULL => f.pad("Null"),
+            Self::PHDR => f.pad("Phdr"),
+            Self::PHENT => f.pad("Phent"),
+            Self::PHNUM => f.pad("Phnum"),
+            Self::PAGE_SIZE => f.pad("PageSize"),
+            Self::BASE => f.pad("Base"),
+            Self::ENTRY => f.pad("Entry"),
+            Self::RANDOM => f.pad("Random"),
+            auxv_type => f.debug_tuple("AuxvType").field(&auxv_type.0).finish(),
+        }
+    }
+}
+
+/// An entry of an [`AuxiliaryVector`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AuxvEntry {
+    /// The [`AuxvType`] of this [`AuxvEntry`].
+    pub key: AuxvType,
+    /// The value of this [`AuxvEntry`].
+    pub value: u64,
+}
+
+impl AuxvEntry {
+    /// Creates a new [`AuxvEntry`] with the given `key` and `value`.
+    pub const fn new(key: AuxvType, value: u64) -> Self {
+        Self { key, value }
+    }
+}
+
+/// The auxiliary vector a loader passes to a program.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct AuxiliaryVector {
+    /// The [`AuxvEntry`]s of this [`AuxiliaryVector`], terminated by [`AuxvType::NULL`].
+    entries: [AuxvEntry; 8],
+    /// The [`Class`] of the program.
+    class: Class,
+    /// The [`Encoding`] of the program.
+    encoding: Encoding,
+}
+
+impl AuxiliaryVector {
+    /// Returns the [`AuxvEntry`]s of this [`AuxiliaryVector`], including the terminating
+    /// [`AuxvType::NULL`] entry.
+    pub fn entries(&self) -> &[AuxvEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of bytes required to encode this [`AuxiliaryVector`].
+    pub fn encoded_size(&self) -> usize {
+        self.entries.len() * 2 * self.word_size()
+    }
+
+    /// Encodes this [`AuxiliaryVector`] into `buffer` using the class and encoding of the
+    /// program, returning the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`EncodeAuxvError::BufferTooSmall`]: Returned if `buffer` is smaller than
+    ///   [`AuxiliaryVector::encoded_size()`].
+    /// - [`EncodeAuxvError::ValueTooLarge`]: Returned if a value does not fit in a 32-bit
+    ///   program's word.
+    pub fn encode(&self, buffer: &mut [u8]) -> Result<usize, EncodeAuxvError> {
+        let size = self.encoded_size();
+        let buffer = buffer
+            .get_mut(..size)
+            .ok_or(EncodeAuxvError::BufferTooSmall)?;
+
+        let word_size = self.word_size();
+        for (entry, chunk) in self
+            .entries
+            .iter()
+            .zip(buffer.chunks_exact_mut(2 * word_size))
+        {
+            let (key, value) = chunk.split_at_mut(word_size);
+            self.encode_word(entry.key.0, key)?;
+            self.encode_word(entry.value, value)?;
+        }
+
+        Ok(size)
+    }
+
+    /// Returns the size of a word of the program.
+    fn word_size(&self) -> usize {
+        if self.class == Class::CLASS32 {
+            4
+        } else {
+            8
+        }
+    }
+
+    /// Encodes `value` as a word of the program into `buffer`.
+    fn encode_word(&self, value: u64, buffer: &mut [u8]) -> Result<(), EncodeAuxvError> {
+        let big_endian = self.encoding == Encoding::MSB2;
+        if self.class == Class::CLASS32 {
+            let value = u32::try_from(value).map_err(|_| EncodeAuxvError::ValueTooLarge)?;
+            let bytes = if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            };
+            buffer.copy_from_slice(&bytes);
+        } else {
+            let bytes = if big_endian {
+                value.to_be_bytes()
+            } else {
+                value.to_le_bytes()
+            };
+            buffer.copy_from_slice(&bytes);
+        }
+
+        Ok(())
+    }
+}
+
+/// Various errors that can occur while encoding an [`AuxiliaryVector`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EncodeAuxvError {
+    /// The buffer is too small to hold the encoded [`AuxiliaryVector`].
+    BufferTooSmall,
+    /// A value does not fit in a word of the program.
+    ValueTooLarge,
+}
+
+impl fmt::Display for EncodeAuxvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => write!(f, "buffer too small to hold auxiliary vector"),
+            Self::ValueTooLarge => write!(f, "auxiliary vector value too large for program class"),
+        }
+    }
+}
+
+impl error::Error for EncodeAuxvError {}
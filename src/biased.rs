@@ -0,0 +1,285 @@
+//! Definitions for views of an ELF file that has been loaded at a bias.
+
+use core::{fmt, iter::FusedIterator};
+
+#[cfg(feature = "dynamic")]
+use crate::{class::to_len, dynamic::ConstDynamicTag};
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    ident::Class,
+    program_header::ProgramHeader,
+    symbol::{self, Symbol, SymbolBinding, SymbolType, SymbolVisibility},
+    ElfFile,
+};
+
+/// View of an [`ElfFile`] that applies a load bias to every address it returns.
+///
+/// The load bias is the difference between the address at which an ELF file was actually loaded
+/// and the address at which it was linked to be loaded, which is negative if the file was loaded
+/// below its link address. Biased addresses wrap around the address space of the class of the
+/// file, as they do for the loaded program, so 32-bit files always yield 32-bit addresses.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BiasedElfFile<'slice, C, E> {
+    /// The underlying [`ElfFile`].
+    pub(crate) file: ElfFile<'slice, C, E>,
+    /// The load bias applied to every address.
+    pub(crate) bias: i64,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> BiasedElfFile<'slice, C, E> {
+    /// Creates a new [`BiasedElfFile`] from the given `file` loaded with the given `bias`.
+    pub fn new(file: ElfFile<'slice, C, E>, bias: i64) -> Self {
+        Self { file, bias }
+    }
+
+    /// Returns the underlying [`ElfFile`] of this [`BiasedElfFile`].
+    pub fn file(&self) -> ElfFile<'slice, C, E> {
+        self.file
+    }
+
+    /// Returns the load bias of this [`BiasedElfFile`].
+    pub fn bias(&self) -> i64 {
+        self.bias
+    }
+
+    /// Returns the given link-time `address` adjusted by the load bias.
+    pub fn translate(&self, address: C::ClassUsize) -> u64 {
+        apply_bias(
+            self.file.header().ident().class(),
+            address.into(),
+            self.bias,
+        )
+    }
+
+    /// Returns the address at which the link-time address zero resides, which is the base
+    /// address of a position-independent file.
+    pub fn base(&self) -> u64 {
+        apply_bias(self.file.header().ident().class(), 0, self.bias)
+    }
+
+    /// Returns the biased virtual address of the entry point of this [`BiasedElfFile`].
+    pub fn entry(&self) -> u64 {
+        self.translate(self.file.header().entry())
+    }
+
+    /// Returns the biased virtual address at which the first byte of the segment controlled by
+    /// `program_header` resides in memory.
+    pub fn segment_virtual_address(&self, program_header: ProgramHeader<'slice, C, E>) -> u64 {
+        self.translate(program_header.virtual_address())
+    }
+
+    /// Returns the biased address of `symbol`, whose value must be a link-time address.
+    pub fn symbol_address(&self, symbol: Symbol<'slice, C, E>) -> u64 {
+        self.translate(symbol.value())
+    }
+
+    /// Returns a [`FunctionSymbols`] over the defined, named [`SymbolType::FUNC`] symbols of the
+    /// [`ElfFile::symbol_table()`] of this [`BiasedElfFile`], at their biased addresses.
+    ///
+    /// This suits a kernel building a symbol table for backtraces at boot from its own image,
+    /// given the offset at which it was loaded. Returns `None` if there is no
+    /// [`SectionType::SYMTAB`][st] section.
+    ///
+    /// [st]: crate::section_header::SectionType::SYMTAB
+    pub fn function_symbols(&self) -> Option<FunctionSymbols<'slice, C, E>> {
+        let symbols = self.file.symbol_table()?.into_iter();
+
+        Some(FunctionSymbols {
+            file: *self,
+            symbols,
+            exported: false,
+        })
+    }
+
+    /// Returns a [`FunctionSymbols`] over the functions exported by the
+    /// [`ElfFile::dynamic_symbol_table()`] of this [`BiasedElfFile`], at their biased addresses.
+    ///
+    /// A function is exported if it is a defined, named [`SymbolType::FUNC`] or
+    /// [`SymbolType::GNU_IFUNC`] symbol that is not [`SymbolBinding::LOCAL`] and is visible to
+    /// other components. The address of a [`SymbolType::GNU_IFUNC`] symbol is that of its
+    /// resolver rather than of the implementation it selects.
+    pub fn exported_functions(&self) -> Option<FunctionSymbols<'slice, C, E>> {
+        let symbols = self.file.dynamic_symbol_table()?.into_iter();
+
+        Some(FunctionSymbols {
+            file: *self,
+            symbols,
+            exported: true,
+        })
+    }
+
+    /// Returns the biased address held by the `tag` entry of the dynamic array, which must be an
+    /// entry holding an address, such as [`ConstDynamicTag::INIT`].
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_address(&self, tag: ConstDynamicTag) -> Option<u64> {
+        let address = self.file.dynamic_table()?.find(tag)?;
+
+        Some(self.translate(address))
+    }
+
+    /// Returns a [`FunctionArray`] over the biased addresses of the initialization functions
+    /// located by the [`ConstDynamicTag::INIT_ARRAY`] entry of the dynamic array.
+    #[cfg(feature = "dynamic")]
+    pub fn init_array(&self) -> Option<FunctionArray<'slice, C, E>> {
+        self.function_array(
+            ConstDynamicTag::INIT_ARRAY,
+            ConstDynamicTag::INIT_ARRAY_SIZE,
+        )
+    }
+
+    /// Returns a [`FunctionArray`] over the biased addresses of the termination functions located
+    /// by the [`ConstDynamicTag::FINI_ARRAY`] entry of the dynamic array.
+    #[cfg(feature = "dynamic")]
+    pub fn fini_array(&self) -> Option<FunctionArray<'slice, C, E>> {
+        self.function_array(
+            ConstDynamicTag::FINI_ARRAY,
+            ConstDynamicTag::FINI_ARRAY_SIZE,
+        )
+    }
+
+    /// Returns a [`FunctionArray`] over the biased addresses of the pre-initialization functions
+    /// located by the [`ConstDynamicTag::PREINIT_ARRAY`] entry of the dynamic array.
+    #[cfg(feature = "dynamic")]
+    pub fn preinit_array(&self) -> Option<FunctionArray<'slice, C, E>> {
+        self.function_array(
+            ConstDynamicTag::PREINIT_ARRAY,
+            ConstDynamicTag::PREINIT_ARRAY_SIZE,
+        )
+    }
+
+    /// Returns the [`FunctionArray`] located by the `address` and `size` entries of the dynamic
+    /// array.
+    #[cfg(feature = "dynamic")]
+    fn function_array(
+        &self,
+        address: ConstDynamicTag,
+        size: ConstDynamicTag,
+    ) -> Option<FunctionArray<'slice, C, E>> {
+        let dynamic = self.file.dynamic_table()?;
+        let size = to_len(dynamic.find(size)?).ok()?;
+        let bytes = self
+            .file
+            .virtual_address_data(dynamic.find(address)?)?
+            .get(..size)?;
+
+        Some(FunctionArray { file: *self, bytes })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for BiasedElfFile<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("BiasedElfFile");
+
+        debug_struct.field("file", &self.file);
+        debug_struct.field("bias", &self.bias);
+
+        debug_struct.finish()
+    }
+}
+
+/// An [`Iterator`] over the biased addresses held by an array of function pointers, such as the
+/// array located by [`ConstDynamicTag::INIT_ARRAY`][ia].
+///
+/// The pointers are read from the file, where linkers store their link-time values even when a
+/// relative relocation also targets them.
+///
+/// [ia]: crate::dynamic::ConstDynamicTag::INIT_ARRAY
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FunctionArray<'slice, C, E> {
+    /// The [`BiasedElfFile`] holding the array.
+    file: BiasedElfFile<'slice, C, E>,
+    /// The bytes of the pointers that have not yet been visited.
+    bytes: &'slice [u8],
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for FunctionArray<'_, C, E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let file = self.file.file;
+        let word_size = if file.header().ident().class() == Class::CLASS32 {
+            4
+        } else {
+            8
+        };
+        if self.bytes.len() < word_size {
+            return None;
+        }
+
+        let address = file
+            .class
+            .parse_class_usize_at(file.encoding, 0, self.bytes);
+        self.bytes = &self.bytes[word_size..];
+
+        Some(self.file.translate(address))
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for FunctionArray<'_, C, E> {}
+
+/// A defined function symbol at its biased address.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BiasedSymbol<'slice> {
+    /// The biased address of the symbol.
+    pub address: u64,
+    /// The name of the symbol, without the terminating null byte.
+    pub name: &'slice [u8],
+    /// The size of the symbol, or zero if it has no size or the size is unknown.
+    pub size: u64,
+}
+
+/// An [`Iterator`] over the [`BiasedSymbol`]s of the defined, named function symbols of a
+/// [`SymbolTable`][table].
+///
+/// [table]: crate::symbol::SymbolTable
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FunctionSymbols<'slice, C, E> {
+    /// The [`BiasedElfFile`] holding the symbols.
+    file: BiasedElfFile<'slice, C, E>,
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// Whether only exported functions are yielded, as described by
+    /// [`BiasedElfFile::exported_functions()`].
+    exported: bool,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for FunctionSymbols<'slice, C, E> {
+    type Item = BiasedSymbol<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let exported = self.exported;
+        self.symbols.find_map(|symbol| {
+            let symbol_type = symbol.symbol_type();
+            let matches = if exported {
+                (symbol_type == SymbolType::FUNC || symbol_type == SymbolType::GNU_IFUNC)
+                    && symbol.binding() != SymbolBinding::LOCAL
+                    && (symbol.visibility() == SymbolVisibility::DEFAULT
+                        || symbol.visibility() == SymbolVisibility::PROTECTED)
+            } else {
+                symbol_type == SymbolType::FUNC
+            };
+            if !matches || !symbol.is_defined() {
+                return None;
+            }
+
+            Some(BiasedSymbol {
+                address: self.file.symbol_address(symbol),
+                name: symbol.name()?,
+                size: symbol.size().into(),
+            })
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for FunctionSymbols<'_, C, E> {}
+
+/// Returns `address` adjusted by `bias`, wrapping around the address space of `class`.
+pub(crate) fn apply_bias(class: Class, address: u64, bias: i64) -> u64 {
+    let address = address.wrapping_add_signed(bias);
+    if class == Class::CLASS32 {
+        address & u64::from(u32::MAX)
+    } else {
+        address
+    }
+}
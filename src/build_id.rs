@@ -0,0 +1,85 @@
+//! Definitions for recomputing and stamping the build ID of an ELF file.
+//!
+//! The build ID is computed over the contents of the ELF file with the descriptor of the
+//! [`NoteType::GNU_BUILD_ID`][bi] note excluded, so stamping is deterministic and can be repeated
+//! on an already stamped file.
+//!
+//! [bi]: crate::note::NoteType::GNU_BUILD_ID
+
+use core::{error, fmt, ops::Range};
+
+use crate::{
+    class::ClassParse, encoding::EncodingParse, note::GnuBuildId, ElfFile, ParseElfFileError,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the range of file offsets occupied by the descriptor of the first
+    /// [`NoteType::GNU_BUILD_ID`][bi] note of this [`ElfFile`].
+    ///
+    /// [bi]: crate::note::NoteType::GNU_BUILD_ID
+    pub fn build_id_range(&self) -> Option<Range<usize>> {
+        let GnuBuildId(descriptor) = self.find_note::<GnuBuildId>()?;
+
+        self.range_of(descriptor)
+    }
+}
+
+/// Recomputes the build ID of the ELF file contained in `bytes` and writes it into its existing
+/// [`NoteType::GNU_BUILD_ID`][bi] note, returning the file offsets of the build ID.
+///
+/// `hash` is called with the contents of the file preceding and following the build ID, and
+/// must fill the build ID, whose length is determined by the existing note.
+///
+/// # Errors
+///
+/// - [`StampBuildIdError::ParseElfFileError`]: Returned if `bytes` does not contain a valid
+///   [`ElfFile`].
+/// - [`StampBuildIdError::MissingBuildId`]: Returned if the [`ElfFile`] has no
+///   [`NoteType::GNU_BUILD_ID`][bi] note.
+///
+/// [bi]: crate::note::NoteType::GNU_BUILD_ID
+pub fn stamp_build_id<C, E, F>(bytes: &mut [u8], hash: F) -> Result<Range<usize>, StampBuildIdError>
+where
+    C: ClassParse,
+    E: EncodingParse,
+    F: FnOnce(&[&[u8]; 2], &mut [u8]),
+{
+    let range = ElfFile::<C, E>::new(bytes)?
+        .build_id_range()
+        .ok_or(StampBuildIdError::MissingBuildId)?;
+
+    let (before, rest) = bytes.split_at_mut(range.start);
+    let (build_id, after) = rest.split_at_mut(range.len());
+    build_id.fill(0);
+    hash(&[before, after], build_id);
+
+    Ok(range)
+}
+
+/// Various errors that can occur while stamping the build ID of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum StampBuildIdError {
+    /// An error occurred while parsing the [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// The [`ElfFile`] has no [`NoteType::GNU_BUILD_ID`][bi] note.
+    ///
+    /// [bi]: crate::note::NoteType::GNU_BUILD_ID
+    MissingBuildId,
+}
+
+impl From<ParseElfFileError> for StampBuildIdError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for StampBuildIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::MissingBuildId => write!(f, "ELF file has no build ID note"),
+        }
+    }
+}
+
+impl error::Error for StampBuildIdError {}
@@ -9,6 +9,8 @@ use crate::{
     ident::{Class, DefElfIdent},
     program_header::{ClassParseProgramHeader, SegmentFlags, SegmentType},
     relocation::ClassParseRelocation,
+    section_header::{ClassParseSectionHeader, SectionType},
+    symbol::ClassParseSymbol,
 };
 
 /// A zero-sized object offering methods to safely parse 32-bit ELF files.
@@ -76,7 +78,7 @@ impl ClassParseElfHeader for Class32 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf32Header {
     pub identifier: DefElfIdent,
 
@@ -139,7 +141,7 @@ impl ClassParseProgramHeader for Class32 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf32ProgramHeader {
     pub segment_type: SegmentType,
     pub file_offset: u32,
@@ -151,6 +153,67 @@ pub(crate) struct Elf32ProgramHeader {
     pub alignment: u32,
 }
 
+impl ClassParseSectionHeader for Class32 {
+    fn section_name_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, name)
+    }
+
+    fn section_type_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, section_type)
+    }
+
+    fn section_flags_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, flags)
+    }
+
+    fn section_address_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, address)
+    }
+
+    fn section_file_offset_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, file_offset)
+    }
+
+    fn section_size_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, size)
+    }
+
+    fn section_link_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, link)
+    }
+
+    fn section_info_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, info)
+    }
+
+    fn section_alignment_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, alignment)
+    }
+
+    fn section_entry_size_offset(self) -> usize {
+        mem::offset_of!(Elf32SectionHeader, entry_size)
+    }
+
+    fn expected_section_header_size(self) -> usize {
+        mem::size_of::<Elf32SectionHeader>()
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
+pub(crate) struct Elf32SectionHeader {
+    pub name: u32,
+    pub section_type: SectionType,
+    pub flags: u32,
+    pub address: u32,
+    pub file_offset: u32,
+    pub size: u32,
+    pub link: u32,
+    pub info: u32,
+    pub alignment: u32,
+    pub entry_size: u32,
+}
+
 impl ClassParseDynamic for Class32 {
     fn dynamic_tag_eq(tag: DynamicTag<Self>, const_tag: ConstDynamicTag) -> bool {
         tag.0 == const_tag.0
@@ -170,12 +233,53 @@ impl ClassParseDynamic for Class32 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf32Dynamic {
     pub tag: i32,
     pub value: u32,
 }
 
+impl ClassParseSymbol for Class32 {
+    fn symbol_name_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, name)
+    }
+
+    fn symbol_value_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, value)
+    }
+
+    fn symbol_size_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, size)
+    }
+
+    fn symbol_info_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, info)
+    }
+
+    fn symbol_other_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, other)
+    }
+
+    fn symbol_section_index_offset(self) -> usize {
+        mem::offset_of!(Elf32Symbol, section_index)
+    }
+
+    fn expected_symbol_size(self) -> usize {
+        mem::size_of::<Elf32Symbol>()
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
+pub(crate) struct Elf32Symbol {
+    pub name: u32,
+    pub value: u32,
+    pub size: u32,
+    pub info: u8,
+    pub other: u8,
+    pub section_index: u16,
+}
+
 impl ClassParseRelocation for Class32 {
     fn relocation_type_raw(self, info: Self::ClassUsize) -> u32 {
         info & 0xFF
@@ -215,14 +319,14 @@ impl ClassParseRelocation for Class32 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf32Rel {
     pub offset: u32,
     pub info: u32,
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf32Rela {
     pub offset: u32,
     pub info: u32,
@@ -9,6 +9,8 @@ use crate::{
     ident::{Class, DefElfIdent},
     program_header::{ClassParseProgramHeader, SegmentFlags, SegmentType},
     relocation::ClassParseRelocation,
+    section_header::{ClassParseSectionHeader, SectionType},
+    symbol::ClassParseSymbol,
 };
 
 /// A zero-sized object offering methods to safely parse 64-bit ELF files.
@@ -76,7 +78,7 @@ impl ClassParseElfHeader for Class64 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf64Header {
     pub identifier: DefElfIdent,
 
@@ -139,7 +141,7 @@ impl ClassParseProgramHeader for Class64 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf64ProgramHeader {
     pub segment_type: SegmentType,
     pub flags: SegmentFlags,
@@ -151,6 +153,67 @@ pub(crate) struct Elf64ProgramHeader {
     pub alignment: u64,
 }
 
+impl ClassParseSectionHeader for Class64 {
+    fn section_name_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, name)
+    }
+
+    fn section_type_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, section_type)
+    }
+
+    fn section_flags_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, flags)
+    }
+
+    fn section_address_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, address)
+    }
+
+    fn section_file_offset_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, file_offset)
+    }
+
+    fn section_size_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, size)
+    }
+
+    fn section_link_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, link)
+    }
+
+    fn section_info_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, info)
+    }
+
+    fn section_alignment_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, alignment)
+    }
+
+    fn section_entry_size_offset(self) -> usize {
+        mem::offset_of!(Elf64SectionHeader, entry_size)
+    }
+
+    fn expected_section_header_size(self) -> usize {
+        mem::size_of::<Elf64SectionHeader>()
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
+pub(crate) struct Elf64SectionHeader {
+    pub name: u32,
+    pub section_type: SectionType,
+    pub flags: u64,
+    pub address: u64,
+    pub file_offset: u64,
+    pub size: u64,
+    pub link: u32,
+    pub info: u32,
+    pub alignment: u64,
+    pub entry_size: u64,
+}
+
 impl ClassParseDynamic for Class64 {
     fn dynamic_tag_eq(tag: DynamicTag<Self>, const_tag: ConstDynamicTag) -> bool {
         tag.0 == const_tag.0 as i64
@@ -170,12 +233,53 @@ impl ClassParseDynamic for Class64 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf64Dynamic {
     pub tag: i64,
     pub value: u64,
 }
 
+impl ClassParseSymbol for Class64 {
+    fn symbol_name_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, name)
+    }
+
+    fn symbol_value_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, value)
+    }
+
+    fn symbol_size_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, size)
+    }
+
+    fn symbol_info_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, info)
+    }
+
+    fn symbol_other_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, other)
+    }
+
+    fn symbol_section_index_offset(self) -> usize {
+        mem::offset_of!(Elf64Symbol, section_index)
+    }
+
+    fn expected_symbol_size(self) -> usize {
+        mem::size_of::<Elf64Symbol>()
+    }
+}
+
+#[repr(C)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
+pub(crate) struct Elf64Symbol {
+    pub name: u32,
+    pub info: u8,
+    pub other: u8,
+    pub section_index: u16,
+    pub value: u64,
+    pub size: u64,
+}
+
 impl ClassParseRelocation for Class64 {
     fn relocation_type_raw(self, info: Self::ClassUsize) -> u32 {
         info as u32
@@ -215,14 +319,14 @@ impl ClassParseRelocation for Class64 {
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf64Rel {
     pub offset: u64,
     pub info: u64,
 }
 
 #[repr(C)]
-#[expect(clippy::missing_docs_in_private_items)]
+#[cfg_attr(not(test), expect(clippy::missing_docs_in_private_items))]
 pub(crate) struct Elf64Rela {
     pub offset: u64,
     pub info: u64,
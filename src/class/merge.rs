@@ -1,15 +1,20 @@
 //! Implementation of merged [`ClassParse`] implementations.
 
 use crate::{
-    class::{ClassParse, ClassParseBase},
+    class::{AdditiveIdentity, ClassParse, ClassParseBase},
     dynamic::ClassParseDynamic,
     header::ClassParseElfHeader,
     program_header::ClassParseProgramHeader,
     relocation::ClassParseRelocation,
+    section_header::ClassParseSectionHeader,
+    symbol::ClassParseSymbol,
 };
 
 /// An object used to dispatch the [`ClassParse`] to the two underlying [`ClassParse`]
 /// implementations.
+///
+/// Class sized integers are widened to [`u64`] and [`i64`], so any two [`ClassParse`]
+/// implementations may be merged in either order.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Merge<A: ClassParse, B: ClassParse> {
     /// The first [`ClassParse`] implementation.
@@ -18,20 +23,9 @@ pub enum Merge<A: ClassParse, B: ClassParse> {
     B(B),
 }
 
-impl<A: ClassParse, B: ClassParse> ClassParse for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
-}
+impl<A: ClassParse, B: ClassParse> ClassParse for Merge<A, B> {}
 
-impl<A: ClassParse, B: ClassParse> ClassParseElfHeader for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
+impl<A: ClassParse, B: ClassParse> ClassParseElfHeader for Merge<A, B> {
     fn elf_type_offset(self) -> usize {
         match self {
             Self::A(a) => a.elf_type_offset(),
@@ -131,12 +125,7 @@ where
     }
 }
 
-impl<A: ClassParse, B: ClassParse> ClassParseProgramHeader for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
+impl<A: ClassParse, B: ClassParse> ClassParseProgramHeader for Merge<A, B> {
     fn segment_type_offset(self) -> usize {
         match self {
             Self::A(a) => a.segment_type_offset(),
@@ -201,17 +190,91 @@ where
     }
 }
 
-impl<A: ClassParse, B: ClassParse> ClassParseDynamic for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
+impl<A: ClassParse, B: ClassParse> ClassParseSectionHeader for Merge<A, B> {
+    fn section_name_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_name_offset(),
+            Self::B(b) => b.section_name_offset(),
+        }
+    }
+
+    fn section_type_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_type_offset(),
+            Self::B(b) => b.section_type_offset(),
+        }
+    }
+
+    fn section_flags_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_flags_offset(),
+            Self::B(b) => b.section_flags_offset(),
+        }
+    }
+
+    fn section_address_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_address_offset(),
+            Self::B(b) => b.section_address_offset(),
+        }
+    }
+
+    fn section_file_offset_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_file_offset_offset(),
+            Self::B(b) => b.section_file_offset_offset(),
+        }
+    }
+
+    fn section_size_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_size_offset(),
+            Self::B(b) => b.section_size_offset(),
+        }
+    }
+
+    fn section_link_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_link_offset(),
+            Self::B(b) => b.section_link_offset(),
+        }
+    }
+
+    fn section_info_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_info_offset(),
+            Self::B(b) => b.section_info_offset(),
+        }
+    }
+
+    fn section_alignment_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_alignment_offset(),
+            Self::B(b) => b.section_alignment_offset(),
+        }
+    }
+
+    fn section_entry_size_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.section_entry_size_offset(),
+            Self::B(b) => b.section_entry_size_offset(),
+        }
+    }
+
+    fn expected_section_header_size(self) -> usize {
+        match self {
+            Self::A(a) => a.expected_section_header_size(),
+            Self::B(b) => b.expected_section_header_size(),
+        }
+    }
+}
+
+impl<A: ClassParse, B: ClassParse> ClassParseDynamic for Merge<A, B> {
     fn dynamic_tag_eq(
         tag: crate::dynamic::DynamicTag<Self>,
         const_tag: crate::dynamic::ConstDynamicTag,
     ) -> bool {
-        B::dynamic_tag_eq(crate::dynamic::DynamicTag::<B>(tag.0), const_tag)
+        tag.0 == i64::from(const_tag.0)
     }
 
     fn dynamic_tag_offset(self) -> usize {
@@ -236,25 +299,18 @@ where
     }
 }
 
-impl<A: ClassParse, B: ClassParse> ClassParseRelocation for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
+impl<A: ClassParse, B: ClassParse> ClassParseRelocation for Merge<A, B> {
     fn relocation_type_raw(self, info: Self::ClassUsize) -> u32 {
         match self {
-            Self::A(a) => {
-                a.relocation_type_raw(A::ClassUsize::try_from(info).map_err(|_| ()).unwrap())
-            }
-            Self::B(b) => b.relocation_type_raw(info),
+            Self::A(a) => a.relocation_type_raw(truncate(info)),
+            Self::B(b) => b.relocation_type_raw(truncate(info)),
         }
     }
 
     fn symbol_raw(self, info: Self::ClassUsize) -> u32 {
         match self {
-            Self::A(a) => a.symbol_raw(A::ClassUsize::try_from(info).map_err(|_| ()).unwrap()),
-            Self::B(b) => b.symbol_raw(info),
+            Self::A(a) => a.symbol_raw(truncate(info)),
+            Self::B(b) => b.symbol_raw(truncate(info)),
         }
     }
 
@@ -308,14 +364,60 @@ where
     }
 }
 
-impl<A: ClassParse, B: ClassParse> ClassParseBase for Merge<A, B>
-where
-    B::ClassUsize: From<A::ClassUsize>,
-    B::ClassIsize: From<A::ClassIsize>,
-    A::ClassUsize: TryFrom<B::ClassUsize>,
-{
-    type ClassUsize = B::ClassUsize;
-    type ClassIsize = B::ClassIsize;
+impl<A: ClassParse, B: ClassParse> ClassParseSymbol for Merge<A, B> {
+    fn symbol_name_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_name_offset(),
+            Self::B(b) => b.symbol_name_offset(),
+        }
+    }
+
+    fn symbol_value_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_value_offset(),
+            Self::B(b) => b.symbol_value_offset(),
+        }
+    }
+
+    fn symbol_size_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_size_offset(),
+            Self::B(b) => b.symbol_size_offset(),
+        }
+    }
+
+    fn symbol_info_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_info_offset(),
+            Self::B(b) => b.symbol_info_offset(),
+        }
+    }
+
+    fn symbol_other_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_other_offset(),
+            Self::B(b) => b.symbol_other_offset(),
+        }
+    }
+
+    fn symbol_section_index_offset(self) -> usize {
+        match self {
+            Self::A(a) => a.symbol_section_index_offset(),
+            Self::B(b) => b.symbol_section_index_offset(),
+        }
+    }
+
+    fn expected_symbol_size(self) -> usize {
+        match self {
+            Self::A(a) => a.expected_symbol_size(),
+            Self::B(b) => b.expected_symbol_size(),
+        }
+    }
+}
+
+impl<A: ClassParse, B: ClassParse> ClassParseBase for Merge<A, B> {
+    type ClassUsize = u64;
+    type ClassIsize = i64;
 
     fn from_elf_class(class: crate::ident::Class) -> Result<Self, super::UnsupportedClassError> {
         if let Ok(a) = A::from_elf_class(class) {
@@ -332,8 +434,8 @@ where
         data: &[u8],
     ) -> Self::ClassUsize {
         match self {
-            Self::A(a) => B::ClassUsize::from(a.parse_class_usize_at(encoding, offset, data)),
-            Self::B(b) => b.parse_class_usize_at(encoding, offset, data),
+            Self::A(a) => a.parse_class_usize_at(encoding, offset, data).into(),
+            Self::B(b) => b.parse_class_usize_at(encoding, offset, data).into(),
         }
     }
 
@@ -344,8 +446,20 @@ where
         data: &[u8],
     ) -> Self::ClassIsize {
         match self {
-            Self::A(a) => B::ClassIsize::from(a.parse_class_isize_at(encoding, offset, data)),
-            Self::B(b) => b.parse_class_isize_at(encoding, offset, data),
+            Self::A(a) => a.parse_class_isize_at(encoding, offset, data).into(),
+            Self::B(b) => b.parse_class_isize_at(encoding, offset, data).into(),
         }
     }
 }
+
+/// Converts the widened `value` into the class sized integer `T`, discarding the bits that do
+/// not fit.
+///
+/// Values parsed from a file always fit, so only values constructed by the caller are truncated.
+/// ELF class sized integers are at least 32 bits wide, so the final fallback is never reached by
+/// the classes defined by the ELF specification.
+fn truncate<T: TryFrom<u64> + AdditiveIdentity>(value: u64) -> T {
+    T::try_from(value)
+        .or_else(|_| T::try_from(value & u64::from(u32::MAX)))
+        .unwrap_or(T::ADDITIVE_IDENTITY)
+}
@@ -5,6 +5,7 @@ use core::{error, fmt};
 use crate::{
     dynamic::ClassParseDynamic, encoding::EncodingParse, header::ClassParseElfHeader, ident::Class,
     program_header::ClassParseProgramHeader, relocation::ClassParseRelocation,
+    section_header::ClassParseSectionHeader, symbol::ClassParseSymbol,
 };
 
 mod class_32;
@@ -23,18 +24,22 @@ pub type AnyClass = Merge<Class32, Class64>;
 pub trait ClassParse:
     ClassParseElfHeader
     + ClassParseProgramHeader
+    + ClassParseSectionHeader
     + ClassParseDynamic
     + ClassParseRelocation
+    + ClassParseSymbol
     + ClassParseBase
 {
 }
 
 /// The base definitions of a class aware parser.
-pub trait ClassParseBase: Clone + Copy {
+pub trait ClassParseBase: Clone + Copy + fmt::Debug {
     /// An unsigned class sized integer.
     type ClassUsize: Clone
         + Copy
         + TryInto<usize>
+        + Into<u64>
+        + TryFrom<u64>
         + fmt::Debug
         + fmt::Display
         + Eq
@@ -45,7 +50,7 @@ pub trait ClassParseBase: Clone + Copy {
         + core::ops::Div<Output = Self::ClassUsize>
         + core::ops::Rem<Output = Self::ClassUsize>;
     /// A signed class sized integer.
-    type ClassIsize: Clone + Copy + fmt::Debug + fmt::Display + Eq + Ord;
+    type ClassIsize: Clone + Copy + Into<i64> + TryFrom<i64> + fmt::Debug + fmt::Display + Eq + Ord;
 
     /// Returns the [`ClassParseBase`] instance that corresponds with the given [`Class`].
     ///
@@ -98,6 +103,49 @@ impl fmt::Display for UnsupportedClassError {
 
 impl error::Error for UnsupportedClassError {}
 
+/// Converts the class sized file offset `offset` into a [`usize`].
+///
+/// # Errors
+///
+/// Returns [`OffsetOverflow`] if `offset` cannot be represented by a [`usize`] on the host.
+pub fn to_file_offset<T: Into<u64>>(offset: T) -> Result<usize, OffsetOverflow> {
+    let offset = offset.into();
+    usize::try_from(offset).map_err(|_| OffsetOverflow::FileOffset(offset))
+}
+
+/// Converts the class sized length `len` into a [`usize`].
+///
+/// # Errors
+///
+/// Returns [`OffsetOverflow`] if `len` cannot be represented by a [`usize`] on the host.
+pub fn to_len<T: Into<u64>>(len: T) -> Result<usize, OffsetOverflow> {
+    let len = len.into();
+    usize::try_from(len).map_err(|_| OffsetOverflow::Length(len))
+}
+
+/// An error that occurs when a class sized value cannot be represented by a [`usize`] on the
+/// host, such as when parsing a 64-bit ELF file on a 32-bit host.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OffsetOverflow {
+    /// The file offset does not fit in a [`usize`].
+    FileOffset(u64),
+    /// The length does not fit in a [`usize`].
+    Length(u64),
+}
+
+impl fmt::Display for OffsetOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileOffset(offset) => {
+                write!(f, "file offset {offset:#x} does not fit in a host usize")
+            }
+            Self::Length(len) => write!(f, "length {len:#x} does not fit in a host usize"),
+        }
+    }
+}
+
+impl error::Error for OffsetOverflow {}
+
 /// Defines a multiplicative identity for [`Self`].
 pub trait MultiplicativeIdentity {
     /// The multiplicative identity of [`Self`].
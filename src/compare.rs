@@ -0,0 +1,137 @@
+//! Definitions for comparing ELF structures across [`Class`][c]es.
+//!
+//! Class sized fields are normalized to [`u64`] so that structures parsed from 32-bit and 64-bit
+//! ELF files can be compared directly.
+//!
+//! [c]: crate::ident::Class
+
+use core::{error, fmt};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{ProgramHeader, ProgramHeaderTable, SegmentFlags, SegmentType},
+};
+
+/// A [`ProgramHeader`] with all class sized fields widened to [`u64`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NormalizedProgramHeader {
+    /// The [`SegmentType`] of the segment.
+    pub segment_type: SegmentType,
+    /// The [`SegmentFlags`] of the segment.
+    pub flags: SegmentFlags,
+    /// The offset within the file at which the segment starts.
+    pub file_offset: u64,
+    /// The number of bytes of the segment when stored within the file.
+    pub file_size: u64,
+    /// The virtual address at which the segment should reside in memory.
+    pub virtual_address: u64,
+    /// The physical address at which the segment should reside.
+    pub physical_address: u64,
+    /// The number of bytes of the segment when loaded into memory.
+    pub memory_size: u64,
+    /// The alignment of the segment.
+    pub alignment: u64,
+}
+
+impl<C: ClassParse, E: EncodingParse> ProgramHeader<'_, C, E> {
+    /// Returns the [`NormalizedProgramHeader`] of this [`ProgramHeader`].
+    pub fn normalize(&self) -> NormalizedProgramHeader {
+        NormalizedProgramHeader {
+            segment_type: self.segment_type(),
+            flags: self.flags(),
+            file_offset: self.file_offset().into(),
+            file_size: self.file_size().into(),
+            virtual_address: self.virtual_address().into(),
+            physical_address: self.physical_address().into(),
+            memory_size: self.memory_size().into(),
+            alignment: self.alignment().into(),
+        }
+    }
+}
+
+/// Checks that the [`SegmentType::LOAD`] segments described by `a` and `b` have matching
+/// [`SegmentFlags`], in order.
+///
+/// The tables may be parsed from ELF files of differing [`Class`][c]es and encodings.
+///
+/// # Errors
+///
+/// - [`SegmentPermissionMismatch::CountMismatch`]: Returned if the tables describe differing
+///   numbers of [`SegmentType::LOAD`] segments.
+/// - [`SegmentPermissionMismatch::FlagsMismatch`]: Returned if a pair of corresponding
+///   [`SegmentType::LOAD`] segments have differing [`SegmentFlags`].
+///
+/// [c]: crate::ident::Class
+pub fn compare_segment_permissions<C1, E1, C2, E2>(
+    a: ProgramHeaderTable<'_, C1, E1>,
+    b: ProgramHeaderTable<'_, C2, E2>,
+) -> Result<(), SegmentPermissionMismatch>
+where
+    C1: ClassParse,
+    E1: EncodingParse,
+    C2: ClassParse,
+    E2: EncodingParse,
+{
+    let a_count = a.segments_of_type(SegmentType::LOAD).count();
+    let b_count = b.segments_of_type(SegmentType::LOAD).count();
+    if a_count != b_count {
+        return Err(SegmentPermissionMismatch::CountMismatch {
+            a: a_count,
+            b: b_count,
+        });
+    }
+
+    let pairs = a
+        .segments_of_type(SegmentType::LOAD)
+        .zip(b.segments_of_type(SegmentType::LOAD));
+    for (index, (a, b)) in pairs.enumerate() {
+        if a.flags() != b.flags() {
+            return Err(SegmentPermissionMismatch::FlagsMismatch {
+                index,
+                a: a.flags(),
+                b: b.flags(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Various mismatches that can occur when comparing the permissions of segments.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SegmentPermissionMismatch {
+    /// The numbers of [`SegmentType::LOAD`] segments differ.
+    CountMismatch {
+        /// The number of [`SegmentType::LOAD`] segments in the first table.
+        a: usize,
+        /// The number of [`SegmentType::LOAD`] segments in the second table.
+        b: usize,
+    },
+    /// The [`SegmentFlags`] of a pair of corresponding [`SegmentType::LOAD`] segments differ.
+    FlagsMismatch {
+        /// The index of the pair among the [`SegmentType::LOAD`] segments.
+        index: usize,
+        /// The [`SegmentFlags`] of the segment in the first table.
+        a: SegmentFlags,
+        /// The [`SegmentFlags`] of the segment in the second table.
+        b: SegmentFlags,
+    },
+}
+
+impl fmt::Display for SegmentPermissionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CountMismatch { a, b } => {
+                write!(f, "load segment count mismatch: {a} and {b}")
+            }
+            Self::FlagsMismatch { index, a, b } => write!(
+                f,
+                "load segment {index} flags mismatch: {:#x} and {:#x}",
+                a.0, b.0
+            ),
+        }
+    }
+}
+
+impl error::Error for SegmentPermissionMismatch {}
@@ -0,0 +1,710 @@
+//! Numeric constants mirroring those defined by `elf.h`.
+//!
+//! The typed wrappers used throughout this crate, such as [`ElfType`][et] and
+//! [`SegmentType`][st], remain the preferred interface. These constants are provided for code
+//! that interoperates with C definitions or matches on raw values read from other sources.
+//!
+//! [et]: crate::header::ElfType
+//! [st]: crate::program_header::SegmentType
+
+// Object file types (`e_type`).
+
+/// No file type.
+pub const ET_NONE: u16 = 0;
+/// Relocatable file.
+pub const ET_REL: u16 = 1;
+/// Executable file.
+pub const ET_EXEC: u16 = 2;
+/// Shared object file.
+pub const ET_DYN: u16 = 3;
+/// Core file.
+pub const ET_CORE: u16 = 4;
+/// Start of the OS-specific file types.
+pub const ET_LOOS: u16 = 0xFE00;
+/// End of the OS-specific file types.
+pub const ET_HIOS: u16 = 0xFEFF;
+/// Start of the processor-specific file types.
+pub const ET_LOPROC: u16 = 0xFF00;
+/// End of the processor-specific file types.
+pub const ET_HIPROC: u16 = 0xFFFF;
+
+// Machine architectures (`e_machine`).
+
+/// No machine.
+pub const EM_NONE: u16 = 0;
+/// AT&T WE 32100.
+pub const EM_M32: u16 = 1;
+/// SPARC.
+pub const EM_SPARC: u16 = 2;
+/// Intel 80386.
+pub const EM_386: u16 = 3;
+/// Motorola 68000.
+pub const EM_68K: u16 = 4;
+/// Motorola 88000.
+pub const EM_88K: u16 = 5;
+/// Intel MCU.
+pub const EM_IAMCU: u16 = 6;
+/// Intel 80860.
+pub const EM_860: u16 = 7;
+/// MIPS I.
+pub const EM_MIPS: u16 = 8;
+/// IBM System/370.
+pub const EM_S370: u16 = 9;
+/// MIPS RS3000, little endian.
+pub const EM_MIPS_RS3_LE: u16 = 10;
+/// HP PA-RISC.
+pub const EM_PARISC: u16 = 15;
+/// SPARC with enhanced instruction set.
+pub const EM_SPARC32PLUS: u16 = 18;
+/// PowerPC.
+pub const EM_PPC: u16 = 20;
+/// 64-bit PowerPC.
+pub const EM_PPC64: u16 = 21;
+/// IBM S/390.
+pub const EM_S390: u16 = 22;
+/// ARM 32-bit.
+pub const EM_ARM: u16 = 40;
+/// Hitachi SuperH.
+pub const EM_SH: u16 = 42;
+/// SPARC v9 64-bit.
+pub const EM_SPARCV9: u16 = 43;
+/// Intel Itanium.
+pub const EM_IA_64: u16 = 50;
+/// AMD x86-64.
+pub const EM_X86_64: u16 = 62;
+/// Atmel AVR 8-bit.
+pub const EM_AVR: u16 = 83;
+/// TI MSP430.
+pub const EM_MSP430: u16 = 105;
+/// Altera Nios II.
+pub const EM_ALTERA_NIOS2: u16 = 113;
+/// ARM 64-bit.
+pub const EM_AARCH64: u16 = 183;
+/// NVIDIA CUDA.
+pub const EM_CUDA: u16 = 190;
+/// Tilera TILE-Gx.
+pub const EM_TILEGX: u16 = 191;
+/// AMD GPU.
+pub const EM_AMDGPU: u16 = 224;
+/// RISC-V.
+pub const EM_RISCV: u16 = 243;
+/// Linux BPF.
+pub const EM_BPF: u16 = 247;
+/// C-SKY.
+pub const EM_CSKY: u16 = 252;
+/// LoongArch.
+pub const EM_LOONGARCH: u16 = 258;
+
+// Segment types (`p_type`).
+
+/// Unused program header table entry.
+pub const PT_NULL: u32 = 0;
+/// Loadable segment.
+pub const PT_LOAD: u32 = 1;
+/// Dynamic linking information.
+pub const PT_DYNAMIC: u32 = 2;
+/// Program interpreter path.
+pub const PT_INTERP: u32 = 3;
+/// Auxiliary information.
+pub const PT_NOTE: u32 = 4;
+/// Reserved.
+pub const PT_SHLIB: u32 = 5;
+/// Program header table.
+pub const PT_PHDR: u32 = 6;
+/// Thread-local storage template.
+pub const PT_TLS: u32 = 7;
+/// Start of the OS-specific segment types.
+pub const PT_LOOS: u32 = 0x6000_0000;
+/// GCC `.eh_frame_hdr` segment.
+pub const PT_GNU_EH_FRAME: u32 = 0x6474_E550;
+/// Stack executability.
+pub const PT_GNU_STACK: u32 = 0x6474_E551;
+/// Read-only after relocation.
+pub const PT_GNU_RELRO: u32 = 0x6474_E552;
+/// GNU property notes.
+pub const PT_GNU_PROPERTY: u32 = 0x6474_E553;
+/// SFrame stack trace information.
+pub const PT_GNU_SFRAME: u32 = 0x6474_E554;
+/// End of the OS-specific segment types.
+pub const PT_HIOS: u32 = 0x6FFF_FFFF;
+/// Start of the processor-specific segment types.
+pub const PT_LOPROC: u32 = 0x7000_0000;
+/// End of the processor-specific segment types.
+pub const PT_HIPROC: u32 = 0x7FFF_FFFF;
+
+// Segment flags (`p_flags`).
+
+/// Segment is executable.
+pub const PF_X: u32 = 0x1;
+/// Segment is writable.
+pub const PF_W: u32 = 0x2;
+/// Segment is readable.
+pub const PF_R: u32 = 0x4;
+/// OS-specific flags.
+pub const PF_MASKOS: u32 = 0xFF0_0000;
+/// Processor-specific flags.
+pub const PF_MASKPROC: u32 = 0xF000_0000;
+
+// Section types (`sh_type`).
+
+/// Inactive section header.
+pub const SHT_NULL: u32 = 0;
+/// Program-defined data.
+pub const SHT_PROGBITS: u32 = 1;
+/// Symbol table.
+pub const SHT_SYMTAB: u32 = 2;
+/// String table.
+pub const SHT_STRTAB: u32 = 3;
+/// Relocation entries with addends.
+pub const SHT_RELA: u32 = 4;
+/// Symbol hash table.
+pub const SHT_HASH: u32 = 5;
+/// Dynamic linking information.
+pub const SHT_DYNAMIC: u32 = 6;
+/// Notes.
+pub const SHT_NOTE: u32 = 7;
+/// Program-defined data occupying no file space.
+pub const SHT_NOBITS: u32 = 8;
+/// Relocation entries without addends.
+pub const SHT_REL: u32 = 9;
+/// Reserved.
+pub const SHT_SHLIB: u32 = 10;
+/// Dynamic linker symbol table.
+pub const SHT_DYNSYM: u32 = 11;
+/// Array of constructors.
+pub const SHT_INIT_ARRAY: u32 = 14;
+/// Array of destructors.
+pub const SHT_FINI_ARRAY: u32 = 15;
+/// Array of pre-constructors.
+pub const SHT_PREINIT_ARRAY: u32 = 16;
+/// Section group.
+pub const SHT_GROUP: u32 = 17;
+/// Extended section indices.
+pub const SHT_SYMTAB_SHNDX: u32 = 18;
+/// Relative relocation bitmaps.
+pub const SHT_RELR: u32 = 19;
+/// Start of the OS-specific section types.
+pub const SHT_LOOS: u32 = 0x6000_0000;
+/// Object attributes.
+pub const SHT_GNU_ATTRIBUTES: u32 = 0x6FFF_FFF5;
+/// GNU-style symbol hash table.
+pub const SHT_GNU_HASH: u32 = 0x6FFF_FFF6;
+/// Prelink library list.
+pub const SHT_GNU_LIBLIST: u32 = 0x6FFF_FFF7;
+/// Checksum for DSO content.
+pub const SHT_CHECKSUM: u32 = 0x6FFF_FFF8;
+/// Symbol version definitions.
+pub const SHT_GNU_VERDEF: u32 = 0x6FFF_FFFD;
+/// Symbol version requirements.
+pub const SHT_GNU_VERNEED: u32 = 0x6FFF_FFFE;
+/// Symbol version table.
+pub const SHT_GNU_VERSYM: u32 = 0x6FFF_FFFF;
+/// End of the OS-specific section types.
+pub const SHT_HIOS: u32 = 0x6FFF_FFFF;
+/// Start of the processor-specific section types.
+pub const SHT_LOPROC: u32 = 0x7000_0000;
+/// End of the processor-specific section types.
+pub const SHT_HIPROC: u32 = 0x7FFF_FFFF;
+/// Start of the application-specific section types.
+pub const SHT_LOUSER: u32 = 0x8000_0000;
+/// End of the application-specific section types.
+pub const SHT_HIUSER: u32 = 0x8FFF_FFFF;
+
+// Section flags (`sh_flags`).
+
+/// Writable during execution.
+pub const SHF_WRITE: u32 = 0x1;
+/// Occupies memory during execution.
+pub const SHF_ALLOC: u32 = 0x2;
+/// Contains executable instructions.
+pub const SHF_EXECINSTR: u32 = 0x4;
+/// May be merged to eliminate duplicates.
+pub const SHF_MERGE: u32 = 0x10;
+/// Contains null-terminated strings.
+pub const SHF_STRINGS: u32 = 0x20;
+/// `sh_info` holds a section index.
+pub const SHF_INFO_LINK: u32 = 0x40;
+/// Preserve order after combining.
+pub const SHF_LINK_ORDER: u32 = 0x80;
+/// Requires OS-specific processing.
+pub const SHF_OS_NONCONFORMING: u32 = 0x100;
+/// Member of a section group.
+pub const SHF_GROUP: u32 = 0x200;
+/// Holds thread-local storage.
+pub const SHF_TLS: u32 = 0x400;
+/// Holds compressed data.
+pub const SHF_COMPRESSED: u32 = 0x800;
+/// OS-specific flags.
+pub const SHF_MASKOS: u32 = 0xFF0_0000;
+/// Not to be garbage collected by the linker.
+pub const SHF_GNU_RETAIN: u32 = 0x20_0000;
+/// Processor-specific flags.
+pub const SHF_MASKPROC: u32 = 0xF000_0000;
+/// Special ordering requirement.
+pub const SHF_ORDERED: u32 = 0x4000_0000;
+/// Excluded unless referenced or allocated.
+pub const SHF_EXCLUDE: u32 = 0x8000_0000;
+
+// Special section indices.
+
+/// Undefined section.
+pub const SHN_UNDEF: u16 = 0;
+/// Start of the reserved indices.
+pub const SHN_LORESERVE: u16 = 0xFF00;
+/// Start of the processor-specific indices.
+pub const SHN_LOPROC: u16 = 0xFF00;
+/// End of the processor-specific indices.
+pub const SHN_HIPROC: u16 = 0xFF1F;
+/// Start of the OS-specific indices.
+pub const SHN_LOOS: u16 = 0xFF20;
+/// End of the OS-specific indices.
+pub const SHN_HIOS: u16 = 0xFF3F;
+/// Absolute values.
+pub const SHN_ABS: u16 = 0xFFF1;
+/// Common symbols.
+pub const SHN_COMMON: u16 = 0xFFF2;
+/// Index is held in an extended section index table.
+pub const SHN_XINDEX: u16 = 0xFFFF;
+/// End of the reserved indices.
+pub const SHN_HIRESERVE: u16 = 0xFFFF;
+
+// Symbol bindings (`ELF_ST_BIND`).
+
+/// Local symbol.
+pub const STB_LOCAL: u8 = 0;
+/// Global symbol.
+pub const STB_GLOBAL: u8 = 1;
+/// Weak symbol.
+pub const STB_WEAK: u8 = 2;
+/// Unique symbol.
+pub const STB_GNU_UNIQUE: u8 = 10;
+/// Start of the OS-specific bindings.
+pub const STB_LOOS: u8 = 10;
+/// End of the OS-specific bindings.
+pub const STB_HIOS: u8 = 12;
+/// Start of the processor-specific bindings.
+pub const STB_LOPROC: u8 = 13;
+/// End of the processor-specific bindings.
+pub const STB_HIPROC: u8 = 15;
+
+// Symbol types (`ELF_ST_TYPE`).
+
+/// Unspecified type.
+pub const STT_NOTYPE: u8 = 0;
+/// Data object.
+pub const STT_OBJECT: u8 = 1;
+/// Code object.
+pub const STT_FUNC: u8 = 2;
+/// Section.
+pub const STT_SECTION: u8 = 3;
+/// Source file name.
+pub const STT_FILE: u8 = 4;
+/// Common data object.
+pub const STT_COMMON: u8 = 5;
+/// Thread-local data object.
+pub const STT_TLS: u8 = 6;
+/// Indirect code object.
+pub const STT_GNU_IFUNC: u8 = 10;
+/// Start of the OS-specific types.
+pub const STT_LOOS: u8 = 10;
+/// End of the OS-specific types.
+pub const STT_HIOS: u8 = 12;
+/// Start of the processor-specific types.
+pub const STT_LOPROC: u8 = 13;
+/// End of the processor-specific types.
+pub const STT_HIPROC: u8 = 15;
+
+// Symbol visibilities (`ELF_ST_VISIBILITY`).
+
+/// Visibility given by the binding.
+pub const STV_DEFAULT: u8 = 0;
+/// Processor-specific hidden class.
+pub const STV_INTERNAL: u8 = 1;
+/// Not visible to other components.
+pub const STV_HIDDEN: u8 = 2;
+/// Visible but not preemptible.
+pub const STV_PROTECTED: u8 = 3;
+
+// Dynamic array tags (`d_tag`).
+
+/// End of the dynamic array.
+pub const DT_NULL: i64 = 0;
+/// Name of a needed library.
+pub const DT_NEEDED: i64 = 1;
+/// Size of the PLT relocations.
+pub const DT_PLTRELSZ: i64 = 2;
+/// Address of the PLT or GOT.
+pub const DT_PLTGOT: i64 = 3;
+/// Address of the symbol hash table.
+pub const DT_HASH: i64 = 4;
+/// Address of the string table.
+pub const DT_STRTAB: i64 = 5;
+/// Address of the symbol table.
+pub const DT_SYMTAB: i64 = 6;
+/// Address of the relocations with addends.
+pub const DT_RELA: i64 = 7;
+/// Size of the relocations with addends.
+pub const DT_RELASZ: i64 = 8;
+/// Size of a relocation with addend.
+pub const DT_RELAENT: i64 = 9;
+/// Size of the string table.
+pub const DT_STRSZ: i64 = 10;
+/// Size of a symbol table entry.
+pub const DT_SYMENT: i64 = 11;
+/// Address of the initialization function.
+pub const DT_INIT: i64 = 12;
+/// Address of the termination function.
+pub const DT_FINI: i64 = 13;
+/// Name of the shared object.
+pub const DT_SONAME: i64 = 14;
+/// Library search path (deprecated).
+pub const DT_RPATH: i64 = 15;
+/// Start symbol search within the object.
+pub const DT_SYMBOLIC: i64 = 16;
+/// Address of the relocations without addends.
+pub const DT_REL: i64 = 17;
+/// Size of the relocations without addends.
+pub const DT_RELSZ: i64 = 18;
+/// Size of a relocation without addend.
+pub const DT_RELENT: i64 = 19;
+/// Type of the PLT relocations.
+pub const DT_PLTREL: i64 = 20;
+/// Reserved for debugger use.
+pub const DT_DEBUG: i64 = 21;
+/// Relocations may modify a non-writable segment.
+pub const DT_TEXTREL: i64 = 22;
+/// Address of the PLT relocations.
+pub const DT_JMPREL: i64 = 23;
+/// Process relocations at load time.
+pub const DT_BIND_NOW: i64 = 24;
+/// Address of the constructor array.
+pub const DT_INIT_ARRAY: i64 = 25;
+/// Address of the destructor array.
+pub const DT_FINI_ARRAY: i64 = 26;
+/// Size of the constructor array.
+pub const DT_INIT_ARRAYSZ: i64 = 27;
+/// Size of the destructor array.
+pub const DT_FINI_ARRAYSZ: i64 = 28;
+/// Library search path.
+pub const DT_RUNPATH: i64 = 29;
+/// Flags for the object.
+pub const DT_FLAGS: i64 = 30;
+/// Start of the encoded tags.
+pub const DT_ENCODING: i64 = 32;
+/// Address of the pre-constructor array.
+pub const DT_PREINIT_ARRAY: i64 = 32;
+/// Size of the pre-constructor array.
+pub const DT_PREINIT_ARRAYSZ: i64 = 33;
+/// Address of the extended section index table.
+pub const DT_SYMTAB_SHNDX: i64 = 34;
+/// Size of the relative relocation bitmaps.
+pub const DT_RELRSZ: i64 = 35;
+/// Address of the relative relocation bitmaps.
+pub const DT_RELR: i64 = 36;
+/// Size of a relative relocation bitmap entry.
+pub const DT_RELRENT: i64 = 37;
+/// Start of the OS-specific tags.
+pub const DT_LOOS: i64 = 0x6000_000D;
+/// End of the OS-specific tags.
+pub const DT_HIOS: i64 = 0x6FFF_F000;
+/// Address of the GNU-style symbol hash table.
+pub const DT_GNU_HASH: i64 = 0x6FFF_FEF5;
+/// Address of the symbol version table.
+pub const DT_VERSYM: i64 = 0x6FFF_FFF0;
+/// Number of relative relocations with addends.
+pub const DT_RELACOUNT: i64 = 0x6FFF_FFF9;
+/// Number of relative relocations without addends.
+pub const DT_RELCOUNT: i64 = 0x6FFF_FFFA;
+/// Additional flags for the object.
+pub const DT_FLAGS_1: i64 = 0x6FFF_FFFB;
+/// Address of the version definitions.
+pub const DT_VERDEF: i64 = 0x6FFF_FFFC;
+/// Number of version definitions.
+pub const DT_VERDEFNUM: i64 = 0x6FFF_FFFD;
+/// Address of the version requirements.
+pub const DT_VERNEED: i64 = 0x6FFF_FFFE;
+/// Number of version requirements.
+pub const DT_VERNEEDNUM: i64 = 0x6FFF_FFFF;
+/// Start of the processor-specific tags.
+pub const DT_LOPROC: i64 = 0x7000_0000;
+/// End of the processor-specific tags.
+pub const DT_HIPROC: i64 = 0x7FFF_FFFF;
+
+// Flags of `DT_FLAGS`.
+
+/// Object may use `$ORIGIN`.
+pub const DF_ORIGIN: u64 = 0x1;
+/// Start symbol search within the object.
+pub const DF_SYMBOLIC: u64 = 0x2;
+/// Relocations may modify a non-writable segment.
+pub const DF_TEXTREL: u64 = 0x4;
+/// Process relocations at load time.
+pub const DF_BIND_NOW: u64 = 0x8;
+/// Object uses the static TLS model.
+pub const DF_STATIC_TLS: u64 = 0x10;
+
+// Flags of `DT_FLAGS_1`.
+
+/// Process relocations at load time.
+pub const DF_1_NOW: u64 = 0x1;
+/// Make symbols available to subsequently loaded objects.
+pub const DF_1_GLOBAL: u64 = 0x2;
+/// Object may not be unloaded.
+pub const DF_1_NODELETE: u64 = 0x8;
+/// Run initializers before those of other objects.
+pub const DF_1_INITFIRST: u64 = 0x20;
+/// Object may not be opened with `dlopen`.
+pub const DF_1_NOOPEN: u64 = 0x40;
+/// Object may use `$ORIGIN`.
+pub const DF_1_ORIGIN: u64 = 0x80;
+/// Object is a position-independent executable.
+pub const DF_1_PIE: u64 = 0x800_0000;
+
+// Note types for notes owned by `GNU`.
+
+/// ABI information.
+pub const NT_GNU_ABI_TAG: u32 = 1;
+/// Synthetic hardware capabilities.
+pub const NT_GNU_HWCAP: u32 = 2;
+/// Unique build identifier.
+pub const NT_GNU_BUILD_ID: u32 = 3;
+/// Version of the gold linker.
+pub const NT_GNU_GOLD_VERSION: u32 = 4;
+/// Program properties.
+pub const NT_GNU_PROPERTY_TYPE_0: u32 = 5;
+
+// Note types for notes owned by `CORE` in core files.
+
+/// Process status.
+pub const NT_PRSTATUS: u32 = 1;
+/// Floating point registers.
+pub const NT_PRFPREG: u32 = 2;
+/// Process information.
+pub const NT_PRPSINFO: u32 = 3;
+/// Task structure.
+pub const NT_TASKSTRUCT: u32 = 4;
+/// Auxiliary vector.
+pub const NT_AUXV: u32 = 6;
+/// Signal information.
+pub const NT_SIGINFO: u32 = 0x5349_4749;
+/// Mapped files.
+pub const NT_FILE: u32 = 0x4649_4C45;
+
+// Relocation types for `EM_386`.
+
+/// No relocation.
+pub const R_386_NONE: u32 = 0;
+/// Direct 32-bit.
+pub const R_386_32: u32 = 1;
+/// PC-relative 32-bit.
+pub const R_386_PC32: u32 = 2;
+/// 32-bit GOT entry.
+pub const R_386_GOT32: u32 = 3;
+/// 32-bit PLT address.
+pub const R_386_PLT32: u32 = 4;
+/// Copy symbol at runtime.
+pub const R_386_COPY: u32 = 5;
+/// Create GOT entry.
+pub const R_386_GLOB_DAT: u32 = 6;
+/// Create PLT entry.
+pub const R_386_JMP_SLOT: u32 = 7;
+/// Adjust by program base.
+pub const R_386_RELATIVE: u32 = 8;
+/// 32-bit offset to GOT.
+pub const R_386_GOTOFF: u32 = 9;
+/// 32-bit PC-relative offset to GOT.
+pub const R_386_GOTPC: u32 = 10;
+/// Adjust indirectly by program base.
+pub const R_386_IRELATIVE: u32 = 42;
+
+// Relocation types for `EM_X86_64`.
+
+/// No relocation.
+pub const R_X86_64_NONE: u32 = 0;
+/// Direct 64-bit.
+pub const R_X86_64_64: u32 = 1;
+/// PC-relative 32-bit signed.
+pub const R_X86_64_PC32: u32 = 2;
+/// 32-bit GOT entry.
+pub const R_X86_64_GOT32: u32 = 3;
+/// 32-bit PLT address.
+pub const R_X86_64_PLT32: u32 = 4;
+/// Copy symbol at runtime.
+pub const R_X86_64_COPY: u32 = 5;
+/// Create GOT entry.
+pub const R_X86_64_GLOB_DAT: u32 = 6;
+/// Create PLT entry.
+pub const R_X86_64_JUMP_SLOT: u32 = 7;
+/// Adjust by program base.
+pub const R_X86_64_RELATIVE: u32 = 8;
+/// 32-bit signed PC-relative offset to GOT.
+pub const R_X86_64_GOTPCREL: u32 = 9;
+/// Direct 32-bit zero extended.
+pub const R_X86_64_32: u32 = 10;
+/// Direct 32-bit sign extended.
+pub const R_X86_64_32S: u32 = 11;
+/// Direct 16-bit zero extended.
+pub const R_X86_64_16: u32 = 12;
+/// 16-bit sign extended PC-relative.
+pub const R_X86_64_PC16: u32 = 13;
+/// Direct 8-bit sign extended.
+pub const R_X86_64_8: u32 = 14;
+/// 8-bit sign extended PC-relative.
+pub const R_X86_64_PC8: u32 = 15;
+/// ID of the module containing the symbol.
+pub const R_X86_64_DTPMOD64: u32 = 16;
+/// Offset in the module's TLS block.
+pub const R_X86_64_DTPOFF64: u32 = 17;
+/// Offset in the initial TLS block.
+pub const R_X86_64_TPOFF64: u32 = 18;
+/// PC-relative offset to a GD GOT entry.
+pub const R_X86_64_TLSGD: u32 = 19;
+/// PC-relative offset to an LD GOT entry.
+pub const R_X86_64_TLSLD: u32 = 20;
+/// 32-bit offset in the TLS block.
+pub const R_X86_64_DTPOFF32: u32 = 21;
+/// PC-relative offset to an IE GOT entry.
+pub const R_X86_64_GOTTPOFF: u32 = 22;
+/// 32-bit offset in the initial TLS block.
+pub const R_X86_64_TPOFF32: u32 = 23;
+/// PC-relative 64-bit.
+pub const R_X86_64_PC64: u32 = 24;
+/// 64-bit offset to GOT.
+pub const R_X86_64_GOTOFF64: u32 = 25;
+/// 32-bit signed PC-relative offset to GOT.
+pub const R_X86_64_GOTPC32: u32 = 26;
+/// 32-bit symbol size.
+pub const R_X86_64_SIZE32: u32 = 32;
+/// 64-bit symbol size.
+pub const R_X86_64_SIZE64: u32 = 33;
+/// Adjust indirectly by program base.
+pub const R_X86_64_IRELATIVE: u32 = 37;
+/// Relaxable 32-bit signed PC-relative offset to GOT.
+pub const R_X86_64_GOTPCRELX: u32 = 41;
+/// Relaxable 32-bit signed PC-relative offset to GOT with a REX prefix.
+pub const R_X86_64_REX_GOTPCRELX: u32 = 42;
+
+// Relocation types for `EM_ARM`.
+
+/// No relocation.
+pub const R_ARM_NONE: u32 = 0;
+/// Direct 32-bit.
+pub const R_ARM_ABS32: u32 = 2;
+/// PC-relative 32-bit.
+pub const R_ARM_REL32: u32 = 3;
+/// Copy symbol at runtime.
+pub const R_ARM_COPY: u32 = 20;
+/// Create GOT entry.
+pub const R_ARM_GLOB_DAT: u32 = 21;
+/// Create PLT entry.
+pub const R_ARM_JUMP_SLOT: u32 = 22;
+/// Adjust by program base.
+pub const R_ARM_RELATIVE: u32 = 23;
+/// Adjust indirectly by program base.
+pub const R_ARM_IRELATIVE: u32 = 160;
+
+// Relocation types for `EM_AARCH64`.
+
+/// No relocation.
+pub const R_AARCH64_NONE: u32 = 0;
+/// Direct 64-bit.
+pub const R_AARCH64_ABS64: u32 = 257;
+/// Direct 32-bit.
+pub const R_AARCH64_ABS32: u32 = 258;
+/// Direct 16-bit.
+pub const R_AARCH64_ABS16: u32 = 259;
+/// PC-relative 64-bit.
+pub const R_AARCH64_PREL64: u32 = 260;
+/// PC-relative 32-bit.
+pub const R_AARCH64_PREL32: u32 = 261;
+/// PC-relative 16-bit.
+pub const R_AARCH64_PREL16: u32 = 262;
+/// Bits 0 to 15 of a direct address, with overflow check.
+pub const R_AARCH64_MOVW_UABS_G0: u32 = 263;
+/// Bits 0 to 15 of a direct address.
+pub const R_AARCH64_MOVW_UABS_G0_NC: u32 = 264;
+/// Bits 16 to 31 of a direct address, with overflow check.
+pub const R_AARCH64_MOVW_UABS_G1: u32 = 265;
+/// Bits 16 to 31 of a direct address.
+pub const R_AARCH64_MOVW_UABS_G1_NC: u32 = 266;
+/// Bits 32 to 47 of a direct address, with overflow check.
+pub const R_AARCH64_MOVW_UABS_G2: u32 = 267;
+/// Bits 32 to 47 of a direct address.
+pub const R_AARCH64_MOVW_UABS_G2_NC: u32 = 268;
+/// Bits 48 to 63 of a direct address.
+pub const R_AARCH64_MOVW_UABS_G3: u32 = 269;
+/// PC-relative 21-bit literal load.
+pub const R_AARCH64_LD_PREL_LO19: u32 = 273;
+/// PC-relative 21-bit `ADR` offset.
+pub const R_AARCH64_ADR_PREL_LO21: u32 = 274;
+/// PC-relative page offset of `ADRP`, with overflow check.
+pub const R_AARCH64_ADR_PREL_PG_HI21: u32 = 275;
+/// PC-relative page offset of `ADRP`.
+pub const R_AARCH64_ADR_PREL_PG_HI21_NC: u32 = 276;
+/// Bits 0 to 11 of a direct address, for `ADD`.
+pub const R_AARCH64_ADD_ABS_LO12_NC: u32 = 277;
+/// Bits 0 to 11 of a direct address, for 8-bit loads and stores.
+pub const R_AARCH64_LDST8_ABS_LO12_NC: u32 = 278;
+/// PC-relative 16-bit test and branch.
+pub const R_AARCH64_TSTBR14: u32 = 279;
+/// PC-relative 21-bit conditional branch.
+pub const R_AARCH64_CONDBR19: u32 = 280;
+/// PC-relative 28-bit branch.
+pub const R_AARCH64_JUMP26: u32 = 282;
+/// PC-relative 28-bit call.
+pub const R_AARCH64_CALL26: u32 = 283;
+/// Bits 1 to 11 of a direct address, for 16-bit loads and stores.
+pub const R_AARCH64_LDST16_ABS_LO12_NC: u32 = 284;
+/// Bits 2 to 11 of a direct address, for 32-bit loads and stores.
+pub const R_AARCH64_LDST32_ABS_LO12_NC: u32 = 285;
+/// Bits 3 to 11 of a direct address, for 64-bit loads and stores.
+pub const R_AARCH64_LDST64_ABS_LO12_NC: u32 = 286;
+/// Bits 4 to 11 of a direct address, for 128-bit loads and stores.
+pub const R_AARCH64_LDST128_ABS_LO12_NC: u32 = 299;
+/// Copy symbol at runtime.
+pub const R_AARCH64_COPY: u32 = 1024;
+/// Create GOT entry.
+pub const R_AARCH64_GLOB_DAT: u32 = 1025;
+/// Create PLT entry.
+pub const R_AARCH64_JUMP_SLOT: u32 = 1026;
+/// Adjust by program base.
+pub const R_AARCH64_RELATIVE: u32 = 1027;
+/// ID of the module containing the symbol.
+pub const R_AARCH64_TLS_DTPMOD: u32 = 1028;
+/// Offset in the module's TLS block.
+pub const R_AARCH64_TLS_DTPREL: u32 = 1029;
+/// Offset in the initial TLS block.
+pub const R_AARCH64_TLS_TPREL: u32 = 1030;
+/// TLS descriptor.
+pub const R_AARCH64_TLSDESC: u32 = 1031;
+/// Adjust indirectly by program base.
+pub const R_AARCH64_IRELATIVE: u32 = 1032;
+
+// Relocation types for `EM_RISCV`.
+
+/// No relocation.
+pub const R_RISCV_NONE: u32 = 0;
+/// Direct 32-bit.
+pub const R_RISCV_32: u32 = 1;
+/// Direct 64-bit.
+pub const R_RISCV_64: u32 = 2;
+/// Adjust by program base.
+pub const R_RISCV_RELATIVE: u32 = 3;
+/// Copy symbol at runtime.
+pub const R_RISCV_COPY: u32 = 4;
+/// Create PLT entry.
+pub const R_RISCV_JUMP_SLOT: u32 = 5;
+/// 32-bit ID of the module containing the symbol.
+pub const R_RISCV_TLS_DTPMOD32: u32 = 6;
+/// 64-bit ID of the module containing the symbol.
+pub const R_RISCV_TLS_DTPMOD64: u32 = 7;
+/// 32-bit offset in the module's TLS block.
+pub const R_RISCV_TLS_DTPREL32: u32 = 8;
+/// 64-bit offset in the module's TLS block.
+pub const R_RISCV_TLS_DTPREL64: u32 = 9;
+/// 32-bit offset in the initial TLS block.
+pub const R_RISCV_TLS_TPREL32: u32 = 10;
+/// 64-bit offset in the initial TLS block.
+pub const R_RISCV_TLS_TPREL64: u32 = 11;
+/// Adjust indirectly by program base.
+pub const R_RISCV_IRELATIVE: u32 = 58;
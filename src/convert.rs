@@ -0,0 +1,296 @@
+//! Definitions for converting simple ELF images between the 32-bit and 64-bit containers.
+//!
+//! Only the ELF header, the program header table, and the file data of each segment are carried
+//! over. The section header table is dropped, and files with dynamic linking information or a
+//! [`SegmentType::PHDR`] segment are rejected.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, Class32, Class64, ClassParse, OffsetOverflow},
+    encoding::EncodingParse,
+    ident::{Class, Encoding},
+    program_header::{ProgramHeader, SegmentType},
+    program_header_edit::ProgramHeaderFields,
+    writer::{ByteLayout, Writer},
+    ElfFile,
+};
+
+/// The size of the ELF identifier.
+const IDENT_SIZE: usize = 16;
+/// The index of the class in the ELF identifier.
+const CLASS_INDEX: usize = 4;
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the number of bytes required to hold this [`ElfFile`] converted to `class`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ElfFile::convert_class()`], except for
+    /// [`ConvertClassError::BufferTooSmall`].
+    pub fn converted_size(&self, class: Class) -> Result<usize, ConvertClassError> {
+        self.conversion_layout(class).map(|layout| layout.size)
+    }
+
+    /// Writes this [`ElfFile`] converted to `class` into the start of `buffer`, returning the
+    /// number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`ConvertClassError::UnsupportedClass`]: Returned if `class` is neither
+    ///   [`Class::CLASS32`] nor [`Class::CLASS64`].
+    /// - [`ConvertClassError::UnsupportedSegment`]: Returned if this [`ElfFile`] contains a
+    ///   [`SegmentType::DYNAMIC`], [`SegmentType::INTERPRETER`], or [`SegmentType::PHDR`]
+    ///   segment.
+    /// - [`ConvertClassError::ValueOutOfRange`]: Returned if a value cannot be represented in
+    ///   `class`.
+    /// - [`ConvertClassError::SegmentOutOfBounds`]: Returned if the file data of a segment is
+    ///   located out of bounds.
+    /// - [`ConvertClassError::OffsetOverflow`]: Returned if an offset cannot be represented by a
+    ///   [`usize`] on the host.
+    /// - [`ConvertClassError::BufferTooSmall`]: Returned if `buffer` is smaller than
+    ///   [`ElfFile::converted_size()`].
+    pub fn convert_class(
+        &self,
+        class: Class,
+        buffer: &mut [u8],
+    ) -> Result<usize, ConvertClassError> {
+        let layout = self.conversion_layout(class)?;
+        let buffer = buffer
+            .get_mut(..layout.size)
+            .ok_or(ConvertClassError::BufferTooSmall {
+                required: layout.size,
+            })?;
+        buffer.fill(0);
+
+        let header = self.header();
+        let mut writer = Writer {
+            buffer,
+            layout: ByteLayout {
+                big_endian: header.ident().encoding() == Encoding::MSB2,
+                wide: layout.wide,
+            },
+        };
+
+        writer.buffer[..IDENT_SIZE].copy_from_slice(&self.bytes[..IDENT_SIZE]);
+        writer.buffer[CLASS_INDEX] = class.0;
+        writer.u16(IDENT_SIZE, header.elf_type().0);
+        writer.u16(IDENT_SIZE + 2, header.machine().0);
+        writer.u32(IDENT_SIZE + 4, header.file_version());
+
+        let (entry, flags) = if layout.wide { (24, 48) } else { (24, 36) };
+        let program_header_offset = entry + writer.word_size();
+        write_word(&mut writer, entry, header.entry().into())?;
+        write_word(
+            &mut writer,
+            program_header_offset,
+            layout.header_size as u64,
+        )?;
+        writer.u32(flags, header.flags());
+        writer.u16(flags + 4, layout.header_size as u16);
+        writer.u16(flags + 6, layout.program_header_size as u16);
+        writer.u16(flags + 8, header.program_header_count());
+
+        let Some(table) = self.program_header_table() else {
+            return Ok(layout.size);
+        };
+        for (index, program_header) in table.into_iter().enumerate() {
+            let start = layout.header_size + index * layout.program_header_size;
+            let offset = converted_offset(program_header, layout.shift)?;
+            write_program_header(&mut writer, start, program_header, offset)?;
+
+            if program_header.file_size().into() != 0 {
+                let data = self
+                    .segment_data(program_header)
+                    .ok_or(ConvertClassError::SegmentOutOfBounds)?;
+                let offset = to_len(offset)?;
+                writer.buffer[offset..offset + data.len()].copy_from_slice(data);
+            }
+        }
+
+        Ok(layout.size)
+    }
+
+    /// Computes the [`ConversionLayout`] of this [`ElfFile`] converted to `class`.
+    fn conversion_layout(&self, class: Class) -> Result<ConversionLayout, ConvertClassError> {
+        let (wide, header_size, program_header_size) = match class {
+            Class::CLASS32 => (false, 52, 32),
+            Class::CLASS64 => (true, 64, 56),
+            _ => return Err(ConvertClassError::UnsupportedClass),
+        };
+
+        let count = usize::from(self.header().program_header_count());
+        let headers_end = header_size + count * program_header_size;
+
+        let mut data_start = u64::MAX;
+        let mut alignment = 1u64;
+        if let Some(table) = self.program_header_table() {
+            for program_header in table {
+                match program_header.segment_type() {
+                    SegmentType::DYNAMIC | SegmentType::INTERPRETER | SegmentType::PHDR => {
+                        return Err(ConvertClassError::UnsupportedSegment);
+                    }
+                    _ => {}
+                }
+
+                if program_header.file_size().into() != 0 {
+                    data_start = data_start.min(program_header.file_offset().into());
+                }
+                alignment = alignment.max(program_header.alignment().into());
+            }
+        }
+
+        let shift = (headers_end as u64)
+            .saturating_sub(data_start)
+            .checked_next_multiple_of(alignment)
+            .ok_or(ConvertClassError::ValueOutOfRange)?;
+
+        let mut size = headers_end;
+        if let Some(table) = self.program_header_table() {
+            for program_header in table {
+                let end = converted_offset(program_header, shift)?
+                    .checked_add(program_header.file_size().into())
+                    .ok_or(ConvertClassError::ValueOutOfRange)?;
+                if program_header.file_size().into() != 0 {
+                    size = size.max(to_len(end)?);
+                }
+            }
+        }
+
+        Ok(ConversionLayout {
+            wide,
+            header_size,
+            program_header_size,
+            shift,
+            size,
+        })
+    }
+}
+
+/// Returns the file offset of the segment controlled by `program_header` after conversion.
+fn converted_offset<C: ClassParse, E: EncodingParse>(
+    program_header: ProgramHeader<'_, C, E>,
+    shift: u64,
+) -> Result<u64, ConvertClassError> {
+    if program_header.file_size().into() == 0 {
+        return Ok(program_header.file_offset().into());
+    }
+
+    Into::<u64>::into(program_header.file_offset())
+        .checked_add(shift)
+        .ok_or(ConvertClassError::ValueOutOfRange)
+}
+
+/// The layout of a converted [`ElfFile`].
+#[derive(Clone, Copy, Debug)]
+struct ConversionLayout {
+    /// Whether the converted [`ElfFile`] is 64-bit.
+    wide: bool,
+    /// The size of the converted ELF header.
+    header_size: usize,
+    /// The size of a converted program header.
+    program_header_size: usize,
+    /// The distance by which segment data is moved to make room for the headers.
+    shift: u64,
+    /// The total size of the converted [`ElfFile`].
+    size: usize,
+}
+
+/// Writes `value` at `offset` as a class sized value.
+fn write_word(writer: &mut Writer<'_>, offset: usize, value: u64) -> Result<(), ConvertClassError> {
+    if !writer.layout.fits_word(value) {
+        return Err(ConvertClassError::ValueOutOfRange);
+    }
+
+    writer.word(offset, value);
+    Ok(())
+}
+
+/// Writes `program_header` at `start`, with its file offset replaced by `offset`.
+fn write_program_header<C: ClassParse, E: EncodingParse>(
+    writer: &mut Writer<'_>,
+    start: usize,
+    program_header: ProgramHeader<'_, C, E>,
+    offset: u64,
+) -> Result<(), ConvertClassError> {
+    let fields = if writer.layout.wide {
+        ProgramHeaderFields::new(Class64)
+    } else {
+        ProgramHeaderFields::new(Class32)
+    };
+
+    writer.u32(start + fields.segment_type, program_header.segment_type().0);
+    writer.u32(start + fields.flags, program_header.flags().0);
+    write_word(writer, start + fields.file_offset, offset)?;
+    write_word(
+        writer,
+        start + fields.virtual_address,
+        program_header.virtual_address().into(),
+    )?;
+    write_word(
+        writer,
+        start + fields.physical_address,
+        program_header.physical_address().into(),
+    )?;
+    write_word(
+        writer,
+        start + fields.file_size,
+        program_header.file_size().into(),
+    )?;
+    write_word(
+        writer,
+        start + fields.memory_size,
+        program_header.memory_size().into(),
+    )?;
+    write_word(
+        writer,
+        start + fields.alignment,
+        program_header.alignment().into(),
+    )
+}
+
+/// Various errors that can occur while converting an [`ElfFile`] to another [`Class`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ConvertClassError {
+    /// The target [`Class`] is not supported.
+    UnsupportedClass,
+    /// The [`ElfFile`] contains a segment that cannot be converted.
+    UnsupportedSegment,
+    /// A value cannot be represented in the target [`Class`].
+    ValueOutOfRange,
+    /// The file data of a segment is located out of bounds.
+    SegmentOutOfBounds,
+    /// A class sized value cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
+    /// The buffer is too small to hold the converted [`ElfFile`].
+    BufferTooSmall {
+        /// The number of bytes required to hold the converted [`ElfFile`].
+        required: usize,
+    },
+}
+
+impl From<OffsetOverflow> for ConvertClassError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl fmt::Display for ConvertClassError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedClass => write!(f, "unsupported target class"),
+            Self::UnsupportedSegment => write!(f, "segment cannot be converted"),
+            Self::ValueOutOfRange => write!(f, "value cannot be represented in target class"),
+            Self::SegmentOutOfBounds => write!(f, "segment located out of bounds"),
+            Self::OffsetOverflow(error) => write!(f, "error while converting offset: {error}"),
+            Self::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "buffer too small to hold converted file of {required} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ConvertClassError {}
@@ -0,0 +1,359 @@
+//! Definitions for building the notes of a core dump.
+//!
+//! [`CoreNoteBuilder`] emits the notes in the order written by `gdb`'s `gcore`: the
+//! [`NoteType::CORE_PRPSINFO`] note, then the [`NoteType::CORE_PRSTATUS`] note of each thread
+//! followed by its [`NoteType::CORE_FPREGSET`] note, then the [`NoteType::CORE_AUXV`] and
+//! [`NoteType::CORE_FILE`] notes. Debuggers attach each register note to the thread of the
+//! preceding [`NoteType::CORE_PRSTATUS`] note, and treat the first thread as the one that
+//! crashed.
+//!
+//! As Linux does for both classes, names and descriptors are padded to a multiple of 4 bytes, so
+//! the [`SegmentType::NOTE`][note] segment holding the notes should have an alignment of 4.
+//!
+//! [note]: crate::program_header::SegmentType::NOTE
+
+use core::{error, fmt};
+
+use crate::{
+    auxv::AuxvEntry,
+    ident::{Class, Encoding},
+    note::{NoteType, OWNER_CORE},
+    writer::ByteLayout,
+};
+
+/// The alignment of the names and descriptors of the notes of a core dump.
+const NOTE_ALIGNMENT: usize = 4;
+
+/// The notes describing a single thread of a core dump.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ThreadNotes<'data> {
+    /// The descriptor of the [`NoteType::CORE_PRSTATUS`] note, an `elf_prstatus` structure of the
+    /// target.
+    pub status: &'data [u8],
+    /// The descriptor of the [`NoteType::CORE_FPREGSET`] note, an `elf_fpregset_t` structure of
+    /// the target, if the thread has floating point registers.
+    pub fp_registers: Option<&'data [u8]>,
+}
+
+/// A file mapped by the dumped process, recorded in the [`NoteType::CORE_FILE`] note.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MappedFile<'data> {
+    /// The address of the start of the mapping.
+    pub start: u64,
+    /// The address of the end of the mapping.
+    pub end: u64,
+    /// The offset of the mapping within the file, in pages.
+    pub page_offset: u64,
+    /// The path of the file, without a null terminator.
+    pub path: &'data [u8],
+}
+
+/// A builder of the notes of a core dump.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct CoreNoteBuilder<'data> {
+    /// The [`Class`] of the core dump.
+    class: Class,
+    /// The [`Encoding`] of the core dump.
+    encoding: Encoding,
+    /// The descriptor of the [`NoteType::CORE_PRPSINFO`] note.
+    process_info: &'data [u8],
+    /// The [`ThreadNotes`] of each thread, starting with the thread that crashed.
+    threads: &'data [ThreadNotes<'data>],
+    /// The entries of the [`NoteType::CORE_AUXV`] note, if present.
+    auxiliary_vector: Option<&'data [AuxvEntry]>,
+    /// The page size and the [`MappedFile`]s of the [`NoteType::CORE_FILE`] note, if present.
+    mapped_files: Option<(u64, &'data [MappedFile<'data>])>,
+}
+
+impl<'data> CoreNoteBuilder<'data> {
+    /// Creates a new [`CoreNoteBuilder`] for a core dump of the given `class` and `encoding`,
+    /// holding the `process_info` descriptor of the [`NoteType::CORE_PRPSINFO`] note and the
+    /// [`ThreadNotes`] of `threads`, starting with the thread that crashed.
+    pub fn new(
+        class: Class,
+        encoding: Encoding,
+        process_info: &'data [u8],
+        threads: &'data [ThreadNotes<'data>],
+    ) -> Self {
+        Self {
+            class,
+            encoding,
+            process_info,
+            threads,
+            auxiliary_vector: None,
+            mapped_files: None,
+        }
+    }
+
+    /// Adds a [`NoteType::CORE_AUXV`] note holding `entries`, which should end with an
+    /// [`AuxvType::NULL`][null] entry.
+    ///
+    /// [null]: crate::auxv::AuxvType::NULL
+    pub fn with_auxiliary_vector(mut self, entries: &'data [AuxvEntry]) -> Self {
+        self.auxiliary_vector = Some(entries);
+        self
+    }
+
+    /// Adds a [`NoteType::CORE_FILE`] note describing `files`, whose offsets are counted in pages
+    /// of `page_size` bytes.
+    ///
+    /// # Errors
+    ///
+    /// - [`BuildCoreNotesError::InteriorNul`]: Returned if the path of a [`MappedFile`] contains
+    ///   a null byte.
+    pub fn with_mapped_files(
+        mut self,
+        page_size: u64,
+        files: &'data [MappedFile<'data>],
+    ) -> Result<Self, BuildCoreNotesError> {
+        if let Some(index) = files.iter().position(|file| file.path.contains(&0)) {
+            return Err(BuildCoreNotesError::InteriorNul { index });
+        }
+
+        self.mapped_files = Some((page_size, files));
+        Ok(self)
+    }
+
+    /// Returns the number of bytes required to hold the notes.
+    pub fn size(&self) -> usize {
+        let threads = self
+            .threads
+            .iter()
+            .map(|thread| {
+                note_size(thread.status.len())
+                    + thread
+                        .fp_registers
+                        .map_or(0, |fp_registers| note_size(fp_registers.len()))
+            })
+            .sum::<usize>();
+        let auxiliary_vector = self
+            .auxiliary_vector
+            .map_or(0, |entries| note_size(self.auxv_size(entries)));
+        let mapped_files = self
+            .mapped_files
+            .map_or(0, |(_, files)| note_size(self.file_size(files)));
+
+        note_size(self.process_info.len()) + threads + auxiliary_vector + mapped_files
+    }
+
+    /// Writes the notes into the start of `buffer`, returning the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`BuildCoreNotesError::BufferTooSmall`]: Returned if `buffer` is smaller than
+    ///   [`CoreNoteBuilder::size()`].
+    /// - [`BuildCoreNotesError::ValueOutOfRange`]: Returned if the size of a descriptor does not
+    ///   fit in 32 bits, or a value of the [`NoteType::CORE_AUXV`] or [`NoteType::CORE_FILE`]
+    ///   notes does not fit in a word of the core dump.
+    pub fn build(&self, buffer: &mut [u8]) -> Result<usize, BuildCoreNotesError> {
+        let size = self.size();
+        let buffer = buffer
+            .get_mut(..size)
+            .ok_or(BuildCoreNotesError::BufferTooSmall { required: size })?;
+        self.validate()?;
+        buffer.fill(0);
+
+        let mut writer = Writer {
+            buffer,
+            cursor: 0,
+            layout: self.layout(),
+        };
+        writer.note(NoteType::CORE_PRPSINFO, self.process_info.len(), |writer| {
+            writer.bytes(self.process_info);
+        });
+        for thread in self.threads {
+            writer.note(NoteType::CORE_PRSTATUS, thread.status.len(), |writer| {
+                writer.bytes(thread.status);
+            });
+            if let Some(fp_registers) = thread.fp_registers {
+                writer.note(NoteType::CORE_FPREGSET, fp_registers.len(), |writer| {
+                    writer.bytes(fp_registers);
+                });
+            }
+        }
+        if let Some(entries) = self.auxiliary_vector {
+            writer.note(NoteType::CORE_AUXV, self.auxv_size(entries), |writer| {
+                for entry in entries {
+                    writer.word(entry.key.0);
+                    writer.word(entry.value);
+                }
+            });
+        }
+        if let Some((page_size, files)) = self.mapped_files {
+            writer.note(NoteType::CORE_FILE, self.file_size(files), |writer| {
+                writer.word(files.len() as u64);
+                writer.word(page_size);
+                for file in files {
+                    writer.word(file.start);
+                    writer.word(file.end);
+                    writer.word(file.page_offset);
+                }
+                for file in files {
+                    writer.bytes(file.path);
+                    writer.bytes(&[0]);
+                }
+            });
+        }
+
+        Ok(size)
+    }
+
+    /// Validates that every descriptor size and word fits in the core dump.
+    fn validate(&self) -> Result<(), BuildCoreNotesError> {
+        let descriptor_sizes = [
+            Some(self.process_info.len()),
+            self.auxiliary_vector.map(|entries| self.auxv_size(entries)),
+            self.mapped_files.map(|(_, files)| self.file_size(files)),
+        ];
+        let thread_sizes = self.threads.iter().flat_map(|thread| {
+            [
+                Some(thread.status.len()),
+                thread.fp_registers.map(<[u8]>::len),
+            ]
+        });
+        if descriptor_sizes
+            .into_iter()
+            .chain(thread_sizes)
+            .flatten()
+            .any(|size| u32::try_from(size).is_err())
+        {
+            return Err(BuildCoreNotesError::ValueOutOfRange);
+        }
+
+        if self.class == Class::CLASS32 {
+            let auxv_words = self
+                .auxiliary_vector
+                .into_iter()
+                .flatten()
+                .flat_map(|entry| [entry.key.0, entry.value]);
+            let file_words = self
+                .mapped_files
+                .into_iter()
+                .flat_map(|(page_size, files)| {
+                    [files.len() as u64, page_size].into_iter().chain(
+                        files
+                            .iter()
+                            .flat_map(|file| [file.start, file.end, file.page_offset]),
+                    )
+                });
+            if auxv_words
+                .chain(file_words)
+                .any(|word| u32::try_from(word).is_err())
+            {
+                return Err(BuildCoreNotesError::ValueOutOfRange);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the size of the descriptor of the [`NoteType::CORE_AUXV`] note holding `entries`.
+    fn auxv_size(&self, entries: &[AuxvEntry]) -> usize {
+        entries.len() * 2 * self.word_size()
+    }
+
+    /// Returns the size of the descriptor of the [`NoteType::CORE_FILE`] note describing `files`.
+    fn file_size(&self, files: &[MappedFile<'_>]) -> usize {
+        (2 + 3 * files.len()) * self.word_size()
+            + files.iter().map(|file| file.path.len() + 1).sum::<usize>()
+    }
+
+    /// Returns the size of a word of the core dump.
+    fn word_size(&self) -> usize {
+        self.layout().word_size()
+    }
+
+    /// Returns the [`ByteLayout`] of the values of the core dump.
+    fn layout(&self) -> ByteLayout {
+        ByteLayout {
+            big_endian: self.encoding == Encoding::MSB2,
+            wide: self.class != Class::CLASS32,
+        }
+    }
+}
+
+/// Returns the size of an [`OWNER_CORE`] note whose descriptor is `descriptor_size` bytes.
+fn note_size(descriptor_size: usize) -> usize {
+    12 + (OWNER_CORE.len() + 1).next_multiple_of(NOTE_ALIGNMENT)
+        + descriptor_size.next_multiple_of(NOTE_ALIGNMENT)
+}
+
+/// A cursor writing notes into a zeroed buffer.
+struct Writer<'buffer> {
+    /// The buffer the notes are written into.
+    buffer: &'buffer mut [u8],
+    /// The offset of the next byte to write.
+    cursor: usize,
+    /// The [`ByteLayout`] of the values of the core dump.
+    layout: ByteLayout,
+}
+
+impl Writer<'_> {
+    /// Writes an [`OWNER_CORE`] note of `note_type`, whose descriptor of `descriptor_size` bytes
+    /// is written by `descriptor`.
+    fn note(
+        &mut self,
+        note_type: NoteType,
+        descriptor_size: usize,
+        descriptor: impl FnOnce(&mut Self),
+    ) {
+        self.value(4, (OWNER_CORE.len() + 1) as u64);
+        self.value(4, descriptor_size as u64);
+        self.value(4, u64::from(note_type.0));
+        self.bytes(OWNER_CORE);
+        self.cursor = (self.cursor + 1).next_multiple_of(NOTE_ALIGNMENT);
+
+        descriptor(self);
+        self.cursor = self.cursor.next_multiple_of(NOTE_ALIGNMENT);
+    }
+
+    /// Writes `bytes`.
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.buffer[self.cursor..self.cursor + bytes.len()].copy_from_slice(bytes);
+        self.cursor += bytes.len();
+    }
+
+    /// Writes `value` as a word of the core dump.
+    fn word(&mut self, value: u64) {
+        self.value(self.layout.word_size(), value);
+    }
+
+    /// Writes `value` as a value of `size` bytes.
+    fn value(&mut self, size: usize, value: u64) {
+        self.layout.write(self.buffer, self.cursor, size, value);
+        self.cursor += size;
+    }
+}
+
+/// Various errors that can occur while building the notes of a core dump.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BuildCoreNotesError {
+    /// The path of a [`MappedFile`] contains a null byte.
+    InteriorNul {
+        /// The index of the [`MappedFile`].
+        index: usize,
+    },
+    /// A descriptor size or a word cannot be represented in the core dump.
+    ValueOutOfRange,
+    /// The buffer is too small to hold the notes.
+    BufferTooSmall {
+        /// The number of bytes required to hold the notes.
+        required: usize,
+    },
+}
+
+impl fmt::Display for BuildCoreNotesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InteriorNul { index } => {
+                write!(f, "path of mapped file {index} contains a null byte")
+            }
+            Self::ValueOutOfRange => write!(f, "value out of range for the core dump"),
+            Self::BufferTooSmall { required } => {
+                write!(f, "buffer too small to hold {required} bytes of notes")
+            }
+        }
+    }
+}
+
+impl error::Error for BuildCoreNotesError {}
@@ -0,0 +1,134 @@
+//! Definitions for collecting every detectable structural problem in an ELF file.
+//!
+//! [`ElfFile::new()`] stops at the first error it encounters, which is unhelpful when presenting
+//! the state of a corrupt file. [`ElfFile::parse_with_diagnostics()`] instead returns whatever
+//! could be parsed alongside an [`Iterator`] over every [`Diagnostic`].
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    anomaly::{Anomalies, Anomaly},
+    class::ClassParse,
+    encoding::EncodingParse,
+    ElfFile, ParseElfFileError,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Parses an [`ElfFile`] from the given `slice`, returning it alongside an [`Iterator`] over
+    /// every [`Diagnostic`] for the file.
+    ///
+    /// The [`ElfFile`] is `None` only if the ELF header or the program header table cannot be
+    /// used, in which case the first [`Diagnostic`] describes why. [`ProgramHeader`][ph]s that
+    /// fail to conform to the ELF specification are reported, but do not prevent parsing.
+    ///
+    /// [ph]: crate::program_header::ProgramHeader
+    pub fn parse_with_diagnostics(
+        slice: &'slice [u8],
+    ) -> (Option<Self>, Diagnostics<'slice, C, E>) {
+        let diagnostics = Diagnostics {
+            slice,
+            stage: Stage::File,
+        };
+
+        (Self::new_unvalidated_segments(slice).ok(), diagnostics)
+    }
+}
+
+/// A structural problem in an ELF file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Diagnostic {
+    /// An error that prevents the file from being parsed, or a [`ProgramHeader`][ph] that fails
+    /// to conform to the ELF specification.
+    ///
+    /// [ph]: crate::program_header::ProgramHeader
+    Error(ParseElfFileError),
+    /// A structural [`Anomaly`] that does not prevent the file from being parsed.
+    Anomaly(Anomaly),
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error(error) => write!(f, "error: {error}"),
+            Self::Anomaly(anomaly) => write!(f, "anomaly: {anomaly}"),
+        }
+    }
+}
+
+/// The checks a [`Diagnostics`] iterator performs next.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+enum Stage<'slice, C: ClassParse, E: EncodingParse> {
+    /// Parsing the ELF header and locating the program header table.
+    File,
+    /// Validating the [`ProgramHeader`][ph] at `index`.
+    ///
+    /// [ph]: crate::program_header::ProgramHeader
+    ProgramHeaders {
+        /// The [`ElfFile`] being checked.
+        file: ElfFile<'slice, C, E>,
+        /// The index of the next [`ProgramHeader`][ph] to validate.
+        ///
+        /// [ph]: crate::program_header::ProgramHeader
+        index: u16,
+    },
+    /// Reporting the [`Anomaly`]s of the file.
+    Anomalies(Anomalies<'slice, C, E>),
+    /// All checks have been performed.
+    Done,
+}
+
+/// An [`Iterator`] over the [`Diagnostic`]s for an ELF file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Diagnostics<'slice, C: ClassParse, E: EncodingParse> {
+    /// The bytes of the ELF file being checked.
+    slice: &'slice [u8],
+    /// The checks to perform next.
+    stage: Stage<'slice, C, E>,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for Diagnostics<'_, C, E> {
+    type Item = Diagnostic;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stage {
+                Stage::File => match ElfFile::new_unvalidated_segments(self.slice) {
+                    Ok(file) => self.stage = Stage::ProgramHeaders { file, index: 0 },
+                    Err(error) => {
+                        self.stage = Stage::Done;
+                        return Some(Diagnostic::Error(error));
+                    }
+                },
+                Stage::ProgramHeaders { file, index } => {
+                    let Some(program_header) = file
+                        .program_header_table()
+                        .and_then(|table| table.get(index))
+                    else {
+                        self.stage = Stage::Anomalies(file.anomalies());
+                        continue;
+                    };
+                    self.stage = match index.checked_add(1) {
+                        Some(index) => Stage::ProgramHeaders { file, index },
+                        None => Stage::Anomalies(file.anomalies()),
+                    };
+
+                    if let Err(error) = program_header.validate_specification() {
+                        return Some(Diagnostic::Error(
+                            ParseElfFileError::ProgramHeaderSpecError {
+                                index: usize::from(index),
+                                error,
+                            },
+                        ));
+                    }
+                }
+                Stage::Anomalies(ref mut anomalies) => match anomalies.next() {
+                    Some(anomaly) => return Some(Diagnostic::Anomaly(anomaly)),
+                    None => self.stage = Stage::Done,
+                },
+                Stage::Done => return None,
+            }
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for Diagnostics<'_, C, E> {}
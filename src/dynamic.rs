@@ -1,11 +1,14 @@
 //! Definitions for ELF dynamic structures.
 
+use core::iter::FusedIterator;
+
 use crate::{
     class::{ClassParse, ClassParseBase},
     encoding::EncodingParse,
 };
 
 /// An ELF dynamic structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Dynamic<C: ClassParse> {
     /// Determinant of how to interpret the [`Dynamic::val`].
     pub tag: DynamicTag<C>,
@@ -25,6 +28,18 @@ impl<C: ClassParseDynamic> PartialEq<ConstDynamicTag> for DynamicTag<C> {
     }
 }
 
+impl<C: ClassParseDynamic> PartialEq<i64> for DynamicTag<C> {
+    fn eq(&self, other: &i64) -> bool {
+        self.0.into() == *other
+    }
+}
+
+impl<C: ClassParseDynamic> From<DynamicTag<C>> for i64 {
+    fn from(value: DynamicTag<C>) -> Self {
+        value.0.into()
+    }
+}
+
 /// Wrapper struct to work around const traits not being available.
 #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ConstDynamicTag(pub(crate) i32);
@@ -131,9 +146,92 @@ impl ConstDynamicTag {
     pub const PREINIT_ARRAY: Self = Self(32);
     /// Holds the size, in bytes, of the array of pointers to pre-initialization functions.
     pub const PREINIT_ARRAY_SIZE: Self = Self(33);
-    /// Holds the address of the [`SHT_SYMTAB_SHNDX`] section associated with the dynamic symbol
-    /// table referenced by the [`ConstDynamicTag::SYMBOL_TABLE`] element.
+    /// Holds the address of the [`SectionType::SYMTAB_SHNDX`][shndx] section associated with the
+    /// dynamic symbol table referenced by the [`ConstDynamicTag::SYMBOL_TABLE`] element.
+    ///
+    /// [shndx]: crate::section_header::SectionType::SYMTAB_SHNDX
     pub const SYMBOL_TABLE_SECTION_INDEX: Self = Self(34);
+    /// Holds the total size, in bytes, of the relocation table pointed to by the
+    /// [`ConstDynamicTag::RELR_TABLE`] entry.
+    pub const RELR_SIZE: Self = Self(35);
+    /// Holds the address of a relocation table of compressed relative relocations.
+    ///
+    /// If this entry is present, the dynamic array must also have [`ConstDynamicTag::RELR_SIZE`] and
+    /// [`ConstDynamicTag::RELR_ENTRY_SIZE`] entries.
+    pub const RELR_TABLE: Self = Self(36);
+    /// Holds the size, in bytes, of an entry in the relocation table pointed to by the
+    /// [`ConstDynamicTag::RELR_TABLE`] entry.
+    pub const RELR_ENTRY_SIZE: Self = Self(37);
+
+    /// Holds the address of the GNU-style symbol hash table, which refers to the symbol table
+    /// referenced in an [`ConstDynamicTag::SYMBOL_TABLE`] entry.
+    pub const GNU_HASH: Self = Self(0x6FFF_FEF5);
+
+    /// Holds the address of the GNU symbol version table, which has one entry per symbol in the
+    /// dynamic symbol table.
+    pub const VERSYM: Self = Self(0x6FFF_FFF0);
+    /// Holds the number of relative relocations at the start of the relocation table pointed to
+    /// by the [`ConstDynamicTag::RELA_TABLE`] entry.
+    pub const RELA_COUNT: Self = Self(0x6FFF_FFF9);
+    /// Holds the number of relative relocations at the start of the relocation table pointed to
+    /// by the [`ConstDynamicTag::REL_TABLE`] entry.
+    pub const REL_COUNT: Self = Self(0x6FFF_FFFA);
+    /// Holds GNU specific flag values specific to the object being loaded.
+    pub const FLAGS_1: Self = Self(0x6FFF_FFFB);
+    /// Holds the address of the GNU symbol version definition table.
+    pub const VERDEF: Self = Self(0x6FFF_FFFC);
+    /// Holds the number of entries in the GNU symbol version definition table.
+    pub const VERDEF_COUNT: Self = Self(0x6FFF_FFFD);
+    /// Holds the address of the GNU symbol version requirement table.
+    pub const VERNEED: Self = Self(0x6FFF_FFFE);
+    /// Holds the number of entries in the GNU symbol version requirement table.
+    pub const VERNEED_COUNT: Self = Self(0x6FFF_FFFF);
+}
+
+impl From<ConstDynamicTag> for i64 {
+    fn from(value: ConstDynamicTag) -> Self {
+        i64::from(value.0)
+    }
+}
+
+/// The flags held by a [`ConstDynamicTag::FLAGS`] entry.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DynamicFlags(pub u64);
+
+impl DynamicFlags {
+    /// The object may reference the `$ORIGIN` substitution string.
+    pub const ORIGIN: Self = Self(0x1);
+    /// Symbol resolution starts from the object itself rather than the executable.
+    pub const SYMBOLIC: Self = Self(0x2);
+    /// Relocations may modify non-writable segments.
+    pub const TEXT_REL: Self = Self(0x4);
+    /// All relocations must be processed before control is transferred to the program.
+    pub const BIND_NOW: Self = Self(0x8);
+    /// The object uses the static thread local storage model.
+    pub const STATIC_TLS: Self = Self(0x10);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`DynamicFlags`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The flags held by a [`ConstDynamicTag::FLAGS_1`] entry.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DynamicFlags1(pub u64);
+
+impl DynamicFlags1 {
+    /// All relocations must be processed before control is transferred to the program.
+    pub const NOW: Self = Self(0x1);
+    /// The object is a position independent executable.
+    pub const PIE: Self = Self(0x0800_0000);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`DynamicFlags1`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
 }
 
 /// A table of [`Dynamic`] structures.
@@ -153,7 +251,7 @@ impl<'slice, C: ClassParse, E: EncodingParse> DynamicTable<'slice, C, E> {
     /// Creates a new [`DynamicTable`] from the given `slice`.
     pub fn new(class: C, encoding: E, slice: &'slice [u8], count: usize) -> Option<Self> {
         if count
-            .checked_add(class.expected_dynamic_size())
+            .checked_mul(class.expected_dynamic_size())
             .is_none_or(|total_size| slice.len() < total_size)
         {
             return None;
@@ -196,6 +294,21 @@ impl<'slice, C: ClassParse, E: EncodingParse> DynamicTable<'slice, C, E> {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Limits this [`DynamicTable`] to at most `max_entries` [`Dynamic`] structures.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.count = self.count.min(max_entries);
+        self
+    }
+
+    /// Returns the value of the first [`Dynamic`] structure with the given `tag`, stopping at the
+    /// [`ConstDynamicTag::NULL`] entry that marks the end of the ELF dynamic array.
+    pub fn find(&self, tag: ConstDynamicTag) -> Option<C::ClassUsize> {
+        self.into_iter()
+            .take_while(|dynamic| dynamic.tag != ConstDynamicTag::NULL)
+            .find(|dynamic| dynamic.tag == tag)
+            .map(|dynamic| dynamic.val)
+    }
 }
 
 impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for DynamicTable<'slice, C, E> {
@@ -230,6 +343,8 @@ impl<C: ClassParse, E: EncodingParse> Iterator for IntoIter<'_, C, E> {
     }
 }
 
+impl<C: ClassParse, E: EncodingParse> FusedIterator for IntoIter<'_, C, E> {}
+
 /// The requirements to implement class aware parsing of ELF dynamic structures.
 pub trait ClassParseDynamic: ClassParseBase {
     /// Returns whether the given [`DynamicTag<Self>`] is equal to the given [`ConstDynamicTag`].
@@ -243,3 +358,38 @@ pub trait ClassParseDynamic: ClassParseBase {
     /// The expected size of an ELF dynamic structure.
     fn expected_dynamic_size(self) -> usize;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class::{Class32, Class64},
+        dynamic::DynamicTable,
+        encoding::{BigEndian, LittleEndian},
+    };
+
+    /// A [`DynamicTable`] whose entries fit in its bytes is accepted.
+    #[test]
+    fn table_in_bounds() {
+        let bytes = [0; 2 * 16];
+
+        let table = DynamicTable::new(Class64, LittleEndian, &bytes, 2).unwrap();
+        assert_eq!(table.into_iter().count(), 2);
+    }
+
+    /// A [`DynamicTable`] with more entries than its bytes can hold is rejected.
+    #[test]
+    fn table_too_small() {
+        let bytes = [0; 2 * 16];
+
+        assert!(DynamicTable::new(Class64, LittleEndian, &bytes, 3).is_none());
+    }
+
+    /// A [`DynamicTable`] whose count times the entry size overflows is rejected.
+    #[test]
+    fn table_size_overflows() {
+        let bytes = [0; 16];
+
+        assert!(DynamicTable::new(Class64, LittleEndian, &bytes, usize::MAX).is_none());
+        assert!(DynamicTable::new(Class32, BigEndian, &bytes, usize::MAX / 4).is_none());
+    }
+}
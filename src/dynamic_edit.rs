@@ -0,0 +1,256 @@
+//! Definitions for editing the dynamic array of an ELF file in place.
+//!
+//! [`DynamicArrayEditor`] works on the raw entries of the dynamic array rather than a semantic
+//! model, so entries that are not edited keep their exact bytes and order.
+
+use core::{error, fmt, ops::Range};
+
+use crate::{
+    class::ClassParse, dynamic::ConstDynamicTag, encoding::EncodingParse,
+    program_header::SegmentType, writer::ByteLayout, ElfFile, ParseElfFileError,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the range of file offsets occupied by the dynamic array of this [`ElfFile`].
+    pub fn dynamic_range(&self) -> Option<Range<usize>> {
+        let program_header = self
+            .program_header_table()?
+            .segments_of_type(SegmentType::DYNAMIC)
+            .next()?;
+        let data = self.segment_data(program_header)?;
+
+        self.range_of(data)
+    }
+}
+
+/// A raw entry of the dynamic array, with class sized values widened to 64 bits.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RawDynamic {
+    /// The tag of the entry.
+    pub tag: i64,
+    /// The value or address held by the entry.
+    pub value: u64,
+}
+
+/// An editor over the raw entries of the dynamic array of an ELF file.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct DynamicArrayEditor<'buffer> {
+    /// The bytes of the dynamic array.
+    bytes: &'buffer mut [u8],
+    /// The [`ByteLayout`] of the values of the ELF file.
+    layout: ByteLayout,
+}
+
+impl<'buffer> DynamicArrayEditor<'buffer> {
+    /// Creates a new [`DynamicArrayEditor`] over the dynamic array of the ELF file contained in
+    /// `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditDynamicError::ParseElfFileError`]: Returned if `bytes` does not contain a valid
+    ///   [`ElfFile`].
+    /// - [`EditDynamicError::MissingDynamic`]: Returned if the [`ElfFile`] has no
+    ///   [`SegmentType::DYNAMIC`] segment or it is located out of bounds.
+    pub fn new<C: ClassParse, E: EncodingParse>(
+        bytes: &'buffer mut [u8],
+    ) -> Result<Self, EditDynamicError> {
+        let file = ElfFile::<C, E>::new(bytes)?;
+        let layout = ByteLayout::new(file.header().ident());
+        let range = file
+            .dynamic_range()
+            .ok_or(EditDynamicError::MissingDynamic)?;
+
+        Ok(Self {
+            bytes: &mut bytes[range],
+            layout,
+        })
+    }
+
+    /// Returns the bytes of the dynamic array.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Returns the number of entries that fit in the dynamic array, including the terminating
+    /// [`ConstDynamicTag::NULL`] entry and any entries following it.
+    pub fn count(&self) -> usize {
+        self.bytes.len() / self.entry_size()
+    }
+
+    /// Returns the [`RawDynamic`] entry located at `index`.
+    pub fn get(&self, index: usize) -> Option<RawDynamic> {
+        if index >= self.count() {
+            return None;
+        }
+
+        let start = index * self.entry_size();
+        let word_size = self.layout.word_size();
+        let tag = self.layout.read_word(self.bytes, start);
+        let tag = if self.layout.wide {
+            tag as i64
+        } else {
+            i64::from(tag as u32 as i32)
+        };
+
+        Some(RawDynamic {
+            tag,
+            value: self.layout.read_word(self.bytes, start + word_size),
+        })
+    }
+
+    /// Returns the index of the first entry with the given `tag`, stopping at the terminating
+    /// [`ConstDynamicTag::NULL`] entry.
+    pub fn position(&self, tag: i64) -> Option<usize> {
+        let end = self.null_position().unwrap_or(self.count());
+        (0..end).find(|&index| self.get(index).is_some_and(|entry| entry.tag == tag))
+    }
+
+    /// Returns the index of the terminating [`ConstDynamicTag::NULL`] entry.
+    pub fn null_position(&self) -> Option<usize> {
+        (0..self.count()).find(|&index| {
+            self.get(index)
+                .is_some_and(|entry| entry.tag == i64::from(ConstDynamicTag::NULL.0))
+        })
+    }
+
+    /// Overwrites the entry located at `index` with `entry`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditDynamicError::IndexOutOfBounds`]: Returned if `index` is out of bounds.
+    /// - [`EditDynamicError::ValueOutOfRange`]: Returned if `entry` cannot be represented in the
+    ///   class of the ELF file.
+    pub fn set(&mut self, index: usize, entry: RawDynamic) -> Result<(), EditDynamicError> {
+        if index >= self.count() {
+            return Err(EditDynamicError::IndexOutOfBounds);
+        }
+
+        let (tag, value) = self.encode(entry)?;
+        let start = index * self.entry_size();
+        let word_size = self.layout.word_size();
+        self.layout.write_word(self.bytes, start, tag);
+        self.layout.write_word(self.bytes, start + word_size, value);
+        Ok(())
+    }
+
+    /// Overwrites the value of the entry located at `index`, keeping its tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`DynamicArrayEditor::set()`].
+    pub fn set_value(&mut self, index: usize, value: u64) -> Result<(), EditDynamicError> {
+        let entry = self.get(index).ok_or(EditDynamicError::IndexOutOfBounds)?;
+        self.set(index, RawDynamic { value, ..entry })
+    }
+
+    /// Inserts `entry` at `index`, moving the following entries, including the terminating
+    /// [`ConstDynamicTag::NULL`] entry, up by one.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditDynamicError::IndexOutOfBounds`]: Returned if `index` is past the terminating
+    ///   [`ConstDynamicTag::NULL`] entry.
+    /// - [`EditDynamicError::NoSpareEntry`]: Returned if there is no entry following the
+    ///   terminating [`ConstDynamicTag::NULL`] entry to grow into.
+    /// - [`EditDynamicError::ValueOutOfRange`]: Returned if `entry` cannot be represented in the
+    ///   class of the ELF file.
+    pub fn insert(&mut self, index: usize, entry: RawDynamic) -> Result<(), EditDynamicError> {
+        let end = self.null_position().ok_or(EditDynamicError::NoSpareEntry)?;
+        if index > end {
+            return Err(EditDynamicError::IndexOutOfBounds);
+        }
+        if end + 1 >= self.count() {
+            return Err(EditDynamicError::NoSpareEntry);
+        }
+
+        let entry_size = self.entry_size();
+        self.encode(entry)?;
+
+        self.bytes.copy_within(
+            index * entry_size..(end + 1) * entry_size,
+            (index + 1) * entry_size,
+        );
+        self.set(index, entry)
+    }
+
+    /// Removes the entry located at `index`, moving the following entries, including the
+    /// terminating [`ConstDynamicTag::NULL`] entry, down by one.
+    ///
+    /// The entry slot freed at the end of the dynamic array is filled with a
+    /// [`ConstDynamicTag::NULL`] entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditDynamicError::IndexOutOfBounds`] if `index` is not before the terminating
+    /// [`ConstDynamicTag::NULL`] entry.
+    pub fn remove(&mut self, index: usize) -> Result<RawDynamic, EditDynamicError> {
+        let end = self
+            .null_position()
+            .ok_or(EditDynamicError::IndexOutOfBounds)?;
+        if index >= end {
+            return Err(EditDynamicError::IndexOutOfBounds);
+        }
+
+        let entry_size = self.entry_size();
+        let removed = self.get(index).ok_or(EditDynamicError::IndexOutOfBounds)?;
+        self.bytes.copy_within(
+            (index + 1) * entry_size..(end + 1) * entry_size,
+            index * entry_size,
+        );
+        self.set(end, RawDynamic::default())?;
+
+        Ok(removed)
+    }
+
+    /// Returns the class sized tag and value of `entry`.
+    fn encode(&self, entry: RawDynamic) -> Result<(u64, u64), EditDynamicError> {
+        if self.layout.wide {
+            return Ok((entry.tag as u64, entry.value));
+        }
+
+        let tag = i32::try_from(entry.tag).map_err(|_| EditDynamicError::ValueOutOfRange)?;
+        let value = u32::try_from(entry.value).map_err(|_| EditDynamicError::ValueOutOfRange)?;
+        Ok((u64::from(tag as u32), u64::from(value)))
+    }
+
+    /// Returns the size of an entry of the dynamic array.
+    fn entry_size(&self) -> usize {
+        2 * self.layout.word_size()
+    }
+}
+
+/// Various errors that can occur while editing the dynamic array of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum EditDynamicError {
+    /// An error occurred while parsing the [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// The [`ElfFile`] has no [`SegmentType::DYNAMIC`] segment located in bounds.
+    MissingDynamic,
+    /// The index of an entry is out of bounds.
+    IndexOutOfBounds,
+    /// A value cannot be represented in the class of the [`ElfFile`].
+    ValueOutOfRange,
+    /// The dynamic array has no spare entry following its terminating
+    /// [`ConstDynamicTag::NULL`] entry.
+    NoSpareEntry,
+}
+
+impl From<ParseElfFileError> for EditDynamicError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for EditDynamicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::MissingDynamic => write!(f, "ELF file has no dynamic array"),
+            Self::IndexOutOfBounds => write!(f, "dynamic array index out of bounds"),
+            Self::ValueOutOfRange => write!(f, "value out of range for the ELF class"),
+            Self::NoSpareEntry => write!(f, "dynamic array has no spare entry"),
+        }
+    }
+}
+
+impl error::Error for EditDynamicError {}
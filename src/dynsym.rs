@@ -0,0 +1,153 @@
+//! Definitions for sizing the dynamic symbol table located by the dynamic array.
+//!
+//! The dynamic array records the address of the dynamic symbol table and the size of each entry,
+//! but not the number of entries. That number is instead recovered from the symbol hash tables,
+//! whose values are not trusted: files altered by obfuscators frequently carry inconsistent
+//! values, which are reported as a [`DynamicSymbolError`].
+//!
+//! Symbols whose section index does not fit in a symbol table entry are resolved through the
+//! extended section index table located by [`ConstDynamicTag::SYMBOL_TABLE_SECTION_INDEX`].
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the size of an entry of the dynamic symbol table, as given by the
+    /// [`ConstDynamicTag::SYMBOL_ENTRY_SIZE`] entry of this [`ElfFile`].
+    ///
+    /// If the entry is missing, the size defined by the ELF specification is returned. Larger
+    /// sizes are respected, since the fields defined by the ELF specification are still present.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DynamicSymbolError::EntrySizeTooSmall`] if the recorded size is smaller than the
+    /// size defined by the ELF specification.
+    pub fn dynamic_symbol_entry_size(&self) -> Result<usize, DynamicSymbolError> {
+        let expected = self.class.expected_symbol_size();
+        let Some(size) = self
+            .dynamic_table()
+            .and_then(|table| table.find(ConstDynamicTag::SYMBOL_ENTRY_SIZE))
+        else {
+            return Ok(expected);
+        };
+
+        match to_len(size) {
+            Ok(size) if size >= expected => Ok(size),
+            _ => Err(DynamicSymbolError::EntrySizeTooSmall(size.into())),
+        }
+    }
+
+    /// Returns the number of entries in the dynamic symbol table, as inferred from the
+    /// [`ConstDynamicTag::HASH`] and [`ConstDynamicTag::GNU_HASH`] tables of this [`ElfFile`].
+    ///
+    /// The [`ConstDynamicTag::GNU_HASH`] table only covers the symbols following those it
+    /// excludes, so when it is the only hash table present, symbols placed after the last hashed
+    /// symbol are not counted. Returns `Ok(None)` if neither hash table is present and readable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DynamicSymbolError::CountMismatch`] if both hash tables are present but imply
+    /// different numbers of symbols.
+    pub fn dynamic_symbol_count(&self) -> Result<Option<usize>, DynamicSymbolError> {
+        match (self.hash_symbol_count(), self.gnu_hash_symbol_count()) {
+            (Some(hash), Some(gnu_hash)) if hash != gnu_hash => {
+                Err(DynamicSymbolError::CountMismatch { hash, gnu_hash })
+            }
+            (hash, gnu_hash) => Ok(hash.or(gnu_hash)),
+        }
+    }
+
+    /// Returns the [`SymbolTable`] located by the [`ConstDynamicTag::SYMBOL_TABLE`] entry of this
+    /// [`ElfFile`], with names located in [`ElfFile::dynamic_string_table()`].
+    ///
+    /// The entries are sized by [`ElfFile::dynamic_symbol_entry_size()`] and counted by
+    /// [`ElfFile::dynamic_symbol_count()`], so `None` is returned if either fails. If a
+    /// [`ConstDynamicTag::SYMBOL_TABLE_SECTION_INDEX`] entry is present, the extended section
+    /// index table it locates is attached to the [`SymbolTable`], and `None` is returned if that
+    /// table cannot be located or holds fewer entries than the [`SymbolTable`].
+    pub fn dynamic_symbol_table_from_dynamic(&self) -> Option<SymbolTable<'slice, C, E>> {
+        let entry_size = self.dynamic_symbol_entry_size().ok()?;
+        let count = self.dynamic_symbol_count().ok()??;
+        let data = self.dynamic_address_data(ConstDynamicTag::SYMBOL_TABLE)?;
+
+        let mut table = SymbolTable::new(self.class, self.encoding, data, count, entry_size)?;
+        let has_indices = self
+            .dynamic_table()
+            .and_then(|dynamic| dynamic.find(ConstDynamicTag::SYMBOL_TABLE_SECTION_INDEX))
+            .is_some();
+        if has_indices {
+            let indices = self.dynamic_address_data(ConstDynamicTag::SYMBOL_TABLE_SECTION_INDEX)?;
+            table = table.with_extended_indices(indices)?;
+        }
+
+        Some(match self.dynamic_string_table() {
+            Some(strings) => table.with_strings(strings),
+            None => table,
+        })
+    }
+
+    /// Returns the data located by the address held in the `tag` entry of the dynamic array.
+    pub(crate) fn dynamic_address_data(&self, tag: ConstDynamicTag) -> Option<&'slice [u8]> {
+        let address = self.dynamic_table()?.find(tag)?;
+
+        self.virtual_address_data(address)
+    }
+
+    /// Returns the number of symbols implied by the [`ConstDynamicTag::HASH`] table, which is the
+    /// number of entries in its chain array.
+    fn hash_symbol_count(&self) -> Option<usize> {
+        let data = self.dynamic_address_data(ConstDynamicTag::HASH)?;
+        if data.len() < 8 {
+            return None;
+        }
+
+        to_len(self.encoding.parse_u32_at(4, data)).ok()
+    }
+
+    /// Returns the number of symbols implied by the [`ConstDynamicTag::GNU_HASH`] table, which is
+    /// one past the last symbol reachable from its buckets.
+    fn gnu_hash_symbol_count(&self) -> Option<usize> {
+        self.gnu_hash_table()?.symbol_count()
+    }
+}
+
+/// Various errors that can occur while sizing the dynamic symbol table.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum DynamicSymbolError {
+    /// The [`ConstDynamicTag::SYMBOL_ENTRY_SIZE`] entry is smaller than a symbol.
+    EntrySizeTooSmall(u64),
+    /// The [`ConstDynamicTag::HASH`] and [`ConstDynamicTag::GNU_HASH`] tables imply different
+    /// numbers of symbols.
+    CountMismatch {
+        /// The number of symbols implied by the [`ConstDynamicTag::HASH`] table.
+        hash: usize,
+        /// The number of symbols implied by the [`ConstDynamicTag::GNU_HASH`] table.
+        gnu_hash: usize,
+    },
+}
+
+impl fmt::Display for DynamicSymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EntrySizeTooSmall(size) => {
+                write!(
+                    f,
+                    "dynamic symbol entry size {size} is smaller than a symbol"
+                )
+            }
+            Self::CountMismatch { hash, gnu_hash } => write!(
+                f,
+                "hash table implies {hash} symbols but GNU hash table implies {gnu_hash}"
+            ),
+        }
+    }
+}
+
+impl error::Error for DynamicSymbolError {}
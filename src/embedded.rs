@@ -0,0 +1,219 @@
+//! Definitions for locating ELF files embedded in larger containers, such as FatELF files,
+//! Android boot images, and firmware images.
+
+use core::{iter::FusedIterator, marker::PhantomData};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    header::ElfHeader,
+    ident::ElfIdent,
+    search::{PatternMatch, PatternMatches},
+    ElfFile,
+};
+
+/// The magic bytes at the start of a FatELF file.
+pub const FATELF_MAGIC: [u8; 4] = [0xFA, 0x70, 0x0E, 0x1F];
+/// The magic bytes at the start of an Android boot image.
+pub const ANDROID_BOOT_MAGIC: &[u8; 8] = b"ANDROID!";
+/// The magic bytes at the start of a U-Boot legacy image.
+pub const UIMAGE_MAGIC: [u8; 4] = [0x27, 0x05, 0x19, 0x56];
+
+/// The format of a container known to embed ELF files.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContainerFormat {
+    /// A FatELF file, holding ELF files for multiple architectures.
+    FatElf,
+    /// An Android boot image, holding a kernel and ramdisk.
+    AndroidBoot,
+    /// A U-Boot legacy image, holding a kernel or firmware payload.
+    UImage,
+}
+
+impl ContainerFormat {
+    /// Returns the [`ContainerFormat`] of `slice`, as identified by its magic bytes.
+    ///
+    /// Returns `None` if `slice` does not start with the magic bytes of a known container.
+    pub fn detect(slice: &[u8]) -> Option<Self> {
+        if slice.starts_with(&FATELF_MAGIC) {
+            Some(Self::FatElf)
+        } else if slice.starts_with(ANDROID_BOOT_MAGIC) {
+            Some(Self::AndroidBoot)
+        } else if slice.starts_with(&UIMAGE_MAGIC) {
+            Some(Self::UImage)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns an [`Iterator`] over the offsets in `slice` at which a valid [`ElfHeader`] starts.
+///
+/// Each candidate must start with [`ElfIdent::MAGIC_BYTES`], be supported by `C` and `E`, and
+/// pass [`ElfHeader::validate_spec()`].
+pub fn find_embedded_elves<C: ClassParse, E: EncodingParse>(
+    slice: &[u8],
+) -> EmbeddedElves<'_, C, E> {
+    EmbeddedElves {
+        slice,
+        matches: PatternMatches::new(slice, 0, &ElfIdent::MAGIC_BYTES),
+        phantom: PhantomData,
+    }
+}
+
+/// An [`Iterator`] over the offsets at which valid [`ElfHeader`]s start in a slice.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct EmbeddedElves<'slice, C: ClassParse, E: EncodingParse> {
+    /// The slice being searched.
+    slice: &'slice [u8],
+    /// The occurrences of [`ElfIdent::MAGIC_BYTES`] in the slice.
+    matches: PatternMatches<'slice, 'static>,
+    /// The class and encoding parsers used to validate candidates.
+    phantom: PhantomData<fn() -> (C, E)>,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for EmbeddedElves<'_, C, E> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.matches.find_map(|PatternMatch { offset, .. }| {
+            ElfHeader::<C, E>::new(&self.slice[offset..])
+                .ok()
+                .filter(|header| header.validate_spec().is_ok())
+                .map(|_| offset)
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for EmbeddedElves<'_, C, E> {}
+
+/// Returns an [`Iterator`] over the [`EmbeddedElf`]s found in `slice`.
+///
+/// Unlike [`find_embedded_elves()`], each candidate must parse as an [`ElfFile`] and every
+/// structure it references in the file must lie within `slice`, so that the yielded span can be
+/// sliced out.
+pub fn extract_embedded_elves<C: ClassParse, E: EncodingParse>(
+    slice: &[u8],
+) -> ExtractEmbeddedElves<'_, C, E> {
+    ExtractEmbeddedElves {
+        slice,
+        candidates: find_embedded_elves(slice),
+        outer_end: 0,
+    }
+}
+
+/// An ELF file embedded in a larger slice.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct EmbeddedElf {
+    /// The offset of the embedded ELF file in the searched slice.
+    pub offset: usize,
+    /// The size, in bytes, of the embedded ELF file.
+    pub size: usize,
+    /// The relation of the embedded ELF file to the outermost embedded ELF file before it.
+    pub overlap: EmbeddedOverlap,
+}
+
+impl EmbeddedElf {
+    /// Returns the offset one past the end of the embedded ELF file.
+    pub fn end(&self) -> usize {
+        self.offset.saturating_add(self.size)
+    }
+
+    /// Returns the bytes of the embedded ELF file in `slice`, the slice that was searched.
+    pub fn bytes<'slice>(&self, slice: &'slice [u8]) -> Option<&'slice [u8]> {
+        slice.get(self.offset..self.end())
+    }
+}
+
+/// The relation of an [`EmbeddedElf`] to the outermost [`EmbeddedElf`] before it.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EmbeddedOverlap {
+    /// The [`EmbeddedElf`] does not overlap any earlier [`EmbeddedElf`].
+    None,
+    /// The [`EmbeddedElf`] lies entirely within an earlier [`EmbeddedElf`], such as a binary
+    /// inside an initramfs embedded in a kernel.
+    Nested,
+    /// The [`EmbeddedElf`] starts within an earlier [`EmbeddedElf`] but extends past its end.
+    ///
+    /// This usually indicates that one of the two candidates is a false positive.
+    Partial,
+}
+
+/// An [`Iterator`] over the [`EmbeddedElf`]s found in a slice.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ExtractEmbeddedElves<'slice, C: ClassParse, E: EncodingParse> {
+    /// The slice being searched.
+    slice: &'slice [u8],
+    /// The offsets at which valid [`ElfHeader`]s start.
+    candidates: EmbeddedElves<'slice, C, E>,
+    /// The end of the outermost [`EmbeddedElf`] yielded so far.
+    outer_end: usize,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for ExtractEmbeddedElves<'_, C, E> {
+    type Item = EmbeddedElf;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (offset, size) = self.candidates.find_map(|offset| {
+            let bytes = &self.slice[offset..];
+            let file = ElfFile::<C, E>::new(bytes).ok()?;
+            let size = extent(&file).filter(|&size| size <= bytes.len() as u64)?;
+
+            Some((offset, size as usize))
+        })?;
+
+        let end = offset + size;
+        let overlap = if offset >= self.outer_end {
+            EmbeddedOverlap::None
+        } else if end <= self.outer_end {
+            EmbeddedOverlap::Nested
+        } else {
+            EmbeddedOverlap::Partial
+        };
+        if overlap != EmbeddedOverlap::Nested {
+            self.outer_end = end;
+        }
+
+        Some(EmbeddedElf {
+            offset,
+            size,
+            overlap,
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for ExtractEmbeddedElves<'_, C, E> {}
+
+/// Returns the offset one past the last byte referenced by `file`, or `None` if it overflows.
+fn extent<C: ClassParse, E: EncodingParse>(file: &ElfFile<C, E>) -> Option<u64> {
+    let header = file.header();
+
+    let mut end = u64::from(header.header_size());
+
+    let table_end =
+        |offset: u64, count: u16, size: u16| offset.checked_add(u64::from(count) * u64::from(size));
+    if header.program_header_count() != 0 {
+        end = end.max(table_end(
+            header.program_header_offset().into(),
+            header.program_header_count(),
+            header.program_header_size(),
+        )?);
+    }
+    if header.section_header_count() != 0 {
+        end = end.max(table_end(
+            header.section_header_offset().into(),
+            header.section_header_count(),
+            header.section_header_size(),
+        )?);
+    }
+
+    for segment in file.program_header_table().into_iter().flatten() {
+        let file_size: u64 = segment.file_size().into();
+        if file_size != 0 {
+            let offset: u64 = segment.file_offset().into();
+            end = end.max(offset.checked_add(file_size)?);
+        }
+    }
+
+    Some(end)
+}
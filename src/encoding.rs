@@ -1,6 +1,6 @@
 //! Implementation of encoding aware parsing.
 
-use core::{error, fmt};
+use core::{error, fmt, iter::FusedIterator, marker::PhantomData};
 
 use crate::ident::Encoding;
 
@@ -55,8 +55,154 @@ pub trait EncodingParse: Clone + Copy {
     ///
     /// Panics if an arithmetic or bounds overflow error occurs.
     fn parse_i64_at(self, offset: usize, data: &[u8]) -> i64;
+
+    /// Fills `values` with the consecutive `u32`s at `offset` bytes from the start of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an arithmetic or bounds overflow error occurs.
+    fn parse_u32_slice_at(self, offset: usize, data: &[u8], values: &mut [u32]) {
+        let bytes = run_at::<u32>(offset, values.len(), data);
+        for (value, chunk) in values.iter_mut().zip(bytes.chunks_exact(4)) {
+            *value = self.parse_u32_at(0, chunk);
+        }
+    }
+
+    /// Fills `values` with the consecutive `u64`s at `offset` bytes from the start of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an arithmetic or bounds overflow error occurs.
+    fn parse_u64_slice_at(self, offset: usize, data: &[u8], values: &mut [u64]) {
+        let bytes = run_at::<u64>(offset, values.len(), data);
+        for (value, chunk) in values.iter_mut().zip(bytes.chunks_exact(8)) {
+            *value = self.parse_u64_at(0, chunk);
+        }
+    }
+
+    /// Returns an [`Iterator`] over the `count` consecutive `u32`s at `offset` bytes from the
+    /// start of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an arithmetic or bounds overflow error occurs.
+    fn parse_u32_iter_at(
+        self,
+        offset: usize,
+        count: usize,
+        data: &[u8],
+    ) -> ParseIter<'_, Self, u32> {
+        ParseIter {
+            encoding: self,
+            data: run_at::<u32>(offset, count, data),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the `count` consecutive `u64`s at `offset` bytes from the
+    /// start of the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an arithmetic or bounds overflow error occurs.
+    fn parse_u64_iter_at(
+        self,
+        offset: usize,
+        count: usize,
+        data: &[u8],
+    ) -> ParseIter<'_, Self, u64> {
+        ParseIter {
+            encoding: self,
+            data: run_at::<u64>(offset, count, data),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Returns the bytes of the `count` consecutive values of type `T` at `offset` bytes from the
+/// start of `data`.
+///
+/// # Panics
+///
+/// Panics if an arithmetic or bounds overflow error occurs.
+fn run_at<T>(offset: usize, count: usize, data: &[u8]) -> &[u8] {
+    let size = count
+        .checked_mul(core::mem::size_of::<T>())
+        .expect("`count * size` overflowed");
+    let byte_after = offset
+        .checked_add(size)
+        .expect("`offset + count * size` overflowed");
+    if byte_after > data.len() {
+        panic!(
+            "attempted read of {} bytes at an offset of {} bytes from {} byte buffer",
+            size,
+            offset,
+            data.len(),
+        )
+    }
+
+    &data[offset..byte_after]
 }
 
+/// An integer type that can be read by a [`ParseIter`].
+pub trait ParseIterItem: Sized {
+    /// Returns the value at the start of `data`, which is exactly `size_of::<Self>()` bytes long.
+    fn parse<E: EncodingParse>(encoding: E, data: &[u8]) -> Self;
+}
+
+impl ParseIterItem for u32 {
+    fn parse<E: EncodingParse>(encoding: E, data: &[u8]) -> Self {
+        encoding.parse_u32_at(0, data)
+    }
+}
+
+impl ParseIterItem for u64 {
+    fn parse<E: EncodingParse>(encoding: E, data: &[u8]) -> Self {
+        encoding.parse_u64_at(0, data)
+    }
+}
+
+/// An [`Iterator`] over a run of consecutive integers decoded by an [`EncodingParse`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ParseIter<'data, E: EncodingParse, T: ParseIterItem> {
+    /// The [`EncodingParse`] used to decode the integers.
+    encoding: E,
+    /// The bytes of the integers that have not been visited.
+    data: &'data [u8],
+    /// The type of the integers.
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<E: EncodingParse, T: ParseIterItem> Iterator for ParseIter<'_, E, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, data) = self.data.split_at_checked(core::mem::size_of::<T>())?;
+        self.data = data;
+
+        Some(T::parse(self.encoding, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.data.len() / core::mem::size_of::<T>();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<E: EncodingParse, T: ParseIterItem> DoubleEndedIterator for ParseIter<'_, E, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let split = self.data.len().checked_sub(core::mem::size_of::<T>())?;
+        let (data, value) = self.data.split_at(split);
+        self.data = data;
+
+        Some(T::parse(self.encoding, value))
+    }
+}
+
+impl<E: EncodingParse, T: ParseIterItem> ExactSizeIterator for ParseIter<'_, E, T> {}
+
+impl<E: EncodingParse, T: ParseIterItem> FusedIterator for ParseIter<'_, E, T> {}
+
 /// An error that occurs when the code does not support a particular [`Encoding`]
 /// object.
 #[derive(Clone, Copy, Hash, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -214,4 +360,18 @@ impl<A: EncodingParse, B: EncodingParse> EncodingParse for Merge<A, B> {
             Self::B(b) => b.parse_i64_at(offset, data),
         }
     }
+
+    fn parse_u32_slice_at(self, offset: usize, data: &[u8], values: &mut [u32]) {
+        match self {
+            Self::A(a) => a.parse_u32_slice_at(offset, data, values),
+            Self::B(b) => b.parse_u32_slice_at(offset, data, values),
+        }
+    }
+
+    fn parse_u64_slice_at(self, offset: usize, data: &[u8], values: &mut [u64]) {
+        match self {
+            Self::A(a) => a.parse_u64_slice_at(offset, data, values),
+            Self::B(b) => b.parse_u64_slice_at(offset, data, values),
+        }
+    }
 }
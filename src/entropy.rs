@@ -0,0 +1,106 @@
+//! Definitions for computing the Shannon entropy of regions of an ELF file.
+//!
+//! Entropy is computed using fixed-point arithmetic so that it is available in `no_std`
+//! environments without floating point support. Regions with an entropy close to
+//! [`Entropy::MAX`] are likely to be compressed or encrypted.
+
+use core::fmt;
+
+use crate::{class::ClassParse, encoding::EncodingParse, program_header::ProgramHeader, ElfFile};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`Entropy`] of the file data associated with the given [`ProgramHeader`].
+    pub fn segment_entropy(&self, program_header: ProgramHeader<'slice, C, E>) -> Option<Entropy> {
+        self.segment_data(program_header).map(entropy)
+    }
+}
+
+/// The Shannon entropy of a sequence of bytes, in bits per byte.
+///
+/// The value is stored as a fixed-point number with [`Entropy::FRACTIONAL_BITS`] fractional
+/// bits.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Entropy(pub u32);
+
+impl Entropy {
+    /// The number of fractional bits of an [`Entropy`].
+    pub const FRACTIONAL_BITS: u32 = 16;
+    /// The entropy of a sequence of bytes that consists of a single repeated value.
+    pub const MIN: Self = Self(0);
+    /// The entropy of a sequence of bytes in which every value occurs equally often.
+    pub const MAX: Self = Self(8 << Self::FRACTIONAL_BITS);
+
+    /// Returns the integer part of this [`Entropy`].
+    pub const fn whole_bits(self) -> u32 {
+        self.0 >> Self::FRACTIONAL_BITS
+    }
+
+    /// Returns this [`Entropy`] scaled to the range `0..=1000`.
+    pub const fn per_mille(self) -> u32 {
+        ((self.0 as u64 * 1000) / Self::MAX.0 as u64) as u32
+    }
+}
+
+impl fmt::Display for Entropy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fraction = (u64::from(self.0 & ((1 << Self::FRACTIONAL_BITS) - 1)) * 1000)
+            >> Self::FRACTIONAL_BITS;
+
+        write!(f, "{}.{fraction:03}", self.whole_bits())
+    }
+}
+
+/// Returns the Shannon [`Entropy`] of `bytes`.
+///
+/// Returns [`Entropy::MIN`] if `bytes` is empty.
+pub fn entropy(bytes: &[u8]) -> Entropy {
+    if bytes.is_empty() {
+        return Entropy::MIN;
+    }
+
+    let mut counts = [0u64; 256];
+    for &byte in bytes {
+        counts[usize::from(byte)] += 1;
+    }
+
+    // H = log2(n) - (1 / n) * sum(c * log2(c)).
+    let total = bytes.len() as u64;
+    let weighted: u128 = counts
+        .iter()
+        .filter(|&&count| count != 0)
+        .map(|&count| u128::from(count) * u128::from(log2_fixed(count)))
+        .sum();
+    let value = u128::from(log2_fixed(total)).saturating_sub(weighted / u128::from(total));
+
+    Entropy(value.min(u128::from(Entropy::MAX.0)) as u32)
+}
+
+/// Returns the base 2 logarithm of `value` as a fixed-point number with
+/// [`Entropy::FRACTIONAL_BITS`] fractional bits.
+///
+/// `value` must be nonzero.
+fn log2_fixed(value: u64) -> u64 {
+    /// The number of fractional bits used while computing the fractional part.
+    const PRECISION: u32 = 31;
+
+    let integer = 63 - value.leading_zeros();
+
+    // Normalize `value` into the range [1, 2) with `PRECISION` fractional bits.
+    let mut mantissa = if integer > PRECISION {
+        value >> (integer - PRECISION)
+    } else {
+        value << (PRECISION - integer)
+    };
+
+    let mut fraction = 0;
+    for bit in (0..Entropy::FRACTIONAL_BITS).rev() {
+        mantissa = (mantissa * mantissa) >> PRECISION;
+        if mantissa >= 2 << PRECISION {
+            mantissa >>= 1;
+            fraction |= 1 << bit;
+        }
+    }
+
+    (u64::from(integer) << Entropy::FRACTIONAL_BITS) | fraction
+}
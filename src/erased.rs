@@ -0,0 +1,151 @@
+//! Definitions for a type-erased view of an ELF file.
+//!
+//! Code that is generic over [`ClassParse`][cp] and [`EncodingParse`][ep] is monomorphized for
+//! each combination in use. [`DynElfFile`] instead dispatches on the class and encoding at
+//! runtime and normalizes all class sized values to [`u64`], so downstream code is compiled once.
+//!
+//! [cp]: crate::class::ClassParse
+//! [ep]: crate::encoding::EncodingParse
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_file_offset, to_len, AnyClass},
+    compare::NormalizedProgramHeader,
+    encoding::AnyEndian,
+    header::{ElfType, Machine},
+    ident::ElfIdent,
+    program_header, ElfFile, ParseElfFileError,
+};
+
+/// A type-erased view of an ELF file of any supported class and encoding.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DynElfFile<'slice> {
+    /// The underlying [`ElfFile`].
+    file: ElfFile<'slice, AnyClass, AnyEndian>,
+}
+
+impl<'slice> DynElfFile<'slice> {
+    /// Creates a new [`DynElfFile`] from the given `slice`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ElfFile::new()`].
+    pub fn new(slice: &'slice [u8]) -> Result<Self, ParseElfFileError> {
+        ElfFile::new(slice).map(|file| Self { file })
+    }
+
+    /// Returns the underlying [`ElfFile`] of this [`DynElfFile`].
+    pub fn file(&self) -> ElfFile<'slice, AnyClass, AnyEndian> {
+        self.file
+    }
+
+    /// Returns the [`ElfIdent`] of this [`DynElfFile`].
+    pub fn ident(&self) -> ElfIdent<'slice> {
+        self.file.header().ident()
+    }
+
+    /// Returns the type of this ELF file.
+    pub fn elf_type(&self) -> ElfType {
+        self.file.header().elf_type()
+    }
+
+    /// Returns the architecture for which this ELF file is targeted.
+    pub fn machine(&self) -> Machine {
+        self.file.header().machine()
+    }
+
+    /// Returns the processor specific flags associated with the ELF file.
+    pub fn flags(&self) -> u32 {
+        self.file.header().flags()
+    }
+
+    /// Returns the virtual address of the entry point of this ELF file.
+    pub fn entry(&self) -> u64 {
+        self.file.header().entry()
+    }
+
+    /// Returns the program header table's file offset in bytes.
+    pub fn program_header_offset(&self) -> u64 {
+        self.file.header().program_header_offset()
+    }
+
+    /// Returns the number of program headers in the program header table.
+    pub fn program_header_count(&self) -> u16 {
+        self.file.header().program_header_count()
+    }
+
+    /// Returns the section header table's file offset in bytes.
+    pub fn section_header_offset(&self) -> u64 {
+        self.file.header().section_header_offset()
+    }
+
+    /// Returns the number of section headers in the section header table.
+    pub fn section_header_count(&self) -> u16 {
+        self.file.header().section_header_count()
+    }
+
+    /// Returns the index into the section header table to obtain the section name string table.
+    pub fn section_header_string_table_index(&self) -> u16 {
+        self.file.header().section_header_string_table_index()
+    }
+
+    /// Returns the [`NormalizedProgramHeader`] located at `index` in the program header table.
+    pub fn program_header(&self, index: u16) -> Option<NormalizedProgramHeader> {
+        self.file
+            .program_header_table()?
+            .get(index)
+            .map(|program_header| program_header.normalize())
+    }
+
+    /// Returns an [`Iterator`] over the [`NormalizedProgramHeader`]s in the program header table.
+    pub fn program_headers(&self) -> DynProgramHeaders<'slice> {
+        DynProgramHeaders {
+            iter: self
+                .file
+                .program_header_table()
+                .map(IntoIterator::into_iter),
+        }
+    }
+
+    /// Returns the file data associated with the given [`NormalizedProgramHeader`].
+    pub fn segment_data(&self, program_header: &NormalizedProgramHeader) -> Option<&'slice [u8]> {
+        let start = to_file_offset(program_header.file_offset).ok()?;
+        let size = to_len(program_header.file_size).ok()?;
+        let end = start.checked_add(size)?;
+
+        self.file.bytes.get(start..end)
+    }
+
+    /// Returns the file data located at the virtual address `address`, extending to the end of the
+    /// file-backed portion of the segment containing `address`.
+    pub fn virtual_address_data(&self, address: u64) -> Option<&'slice [u8]> {
+        self.file.virtual_address_data(address)
+    }
+}
+
+impl fmt::Debug for DynElfFile<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.file, f)
+    }
+}
+
+/// An [`Iterator`] over the [`NormalizedProgramHeader`]s of a [`DynElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DynProgramHeaders<'slice> {
+    /// The underlying [`Iterator`] over the program header table.
+    iter: Option<program_header::IntoIter<'slice, AnyClass, AnyEndian>>,
+}
+
+impl Iterator for DynProgramHeaders<'_> {
+    type Item = NormalizedProgramHeader;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .as_mut()?
+            .next()
+            .map(|program_header| program_header.normalize())
+    }
+}
+
+impl FusedIterator for DynProgramHeaders<'_> {}
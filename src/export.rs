@@ -0,0 +1,184 @@
+//! Definitions for exporting the [`SegmentType::LOAD`] segments of an ELF file in the Intel HEX
+//! and Motorola S-record formats.
+//!
+//! Both formats place the file-backed contents of each [`SegmentType::LOAD`] segment at its
+//! physical address and are limited to 32-bit addresses.
+
+use core::{error, fmt};
+
+use crate::{class::ClassParse, encoding::EncodingParse, program_header::SegmentType, ElfFile};
+
+/// The maximum number of data bytes emitted in a single record.
+const RECORD_DATA_SIZE: usize = 16;
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Writes the [`SegmentType::LOAD`] segments of this [`ElfFile`] to `writer` in the Intel
+    /// HEX format.
+    ///
+    /// Extended linear address records are emitted whenever the upper 16 bits of the address
+    /// change, and the entry point, if any, is emitted as a start linear address record.
+    ///
+    /// # Errors
+    ///
+    /// - [`ExportError::SegmentOutOfBounds`]: Returned if the file data of a
+    ///   [`SegmentType::LOAD`] segment is located out of bounds.
+    /// - [`ExportError::AddressOutOfRange`]: Returned if an address does not fit in 32 bits.
+    /// - [`ExportError::Write`]: Returned if writing to `writer` fails.
+    pub fn write_intel_hex<W: fmt::Write>(&self, writer: &mut W) -> Result<(), ExportError> {
+        let mut upper = 0;
+        self.for_each_record(|address, data| {
+            let address = u32::try_from(address).map_err(|_| ExportError::AddressOutOfRange)?;
+            let record_upper = (address >> 16) as u16;
+            if record_upper != upper {
+                upper = record_upper;
+                write_hex_record(writer, 0, 0x04, &upper.to_be_bytes())?;
+            }
+
+            write_hex_record(writer, address as u16, 0x00, data)
+        })?;
+
+        let entry: u64 = self.header().entry().into();
+        if entry != 0 {
+            let entry = u32::try_from(entry).map_err(|_| ExportError::AddressOutOfRange)?;
+            write_hex_record(writer, 0, 0x05, &entry.to_be_bytes())?;
+        }
+
+        write_hex_record(writer, 0, 0x01, &[])
+    }
+
+    /// Writes the [`SegmentType::LOAD`] segments of this [`ElfFile`] to `writer` in the Motorola
+    /// S-record format.
+    ///
+    /// Data is emitted as S3 records with 32-bit addresses, terminated by an S7 record carrying
+    /// the entry point.
+    ///
+    /// # Errors
+    ///
+    /// - [`ExportError::SegmentOutOfBounds`]: Returned if the file data of a
+    ///   [`SegmentType::LOAD`] segment is located out of bounds.
+    /// - [`ExportError::AddressOutOfRange`]: Returned if an address does not fit in 32 bits.
+    /// - [`ExportError::Write`]: Returned if writing to `writer` fails.
+    pub fn write_srecords<W: fmt::Write>(&self, writer: &mut W) -> Result<(), ExportError> {
+        write_srecord(writer, 0, &[0; 2], &[])?;
+
+        self.for_each_record(|address, data| {
+            let address = u32::try_from(address).map_err(|_| ExportError::AddressOutOfRange)?;
+            write_srecord(writer, 3, &address.to_be_bytes(), data)
+        })?;
+
+        let entry = u32::try_from(self.header().entry().into())
+            .map_err(|_| ExportError::AddressOutOfRange)?;
+        write_srecord(writer, 7, &entry.to_be_bytes(), &[])
+    }
+
+    /// Calls `f` with the physical address and data of each record of at most
+    /// [`RECORD_DATA_SIZE`] bytes.
+    ///
+    /// Records never cross a 64 KiB boundary.
+    fn for_each_record<F: FnMut(u64, &[u8]) -> Result<(), ExportError>>(
+        &self,
+        mut f: F,
+    ) -> Result<(), ExportError> {
+        let Some(table) = self.program_header_table() else {
+            return Ok(());
+        };
+
+        for program_header in table.segments_of_type(SegmentType::LOAD) {
+            let mut data = self
+                .segment_data(program_header)
+                .ok_or(ExportError::SegmentOutOfBounds)?;
+            let mut address: u64 = program_header.physical_address().into();
+
+            while !data.is_empty() {
+                let boundary = 0x1_0000 - (address & 0xFFFF);
+                let length = data
+                    .len()
+                    .min(RECORD_DATA_SIZE)
+                    .min(usize::try_from(boundary).unwrap_or(usize::MAX));
+                let (record, remaining) = data.split_at(length);
+
+                f(address, record)?;
+
+                data = remaining;
+                address = address
+                    .checked_add(length as u64)
+                    .ok_or(ExportError::AddressOutOfRange)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a single Intel HEX record.
+fn write_hex_record<W: fmt::Write>(
+    writer: &mut W,
+    address: u16,
+    record_type: u8,
+    data: &[u8],
+) -> Result<(), ExportError> {
+    let [high, low] = address.to_be_bytes();
+    let mut sum = (data.len() as u8)
+        .wrapping_add(high)
+        .wrapping_add(low)
+        .wrapping_add(record_type);
+
+    write!(writer, ":{:02X}{address:04X}{record_type:02X}", data.len())?;
+    for &byte in data {
+        sum = sum.wrapping_add(byte);
+        write!(writer, "{byte:02X}")?;
+    }
+    writeln!(writer, "{:02X}", sum.wrapping_neg())?;
+
+    Ok(())
+}
+
+/// Writes a single Motorola S-record.
+fn write_srecord<W: fmt::Write>(
+    writer: &mut W,
+    record_type: u8,
+    address: &[u8],
+    data: &[u8],
+) -> Result<(), ExportError> {
+    let count = (address.len() + data.len() + 1) as u8;
+    let mut sum = count;
+
+    write!(writer, "S{record_type}{count:02X}")?;
+    for &byte in address.iter().chain(data) {
+        sum = sum.wrapping_add(byte);
+        write!(writer, "{byte:02X}")?;
+    }
+    writeln!(writer, "{:02X}", !sum)?;
+
+    Ok(())
+}
+
+/// Various errors that can occur while exporting the [`SegmentType::LOAD`] segments of an
+/// [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ExportError {
+    /// The file data of a [`SegmentType::LOAD`] segment is located out of bounds.
+    SegmentOutOfBounds,
+    /// An address does not fit in the 32-bit address space of the output format.
+    AddressOutOfRange,
+    /// An error occurred while writing the output.
+    Write(fmt::Error),
+}
+
+impl From<fmt::Error> for ExportError {
+    fn from(value: fmt::Error) -> Self {
+        Self::Write(value)
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SegmentOutOfBounds => write!(f, "load segment located out of bounds"),
+            Self::AddressOutOfRange => write!(f, "address does not fit in 32 bits"),
+            Self::Write(error) => write!(f, "error while writing output: {error}"),
+        }
+    }
+}
+
+impl error::Error for ExportError {}
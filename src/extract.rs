@@ -0,0 +1,716 @@
+//! Definitions for extracting a single section of an ELF file into a new relocatable file.
+//!
+//! The new file holds the section, the relocation section that applies to it, and a symbol table
+//! with the symbols defined in the section and those its relocations refer to, similar to
+//! `objcopy --only-section`. Symbols defined in other sections become undefined, so the new file
+//! links against whichever objects provide them. Local symbols defined in other sections,
+//! including the section symbols used by relocations against them, cannot be carried over, so
+//! their presence makes the extraction fail.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, ClassParse, OffsetOverflow},
+    encoding::EncodingParse,
+    header::ElfType,
+    relocation::{RelTable, RelaTable},
+    section_header::{SectionFlags, SectionHeader, SectionType},
+    symbol::{SectionIndex, Symbol, SymbolBinding, SymbolTable, SymbolType},
+    writer::{ByteLayout, Writer},
+    ElfFile,
+};
+
+/// The size of the ELF identifier.
+const IDENT_SIZE: usize = 16;
+/// The names of the symbol table, its string table and the section name string table.
+const TABLE_NAMES: [&[u8]; 3] = [b".symtab", b".strtab", b".shstrtab"];
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Writes a new [`ElfType::RELOCATABLE`] file holding the section at `index`, its relocations
+    /// and the symbols it defines or refers to into the start of `buffer`, returning the number
+    /// of bytes written.
+    ///
+    /// `mapping` receives the index of each symbol of this [`ElfFile`] in the new file, or zero
+    /// if it was not carried over, and must hold at least one entry per symbol. Symbol values
+    /// and relocation offsets are made relative to the start of the section.
+    ///
+    /// # Errors
+    ///
+    /// - [`ExtractSectionError::MissingSection`]: Returned if there is no section at `index`, or
+    ///   it or its name is located out of bounds.
+    /// - [`ExtractSectionError::UnsupportedSection`]: Returned if the section is a symbol table,
+    ///   relocation section or section group, which only have meaning alongside other sections.
+    /// - [`ExtractSectionError::MissingSymbolTable`]: Returned if the relocation section that
+    ///   applies to the section does not link to a symbol table located in bounds.
+    /// - [`ExtractSectionError::MappingTooSmall`]: Returned if `mapping` holds fewer entries than
+    ///   there are symbols.
+    /// - [`ExtractSectionError::InvalidSymbolIndex`]: Returned if a relocation refers to a symbol
+    ///   that does not exist.
+    /// - [`ExtractSectionError::LocalSymbolOutsideSection`]: Returned if a relocation refers to a
+    ///   local symbol defined in another section.
+    /// - [`ExtractSectionError::OffsetOverflow`]: Returned if an offset cannot be represented by
+    ///   a [`usize`] on the host.
+    /// - [`ExtractSectionError::BufferTooSmall`]: Returned if `buffer` is too small to hold the
+    ///   new file.
+    pub fn extract_section(
+        &self,
+        index: u16,
+        mapping: &mut [u32],
+        buffer: &mut [u8],
+    ) -> Result<usize, ExtractSectionError> {
+        let layout = self.extraction_layout(index, mapping)?;
+        let buffer = buffer
+            .get_mut(..layout.size)
+            .ok_or(ExtractSectionError::BufferTooSmall {
+                required: layout.size,
+            })?;
+        buffer.fill(0);
+
+        let header = self.header();
+        let mut writer = Writer {
+            buffer,
+            layout: ByteLayout::new(header.ident()),
+        };
+
+        let word_size = writer.word_size() as u64;
+        let section_header_size = self.class.expected_section_header_size();
+        let section_count = if layout.relocations.is_some() { 6 } else { 5 };
+        writer.buffer[..IDENT_SIZE].copy_from_slice(&self.bytes[..IDENT_SIZE]);
+        writer.u16(self.class.elf_type_offset(), ElfType::RELOCATABLE.0);
+        writer.u16(self.class.machine_offset(), header.machine().0);
+        writer.u32(self.class.file_version_offset(), header.file_version());
+        writer.word(
+            self.class.section_header_offset_offset(),
+            layout.section_headers_offset as u64,
+        );
+        writer.u32(self.class.flags_offset(), header.flags());
+        writer.u16(
+            self.class.header_size_offset(),
+            self.class.expected_elf_header_size() as u16,
+        );
+        writer.u16(
+            self.class.section_header_size_offset(),
+            section_header_size as u16,
+        );
+        writer.u16(self.class.section_header_count_offset(), section_count);
+        writer.u16(
+            self.class.section_header_string_table_index_offset(),
+            section_count - 1,
+        );
+
+        // The section name string table holds the names of the sections in order.
+        let mut names = StringWriter {
+            start: layout.names_offset,
+            next: layout.names_offset + 1,
+        };
+        let section_name = names.push(&mut writer, layout.name);
+        let relocation_name = layout
+            .relocations
+            .map(|relocations| names.push(&mut writer, relocations.name));
+        let [symbols_name, strings_name, names_name] =
+            TABLE_NAMES.map(|name| names.push(&mut writer, name));
+
+        // The extracted section.
+        let mut section_headers = layout.section_headers_offset + section_header_size;
+        let data_size = if layout.section.section_type() == SectionType::NOBITS {
+            to_len(layout.section.size())?
+        } else {
+            writer.buffer[layout.data_offset..layout.data_offset + layout.data.len()]
+                .copy_from_slice(layout.data);
+            layout.data.len()
+        };
+        self.write_section_header(
+            &mut writer,
+            section_headers,
+            SectionHeaderFields {
+                name: section_name,
+                section_type: layout.section.section_type(),
+                flags: SectionFlags(layout.section.flags().0 & !SectionFlags::GROUP.0),
+                offset: layout.data_offset,
+                size: data_size,
+                link: 0,
+                info: 0,
+                alignment: layout.section.alignment().into(),
+                entry_size: layout.section.entry_size().into(),
+            },
+        );
+        section_headers += section_header_size;
+
+        // The symbol table, starting with the null symbol and the symbol of the section.
+        let symbol_size = self.class.expected_symbol_size();
+        let base = layout.base;
+        let mut strings = StringWriter {
+            start: layout.strings_offset,
+            next: layout.strings_offset + 1,
+        };
+        let section_symbol = layout.symbols_offset + symbol_size;
+        writer.buffer[section_symbol + self.class.symbol_info_offset()] =
+            (SymbolBinding::LOCAL.0 << 4) | SymbolType::SECTION.0;
+        writer.u16(section_symbol + self.class.symbol_section_index_offset(), 1);
+
+        if let Some(symbols) = layout.symbols {
+            for (symbol_index, symbol) in symbols.into_iter().enumerate() {
+                let new_index = mapping[symbol_index] as usize;
+                if new_index <= 1 {
+                    continue;
+                }
+
+                let start = layout.symbols_offset + new_index * symbol_size;
+                writer.buffer[start..start + symbol_size]
+                    .copy_from_slice(&symbol.raw_bytes()[..symbol_size]);
+
+                let name = symbol.name().unwrap_or(&[]);
+                let name_offset = if name.is_empty() {
+                    0
+                } else {
+                    strings.push(&mut writer, name)
+                };
+                writer.u32(start + self.class.symbol_name_offset(), name_offset);
+
+                let section_index = symbol.section_index();
+                let (value, section_index) = if section_index.0 == index {
+                    (Into::<u64>::into(symbol.value()).wrapping_sub(base), 1)
+                } else if section_index.is_reserved() && section_index != SectionIndex::EXTENDED {
+                    (symbol.value().into(), section_index.0)
+                } else {
+                    writer.word(start + self.class.symbol_size_offset(), 0);
+                    (0, SectionIndex::UNDEFINED.0)
+                };
+                writer.word(start + self.class.symbol_value_offset(), value);
+                writer.u16(
+                    start + self.class.symbol_section_index_offset(),
+                    section_index,
+                );
+            }
+        }
+
+        // The relocation section, with entries referring to the new symbol indices.
+        if let Some(relocations) = layout.relocations {
+            for entry in 0..relocations.count {
+                let Some((offset, info, addend)) = relocations.get(entry) else {
+                    continue;
+                };
+                let symbol = mapping
+                    .get(self.class.symbol_raw(info) as usize)
+                    .copied()
+                    .unwrap_or(0);
+                let kind = self.class.relocation_type_raw(info);
+                let info = if writer.layout.wide {
+                    (u64::from(symbol) << 32) | u64::from(kind)
+                } else {
+                    (u64::from(symbol) << 8) | u64::from(kind & 0xFF)
+                };
+
+                let start = layout.relocations_offset + entry * relocations.entry_size();
+                match addend {
+                    Some(addend) => {
+                        writer.word(
+                            start + self.class.rela_offset_offset(),
+                            offset.wrapping_sub(base),
+                        );
+                        writer.word(start + self.class.rela_info_offset(), info);
+                        writer.word(start + self.class.rela_addend_offset(), addend as u64);
+                    }
+                    None => {
+                        writer.word(
+                            start + self.class.rel_offset_offset(),
+                            offset.wrapping_sub(base),
+                        );
+                        writer.word(start + self.class.rel_info_offset(), info);
+                    }
+                }
+            }
+
+            self.write_section_header(
+                &mut writer,
+                section_headers,
+                SectionHeaderFields {
+                    name: relocation_name.unwrap_or(0),
+                    section_type: relocations.section.section_type(),
+                    flags: SectionFlags::INFO_LINK,
+                    offset: layout.relocations_offset,
+                    size: relocations.count * relocations.entry_size(),
+                    link: 3,
+                    info: 1,
+                    alignment: word_size,
+                    entry_size: relocations.entry_size() as u64,
+                },
+            );
+            section_headers += section_header_size;
+        }
+
+        let symbols_index = section_count - 3;
+        let table_headers = [
+            SectionHeaderFields {
+                name: symbols_name,
+                section_type: SectionType::SYMTAB,
+                flags: SectionFlags(0),
+                offset: layout.symbols_offset,
+                size: layout.symbol_count * symbol_size,
+                link: u32::from(symbols_index + 1),
+                info: layout.first_global,
+                alignment: word_size,
+                entry_size: symbol_size as u64,
+            },
+            SectionHeaderFields {
+                name: strings_name,
+                section_type: SectionType::STRTAB,
+                flags: SectionFlags(0),
+                offset: layout.strings_offset,
+                size: layout.strings_size,
+                link: 0,
+                info: 0,
+                alignment: 1,
+                entry_size: 0,
+            },
+            SectionHeaderFields {
+                name: names_name,
+                section_type: SectionType::STRTAB,
+                flags: SectionFlags(0),
+                offset: layout.names_offset,
+                size: layout.names_size,
+                link: 0,
+                info: 0,
+                alignment: 1,
+                entry_size: 0,
+            },
+        ];
+        for fields in table_headers {
+            self.write_section_header(&mut writer, section_headers, fields);
+            section_headers += section_header_size;
+        }
+
+        Ok(layout.size)
+    }
+
+    /// Computes the [`ExtractionLayout`] of the section at `index`, filling `mapping` with the
+    /// new index of each symbol.
+    fn extraction_layout(
+        &self,
+        index: u16,
+        mapping: &mut [u32],
+    ) -> Result<ExtractionLayout<'slice, C, E>, ExtractSectionError> {
+        let table = self
+            .section_header_table()
+            .ok_or(ExtractSectionError::MissingSection)?;
+        let section = table
+            .get(index)
+            .filter(|_| index != 0)
+            .ok_or(ExtractSectionError::MissingSection)?;
+        if matches!(
+            section.section_type(),
+            SectionType::NULL
+                | SectionType::SYMTAB
+                | SectionType::DYNSYM
+                | SectionType::REL
+                | SectionType::RELA
+                | SectionType::GROUP
+                | SectionType::SYMTAB_SHNDX
+        ) {
+            return Err(ExtractSectionError::UnsupportedSection);
+        }
+        let data = self
+            .section_data(section)
+            .ok_or(ExtractSectionError::MissingSection)?;
+        let name = self
+            .section_name(section)
+            .ok_or(ExtractSectionError::MissingSection)?;
+
+        let relocations = table
+            .into_iter()
+            .find(|relocations| {
+                matches!(
+                    relocations.section_type(),
+                    SectionType::REL | SectionType::RELA
+                ) && relocations.info() == u32::from(index)
+            })
+            .map(|relocations| self.relocations(relocations))
+            .transpose()?;
+
+        let symbols = match relocations {
+            Some(relocations) => Some(
+                u16::try_from(relocations.section.link())
+                    .ok()
+                    .and_then(|link| table.get(link))
+                    .and_then(|symbols| self.symbol_table_from_section(symbols))
+                    .ok_or(ExtractSectionError::MissingSymbolTable)?,
+            ),
+            None => self.symbol_table(),
+        };
+
+        let symbol_count = symbols.map_or(0, |symbols| symbols.count());
+        let mapping =
+            mapping
+                .get_mut(..symbol_count)
+                .ok_or(ExtractSectionError::MappingTooSmall {
+                    required: symbol_count,
+                })?;
+        mapping.fill(0);
+
+        // Mark the symbols to carry over, mapping the symbol of the section to its new symbol.
+        const KEPT: u32 = u32::MAX;
+        let is_kept = |symbol: &Symbol<'slice, C, E>| {
+            let section_index = symbol.section_index();
+            section_index.0 == index
+                || (section_index.is_reserved()
+                    && section_index != SectionIndex::UNDEFINED
+                    && section_index != SectionIndex::EXTENDED)
+        };
+        if let Some(symbols) = symbols {
+            for (symbol_index, symbol) in symbols.into_iter().enumerate().skip(1) {
+                if symbol.section_index().0 != index || symbol.symbol_type() == SymbolType::FILE {
+                    continue;
+                }
+
+                mapping[symbol_index] = if symbol.symbol_type() == SymbolType::SECTION {
+                    1
+                } else {
+                    KEPT
+                };
+            }
+        }
+        if let (Some(relocations), Some(symbols)) = (relocations, symbols) {
+            for entry in 0..relocations.count {
+                let Some((_, info, _)) = relocations.get(entry) else {
+                    continue;
+                };
+                let symbol_index = self.class.symbol_raw(info) as usize;
+                if symbol_index == 0 || mapping.get(symbol_index).is_some_and(|&new| new != 0) {
+                    continue;
+                }
+
+                let symbol = symbols
+                    .get(symbol_index)
+                    .ok_or(ExtractSectionError::InvalidSymbolIndex)?;
+                if symbol.binding() == SymbolBinding::LOCAL && !is_kept(&symbol) {
+                    return Err(ExtractSectionError::LocalSymbolOutsideSection);
+                }
+                mapping[symbol_index] = KEPT;
+            }
+        }
+
+        // Assign the new indices, placing the local symbols first, and size the string table.
+        let mut next = 2;
+        let mut strings_size = 1;
+        let mut first_global = 0;
+        for locals in [true, false] {
+            if !locals {
+                first_global = next;
+            }
+
+            let Some(symbols) = symbols else {
+                continue;
+            };
+            for (symbol_index, symbol) in symbols.into_iter().enumerate() {
+                if mapping[symbol_index] != KEPT
+                    || (symbol.binding() == SymbolBinding::LOCAL) != locals
+                {
+                    continue;
+                }
+
+                mapping[symbol_index] = next;
+                next += 1;
+                strings_size += symbol.name().map_or(0, |name| match name.len() {
+                    0 => 0,
+                    len => len + 1,
+                });
+            }
+        }
+
+        let names_size = 1
+            + name.len()
+            + 1
+            + relocations.map_or(0, |relocations| relocations.name.len() + 1)
+            + TABLE_NAMES.iter().map(|name| name.len() + 1).sum::<usize>();
+
+        let word_size = ByteLayout::new(self.header().ident()).word_size();
+        let alignment = to_len(section.alignment())?.max(1);
+        let data_offset = self
+            .class
+            .expected_elf_header_size()
+            .next_multiple_of(alignment);
+        let relocations_offset = (data_offset + data.len()).next_multiple_of(word_size);
+        let symbols_offset = (relocations_offset
+            + relocations.map_or(0, |relocations| {
+                relocations.count * relocations.entry_size()
+            }))
+        .next_multiple_of(word_size);
+        let symbol_count = next as usize;
+        let strings_offset = symbols_offset + symbol_count * self.class.expected_symbol_size();
+        let names_offset = strings_offset + strings_size;
+        let section_headers_offset = (names_offset + names_size).next_multiple_of(word_size);
+        let section_count = if relocations.is_some() { 6 } else { 5 };
+        let size =
+            section_headers_offset + section_count * self.class.expected_section_header_size();
+
+        Ok(ExtractionLayout {
+            section,
+            data,
+            name,
+            base: if self.header().elf_type() == ElfType::RELOCATABLE {
+                0
+            } else {
+                section.address().into()
+            },
+            relocations,
+            symbols,
+            symbol_count,
+            first_global,
+            data_offset,
+            relocations_offset,
+            symbols_offset,
+            strings_offset,
+            strings_size,
+            names_offset,
+            names_size,
+            section_headers_offset,
+            size,
+        })
+    }
+
+    /// Returns the [`Relocations`] held by the section `section` describes.
+    fn relocations(
+        &self,
+        section: SectionHeader<'slice, C, E>,
+    ) -> Result<Relocations<'slice, C, E>, ExtractSectionError> {
+        let name = self
+            .section_name(section)
+            .ok_or(ExtractSectionError::MissingSection)?;
+        let data = self
+            .section_data(section)
+            .ok_or(ExtractSectionError::MissingSection)?;
+
+        let entries = if section.section_type() == SectionType::RELA {
+            let count = data.len() / self.class.expected_rela_size();
+            RelaTable::new(self.class, self.encoding, data, count).map(RelocationEntries::Rela)
+        } else {
+            let count = data.len() / self.class.expected_rel_size();
+            RelTable::new(self.class, self.encoding, data, count).map(RelocationEntries::Rel)
+        }
+        .ok_or(ExtractSectionError::MissingSection)?;
+        let count = match entries {
+            RelocationEntries::Rel(table) => table.count(),
+            RelocationEntries::Rela(table) => table.count(),
+        };
+
+        Ok(Relocations {
+            section,
+            name,
+            entries,
+            count,
+        })
+    }
+
+    /// Writes the section header described by `fields` at `start`.
+    fn write_section_header(
+        &self,
+        writer: &mut Writer<'_>,
+        start: usize,
+        fields: SectionHeaderFields,
+    ) {
+        writer.u32(start + self.class.section_name_offset(), fields.name);
+        writer.u32(
+            start + self.class.section_type_offset(),
+            fields.section_type.0,
+        );
+        writer.word(start + self.class.section_flags_offset(), fields.flags.0);
+        writer.word(
+            start + self.class.section_file_offset_offset(),
+            fields.offset as u64,
+        );
+        writer.word(start + self.class.section_size_offset(), fields.size as u64);
+        writer.u32(start + self.class.section_link_offset(), fields.link);
+        writer.u32(start + self.class.section_info_offset(), fields.info);
+        writer.word(
+            start + self.class.section_alignment_offset(),
+            fields.alignment,
+        );
+        writer.word(
+            start + self.class.section_entry_size_offset(),
+            fields.entry_size,
+        );
+    }
+}
+
+/// The layout of an extracted section.
+struct ExtractionLayout<'slice, C, E> {
+    /// The [`SectionHeader`] of the extracted section.
+    section: SectionHeader<'slice, C, E>,
+    /// The file data of the extracted section.
+    data: &'slice [u8],
+    /// The name of the extracted section.
+    name: &'slice [u8],
+    /// The address subtracted from symbol values and relocation offsets.
+    base: u64,
+    /// The relocations that apply to the extracted section, if any.
+    relocations: Option<Relocations<'slice, C, E>>,
+    /// The symbol table the relocations refer to, if any.
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The number of symbols in the new file.
+    symbol_count: usize,
+    /// The index of the first non-local symbol in the new file.
+    first_global: u32,
+    /// The offset of the data of the extracted section.
+    data_offset: usize,
+    /// The offset of the relocation entries.
+    relocations_offset: usize,
+    /// The offset of the symbol table.
+    symbols_offset: usize,
+    /// The offset of the string table holding the names of the symbols.
+    strings_offset: usize,
+    /// The size of the string table holding the names of the symbols.
+    strings_size: usize,
+    /// The offset of the section name string table.
+    names_offset: usize,
+    /// The size of the section name string table.
+    names_size: usize,
+    /// The offset of the section header table.
+    section_headers_offset: usize,
+    /// The total size of the new file.
+    size: usize,
+}
+
+/// A relocation section and its entries.
+#[derive(Clone, Copy)]
+struct Relocations<'slice, C, E> {
+    /// The [`SectionHeader`] of the relocation section.
+    section: SectionHeader<'slice, C, E>,
+    /// The name of the relocation section.
+    name: &'slice [u8],
+    /// The entries of the relocation section.
+    entries: RelocationEntries<'slice, C, E>,
+    /// The number of entries in the relocation section.
+    count: usize,
+}
+
+impl<C: ClassParse, E: EncodingParse> Relocations<'_, C, E> {
+    /// Returns the offset, information and addend, if explicit, of the entry at `index`.
+    fn get(&self, index: usize) -> Option<(u64, C::ClassUsize, Option<i64>)> {
+        match self.entries {
+            RelocationEntries::Rel(table) => {
+                let rel = table.get(index)?;
+                Some((rel.offset.into(), rel.info, None))
+            }
+            RelocationEntries::Rela(table) => {
+                let rela = table.get(index)?;
+                Some((rela.offset.into(), rela.info, Some(rela.addend.into())))
+            }
+        }
+    }
+
+    /// Returns the size of each entry.
+    fn entry_size(&self) -> usize {
+        match self.entries {
+            RelocationEntries::Rel(table) => table.class.expected_rel_size(),
+            RelocationEntries::Rela(table) => table.class.expected_rela_size(),
+        }
+    }
+}
+
+/// The entries of a relocation section.
+#[derive(Clone, Copy)]
+enum RelocationEntries<'slice, C, E> {
+    /// Entries without an explicit addend.
+    Rel(RelTable<'slice, C, E>),
+    /// Entries with an explicit addend.
+    Rela(RelaTable<'slice, C, E>),
+}
+
+/// The fields of a section header in the new file.
+struct SectionHeaderFields {
+    /// The offset of the name in the section name string table.
+    name: u32,
+    /// The [`SectionType`] of the section.
+    section_type: SectionType,
+    /// The [`SectionFlags`] of the section.
+    flags: SectionFlags,
+    /// The offset of the data of the section.
+    offset: usize,
+    /// The size of the section.
+    size: usize,
+    /// The index of the associated section.
+    link: u32,
+    /// The extra information of the section.
+    info: u32,
+    /// The alignment of the section.
+    alignment: u64,
+    /// The size of each entry of the section.
+    entry_size: u64,
+}
+
+/// Helper for appending null-terminated strings to a string table.
+struct StringWriter {
+    /// The offset of the string table.
+    start: usize,
+    /// The offset at which the next string is written.
+    next: usize,
+}
+
+impl StringWriter {
+    /// Appends `string` to the string table, returning its offset within the table.
+    fn push(&mut self, writer: &mut Writer<'_>, string: &[u8]) -> u32 {
+        writer.buffer[self.next..self.next + string.len()].copy_from_slice(string);
+        let offset = self.next - self.start;
+        self.next += string.len() + 1;
+        offset as u32
+    }
+}
+
+/// Various errors that can occur while extracting a section of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ExtractSectionError {
+    /// The section, its name or its relocation section is missing or located out of bounds.
+    MissingSection,
+    /// The section cannot be extracted on its own.
+    UnsupportedSection,
+    /// The relocation section does not link to a symbol table located in bounds.
+    MissingSymbolTable,
+    /// The buffer receiving the new symbol indices is too small.
+    MappingTooSmall {
+        /// The number of entries required to hold the new symbol indices.
+        required: usize,
+    },
+    /// A relocation refers to a symbol that does not exist.
+    InvalidSymbolIndex,
+    /// A relocation refers to a local symbol defined in another section.
+    LocalSymbolOutsideSection,
+    /// A class sized value cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
+    /// The buffer is too small to hold the new file.
+    BufferTooSmall {
+        /// The number of bytes required to hold the new file.
+        required: usize,
+    },
+}
+
+impl From<OffsetOverflow> for ExtractSectionError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl fmt::Display for ExtractSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSection => write!(f, "section missing or located out of bounds"),
+            Self::UnsupportedSection => write!(f, "section cannot be extracted on its own"),
+            Self::MissingSymbolTable => write!(f, "relocation section has no symbol table"),
+            Self::MappingTooSmall { required } => {
+                write!(f, "symbol mapping too small to hold {required} entries")
+            }
+            Self::InvalidSymbolIndex => write!(f, "relocation refers to a nonexistent symbol"),
+            Self::LocalSymbolOutsideSection => {
+                write!(f, "relocation refers to a local symbol of another section")
+            }
+            Self::OffsetOverflow(error) => write!(f, "error while converting offset: {error}"),
+            Self::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "buffer too small to hold extracted file of {required} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for ExtractSectionError {}
@@ -0,0 +1,328 @@
+//! Definitions for decoding the processor specific flags of an ELF file.
+
+use core::fmt;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    header::{ElfHeader, Machine},
+};
+
+impl<C: ClassParse, E: EncodingParse> ElfHeader<'_, C, E> {
+    /// Returns the processor specific flags associated with the ELF file, decoded according to
+    /// the [`Machine`] of this [`ElfHeader`].
+    pub fn machine_flags(&self) -> MachineFlags {
+        MachineFlags::new(self.machine(), self.flags())
+    }
+}
+
+/// The processor specific flags of an ELF file, decoded according to its [`Machine`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MachineFlags {
+    /// Flags of an ELF file targeting [`Machine::ARM`].
+    Arm(ArmFlags),
+    /// Flags of an ELF file targeting [`Machine::RISCV`].
+    RiscV(RiscVFlags),
+    /// Flags of an ELF file targeting [`Machine::MIPS`].
+    Mips(MipsFlags),
+    /// Flags of an ELF file targeting [`Machine::PPC64`].
+    PowerPc64(PowerPc64Flags),
+    /// Flags of an ELF file targeting a [`Machine`] whose flags are not decoded by this crate.
+    Unknown(u32),
+}
+
+impl MachineFlags {
+    /// Decodes the raw `flags` of an ELF file targeting `machine`.
+    pub const fn new(machine: Machine, flags: u32) -> Self {
+        match machine {
+            Machine::ARM => Self::Arm(ArmFlags(flags)),
+            Machine::RISCV => Self::RiscV(RiscVFlags(flags)),
+            Machine::MIPS => Self::Mips(MipsFlags(flags)),
+            Machine::PPC64 => Self::PowerPc64(PowerPc64Flags(flags)),
+            _ => Self::Unknown(flags),
+        }
+    }
+
+    /// Returns the raw flags.
+    pub const fn raw(self) -> u32 {
+        match self {
+            Self::Arm(flags) => flags.0,
+            Self::RiscV(flags) => flags.0,
+            Self::Mips(flags) => flags.0,
+            Self::PowerPc64(flags) => flags.0,
+            Self::Unknown(flags) => flags,
+        }
+    }
+}
+
+/// The processor specific flags of an ELF file targeting [`Machine::ARM`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArmFlags(pub u32);
+
+impl ArmFlags {
+    /// Mask of the bits holding the EABI version.
+    pub const EABI_MASK: u32 = 0xFF00_0000;
+    /// The code uses the BE8 byte ordering.
+    pub const BE8: u32 = 0x0080_0000;
+    /// The code uses the soft-float calling convention.
+    pub const ABI_FLOAT_SOFT: u32 = 0x200;
+    /// The code uses the hard-float calling convention.
+    pub const ABI_FLOAT_HARD: u32 = 0x400;
+
+    /// Returns the version of the ARM EABI to which the ELF file conforms.
+    ///
+    /// A version of 0 indicates that the ELF file does not conform to the EABI.
+    pub const fn eabi_version(self) -> u8 {
+        ((self.0 & Self::EABI_MASK) >> 24) as u8
+    }
+
+    /// Returns the [`ArmFloatAbi`] used by the ELF file.
+    pub const fn float_abi(self) -> ArmFloatAbi {
+        match (
+            self.0 & Self::ABI_FLOAT_HARD != 0,
+            self.0 & Self::ABI_FLOAT_SOFT != 0,
+        ) {
+            (true, false) => ArmFloatAbi::Hard,
+            (false, true) => ArmFloatAbi::Soft,
+            (false, false) => ArmFloatAbi::Unspecified,
+            (true, true) => ArmFloatAbi::Conflicting,
+        }
+    }
+
+    /// Returns `true` if the code uses the BE8 byte ordering.
+    pub const fn is_be8(self) -> bool {
+        self.0 & Self::BE8 != 0
+    }
+}
+
+/// The floating point calling convention of an ELF file targeting [`Machine::ARM`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ArmFloatAbi {
+    /// Neither floating point calling convention is specified.
+    Unspecified,
+    /// Floating point arguments are passed in integer registers.
+    Soft,
+    /// Floating point arguments are passed in floating point registers.
+    Hard,
+    /// Both floating point calling conventions are specified.
+    Conflicting,
+}
+
+/// The processor specific flags of an ELF file targeting [`Machine::RISCV`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RiscVFlags(pub u32);
+
+impl RiscVFlags {
+    /// The code uses compressed instructions.
+    pub const RVC: u32 = 0x1;
+    /// Mask of the bits holding the [`RiscVFloatAbi`].
+    pub const FLOAT_ABI_MASK: u32 = 0x6;
+    /// The code targets the RV32E base integer instruction set.
+    pub const RVE: u32 = 0x8;
+    /// The code requires the RVTSO memory consistency model.
+    pub const TSO: u32 = 0x10;
+
+    /// Returns `true` if the code uses compressed instructions.
+    pub const fn rvc(self) -> bool {
+        self.0 & Self::RVC != 0
+    }
+
+    /// Returns the [`RiscVFloatAbi`] used by the ELF file.
+    pub const fn float_abi(self) -> RiscVFloatAbi {
+        RiscVFloatAbi(self.0 & Self::FLOAT_ABI_MASK)
+    }
+
+    /// Returns `true` if the code targets the RV32E base integer instruction set.
+    pub const fn rve(self) -> bool {
+        self.0 & Self::RVE != 0
+    }
+
+    /// Returns `true` if the code requires the RVTSO memory consistency model.
+    pub const fn tso(self) -> bool {
+        self.0 & Self::TSO != 0
+    }
+}
+
+/// The floating point calling convention of an ELF file targeting [`Machine::RISCV`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RiscVFloatAbi(pub u32);
+
+impl RiscVFloatAbi {
+    /// Floating point arguments are passed in integer registers.
+    pub const SOFT: Self = Self(0x0);
+    /// Single precision floating point arguments are passed in floating point registers.
+    pub const SINGLE: Self = Self(0x2);
+    /// Double precision floating point arguments are passed in floating point registers.
+    pub const DOUBLE: Self = Self(0x4);
+    /// Quad precision floating point arguments are passed in floating point registers.
+    pub const QUAD: Self = Self(0x6);
+}
+
+impl fmt::Debug for RiscVFloatAbi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::SOFT => f.pad("Soft"),
+            Self::SINGLE => f.pad("Single"),
+            Self::DOUBLE => f.pad("Double"),
+            Self::QUAD => f.pad("Quad"),
+            float_abi => f.debug_tuple("RiscVFloatAbi").field(&float_abi.0).finish(),
+        }
+    }
+}
+
+/// The processor specific flags of an ELF file targeting [`Machine::MIPS`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MipsFlags(pub u32);
+
+impl MipsFlags {
+    /// The code contains `.noreorder` directives.
+    pub const NOREORDER: u32 = 0x1;
+    /// The code is position independent.
+    pub const PIC: u32 = 0x2;
+    /// The code uses position independent calling sequences.
+    pub const CPIC: u32 = 0x4;
+    /// The code uses the N32 ABI.
+    pub const ABI2: u32 = 0x20;
+    /// The code uses 64-bit floating point registers.
+    pub const FP64: u32 = 0x200;
+    /// The code uses the IEEE 754-2008 NaN encoding.
+    pub const NAN2008: u32 = 0x400;
+    /// Mask of the bits holding the [`MipsAbi`].
+    pub const ABI_MASK: u32 = 0x0000_F000;
+    /// Mask of the bits holding the [`MipsArch`].
+    pub const ARCH_MASK: u32 = 0xF000_0000;
+
+    /// Returns the [`MipsArch`] targeted by the ELF file.
+    pub const fn arch(self) -> MipsArch {
+        MipsArch(self.0 & Self::ARCH_MASK)
+    }
+
+    /// Returns the [`MipsAbi`] used by the ELF file.
+    pub const fn abi(self) -> MipsAbi {
+        MipsAbi(self.0 & Self::ABI_MASK)
+    }
+
+    /// Returns `true` if the code uses the N32 ABI.
+    pub const fn is_n32(self) -> bool {
+        self.0 & Self::ABI2 != 0
+    }
+
+    /// Returns `true` if the code is position independent.
+    pub const fn is_pic(self) -> bool {
+        self.0 & Self::PIC != 0
+    }
+
+    /// Returns `true` if the code uses 64-bit floating point registers.
+    pub const fn is_fp64(self) -> bool {
+        self.0 & Self::FP64 != 0
+    }
+
+    /// Returns `true` if the code uses the IEEE 754-2008 NaN encoding.
+    pub const fn is_nan2008(self) -> bool {
+        self.0 & Self::NAN2008 != 0
+    }
+}
+
+/// The architecture level targeted by an ELF file targeting [`Machine::MIPS`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MipsArch(pub u32);
+
+impl MipsArch {
+    /// MIPS I.
+    pub const ARCH_1: Self = Self(0x0000_0000);
+    /// MIPS II.
+    pub const ARCH_2: Self = Self(0x1000_0000);
+    /// MIPS III.
+    pub const ARCH_3: Self = Self(0x2000_0000);
+    /// MIPS IV.
+    pub const ARCH_4: Self = Self(0x3000_0000);
+    /// MIPS V.
+    pub const ARCH_5: Self = Self(0x4000_0000);
+    /// MIPS32.
+    pub const ARCH_32: Self = Self(0x5000_0000);
+    /// MIPS64.
+    pub const ARCH_64: Self = Self(0x6000_0000);
+    /// MIPS32 Release 2.
+    pub const ARCH_32R2: Self = Self(0x7000_0000);
+    /// MIPS64 Release 2.
+    pub const ARCH_64R2: Self = Self(0x8000_0000);
+    /// MIPS32 Release 6.
+    pub const ARCH_32R6: Self = Self(0x9000_0000);
+    /// MIPS64 Release 6.
+    pub const ARCH_64R6: Self = Self(0xA000_0000);
+}
+
+impl fmt::Debug for MipsArch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ARCH_1 => f.pad("Mips1"),
+            Self::ARCH_2 => f.pad("Mips2"),
+            Self::ARCH_3 => f.pad("Mips3"),
+            Self::ARCH_4 => f.pad("Mips4"),
+            Self::ARCH_5 => f.pad("Mips5"),
+            Self::ARCH_32 => f.pad("Mips32"),
+            Self::ARCH_64 => f.pad("Mips64"),
+            Self::ARCH_32R2 => f.pad("Mips32r2"),
+            Self::ARCH_64R2 => f.pad("Mips64r2"),
+            Self::ARCH_32R6 => f.pad("Mips32r6"),
+            Self::ARCH_64R6 => f.pad("Mips64r6"),
+            arch => f.debug_tuple("MipsArch").field(&arch.0).finish(),
+        }
+    }
+}
+
+/// The ABI used by an ELF file targeting [`Machine::MIPS`].
+///
+/// The N32 ABI is indicated by [`MipsFlags::is_n32()`] and the N64 ABI is implied by a 64-bit ELF
+/// file with [`MipsAbi::NONE`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MipsAbi(pub u32);
+
+impl MipsAbi {
+    /// No ABI is specified.
+    pub const NONE: Self = Self(0x0000);
+    /// The O32 ABI.
+    pub const O32: Self = Self(0x1000);
+    /// The O64 ABI.
+    pub const O64: Self = Self(0x2000);
+    /// The EABI in 32-bit mode.
+    pub const EABI32: Self = Self(0x3000);
+    /// The EABI in 64-bit mode.
+    pub const EABI64: Self = Self(0x4000);
+}
+
+impl fmt::Debug for MipsAbi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("None"),
+            Self::O32 => f.pad("O32"),
+            Self::O64 => f.pad("O64"),
+            Self::EABI32 => f.pad("Eabi32"),
+            Self::EABI64 => f.pad("Eabi64"),
+            abi => f.debug_tuple("MipsAbi").field(&abi.0).finish(),
+        }
+    }
+}
+
+/// The processor specific flags of an ELF file targeting [`Machine::PPC64`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PowerPc64Flags(pub u32);
+
+impl PowerPc64Flags {
+    /// Mask of the bits holding the ABI version.
+    pub const ABI_MASK: u32 = 0x3;
+
+    /// Returns the version of the 64-bit PowerPC ELF ABI used by the ELF file.
+    ///
+    /// A version of 0 indicates that the ABI version is unspecified.
+    pub const fn abi_version(self) -> u8 {
+        (self.0 & Self::ABI_MASK) as u8
+    }
+}
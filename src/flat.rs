@@ -0,0 +1,142 @@
+//! Definitions for extracting the flat memory image of an ELF file.
+//!
+//! The flat image contains the file-backed contents of every [`SegmentType::LOAD`] segment placed
+//! at its physical address relative to the lowest such address, with any gaps between segments
+//! zero-filled. This matches the output of `objcopy -O binary`, except that the image is built
+//! from segments rather than sections, so any headers mapped by the first segment are included.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, ClassParse, OffsetOverflow},
+    encoding::EncodingParse,
+    program_header::{ProgramHeader, SegmentType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`FlatImageLayout`] of this [`ElfFile`].
+    ///
+    /// Returns `None` if this [`ElfFile`] has no [`SegmentType::LOAD`] segments with file data or
+    /// their extent overflows.
+    pub fn flat_image_layout(&self) -> Option<FlatImageLayout> {
+        let (base, end) = self
+            .program_header_table()?
+            .segments_of_type(SegmentType::LOAD)
+            .filter(|program_header| program_header.file_size().into() != 0)
+            .map(|program_header| {
+                let start: u64 = program_header.physical_address().into();
+                let end = start.checked_add(program_header.file_size().into())?;
+                Some((start, end))
+            })
+            .try_fold(None, |extent, range| {
+                let (start, end) = range?;
+                Some(Some(match extent {
+                    Some((base, top)) => (start.min(base), end.max(top)),
+                    None => (start, end),
+                }))
+            })??;
+
+        Some(FlatImageLayout {
+            base_address: base,
+            size: end - base,
+        })
+    }
+
+    /// Writes the flat memory image of this [`ElfFile`] into the start of `buffer`, returning its
+    /// [`FlatImageLayout`].
+    ///
+    /// # Errors
+    ///
+    /// - [`FlattenError::NoLoadSegments`]: Returned if this [`ElfFile`] has no
+    ///   [`SegmentType::LOAD`] segments with file data.
+    /// - [`FlattenError::OffsetOverflow`]: Returned if the size of the flat image cannot be
+    ///   represented by a [`usize`] on the host.
+    /// - [`FlattenError::BufferTooSmall`]: Returned if `buffer` is smaller than the flat image.
+    /// - [`FlattenError::SegmentOutOfBounds`]: Returned if the file data of a
+    ///   [`SegmentType::LOAD`] segment is located out of bounds.
+    pub fn flatten(&self, buffer: &mut [u8]) -> Result<FlatImageLayout, FlattenError> {
+        let layout = self
+            .flat_image_layout()
+            .ok_or(FlattenError::NoLoadSegments)?;
+        let size = to_len(layout.size)?;
+        let image = buffer
+            .get_mut(..size)
+            .ok_or(FlattenError::BufferTooSmall { required: size })?;
+        image.fill(0);
+
+        let Some(table) = self.program_header_table() else {
+            return Ok(layout);
+        };
+        for program_header in table.segments_of_type(SegmentType::LOAD) {
+            if program_header.file_size().into() == 0 {
+                continue;
+            }
+
+            let data = self
+                .segment_data(program_header)
+                .ok_or(FlattenError::SegmentOutOfBounds)?;
+            let start = flat_offset(program_header, layout)?;
+            image[start..start + data.len()].copy_from_slice(data);
+        }
+
+        Ok(layout)
+    }
+}
+
+/// Returns the offset in the flat image at which the segment controlled by `program_header`
+/// starts.
+fn flat_offset<C: ClassParse, E: EncodingParse>(
+    program_header: ProgramHeader<'_, C, E>,
+    layout: FlatImageLayout,
+) -> Result<usize, FlattenError> {
+    let address: u64 = program_header.physical_address().into();
+
+    Ok(to_len(address - layout.base_address)?)
+}
+
+/// The location and size of the flat memory image of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FlatImageLayout {
+    /// The physical address at which the first byte of the flat image should be placed.
+    pub base_address: u64,
+    /// The size of the flat image in bytes.
+    pub size: u64,
+}
+
+/// Various errors that can occur while extracting the flat memory image of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum FlattenError {
+    /// The [`ElfFile`] has no [`SegmentType::LOAD`] segments with file data.
+    NoLoadSegments,
+    /// A class sized value cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
+    /// The buffer is too small to hold the flat image.
+    BufferTooSmall {
+        /// The number of bytes required to hold the flat image.
+        required: usize,
+    },
+    /// The file data of a [`SegmentType::LOAD`] segment is located out of bounds.
+    SegmentOutOfBounds,
+}
+
+impl From<OffsetOverflow> for FlattenError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl fmt::Display for FlattenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoLoadSegments => write!(f, "no load segments with file data"),
+            Self::OffsetOverflow(error) => write!(f, "error while converting offset: {error}"),
+            Self::BufferTooSmall { required } => {
+                write!(f, "buffer too small to hold flat image of {required} bytes")
+            }
+            Self::SegmentOutOfBounds => write!(f, "load segment located out of bounds"),
+        }
+    }
+}
+
+impl error::Error for FlattenError {}
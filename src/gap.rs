@@ -0,0 +1,172 @@
+//! Definitions for enumerating the bytes of an ELF file that no structure claims.
+//!
+//! Such gaps are normally alignment padding, but are also a common place to hide data.
+
+use core::iter::FusedIterator;
+
+use crate::{class::ClassParse, encoding::EncodingParse, section_header::SectionType, ElfFile};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`Gap`]s in this [`ElfFile`], in file order.
+    ///
+    /// The ELF header, the program header table, the section header table, the file data of
+    /// every segment, and the data of every section located in bounds are considered to claim the
+    /// bytes they occupy.
+    pub fn gaps(&self) -> Gaps<'slice, C, E> {
+        Gaps {
+            file: *self,
+            cursor: 0,
+        }
+    }
+}
+
+/// A structure occupying a range of bytes in an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FileRegion {
+    /// The ELF header.
+    ElfHeader,
+    /// The program header table.
+    ProgramHeaderTable,
+    /// The section header table.
+    SectionHeaderTable,
+    /// The file data of a segment.
+    Segment {
+        /// The index of the [`ProgramHeader`][ph] describing the segment.
+        ///
+        /// [ph]: crate::program_header::ProgramHeader
+        index: u16,
+    },
+    /// The data of a section.
+    Section {
+        /// The index of the [`SectionHeader`][sh] describing the section.
+        ///
+        /// [sh]: crate::section_header::SectionHeader
+        index: u16,
+    },
+}
+
+/// A range of bytes in an [`ElfFile`] that is not claimed by any [`FileRegion`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Gap {
+    /// The file offset of the start of the [`Gap`].
+    pub offset: u64,
+    /// The size, in bytes, of the [`Gap`].
+    pub size: u64,
+    /// The [`FileRegion`] ending at the start of the [`Gap`], if any.
+    pub before: Option<FileRegion>,
+    /// The [`FileRegion`] starting at the end of the [`Gap`], or `None` if the [`Gap`] extends to
+    /// the end of the file.
+    pub after: Option<FileRegion>,
+}
+
+/// An [`Iterator`] over the [`Gap`]s in an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Gaps<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being scanned.
+    file: ElfFile<'slice, C, E>,
+    /// The file offset from which to search for the next [`Gap`].
+    cursor: u64,
+}
+
+/// Returns an [`Iterator`] over every non-empty [`FileRegion`] of `file` and the range of file
+/// offsets it occupies.
+fn regions<'slice, C: ClassParse, E: EncodingParse>(
+    file: ElfFile<'slice, C, E>,
+) -> impl Iterator<Item = (FileRegion, u64, u64)> + use<'slice, C, E> {
+    let header = file.header();
+
+    let table = |offset: u64, count: u16, size: u16| {
+        let end = offset.saturating_add(u64::from(count) * u64::from(size));
+        (offset, end)
+    };
+    let (program_start, program_end) = table(
+        header.program_header_offset().into(),
+        header.program_header_count(),
+        header.program_header_size(),
+    );
+    let (section_start, section_end) = table(
+        header.section_header_offset().into(),
+        header.section_header_count(),
+        header.section_header_size(),
+    );
+
+    let fixed = [
+        (FileRegion::ElfHeader, 0, u64::from(header.header_size())),
+        (FileRegion::ProgramHeaderTable, program_start, program_end),
+        (FileRegion::SectionHeaderTable, section_start, section_end),
+    ];
+    let segments = file
+        .program_header_table()
+        .into_iter()
+        .flatten()
+        .zip(0..)
+        .map(|(segment, index)| {
+            let start: u64 = segment.file_offset().into();
+            let end = start.saturating_add(segment.file_size().into());
+            (FileRegion::Segment { index }, start, end)
+        });
+    let sections = file
+        .section_header_table()
+        .into_iter()
+        .flatten()
+        .zip(0..)
+        .filter(move |&(section, _)| {
+            section.section_type() != SectionType::NOBITS && file.section_data(section).is_some()
+        })
+        .map(|(section, index)| {
+            let start: u64 = section.file_offset().into();
+            let end = start.saturating_add(section.size().into());
+            (FileRegion::Section { index }, start, end)
+        });
+
+    fixed
+        .into_iter()
+        .chain(segments)
+        .chain(sections)
+        .filter(|&(_, start, end)| start < end)
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for Gaps<'_, C, E> {
+    type Item = Gap;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let file_size = self.file.bytes.len() as u64;
+
+        let mut before = None;
+        let mut extended = true;
+        while extended {
+            extended = false;
+            for (region, start, end) in regions(self.file) {
+                if start <= self.cursor && self.cursor < end {
+                    self.cursor = end;
+                    before = Some(region);
+                    extended = true;
+                }
+            }
+        }
+
+        if self.cursor >= file_size {
+            self.cursor = file_size;
+            return None;
+        }
+
+        let after = regions(self.file)
+            .filter(|&(_, start, _)| start > self.cursor)
+            .min_by_key(|&(_, start, _)| start);
+        let end = after.map_or(file_size, |(_, start, _)| start.min(file_size));
+
+        let gap = Gap {
+            offset: self.cursor,
+            size: end - self.cursor,
+            before,
+            after: after
+                .filter(|&(_, start, _)| start < file_size)
+                .map(|(region, ..)| region),
+        };
+        self.cursor = end;
+
+        Some(gap)
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for Gaps<'_, C, E> {}
@@ -0,0 +1,460 @@
+//! Definitions for inspecting the GNU symbol hash table.
+//!
+//! The table located by the [`ConstDynamicTag::GNU_HASH`] entry starts with the number of
+//! buckets, the index of the first hashed symbol, the number of bloom filter words and the bloom
+//! filter shift, followed by the bloom filter words, the bucket array and the chain array. The
+//! hashed symbols are sorted by bucket, so each bucket holds the index of the first symbol of a
+//! contiguous run, and the chain entry of each symbol holds its hash with the lowest bit set on
+//! the last symbol of the run.
+//!
+//! The bloom filter words are as wide as the class of the file, so the chain array does not
+//! start at a fixed offset.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    hash::{HashStatistics, HashTableError},
+    ident::Class,
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`GnuHashTable`] located by the [`ConstDynamicTag::GNU_HASH`] entry of this
+    /// [`ElfFile`].
+    pub fn gnu_hash_table(&self) -> Option<GnuHashTable<'slice, E>> {
+        let word_size = if self.header().ident().class() == Class::CLASS32 {
+            4
+        } else {
+            8
+        };
+
+        GnuHashTable::new(
+            self.encoding,
+            self.dynamic_address_data(ConstDynamicTag::GNU_HASH)?,
+            word_size,
+        )
+    }
+}
+
+/// Returns the GNU hash of `name`, as used to select a bucket of a [`GnuHashTable`].
+pub fn gnu_hash(name: &[u8]) -> u32 {
+    name.iter().fold(5381u32, |hash, &byte| {
+        hash.wrapping_mul(33).wrapping_add(u32::from(byte))
+    })
+}
+
+/// A GNU symbol hash table.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct GnuHashTable<'slice, E> {
+    /// The underlying bytes of this [`GnuHashTable`], starting at its header and extending to
+    /// the end of the containing data, since the length of the chain array is not recorded.
+    bytes: &'slice [u8],
+    /// The number of buckets in this [`GnuHashTable`].
+    bucket_count: usize,
+    /// The index of the first symbol covered by this [`GnuHashTable`].
+    symbol_offset: usize,
+    /// The number of words of the bloom filter.
+    bloom_count: usize,
+    /// The shift applied to the hash to select the second bit of the bloom filter.
+    bloom_shift: u32,
+    /// The size, in bytes, of a word of the bloom filter.
+    word_size: usize,
+    /// The [`EncodingParse`] of this [`GnuHashTable`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> GnuHashTable<'slice, E> {
+    /// Creates a new [`GnuHashTable`] from the given `slice`, which may extend past the end of
+    /// the table, with bloom filter words of `word_size` bytes.
+    ///
+    /// Returns `None` if `word_size` is neither 4 nor 8, or `slice` is too small to hold the
+    /// bloom filter and bucket array described by its header.
+    pub fn new(encoding: E, slice: &'slice [u8], word_size: usize) -> Option<Self> {
+        if slice.len() < 16 || (word_size != 4 && word_size != 8) {
+            return None;
+        }
+
+        let table = Self {
+            bytes: slice,
+            bucket_count: to_len(encoding.parse_u32_at(0, slice)).ok()?,
+            symbol_offset: to_len(encoding.parse_u32_at(4, slice)).ok()?,
+            bloom_count: to_len(encoding.parse_u32_at(8, slice)).ok()?,
+            bloom_shift: encoding.parse_u32_at(12, slice),
+            word_size,
+            encoding,
+        };
+        if table.chains_start()? > slice.len() {
+            return None;
+        }
+
+        Some(table)
+    }
+
+    /// Returns the number of buckets in this [`GnuHashTable`].
+    pub fn bucket_count(&self) -> usize {
+        self.bucket_count
+    }
+
+    /// Returns the index of the first symbol covered by this [`GnuHashTable`].
+    ///
+    /// The symbols before it, usually the undefined symbols, cannot be looked up through this
+    /// [`GnuHashTable`].
+    pub fn symbol_offset(&self) -> usize {
+        self.symbol_offset
+    }
+
+    /// Returns the word of the bloom filter at `index`.
+    pub fn bloom_word(&self, index: usize) -> Option<u64> {
+        if index >= self.bloom_count {
+            return None;
+        }
+
+        let offset = 16 + index * self.word_size;
+        Some(match self.word_size {
+            4 => self.encoding.parse_u32_at(offset, self.bytes).into(),
+            _ => self.encoding.parse_u64_at(offset, self.bytes),
+        })
+    }
+
+    /// Returns the [`BloomFilter`] parameters of this [`GnuHashTable`].
+    pub fn bloom_filter(&self) -> BloomFilter {
+        let set_bits = (0..self.bloom_count)
+            .filter_map(|index| self.bloom_word(index))
+            .map(|word| word.count_ones() as usize)
+            .sum();
+
+        BloomFilter {
+            word_count: self.bloom_count,
+            word_bits: self.word_size * 8,
+            shift: self.bloom_shift,
+            set_bits,
+        }
+    }
+
+    /// Returns the index of the first symbol in the bucket at `index`, or zero if the bucket is
+    /// empty.
+    pub fn bucket(&self, index: usize) -> Option<u32> {
+        if index >= self.bucket_count {
+            return None;
+        }
+
+        let offset = 16 + self.bloom_count * self.word_size + index * 4;
+        Some(self.encoding.parse_u32_at(offset, self.bytes))
+    }
+
+    /// Returns the chain entry of the symbol at `index`: its hash, with the lowest bit set if it
+    /// is the last symbol of its bucket.
+    ///
+    /// Returns `None` if the symbol is not covered by this [`GnuHashTable`] or its chain entry
+    /// is located out of bounds.
+    pub fn chain(&self, index: usize) -> Option<u32> {
+        let offset = index
+            .checked_sub(self.symbol_offset)?
+            .checked_mul(4)?
+            .checked_add(self.chains_start()?)?;
+
+        Some(
+            self.encoding
+                .parse_u32_at(offset, self.bytes.get(..offset.checked_add(4)?)?),
+        )
+    }
+
+    /// Returns the number of symbols implied by this [`GnuHashTable`], which is one past the
+    /// last symbol reachable from its buckets.
+    ///
+    /// Symbols placed after the last hashed symbol are not counted. Returns `None` if the chain
+    /// of the last bucket is not terminated.
+    pub fn symbol_count(&self) -> Option<usize> {
+        let last = (0..self.bucket_count)
+            .filter_map(|index| self.bucket(index))
+            .max()
+            .unwrap_or(0);
+        let Ok(mut index) = to_len(last) else {
+            return None;
+        };
+        if index < self.symbol_offset {
+            return Some(self.symbol_offset);
+        }
+
+        // The chain of the last bucket ends at the last symbol, marked by its lowest bit.
+        loop {
+            let chain = self.chain(index)?;
+            index = index.checked_add(1)?;
+            if chain & 1 != 0 {
+                return Some(index);
+            }
+        }
+    }
+
+    /// Returns the [`HashStatistics`] of this [`GnuHashTable`].
+    ///
+    /// Chains are followed until their last symbol or the end of the chain array. To bound the
+    /// work spent on malformed tables, no more chain entries are visited in total than the chain
+    /// array can hold, so the statistics of such tables may be incomplete.
+    pub fn statistics(&self) -> HashStatistics {
+        let mut statistics = HashStatistics {
+            bucket_count: self.bucket_count,
+            ..HashStatistics::default()
+        };
+        let mut remaining = self
+            .chains_start()
+            .map_or(0, |start| (self.bytes.len() - start) / 4);
+
+        for bucket in 0..self.bucket_count {
+            let mut length = 0;
+            let mut next = self
+                .bucket(bucket)
+                .and_then(|index| to_len(index).ok())
+                .filter(|&index| index != 0);
+            while let Some(index) = next.filter(|_| remaining != 0) {
+                let Some(chain) = self.chain(index) else {
+                    break;
+                };
+                remaining -= 1;
+                length += 1;
+                next = (chain & 1 == 0).then(|| index + 1);
+            }
+            statistics.record_chain(length);
+        }
+
+        statistics
+    }
+
+    /// Validates the internal consistency of this [`GnuHashTable`] against `symbol_count`, the
+    /// number of symbols in the associated symbol table.
+    ///
+    /// # Errors
+    ///
+    /// - [`HashTableError::BloomCountNotPowerOfTwo`]: Returned if the number of bloom filter
+    ///   words is not a power of two.
+    /// - [`HashTableError::SymbolOffsetOutOfBounds`]: Returned if the index of the first hashed
+    ///   symbol is past the end of the symbol table.
+    /// - [`HashTableError::BucketOutOfBounds`]: Returned if a bucket holds an index before the
+    ///   first hashed symbol or past the end of the symbol table.
+    /// - [`HashTableError::UnterminatedChain`]: Returned if a chain runs past the end of the
+    ///   symbol table or the chain array.
+    /// - [`HashTableError::ChainsOverlap`]: Returned if the chains visit more symbols than are
+    ///   hashed, which happens when two buckets share symbols.
+    /// - [`HashTableError::ChainCountMismatch`]: Returned if the chains do not end at the last
+    ///   symbol of the symbol table.
+    pub fn validate(&self, symbol_count: usize) -> Result<(), HashTableError> {
+        if !self.bloom_count.is_power_of_two() {
+            return Err(HashTableError::BloomCountNotPowerOfTwo(self.bloom_count));
+        }
+        let Some(mut remaining) = symbol_count.checked_sub(self.symbol_offset) else {
+            return Err(HashTableError::SymbolOffsetOutOfBounds {
+                symbol_offset: self.symbol_offset,
+                symbol_count,
+            });
+        };
+
+        let mut end = self.symbol_offset;
+        for bucket in 0..self.bucket_count {
+            let start = self.bucket(bucket).unwrap_or(0);
+            if start == 0 {
+                continue;
+            }
+
+            let mut index = to_len(start)
+                .ok()
+                .filter(|&index| index >= self.symbol_offset && index < symbol_count)
+                .ok_or(HashTableError::BucketOutOfBounds {
+                    bucket,
+                    index: start,
+                })?;
+            loop {
+                let chain = self
+                    .chain(index)
+                    .filter(|_| index < symbol_count)
+                    .ok_or(HashTableError::UnterminatedChain { bucket })?;
+                remaining = remaining
+                    .checked_sub(1)
+                    .ok_or(HashTableError::ChainsOverlap { bucket })?;
+                index += 1;
+                if chain & 1 != 0 {
+                    break;
+                }
+            }
+            end = end.max(index);
+        }
+
+        if end != symbol_count {
+            return Err(HashTableError::ChainCountMismatch {
+                chain_count: end,
+                symbol_count,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the hashed symbols of `symbols`, the [`SymbolTable`] associated with this
+    /// [`GnuHashTable`], are sorted by bucket as the GNU hash table requires.
+    ///
+    /// Post-processing tools that reorder or append dynamic symbols without rebuilding the table
+    /// break this ordering, which makes lookups of the affected symbols fail. Returns `Ok(())`
+    /// without checking anything if this [`GnuHashTable`] has no buckets.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`GnuHashOrderError`] describing the first symbol, in table order, that
+    /// violates the ordering.
+    pub fn validate_order<C: ClassParse>(
+        &self,
+        symbols: SymbolTable<'_, C, E>,
+    ) -> Result<(), GnuHashOrderError> {
+        if self.bucket_count == 0 {
+            return Ok(());
+        }
+
+        let mut previous: Option<(usize, u32)> = None;
+        for index in self.symbol_offset..symbols.count() {
+            let hash = symbols
+                .get(index)
+                .and_then(|symbol| symbol.name())
+                .map(gnu_hash)
+                .ok_or(GnuHashOrderError::MissingName { index })?;
+            let bucket = hash as usize % self.bucket_count;
+
+            if let Some((previous_bucket, previous_chain)) = previous {
+                if bucket < previous_bucket {
+                    return Err(GnuHashOrderError::OutOfOrder {
+                        index,
+                        bucket,
+                        previous_bucket,
+                    });
+                }
+                if (previous_chain & 1 != 0) != (bucket != previous_bucket) {
+                    return Err(GnuHashOrderError::ChainEndMismatch { index: index - 1 });
+                }
+            }
+
+            let chain = self
+                .chain(index)
+                .filter(|chain| (chain ^ hash) & !1 == 0)
+                .ok_or(GnuHashOrderError::HashMismatch { index })?;
+            if previous.is_none_or(|(previous_bucket, _)| previous_bucket != bucket)
+                && self.bucket(bucket).and_then(|start| to_len(start).ok()) != Some(index)
+            {
+                return Err(GnuHashOrderError::BucketStartMismatch { index, bucket });
+            }
+
+            previous = Some((bucket, chain));
+        }
+
+        match previous {
+            Some((_, chain)) if chain & 1 == 0 => Err(GnuHashOrderError::ChainEndMismatch {
+                index: symbols.count() - 1,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the offset of the chain array from the start of this [`GnuHashTable`].
+    fn chains_start(&self) -> Option<usize> {
+        self.bloom_count
+            .checked_mul(self.word_size)?
+            .checked_add(16)?
+            .checked_add(self.bucket_count.checked_mul(4)?)
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for GnuHashTable<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GnuHashTable")
+            .field("bucket_count", &self.bucket_count)
+            .field("symbol_offset", &self.symbol_offset)
+            .field("bloom_count", &self.bloom_count)
+            .field("bloom_shift", &self.bloom_shift)
+            .finish()
+    }
+}
+
+/// The parameters of the bloom filter of a [`GnuHashTable`].
+///
+/// Each hashed symbol sets two bits of a single word: the bit selected by its hash, and the bit
+/// selected by its hash shifted right by [`BloomFilter::shift`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct BloomFilter {
+    /// The number of words of the bloom filter.
+    pub word_count: usize,
+    /// The number of bits in each word of the bloom filter.
+    pub word_bits: usize,
+    /// The shift applied to the hash to select the second bit.
+    pub shift: u32,
+    /// The number of bits set across every word, which determines the rate of false positives.
+    pub set_bits: usize,
+}
+
+/// Various errors that indicate the hashed symbols of a symbol table are not ordered as its
+/// [`GnuHashTable`] requires.
+///
+/// Each variant holds the index of the first symbol found to violate the ordering.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum GnuHashOrderError {
+    /// The symbol or its name cannot be read, so its bucket cannot be determined.
+    MissingName {
+        /// The index of the symbol.
+        index: usize,
+    },
+    /// The symbol belongs to an earlier bucket than the symbol preceding it.
+    OutOfOrder {
+        /// The index of the symbol.
+        index: usize,
+        /// The bucket the symbol belongs to.
+        bucket: usize,
+        /// The bucket the preceding symbol belongs to.
+        previous_bucket: usize,
+    },
+    /// The chain entry of the symbol is missing or does not hold the hash of its name.
+    HashMismatch {
+        /// The index of the symbol.
+        index: usize,
+    },
+    /// The chain entry of the symbol marks it as the last symbol of its bucket when it is not,
+    /// or fails to when it is.
+    ChainEndMismatch {
+        /// The index of the symbol.
+        index: usize,
+    },
+    /// The symbol is the first of its bucket, but the bucket holds a different index.
+    BucketStartMismatch {
+        /// The index of the symbol.
+        index: usize,
+        /// The bucket the symbol belongs to.
+        bucket: usize,
+    },
+}
+
+impl fmt::Display for GnuHashOrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingName { index } => write!(f, "name of symbol {index} cannot be read"),
+            Self::OutOfOrder {
+                index,
+                bucket,
+                previous_bucket,
+            } => write!(
+                f,
+                "symbol {index} in bucket {bucket} follows a symbol in bucket {previous_bucket}"
+            ),
+            Self::HashMismatch { index } => {
+                write!(f, "chain entry of symbol {index} does not match its hash")
+            }
+            Self::ChainEndMismatch { index } => write!(
+                f,
+                "chain entry of symbol {index} does not mark the end of its bucket correctly"
+            ),
+            Self::BucketStartMismatch { index, bucket } => write!(
+                f,
+                "bucket {bucket} does not start at its first symbol {index}"
+            ),
+        }
+    }
+}
+
+impl error::Error for GnuHashOrderError {}
@@ -0,0 +1,284 @@
+//! Definitions for hardening the memory permissions of an ELF file by editing its program header
+//! table.
+//!
+//! The operations are built on [`ProgramHeaderEditor`] and validate their inputs before writing,
+//! so a failed operation leaves the program header table unchanged.
+//! [`ProgramHeaderEditor::validate_hardening()`] checks the resulting table against the policies
+//! the operations establish.
+
+use core::{error, fmt};
+
+use crate::{
+    class::AnyClass,
+    encoding::AnyEndian,
+    program_header::{SegmentFlags, SegmentType, ValidateProgramHeaderSpecError},
+    program_header_edit::{EditProgramHeaderError, ProgramHeaderEditor, RawProgramHeader},
+    section_header::SectionFlags,
+    ElfFile, ParseElfFileError,
+};
+
+impl ProgramHeaderEditor<'_> {
+    /// Clears [`SegmentFlags::WRITE`] on the segment located at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HardenError::EditProgramHeaderError`] if `index` is out of bounds.
+    pub fn clear_write(&mut self, index: u16) -> Result<(), HardenError> {
+        let mut program_header = self
+            .get(index)
+            .ok_or(EditProgramHeaderError::IndexOutOfBounds)?;
+
+        program_header.flags = SegmentFlags(program_header.flags.0 & !SegmentFlags::WRITE.0);
+        self.set(index, program_header)?;
+        Ok(())
+    }
+
+    /// Clears [`SegmentFlags::EXECUTE`] on the [`SegmentType::GNU_STACK`] segment, appending a
+    /// readable and writable [`SegmentType::GNU_STACK`] segment if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HardenError::EditProgramHeaderError`] if a [`SegmentType::GNU_STACK`] segment
+    /// must be appended and [`ProgramHeaderEditor::insert()`] fails.
+    pub fn make_stack_non_executable(&mut self) -> Result<(), HardenError> {
+        match self
+            .position(SegmentType::GNU_STACK)
+            .and_then(|index| Some((index, self.get(index)?)))
+        {
+            Some((index, mut program_header)) => {
+                program_header.flags =
+                    SegmentFlags(program_header.flags.0 & !SegmentFlags::EXECUTE.0);
+                self.set(index, program_header)?;
+            }
+            None => {
+                let program_header = RawProgramHeader {
+                    segment_type: SegmentType::GNU_STACK,
+                    flags: SegmentFlags::READ | SegmentFlags::WRITE,
+                    file_offset: 0,
+                    virtual_address: 0,
+                    physical_address: 0,
+                    file_size: 0,
+                    memory_size: 0,
+                    alignment: 16,
+                };
+                self.insert(self.count(), program_header)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes the sections located at `sections` read-only after relocation by covering them with
+    /// a [`SegmentType::GNU_RELRO`] segment, replacing the existing one if present.
+    ///
+    /// The segment spans from the lowest start to the highest end of the sections, which must
+    /// lie in a single writable [`SegmentType::LOAD`] segment. The dynamic linker rounds the end
+    /// of the segment down to a page boundary, so bytes of the last page are only protected if
+    /// the sections end on a page boundary.
+    ///
+    /// # Errors
+    ///
+    /// - [`HardenError::ParseElfFileError`]: Returned if the edited file cannot be parsed.
+    /// - [`HardenError::InvalidSection`]: Returned if `sections` is empty, or an index is out of
+    ///   bounds or locates a section that does not occupy memory.
+    /// - [`HardenError::RelroOutsideWritableSegment`]: Returned if the sections do not lie in a
+    ///   single writable [`SegmentType::LOAD`] segment.
+    /// - [`HardenError::EditProgramHeaderError`]: Returned if a [`SegmentType::GNU_RELRO`]
+    ///   segment must be appended and [`ProgramHeaderEditor::insert()`] fails.
+    pub fn add_relro(&mut self, sections: &[u16]) -> Result<(), HardenError> {
+        let (start, end) = {
+            let file = ElfFile::<AnyClass, AnyEndian>::new(self.bytes())?;
+            let table = file
+                .section_header_table()
+                .ok_or(HardenError::InvalidSection)?;
+
+            let mut range: Option<(u64, u64)> = None;
+            for &index in sections {
+                let section = table.get(index).ok_or(HardenError::InvalidSection)?;
+                if !section.flags().contains(SectionFlags::ALLOC) {
+                    return Err(HardenError::InvalidSection);
+                }
+
+                let start = section.address();
+                let end = start
+                    .checked_add(section.size())
+                    .ok_or(HardenError::InvalidSection)?;
+                range =
+                    Some(range.map_or((start, end), |(low, high)| (low.min(start), high.max(end))));
+            }
+
+            range.ok_or(HardenError::InvalidSection)?
+        };
+
+        let load = (0..self.count())
+            .filter_map(|index| self.get(index))
+            .find(|program_header| {
+                program_header.segment_type == SegmentType::LOAD
+                    && program_header.flags.contains(SegmentFlags::WRITE)
+                    && contains(program_header, start, end)
+            })
+            .ok_or(HardenError::RelroOutsideWritableSegment)?;
+
+        let program_header = RawProgramHeader {
+            segment_type: SegmentType::GNU_RELRO,
+            flags: SegmentFlags::READ,
+            file_offset: load.file_offset + (start - load.virtual_address),
+            virtual_address: start,
+            physical_address: start,
+            file_size: end - start,
+            memory_size: end - start,
+            alignment: 1,
+        };
+        match self.position(SegmentType::GNU_RELRO) {
+            Some(index) => self.set(index, program_header)?,
+            None => self.insert(self.count(), program_header)?,
+        }
+
+        Ok(())
+    }
+
+    /// Validates that the edited program header table is well formed and hardened.
+    ///
+    /// # Errors
+    ///
+    /// - [`HardenError::ParseElfFileError`]: Returned if the edited file cannot be parsed.
+    /// - [`HardenError::InvalidSegment`]: Returned if an entry does not match the ELF
+    ///   specification.
+    /// - [`HardenError::WritableExecutable`]: Returned if a [`SegmentType::LOAD`] segment is both
+    ///   writable and executable.
+    /// - [`HardenError::ExecutableStack`]: Returned if the [`SegmentType::GNU_STACK`] segment is
+    ///   executable or missing, which leaves the stack executable on most targets.
+    /// - [`HardenError::RelroOutsideWritableSegment`]: Returned if a [`SegmentType::GNU_RELRO`]
+    ///   segment does not lie in a single writable [`SegmentType::LOAD`] segment.
+    pub fn validate_hardening(&self) -> Result<(), HardenError> {
+        let file = ElfFile::<AnyClass, AnyEndian>::new(self.bytes())?;
+        if let Some(table) = file.program_header_table() {
+            for index in 0..table.count() {
+                let Some(program_header) = table.get(index) else {
+                    continue;
+                };
+                program_header
+                    .validate_specification()
+                    .map_err(|error| HardenError::InvalidSegment { index, error })?;
+            }
+        }
+
+        let mut stack = false;
+        for index in 0..self.count() {
+            let Some(program_header) = self.get(index) else {
+                continue;
+            };
+
+            match program_header.segment_type {
+                SegmentType::LOAD
+                    if program_header
+                        .flags
+                        .contains(SegmentFlags::WRITE | SegmentFlags::EXECUTE) =>
+                {
+                    return Err(HardenError::WritableExecutable { index });
+                }
+                SegmentType::GNU_STACK => {
+                    if program_header.flags.contains(SegmentFlags::EXECUTE) {
+                        return Err(HardenError::ExecutableStack);
+                    }
+                    stack = true;
+                }
+                SegmentType::GNU_RELRO => {
+                    let end = program_header
+                        .virtual_address
+                        .checked_add(program_header.memory_size)
+                        .ok_or(HardenError::RelroOutsideWritableSegment)?;
+                    let covered =
+                        (0..self.count())
+                            .filter_map(|index| self.get(index))
+                            .any(|load| {
+                                load.segment_type == SegmentType::LOAD
+                                    && load.flags.contains(SegmentFlags::WRITE)
+                                    && contains(&load, program_header.virtual_address, end)
+                            });
+                    if !covered {
+                        return Err(HardenError::RelroOutsideWritableSegment);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !stack {
+            return Err(HardenError::ExecutableStack);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the addresses from `start` to `end` lie in the memory of `program_header`.
+fn contains(program_header: &RawProgramHeader, start: u64, end: u64) -> bool {
+    program_header
+        .virtual_address
+        .checked_add(program_header.memory_size)
+        .is_some_and(|segment_end| program_header.virtual_address <= start && end <= segment_end)
+}
+
+/// Various errors that can occur while hardening the program header table of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum HardenError {
+    /// An error occurred while editing the program header table.
+    EditProgramHeaderError(EditProgramHeaderError),
+    /// An error occurred while parsing the edited [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// A section index is out of bounds or locates a section that does not occupy memory.
+    InvalidSection,
+    /// A [`SegmentType::GNU_RELRO`] segment does not lie in a single writable
+    /// [`SegmentType::LOAD`] segment.
+    RelroOutsideWritableSegment,
+    /// An entry of the program header table does not match the ELF specification.
+    InvalidSegment {
+        /// The index of the entry.
+        index: u16,
+        /// The violation of the ELF specification.
+        error: ValidateProgramHeaderSpecError,
+    },
+    /// A [`SegmentType::LOAD`] segment is both writable and executable.
+    WritableExecutable {
+        /// The index of the entry.
+        index: u16,
+    },
+    /// The [`SegmentType::GNU_STACK`] segment is executable or missing.
+    ExecutableStack,
+}
+
+impl From<EditProgramHeaderError> for HardenError {
+    fn from(value: EditProgramHeaderError) -> Self {
+        Self::EditProgramHeaderError(value)
+    }
+}
+
+impl From<ParseElfFileError> for HardenError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for HardenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EditProgramHeaderError(error) => {
+                write!(f, "error while editing program header table: {error}")
+            }
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::InvalidSection => write!(f, "section does not occupy memory"),
+            Self::RelroOutsideWritableSegment => {
+                write!(f, "relro segment outside of a writable load segment")
+            }
+            Self::InvalidSegment { index, error } => {
+                write!(f, "invalid program header {index}: {error}")
+            }
+            Self::WritableExecutable { index } => {
+                write!(f, "load segment {index} is writable and executable")
+            }
+            Self::ExecutableStack => write!(f, "stack is executable"),
+        }
+    }
+}
+
+impl error::Error for HardenError {}
@@ -0,0 +1,396 @@
+//! Definitions for looking up symbols through the SysV symbol hash table.
+//!
+//! The table located by the [`ConstDynamicTag::HASH`] entry starts with the number of buckets
+//! and the number of chain entries, followed by both arrays of 32-bit words. Each bucket holds
+//! the index of the first symbol whose name hashes to it, and the chain entry of each symbol
+//! holds the index of the next symbol sharing its bucket, with zero ending the chain.
+//!
+//! The [`HashStatistics`] and [`HashTableError`] defined here are shared with the
+//! [`GnuHashTable`][gnu] view.
+//!
+//! [gnu]: crate::gnu_hash::GnuHashTable
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`HashTable`] located by the [`ConstDynamicTag::HASH`] entry of this
+    /// [`ElfFile`].
+    pub fn hash_table(&self) -> Option<HashTable<'slice, E>> {
+        HashTable::new(
+            self.encoding,
+            self.dynamic_address_data(ConstDynamicTag::HASH)?,
+        )
+    }
+}
+
+/// Returns the SysV hash of `name`, as used to select a bucket of a [`HashTable`].
+pub fn sysv_hash(name: &[u8]) -> u32 {
+    name.iter().fold(0u32, |hash, &byte| {
+        let hash = (hash << 4).wrapping_add(u32::from(byte));
+        let high = hash & 0xF000_0000;
+        (hash ^ (high >> 24)) & !high
+    })
+}
+
+/// A SysV symbol hash table.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct HashTable<'slice, E> {
+    /// The underlying bytes of this [`HashTable`], including its header.
+    bytes: &'slice [u8],
+    /// The number of buckets in this [`HashTable`].
+    bucket_count: usize,
+    /// The number of chain entries in this [`HashTable`].
+    chain_count: usize,
+    /// The [`EncodingParse`] of this [`HashTable`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> HashTable<'slice, E> {
+    /// Creates a new [`HashTable`] from the given `slice`, which may extend past the end of the
+    /// table.
+    ///
+    /// Returns `None` if `slice` is too small to hold the arrays described by its header.
+    pub fn new(encoding: E, slice: &'slice [u8]) -> Option<Self> {
+        if slice.len() < 8 {
+            return None;
+        }
+
+        let bucket_count = to_len(encoding.parse_u32_at(0, slice)).ok()?;
+        let chain_count = to_len(encoding.parse_u32_at(4, slice)).ok()?;
+        let size = bucket_count
+            .checked_add(chain_count)?
+            .checked_add(2)?
+            .checked_mul(4)?;
+
+        let table = Self {
+            bytes: slice.get(..size)?,
+            bucket_count,
+            chain_count,
+            encoding,
+        };
+
+        Some(table)
+    }
+
+    /// Returns the number of buckets in this [`HashTable`].
+    pub fn bucket_count(&self) -> usize {
+        self.bucket_count
+    }
+
+    /// Returns the number of chain entries in this [`HashTable`], which equals the number of
+    /// symbols in the associated symbol table.
+    pub fn chain_count(&self) -> usize {
+        self.chain_count
+    }
+
+    /// Returns the index of the first symbol in the bucket at `index`.
+    pub fn bucket(&self, index: usize) -> Option<u32> {
+        if index >= self.bucket_count {
+            return None;
+        }
+
+        Some(self.encoding.parse_u32_at(8 + index * 4, self.bytes))
+    }
+
+    /// Returns the index of the symbol following the symbol at `index` in its chain, or zero if
+    /// it is the last symbol of its chain.
+    pub fn chain(&self, index: usize) -> Option<u32> {
+        if index >= self.chain_count {
+            return None;
+        }
+
+        Some(
+            self.encoding
+                .parse_u32_at(8 + (self.bucket_count + index) * 4, self.bytes),
+        )
+    }
+
+    /// Returns a [`Chain`] over the indices of the symbols whose names hash to the same bucket as
+    /// `name`.
+    pub fn candidates(&self, name: &[u8]) -> Chain<'slice, E> {
+        let next = match self.bucket_count {
+            0 => 0,
+            count => self.bucket(sysv_hash(name) as usize % count).unwrap_or(0),
+        };
+
+        Chain {
+            table: *self,
+            next,
+            remaining: self.chain_count,
+        }
+    }
+
+    /// Returns the index of the [`Symbol`][sym] named `name` in `symbols`, the [`SymbolTable`]
+    /// associated with this [`HashTable`].
+    ///
+    /// Symbol versions are not considered, so if several symbols share `name`, the first one in
+    /// its chain is returned. Symbols that cannot be read or whose names cannot be located are
+    /// skipped.
+    ///
+    /// [sym]: crate::symbol::Symbol
+    pub fn lookup<C: ClassParse>(
+        &self,
+        name: &[u8],
+        symbols: SymbolTable<'_, C, E>,
+    ) -> Option<u32> {
+        self.candidates(name).find(|&index| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| symbols.get(index))
+                .and_then(|symbol| symbol.name())
+                == Some(name)
+        })
+    }
+
+    /// Returns the [`HashStatistics`] of this [`HashTable`].
+    ///
+    /// Chains are followed as [`HashTable::candidates()`] follows them, so the statistics of a
+    /// malformed table count at most [`HashTable::chain_count()`] symbols per chain.
+    pub fn statistics(&self) -> HashStatistics {
+        let mut statistics = HashStatistics {
+            bucket_count: self.bucket_count,
+            ..HashStatistics::default()
+        };
+
+        for bucket in 0..self.bucket_count {
+            let length = Chain {
+                table: *self,
+                next: self.bucket(bucket).unwrap_or(0),
+                remaining: self.chain_count,
+            }
+            .count();
+            statistics.record_chain(length);
+        }
+
+        statistics
+    }
+
+    /// Validates the internal consistency of this [`HashTable`] against `symbol_count`, the
+    /// number of symbols in the associated symbol table.
+    ///
+    /// # Errors
+    ///
+    /// - [`HashTableError::ChainCountMismatch`]: Returned if the number of chain entries differs
+    ///   from `symbol_count`.
+    /// - [`HashTableError::BucketOutOfBounds`]: Returned if a bucket holds an index past the end
+    ///   of the symbol table.
+    /// - [`HashTableError::ChainOutOfBounds`]: Returned if a chain entry holds an index past the
+    ///   end of the symbol table.
+    /// - [`HashTableError::ChainsOverlap`]: Returned if the chains visit more symbols than the
+    ///   symbol table holds, which happens when a chain loops or two chains merge.
+    pub fn validate(&self, symbol_count: usize) -> Result<(), HashTableError> {
+        if self.chain_count != symbol_count {
+            return Err(HashTableError::ChainCountMismatch {
+                chain_count: self.chain_count,
+                symbol_count,
+            });
+        }
+
+        // In a well-formed table every symbol belongs to at most one chain, so the chains visit
+        // at most `symbol_count` symbols in total.
+        let mut remaining = self.chain_count;
+        for bucket in 0..self.bucket_count {
+            let mut next = self.bucket(bucket).unwrap_or(0);
+            if usize::try_from(next).is_ok_and(|next| next >= self.chain_count) {
+                return Err(HashTableError::BucketOutOfBounds {
+                    bucket,
+                    index: next,
+                });
+            }
+
+            while next != 0 {
+                remaining = remaining
+                    .checked_sub(1)
+                    .ok_or(HashTableError::ChainsOverlap { bucket })?;
+                next = usize::try_from(next)
+                    .ok()
+                    .and_then(|index| self.chain(index))
+                    .unwrap_or(0);
+                if usize::try_from(next).is_ok_and(|next| next >= self.chain_count) {
+                    return Err(HashTableError::ChainOutOfBounds {
+                        bucket,
+                        index: next,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for HashTable<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashTable")
+            .field("bucket_count", &self.bucket_count)
+            .field("chain_count", &self.chain_count)
+            .finish()
+    }
+}
+
+/// An [`Iterator`] over the indices of the symbols in a chain of a [`HashTable`].
+///
+/// Iteration stops after visiting as many symbols as there are chain entries, so malformed
+/// tables whose chains loop cannot cause an infinite loop.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Chain<'slice, E> {
+    /// The [`HashTable`] holding the chain.
+    table: HashTable<'slice, E>,
+    /// The index of the next symbol, or zero if the chain has ended.
+    next: u32,
+    /// The number of symbols that may still be visited.
+    remaining: usize,
+}
+
+impl<E: EncodingParse> Iterator for Chain<'_, E> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == 0 || self.remaining == 0 {
+            return None;
+        }
+
+        let index = self.next;
+        self.remaining -= 1;
+        self.next = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.table.chain(index))
+            .unwrap_or(0);
+
+        Some(index)
+    }
+}
+
+impl<E: EncodingParse> FusedIterator for Chain<'_, E> {}
+
+/// Statistics describing the distribution of the symbols of a symbol hash table over its buckets.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct HashStatistics {
+    /// The number of buckets.
+    pub bucket_count: usize,
+    /// The number of buckets whose chain is empty.
+    pub empty_buckets: usize,
+    /// The number of symbols reachable from the buckets.
+    pub symbol_count: usize,
+    /// The number of symbols in the longest chain.
+    pub longest_chain: usize,
+}
+
+impl HashStatistics {
+    /// Returns the average number of symbols in the non-empty chains, scaled by 1000.
+    ///
+    /// This is the average number of names compared by a successful lookup, so a well-sized
+    /// table stays close to 1000. Returns zero if every chain is empty.
+    pub fn average_chain_length_milli(&self) -> usize {
+        match self.bucket_count - self.empty_buckets {
+            0 => 0,
+            used => self.symbol_count.saturating_mul(1000) / used,
+        }
+    }
+
+    /// Records a chain holding `length` symbols.
+    pub(crate) fn record_chain(&mut self, length: usize) {
+        if length == 0 {
+            self.empty_buckets += 1;
+        }
+        self.symbol_count += length;
+        self.longest_chain = self.longest_chain.max(length);
+    }
+}
+
+/// Various errors that indicate a symbol hash table is internally inconsistent.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum HashTableError {
+    /// The number of symbols covered by the table differs from the number of symbols in the
+    /// symbol table.
+    ChainCountMismatch {
+        /// The number of symbols covered by the table.
+        chain_count: usize,
+        /// The number of symbols in the symbol table.
+        symbol_count: usize,
+    },
+    /// A bucket holds an index outside of the symbols covered by the table.
+    BucketOutOfBounds {
+        /// The index of the bucket.
+        bucket: usize,
+        /// The symbol index held by the bucket.
+        index: u32,
+    },
+    /// A chain entry holds an index past the end of the symbol table.
+    ChainOutOfBounds {
+        /// The index of the bucket the chain starts at.
+        bucket: usize,
+        /// The symbol index held by the chain entry.
+        index: u32,
+    },
+    /// A chain runs past the end of the symbol table without being terminated.
+    UnterminatedChain {
+        /// The index of the bucket the chain starts at.
+        bucket: usize,
+    },
+    /// The chains visit more symbols than the symbol table holds.
+    ChainsOverlap {
+        /// The index of the bucket whose chain exceeded the symbol table.
+        bucket: usize,
+    },
+    /// The index of the first hashed symbol is past the end of the symbol table.
+    SymbolOffsetOutOfBounds {
+        /// The index of the first hashed symbol.
+        symbol_offset: usize,
+        /// The number of symbols in the symbol table.
+        symbol_count: usize,
+    },
+    /// The number of words of the bloom filter is not a power of two.
+    BloomCountNotPowerOfTwo(usize),
+}
+
+impl fmt::Display for HashTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ChainCountMismatch {
+                chain_count,
+                symbol_count,
+            } => write!(
+                f,
+                "hash table covers {chain_count} symbols but symbol table holds {symbol_count}"
+            ),
+            Self::BucketOutOfBounds { bucket, index } => {
+                write!(
+                    f,
+                    "bucket {bucket} holds out of bounds symbol index {index}"
+                )
+            }
+            Self::ChainOutOfBounds { bucket, index } => write!(
+                f,
+                "chain of bucket {bucket} holds out of bounds symbol index {index}"
+            ),
+            Self::UnterminatedChain { bucket } => {
+                write!(f, "chain of bucket {bucket} is not terminated")
+            }
+            Self::ChainsOverlap { bucket } => write!(
+                f,
+                "chain of bucket {bucket} visits more symbols than the symbol table holds"
+            ),
+            Self::SymbolOffsetOutOfBounds {
+                symbol_offset,
+                symbol_count,
+            } => write!(
+                f,
+                "first hashed symbol {symbol_offset} is past the end of the {symbol_count} symbols"
+            ),
+            Self::BloomCountNotPowerOfTwo(count) => {
+                write!(f, "bloom filter word count {count} is not a power of two")
+            }
+        }
+    }
+}
+
+impl error::Error for HashTableError {}
@@ -1,6 +1,6 @@
 //! Definitions for the ELF file header.
 
-use core::{error, fmt};
+use core::{error, fmt, ops::Range};
 
 use crate::{
     class::{ClassParse, ClassParseBase, UnsupportedClassError},
@@ -27,11 +27,11 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfHeader<'slice, C, E> {
     /// # Errors
     ///
     /// - [`ParseElfHeaderError::TooSmall`]: Returned if the given `slice` is too small to
-    ///     contain an [`ElfHeader`].
+    ///   contain an [`ElfHeader`].
     /// - [`ParseElfHeaderError::UnsupportedClass`]: Returned if the [`Class`][c] of the
-    ///     [`ElfHeader`] is not supported.
+    ///   [`ElfHeader`] is not supported.
     /// - [`ParseElfHeaderError::UnsupportedEncoding`]: Returned if the [`Encoding`][e] of the
-    ///     [`ElfHeader`] is not supported.
+    ///   [`ElfHeader`] is not supported.
     ///
     /// [c]: crate::ident::Class
     /// [e]: crate::ident::Encoding
@@ -59,9 +59,9 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfHeader<'slice, C, E> {
     /// # Errors
     ///
     /// - [`ValidateElfHeaderSpecError::IdentError`]: Returned if an error occurs while parsing the
-    ///     [`ElfIdent`] of this [`ElfHeader`].
+    ///   [`ElfIdent`] of this [`ElfHeader`].
     /// - [`ValidateElfHeaderSpecError::InvalidElfHeaderSize`]: Returned if the size of the
-    ///     [`ElfHeader`] is smaller than expected.
+    ///   [`ElfHeader`] is smaller than expected.
     pub fn validate_spec(&self) -> Result<(), ValidateElfHeaderSpecError> {
         self.ident().validate_spec()?;
 
@@ -72,6 +72,67 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfHeader<'slice, C, E> {
         Ok(())
     }
 
+    /// Validates this [`ElfHeader`] more strictly than [`ElfHeader::validate_spec()`], checking
+    /// that its fields are consistent with each other.
+    ///
+    /// # Errors
+    ///
+    /// - [`ValidateElfHeaderStrictError::Spec`]: Returned if [`ElfHeader::validate_spec()`]
+    ///   fails.
+    /// - [`ValidateElfHeaderStrictError::UnsupportedFileVersion`]: Returned if
+    ///   [`ElfHeader::file_version()`] is not the current version.
+    /// - [`ValidateElfHeaderStrictError::ProgramHeaderSizeMismatch`]: Returned if program
+    ///   headers are present and [`ElfHeader::program_header_size()`] is not exactly the size
+    ///   expected for the class.
+    /// - [`ValidateElfHeaderStrictError::SectionHeaderSizeMismatch`]: Returned if a section
+    ///   header table is present and [`ElfHeader::section_header_size()`] is not exactly the size
+    ///   expected for the class.
+    /// - [`ValidateElfHeaderStrictError::SectionHeaderCountWithoutTable`]: Returned if there is
+    ///   no section header table but [`ElfHeader::section_header_count()`] is non-zero.
+    /// - [`ValidateElfHeaderStrictError::StringTableIndexWithoutTable`]: Returned if there is no
+    ///   section header table but [`ElfHeader::section_header_string_table_index()`] is non-zero.
+    pub fn validate_strict(&self) -> Result<(), ValidateElfHeaderStrictError> {
+        self.validate_spec()?;
+
+        if self.file_version() != u32::from(ElfIdent::CURRENT_HEADER_VERSION) {
+            return Err(ValidateElfHeaderStrictError::UnsupportedFileVersion(
+                self.file_version(),
+            ));
+        }
+
+        let program_header_size = self.program_header_size();
+        if self.program_header_count() != 0
+            && usize::from(program_header_size) != self.class.expected_program_header_size()
+        {
+            return Err(ValidateElfHeaderStrictError::ProgramHeaderSizeMismatch(
+                program_header_size,
+            ));
+        }
+
+        let section_header_size = self.section_header_size();
+        if self.section_header_offset().into() == 0 {
+            if self.section_header_count() != 0 {
+                return Err(
+                    ValidateElfHeaderStrictError::SectionHeaderCountWithoutTable(
+                        self.section_header_count(),
+                    ),
+                );
+            }
+
+            if self.section_header_string_table_index() != 0 {
+                return Err(ValidateElfHeaderStrictError::StringTableIndexWithoutTable(
+                    self.section_header_string_table_index(),
+                ));
+            }
+        } else if usize::from(section_header_size) != self.class.expected_section_header_size() {
+            return Err(ValidateElfHeaderStrictError::SectionHeaderSizeMismatch(
+                section_header_size,
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Returns the [`ClassParse`] implementation of this [`ElfHeader`].
     pub fn class_parse(&self) -> C {
         self.class
@@ -124,6 +185,30 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfHeader<'slice, C, E> {
             .parse_u16_at(self.class.header_size_offset(), self.bytes)
     }
 
+    /// Returns the bytes of this [`ElfHeader`] that follow the fields defined by the ELF
+    /// specification, as given by [`ElfHeader::header_size()`].
+    ///
+    /// Returns `None` if the extension is located out of bounds.
+    pub fn header_extension(&self) -> Option<&'slice [u8]> {
+        let start = self.class.expected_elf_header_size();
+        let end = usize::from(self.header_size()).max(start);
+
+        self.bytes.get(start..end)
+    }
+
+    /// Returns the range of file offsets occupied by this [`ElfHeader`], as given by
+    /// [`ElfHeader::header_size()`].
+    pub fn file_range(&self) -> Range<usize> {
+        0..usize::from(self.header_size()).max(self.class.expected_elf_header_size())
+    }
+
+    /// Returns the bytes of this [`ElfHeader`], including any [`ElfHeader::header_extension()`].
+    ///
+    /// Returns `None` if the header extends out of bounds.
+    pub fn raw_bytes(&self) -> Option<&'slice [u8]> {
+        self.bytes.get(self.file_range())
+    }
+
     /// Returns the virtual address of the entry point of this ELF file.
     pub fn entry(&self) -> C::ClassUsize {
         self.class
@@ -278,6 +363,55 @@ impl fmt::Display for ValidateElfHeaderSpecError {
 
 impl error::Error for ValidateElfHeaderSpecError {}
 
+/// Various errors that can occur when strictly validating an [`ElfHeader`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValidateElfHeaderStrictError {
+    /// The [`ElfHeader`] does not follow the ELF specification.
+    Spec(ValidateElfHeaderSpecError),
+    /// The version of the ELF file is not the current version.
+    UnsupportedFileVersion(u32),
+    /// The size of a program header is not the size expected for the class.
+    ProgramHeaderSizeMismatch(u16),
+    /// The size of a section header is not the size expected for the class.
+    SectionHeaderSizeMismatch(u16),
+    /// The section header count is non-zero but there is no section header table.
+    SectionHeaderCountWithoutTable(u16),
+    /// The section name string table index is non-zero but there is no section header table.
+    StringTableIndexWithoutTable(u16),
+}
+
+impl From<ValidateElfHeaderSpecError> for ValidateElfHeaderStrictError {
+    fn from(value: ValidateElfHeaderSpecError) -> Self {
+        Self::Spec(value)
+    }
+}
+
+impl fmt::Display for ValidateElfHeaderStrictError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spec(error) => fmt::Display::fmt(error, f),
+            Self::UnsupportedFileVersion(version) => {
+                write!(f, "unsupported ELF file version: {version}")
+            }
+            Self::ProgramHeaderSizeMismatch(size) => {
+                write!(f, "unexpected program header size: {size}")
+            }
+            Self::SectionHeaderSizeMismatch(size) => {
+                write!(f, "unexpected section header size: {size}")
+            }
+            Self::SectionHeaderCountWithoutTable(count) => {
+                write!(f, "{count} section headers but no section header table")
+            }
+            Self::StringTableIndexWithoutTable(index) => write!(
+                f,
+                "section name string table index {index} but no section header table"
+            ),
+        }
+    }
+}
+
+impl error::Error for ValidateElfHeaderStrictError {}
+
 /// The type of the ELF file.
 #[repr(transparent)]
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -309,6 +443,24 @@ impl fmt::Debug for ElfType {
     }
 }
 
+impl From<u16> for ElfType {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ElfType> for u16 {
+    fn from(value: ElfType) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u16> for ElfType {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
 /// The architecture of the ELF file.
 #[repr(transparent)]
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -319,12 +471,18 @@ impl Machine {
     pub const NONE: Self = Self(0);
     /// ELF file requires the Intel 80386 architecture.
     pub const INTEL_386: Self = Self(3);
+    /// ELF file requires the MIPS architecture.
+    pub const MIPS: Self = Self(8);
+    /// ELF file requires the 64-bit PowerPC architecture.
+    pub const PPC64: Self = Self(21);
     /// ELF file requires the AArch32 architecture.
     pub const ARM: Self = Self(40);
     /// ELF file requires the AMD x86_64 architecture.
     pub const X86_64: Self = Self(62);
     /// ELF file requires the AArch64 architecture.
     pub const AARCH64: Self = Self(183);
+    /// ELF file requires the RISC-V architecture.
+    pub const RISCV: Self = Self(243);
 }
 
 impl fmt::Debug for Machine {
@@ -332,14 +490,35 @@ impl fmt::Debug for Machine {
         match *self {
             Self::NONE => f.pad("None"),
             Self::INTEL_386 => f.pad("Intel386"),
+            Self::MIPS => f.pad("Mips"),
+            Self::PPC64 => f.pad("PowerPc64"),
             Self::ARM => f.pad("Aarch32"),
             Self::X86_64 => f.pad("x86_64"),
             Self::AARCH64 => f.pad("Aarch64"),
+            Self::RISCV => f.pad("RiscV"),
             machine => f.debug_tuple("Machine").field(&machine.0).finish(),
         }
     }
 }
 
+impl From<u16> for Machine {
+    fn from(value: u16) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Machine> for u16 {
+    fn from(value: Machine) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u16> for Machine {
+    fn eq(&self, other: &u16) -> bool {
+        self.0 == *other
+    }
+}
+
 /// The information required to implement class aware parsing of an ELF file header.
 pub trait ClassParseElfHeader: ClassParseBase {
     /// The offset of the [`ElfType`].
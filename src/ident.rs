@@ -4,6 +4,9 @@ use core::{error, fmt, mem};
 
 /// Contains basic information about an ELF file that can be obtained in an architecture
 /// independent manner.
+///
+/// The [`Display`][fmt::Display] implementations of [`ElfIdent`], [`Class`], [`Encoding`], and
+/// [`OsAbi`] match the output of `readelf -h`.
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct ElfIdent<'slice> {
     /// The underlying bytes of the ELF file identifier.
@@ -25,11 +28,11 @@ impl<'slice> ElfIdent<'slice> {
     ///
     /// # Errors
     /// - [`ValidateElfIdentSpecError::InvalidMagicBytes`]: Returned when this [`ElfIdent`]'s magic
-    ///     bytes are invalid.
+    ///   bytes are invalid.
     /// - [`ValidateElfIdentSpecError::UnsupportedElfHeaderVersion`]: Returned when this ELF header
-    ///     version is not supported.
+    ///   version is not supported.
     /// - [`ValidateElfIdentSpecError::NonZeroPadding`]: Returned when the padding of this
-    ///     [`ElfIdent`] is non-zero.
+    ///   [`ElfIdent`] is non-zero.
     pub fn validate_spec(&self) -> Result<(), ValidateElfIdentSpecError> {
         if self.magic() != Self::MAGIC_BYTES {
             return Err(ValidateElfIdentSpecError::InvalidMagicBytes(self.magic()));
@@ -54,6 +57,11 @@ impl<'slice> ElfIdent<'slice> {
     /// The current version of the ELF file header.
     pub const CURRENT_HEADER_VERSION: u8 = 1;
 
+    /// Returns the underlying bytes of this [`ElfIdent`].
+    pub const fn bytes(&self) -> &'slice [u8; 16] {
+        self.bytes
+    }
+
     /// Returns the magic bytes that identify this file as an ELF file.
     pub const fn magic(&self) -> [u8; 4] {
         match self.bytes.first_chunk() {
@@ -111,6 +119,19 @@ impl core::fmt::Debug for ElfIdent<'_> {
     }
 }
 
+impl fmt::Display for ElfIdent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, byte) in self.bytes.iter().enumerate() {
+            if index != 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Various errors that can occur when validating an [`ElfIdent`] follows the ELF specification and
 /// is supported by this crate.
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -163,6 +184,17 @@ impl fmt::Debug for Class {
     }
 }
 
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("none"),
+            Self::CLASS32 => f.pad("ELF32"),
+            Self::CLASS64 => f.pad("ELF64"),
+            Self(class) => write!(f, "<unknown: {class:x}>"),
+        }
+    }
+}
+
 /// Specifier of the ELF file data encoding, which determines the encoding
 /// of both the data structures used by the ELF file format and data contained
 /// in the object file sections.
@@ -192,6 +224,17 @@ impl fmt::Debug for Encoding {
     }
 }
 
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("none"),
+            Self::LSB2 => f.pad("2's complement, little endian"),
+            Self::MSB2 => f.pad("2's complement, big endian"),
+            Self(encoding) => write!(f, "<unknown: {encoding:x}>"),
+        }
+    }
+}
+
 /// Specifier of the OS or ABI specific ELF extensions used by this file.
 ///
 /// This field determines the interpretation of various OS or ABI specific values.
@@ -266,6 +309,31 @@ impl fmt::Debug for OsAbi {
     }
 }
 
+impl fmt::Display for OsAbi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("UNIX - System V"),
+            Self::HP_UX => f.pad("UNIX - HP-UX"),
+            Self::NETBSD => f.pad("UNIX - NetBSD"),
+            Self::GNU => f.pad("UNIX - GNU"),
+            Self::SUN_SOLARIS => f.pad("UNIX - Solaris"),
+            Self::AIX => f.pad("UNIX - AIX"),
+            Self::IRIX => f.pad("UNIX - IRIX"),
+            Self::FREE_BSD => f.pad("UNIX - FreeBSD"),
+            Self::COMPAQ_TRU64_UNIX => f.pad("UNIX - TRU64"),
+            Self::NOVELL_MODESTO => f.pad("Novell - Modesto"),
+            Self::OPEN_BSD => f.pad("UNIX - OpenBSD"),
+            Self::OPEN_VMS => f.pad("VMS - OpenVMS"),
+            Self::HP_NSK => f.pad("HP - Non-Stop Kernel"),
+            Self::AMIGA_RESEARCH => f.pad("AROS"),
+            Self::FENIXOS => f.pad("FenixOS"),
+            Self::CLOUD_ABI => f.pad("Nuxi CloudABI"),
+            Self::OPENVOS => f.pad("Stratus Technologies OpenVOS"),
+            Self(os_abi) => write!(f, "<unknown: {os_abi:x}>"),
+        }
+    }
+}
+
 /// Block of machine-independent data to mark the file as an ELF file
 /// and provide enough information for the remainder of the ELF file to be
 /// decoded.
@@ -0,0 +1,211 @@
+//! Definitions for attributing the symbols imported by an ELF file to the shared objects
+//! expected to provide them.
+//!
+//! A versioned import names the shared object it is required from in the version requirement
+//! table, so its attribution is exact. An unversioned import may be provided by any of the
+//! shared objects named by [`ConstDynamicTag::NEEDED`] entries, which the dynamic linker searches
+//! in order after the executable, so it is attributed to the first of them as a heuristic.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    dynamic::{self, ConstDynamicTag},
+    encoding::EncodingParse,
+    string_table::StringTable,
+    symbol::{self, Symbol, SymbolBinding},
+    versym::{Versym, VersymTable},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`NeededLibraries`] over the file names held by the [`ConstDynamicTag::NEEDED`]
+    /// entries of this [`ElfFile`], in the order the dynamic linker searches them.
+    pub fn needed_libraries(&self) -> Option<NeededLibraries<'slice, C, E>> {
+        Some(NeededLibraries {
+            entries: self.dynamic_table()?.into_iter(),
+            strings: self.dynamic_string_table()?,
+            done: false,
+        })
+    }
+
+    /// Returns an [`Imports`] over the symbols imported by the
+    /// [`ElfFile::dynamic_symbol_table()`] of this [`ElfFile`], along with the shared objects
+    /// expected to provide them.
+    pub fn imports(&self) -> Option<Imports<'slice, C, E>> {
+        let symbols = self.dynamic_symbol_table()?;
+
+        Some(Imports {
+            file: *self,
+            symbols: symbols.into_iter(),
+            next: 0,
+            versyms: self.versym_table(symbols.count()),
+            first_needed: self.needed_libraries().and_then(|mut needed| needed.next()),
+        })
+    }
+
+    /// Returns an [`ImportsFrom`] over the [`Import`]s of this [`ElfFile`] attributed to the
+    /// shared object named `library`, as it appears in a [`ConstDynamicTag::NEEDED`] entry.
+    ///
+    /// Calling this for each of the [`ElfFile::needed_libraries()`] lists the imports per shared
+    /// object.
+    pub fn imports_from<'name>(
+        &self,
+        library: &'name [u8],
+    ) -> Option<ImportsFrom<'slice, 'name, C, E>> {
+        Some(ImportsFrom {
+            imports: self.imports()?,
+            library,
+        })
+    }
+}
+
+/// An [`Iterator`] over the file names held by the [`ConstDynamicTag::NEEDED`] entries of a
+/// [`DynamicTable`][dt].
+///
+/// [dt]: dynamic::DynamicTable
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NeededLibraries<'slice, C: ClassParse, E: EncodingParse> {
+    /// The entries that have not yet been visited.
+    entries: dynamic::IntoIter<'slice, C, E>,
+    /// The dynamic [`StringTable`].
+    strings: StringTable<'slice>,
+    /// Whether the [`ConstDynamicTag::NULL`] entry has been reached.
+    done: bool,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for NeededLibraries<'slice, C, E> {
+    type Item = &'slice [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let entry = self.entries.next()?;
+            if entry.tag == ConstDynamicTag::NULL {
+                self.done = true;
+            } else if entry.tag == ConstDynamicTag::NEEDED {
+                let Ok(offset) = entry.val.try_into() else {
+                    continue;
+                };
+                if let Ok(name) = self.strings.get(offset) {
+                    return Some(name);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for NeededLibraries<'_, C, E> {}
+
+/// How an [`Import`] was attributed to a shared object.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImportAttribution {
+    /// The import is versioned, and the version requirement table names the shared object the
+    /// version is required from.
+    Version,
+    /// The import is unversioned, and was attributed to the first shared object the dynamic
+    /// linker searches.
+    NeededOrder,
+    /// The import is unversioned, and this [`ElfFile`] requires no shared objects.
+    Unknown,
+}
+
+/// A symbol imported by an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Import<'slice, C: ClassParse, E: EncodingParse> {
+    /// The index of the symbol in the dynamic symbol table.
+    pub index: usize,
+    /// The imported symbol.
+    pub symbol: Symbol<'slice, C, E>,
+    /// The file name of the shared object expected to provide the symbol.
+    pub library: Option<&'slice [u8]>,
+    /// The name of the version required, if the import is versioned.
+    pub version: Option<&'slice [u8]>,
+    /// How [`Import::library`] was determined.
+    pub attribution: ImportAttribution,
+}
+
+/// An [`Iterator`] over the [`Import`]s of an [`ElfFile`].
+///
+/// A symbol is imported if it is undefined, named and not [`SymbolBinding::LOCAL`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Imports<'slice, C, E> {
+    /// The [`ElfFile`] the imports belong to.
+    file: ElfFile<'slice, C, E>,
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// The index of the next symbol.
+    next: usize,
+    /// The symbol version table, if present.
+    versyms: Option<VersymTable<'slice, E>>,
+    /// The file name held by the first [`ConstDynamicTag::NEEDED`] entry, if any.
+    first_needed: Option<&'slice [u8]>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for Imports<'slice, C, E> {
+    type Item = Import<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for symbol in self.symbols.by_ref() {
+            let index = self.next;
+            self.next += 1;
+
+            if index == 0
+                || symbol.is_defined()
+                || symbol.binding() == SymbolBinding::LOCAL
+                || symbol.name().is_none_or(<[u8]>::is_empty)
+            {
+                continue;
+            }
+
+            let versym = self
+                .versyms
+                .and_then(|versyms| versyms.get(index))
+                .map_or(Versym::GLOBAL.0, Versym::index);
+            let required = (versym > Versym::GLOBAL.0)
+                .then(|| self.file.required_version(versym))
+                .flatten();
+
+            let (library, version, attribution) = match (required, self.first_needed) {
+                (Some((library, version)), _) => {
+                    (Some(library), Some(version), ImportAttribution::Version)
+                }
+                (None, Some(library)) => (Some(library), None, ImportAttribution::NeededOrder),
+                (None, None) => (None, None, ImportAttribution::Unknown),
+            };
+
+            return Some(Import {
+                index,
+                symbol,
+                library,
+                version,
+                attribution,
+            });
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for Imports<'_, C, E> {}
+
+/// An [`Iterator`] over the [`Import`]s of an [`ElfFile`] attributed to a single shared object.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ImportsFrom<'slice, 'name, C, E> {
+    /// The imports that have not yet been visited.
+    imports: Imports<'slice, C, E>,
+    /// The file name of the shared object.
+    library: &'name [u8],
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for ImportsFrom<'slice, '_, C, E> {
+    type Item = Import<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let library = self.library;
+        self.imports.find(|import| import.library == Some(library))
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for ImportsFrom<'_, '_, C, E> {}
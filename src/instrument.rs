@@ -0,0 +1,31 @@
+//! Crate-private macros for emitting diagnostic records when the `log` feature is enabled.
+//!
+//! When the `log` feature is disabled, the arguments are still type checked, but no code is
+//! generated.
+
+/// Emits a `debug` level record describing a major parsing step.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        ::log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = ::core::format_args!($($arg)*);
+        }
+    };
+}
+
+/// Emits a `trace` level record describing a minor parsing step.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "log")]
+        ::log::trace!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = ::core::format_args!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use debug;
+pub(crate) use trace;
@@ -22,26 +22,163 @@
 //! This crate implements parsing in such a manner that avoids heap allocations. ELF structures are
 //! lazily parsed with iterators or tables that only parse the requested structure when required.
 //!
+//! ## Deterministic Iteration Order
+//!
+//! Iterators over tables, such as the program header table or the dynamic array, yield entries in
+//! the order in which they are stored in the file. Other orders are provided by adapters that
+//! write indices into a caller-provided buffer, such as
+//! [`ProgramHeaderTable::sorted_by_virtual_address()`].
+//!
+//! ## Optional subsystems
+//!
+//! Subsystems that are not required to parse the ELF file header and program headers can be
+//! compiled out using the following Cargo features, all of which are enabled by default:
+//!
+//! - `dynamic`: Locating the dynamic array, procedure linkage table, symbol hash table and symbol
+//!   version tables of an ELF file, and auditing its relocations.
+//! - `notes`: Parsing of ELF notes.
+//! - `machine-flags`: Decoding of processor specific flags and ABI compatibility checking.
+//! - `sframe`: Parsing of SFrame stack trace information.
+//! - `archive`: Parsing of `ar` archives, such as static libraries.
+//! - `writer`: Exporting load segments in the Intel HEX and Motorola S-record formats, building
+//!   string tables, and rewriting parts of an ELF file in place.
+//! - `demangle`: Matching symbols by the path encoded in their mangled names.
+//!
+//! The `log` feature, which is disabled by default, emits [`log`](https://docs.rs/log) records
+//! describing major parsing steps, such as locating the program header table or the dynamic
+//! array, along with the offsets and counts involved.
+//!
 //! ## Uses no unsafe code
 //!
 //! This crate contains zero unsafe blocks of code.
 
 #![no_std]
 
-use core::fmt;
+use core::{fmt, ops::Range};
 
-use class::ClassParse;
-use encoding::EncodingParse;
+use biased::BiasedElfFile;
+use class::{to_file_offset, to_len, ClassParse, OffsetOverflow, UnsupportedClassError};
+#[cfg(feature = "dynamic")]
+use dynamic::DynamicTable;
+use encoding::{EncodingParse, UnsupportedEncodingError};
 use header::{ElfHeader, ParseElfHeaderError, ValidateElfHeaderSpecError};
-use program_header::{ProgramHeader, ProgramHeaderTable, ValidateProgramHeaderSpecError};
+use limits::LimitExceededError;
+use program_header::{
+    ProgramHeader, ProgramHeaderTable, SegmentType, ValidatePhdrSegmentError,
+    ValidateProgramHeaderSpecError,
+};
+use section_header::{SectionHeader, SectionHeaderTable, SectionType};
+use string_table::StringTable;
 
+#[cfg(feature = "machine-flags")]
+pub mod abi;
+#[cfg(feature = "dynamic")]
+pub mod abi_surface;
+pub mod alignment;
+pub mod anomaly;
+#[cfg(feature = "archive")]
+pub mod archive;
+#[cfg(feature = "dynamic")]
+pub mod audit;
+pub mod auxv;
+pub mod biased;
+#[cfg(all(feature = "notes", feature = "writer"))]
+pub mod build_id;
 pub mod class;
+pub mod compare;
+pub mod consts;
+#[cfg(feature = "writer")]
+pub mod convert;
+#[cfg(all(feature = "notes", feature = "writer"))]
+pub mod core_note;
+pub mod diagnostic;
 pub mod dynamic;
+#[cfg(all(feature = "dynamic", feature = "writer"))]
+pub mod dynamic_edit;
+#[cfg(feature = "dynamic")]
+pub mod dynsym;
+pub mod embedded;
 pub mod encoding;
+pub mod entropy;
+pub mod erased;
+#[cfg(feature = "writer")]
+pub mod export;
+#[cfg(feature = "writer")]
+pub mod extract;
+#[cfg(feature = "machine-flags")]
+pub mod flags;
+pub mod flat;
+pub mod gap;
+#[cfg(feature = "dynamic")]
+pub mod gnu_hash;
+#[cfg(feature = "writer")]
+pub mod harden;
+#[cfg(feature = "dynamic")]
+pub mod hash;
 pub mod header;
 pub mod ident;
+#[cfg(feature = "dynamic")]
+pub mod imports;
+mod instrument;
+pub mod limits;
+#[cfg(feature = "writer")]
+pub mod link;
+pub mod linker_map;
+pub mod memtag;
+pub mod multiversion;
+#[cfg(feature = "writer")]
+pub mod normalize;
+#[cfg(feature = "notes")]
+pub mod note;
+#[cfg(all(feature = "dynamic", feature = "notes"))]
+pub mod object;
+pub mod plan;
+#[cfg(feature = "dynamic")]
+pub mod plt;
+#[cfg(all(feature = "dynamic", feature = "writer"))]
+pub mod prelink;
 pub mod program_header;
+#[cfg(feature = "writer")]
+pub mod program_header_edit;
+#[cfg(feature = "notes")]
+pub mod property;
+#[cfg(feature = "dynamic")]
+pub mod reconstruct;
+pub mod region;
 pub mod relocation;
+#[cfg(feature = "dynamic")]
+pub mod relocation_stats;
+#[cfg(feature = "dynamic")]
+pub mod relro;
+pub mod resolve;
+pub mod search;
+pub mod section;
+#[cfg(feature = "writer")]
+pub mod section_edit;
+pub mod section_header;
+pub mod segment;
+#[cfg(feature = "sframe")]
+pub mod sframe;
+pub mod size;
+pub mod string_table;
+#[cfg(feature = "writer")]
+pub mod string_table_builder;
+pub mod symbol;
+#[cfg(feature = "writer")]
+pub mod symbol_edit;
+pub mod symbol_search;
+pub mod target;
+pub mod uimage;
+#[cfg(feature = "dynamic")]
+pub mod unchecked;
+#[cfg(feature = "dynamic")]
+pub mod version;
+pub mod version_script;
+#[cfg(feature = "dynamic")]
+pub mod versym;
+#[cfg(feature = "writer")]
+mod writer;
+pub mod xref;
 
 /// An ELF file.
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
@@ -60,25 +197,55 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
     /// # Errors
     ///
     /// - [`ParseElfFileError::ParseElfHeaderError`]: Returned if an error occurs when parsing the
-    ///     [`ElfHeader`] contained in the given `slice`.
+    ///   [`ElfHeader`] contained in the given `slice`.
     /// - [`ParseElfFileError::ElfHeaderSpecError`]: Returned if an error occurs when validating
-    ///     the [`ElfHeader`] follows the ELF specification.
+    ///   the [`ElfHeader`] follows the ELF specification.
+    /// - [`ParseElfFileError::OffsetOverflow`]: Returned if the offset of the
+    ///   [`ProgramHeaderTable`] cannot be represented by a [`usize`] on the host.
+    /// - [`ParseElfFileError::ProgramHeaderEntrySizeTooSmall`]: Returned if the
+    ///   [`ProgramHeaderTable`] is present but its entries are smaller than a [`ProgramHeader`].
     /// - [`ParseElfFileError::ProgramHeaderTableOutOfBounds`]: Returned if the
-    ///     [`ProgramHeaderTable`] is out of the bounds of the given `slice`.
+    ///   [`ProgramHeaderTable`] is out of the bounds of the given `slice`.
     pub fn new(slice: &'slice [u8]) -> Result<Self, ParseElfFileError> {
-        let header = ElfHeader::new(slice)?;
+        let file = Self::new_unvalidated_segments(slice)?;
+
+        if let Some(table) = file.program_header_table() {
+            for (index, header) in table.into_iter().enumerate() {
+                header
+                    .validate_specification()
+                    .map_err(|error| ParseElfFileError::ProgramHeaderSpecError { index, error })?;
+            }
+            instrument::debug!("validated {} program headers", table.count());
+        }
+
+        Ok(file)
+    }
+
+    /// Creates a new [`ElfFile`] from the given `slice` without validating its
+    /// [`ProgramHeader`]s.
+    pub(crate) fn new_unvalidated_segments(slice: &'slice [u8]) -> Result<Self, ParseElfFileError> {
+        let header = ElfHeader::<C, E>::new(slice)?;
         header.validate_spec()?;
+        instrument::debug!(
+            "parsed {} header: {} program headers at {:#x}",
+            header.ident().class(),
+            header.program_header_count(),
+            Into::<u64>::into(header.program_header_offset()),
+        );
 
         if header.program_header_count() != 0 {
-            let offset = TryInto::<usize>::try_into(header.program_header_offset())
-                .map_err(|_| ParseElfFileError::ProgramHeaderTableOutOfBounds)?;
+            let entry_size = header.program_header_size();
+            if usize::from(entry_size) < header.class.expected_program_header_size() {
+                return Err(ParseElfFileError::ProgramHeaderEntrySizeTooSmall(
+                    entry_size,
+                ));
+            }
 
-            let total_size = usize::from(
-                header
-                    .program_header_count()
-                    .checked_add(header.program_header_size())
-                    .ok_or(ParseElfFileError::ProgramHeaderTableOutOfBounds)?,
-            );
+            let offset = to_file_offset(header.program_header_offset())?;
+
+            let total_size = usize::from(header.program_header_count())
+                .checked_mul(usize::from(header.program_header_size()))
+                .ok_or(ParseElfFileError::ProgramHeaderTableOutOfBounds)?;
 
             if offset
                 .checked_add(total_size)
@@ -94,14 +261,6 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
             encoding: header.encoding,
         };
 
-        if let Some(table) = file.program_header_table() {
-            for (index, header) in table.into_iter().enumerate() {
-                header
-                    .validate_specification()
-                    .map_err(|error| ParseElfFileError::ProgramHeaderSpecError { index, error })?;
-            }
-        }
-
         Ok(file)
     }
 }
@@ -124,19 +283,14 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
             return None;
         }
 
-        let Ok(offset) = self.header().program_header_offset().try_into() else {
-            unreachable!()
-        };
-
-        let table = ProgramHeaderTable {
-            bytes: &self.bytes[offset..],
-            entry_count: self.header().program_header_count(),
-            entry_size: self.header().program_header_size(),
-            class: self.class,
-            encoding: self.encoding,
-        };
-
-        Some(table)
+        let offset = to_file_offset(self.header().program_header_offset()).ok()?;
+        ProgramHeaderTable::new(
+            self.class,
+            self.encoding,
+            self.bytes.get(offset..)?,
+            self.header().program_header_count(),
+            self.header().program_header_size(),
+        )
     }
 
     /// Returns the file data associated with the given [`ProgramHeader`].
@@ -144,12 +298,388 @@ impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
         &self,
         program_header: ProgramHeader<'slice, C, E>,
     ) -> Option<&'slice [u8]> {
-        let start: usize = program_header.file_offset().try_into().ok()?;
-        let size = program_header.file_size().try_into().ok()?;
+        let start = to_file_offset(program_header.file_offset()).ok()?;
+        let size = to_len(program_header.file_size()).ok()?;
         let end = start.checked_add(size)?;
 
         self.bytes.get(start..end)
     }
+
+    /// Returns the [`SectionHeaderTable`] of this [`ElfFile`].
+    ///
+    /// The presence of a [`SectionHeaderTable`] is not guaranteed, and it is not needed to load
+    /// the program, so it is only bounds-checked when requested. Returns `None` if the
+    /// [`SectionHeaderTable`] is absent or does not fit within the file.
+    pub fn section_header_table(&self) -> Option<SectionHeaderTable<'slice, C, E>> {
+        let header = self.header();
+        if header.section_header_count() == 0 {
+            return None;
+        }
+
+        let offset = to_file_offset(header.section_header_offset()).ok()?;
+        SectionHeaderTable::new(
+            self.class,
+            self.encoding,
+            self.bytes.get(offset..)?,
+            header.section_header_count(),
+            header.section_header_size(),
+        )
+    }
+
+    /// Returns the file data associated with the given [`SectionHeader`].
+    ///
+    /// A [`SectionType::NOBITS`] section occupies no bytes in the file, so its data is empty.
+    pub fn section_data(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+    ) -> Option<&'slice [u8]> {
+        if section_header.section_type() == SectionType::NOBITS {
+            return Some(&[]);
+        }
+
+        let start = to_file_offset(section_header.file_offset()).ok()?;
+        let size = to_len(section_header.size()).ok()?;
+        let end = start.checked_add(size)?;
+
+        self.bytes.get(start..end)
+    }
+
+    /// Returns the section name [`StringTable`] of this [`ElfFile`].
+    ///
+    /// Returns `None` if this [`ElfFile`] has no section name [`StringTable`] or it is located
+    /// out of bounds.
+    pub fn section_name_table(&self) -> Option<StringTable<'slice>> {
+        let index = self.header().section_header_string_table_index();
+        if index == 0 {
+            return None;
+        }
+
+        let section_header = self.section_header_table()?.get(index)?;
+        self.section_data(section_header).map(StringTable::new)
+    }
+
+    /// Returns the name of the given [`SectionHeader`], without the terminating null byte.
+    ///
+    /// Returns `None` if this [`ElfFile`] has no section name [`StringTable`] or the name is not
+    /// a null-terminated string within it.
+    pub fn section_name(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+    ) -> Option<&'slice [u8]> {
+        let offset = to_file_offset(section_header.name_offset()).ok()?;
+
+        self.section_name_table()?.get(offset).ok()
+    }
+
+    /// Returns the [`DynamicTable`] described by the [`SegmentType::DYNAMIC`] segment of this
+    /// [`ElfFile`].
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_table(&self) -> Option<DynamicTable<'slice, C, E>> {
+        let program_header = self
+            .program_header_table()?
+            .segments_of_type(SegmentType::DYNAMIC)
+            .next()?;
+
+        self.dynamic_table_from_segment(program_header)
+    }
+
+    /// Returns the [`DynamicTable`] held by the segment `program_header` controls.
+    ///
+    /// This locates the dynamic array of files whose section header table has been stripped.
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_table_from_segment(
+        &self,
+        program_header: ProgramHeader<'slice, C, E>,
+    ) -> Option<DynamicTable<'slice, C, E>> {
+        let data = self.segment_data(program_header)?;
+        let count = data.len() / self.class.expected_dynamic_size();
+        instrument::debug!(
+            "located dynamic array: {count} entries at {:#x}",
+            Into::<u64>::into(program_header.file_offset()),
+        );
+
+        DynamicTable::new(self.class, self.encoding, data, count)
+    }
+
+    /// Returns the [`DynamicTable`] held by the [`SectionType::DYNAMIC`] section of this
+    /// [`ElfFile`].
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_section_table(&self) -> Option<DynamicTable<'slice, C, E>> {
+        let section_header = self
+            .section_header_table()?
+            .sections_of_type(SectionType::DYNAMIC)
+            .next()?;
+
+        self.dynamic_table_from_section(section_header)
+    }
+
+    /// Returns the [`DynamicTable`] held by the section `section_header` describes.
+    ///
+    /// Returns `None` if the section is located out of bounds or its entry size is neither zero
+    /// nor the size of a dynamic structure.
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_table_from_section(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+    ) -> Option<DynamicTable<'slice, C, E>> {
+        let expected = self.class.expected_dynamic_size();
+        let entry_size = to_len(section_header.entry_size()).ok()?;
+        if entry_size != 0 && entry_size != expected {
+            return None;
+        }
+
+        let data = self.section_data(section_header)?;
+        DynamicTable::new(self.class, self.encoding, data, data.len() / expected)
+    }
+
+    /// Returns the dynamic [`StringTable`] located by the [`ConstDynamicTag::STRING_TABLE`][st]
+    /// entry of this [`ElfFile`].
+    ///
+    /// The table is limited to the size given by the
+    /// [`ConstDynamicTag::STRING_TABLE_SIZE`][sz] entry, if present and in bounds.
+    ///
+    /// [st]: dynamic::ConstDynamicTag::STRING_TABLE
+    /// [sz]: dynamic::ConstDynamicTag::STRING_TABLE_SIZE
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_string_table(&self) -> Option<StringTable<'slice>> {
+        let dynamic = self.dynamic_table()?;
+        let data =
+            self.virtual_address_data(dynamic.find(dynamic::ConstDynamicTag::STRING_TABLE)?)?;
+        let data = match dynamic
+            .find(dynamic::ConstDynamicTag::STRING_TABLE_SIZE)
+            .and_then(|size| to_len(size).ok())
+        {
+            Some(size) => data.get(..size).unwrap_or(data),
+            None => data,
+        };
+
+        Some(StringTable::new(data))
+    }
+
+    /// Returns the file data located at the virtual address `address`, extending to the end of the
+    /// file-backed portion of the [`SegmentType::LOAD`] segment containing `address`.
+    pub fn virtual_address_data(&self, address: C::ClassUsize) -> Option<&'slice [u8]> {
+        let address: u64 = address.into();
+
+        self.program_header_table()?
+            .segments_of_type(SegmentType::LOAD)
+            .find_map(|program_header| {
+                let offset = address.checked_sub(program_header.virtual_address().into())?;
+                if offset >= program_header.file_size().into() {
+                    return None;
+                }
+
+                let data = self.segment_data(program_header)?;
+                data.get(to_len(offset).ok()?..)
+            })
+    }
+
+    /// Validates that the [`SegmentType::PHDR`] segment of this [`ElfFile`], if present, is
+    /// consistent with the program header table described by the [`ElfHeader`].
+    ///
+    /// # Errors
+    ///
+    /// - [`ValidatePhdrSegmentError::MultipleSegments`]: Returned if more than one
+    ///   [`SegmentType::PHDR`] segment is present.
+    /// - [`ValidatePhdrSegmentError::FollowsLoadSegment`]: Returned if the [`SegmentType::PHDR`]
+    ///   segment follows a [`SegmentType::LOAD`] segment.
+    /// - [`ValidatePhdrSegmentError::OffsetMismatch`]: Returned if the [`SegmentType::PHDR`]
+    ///   segment does not start at the program header table offset.
+    /// - [`ValidatePhdrSegmentError::SizeMismatch`]: Returned if the [`SegmentType::PHDR`]
+    ///   segment does not have the size of the program header table.
+    /// - [`ValidatePhdrSegmentError::NotInLoadSegment`]: Returned if the [`SegmentType::PHDR`]
+    ///   segment is not mapped at its virtual address by a [`SegmentType::LOAD`] segment.
+    pub fn validate_phdr_segment(&self) -> Result<(), ValidatePhdrSegmentError> {
+        let Some(table) = self.program_header_table() else {
+            return Ok(());
+        };
+
+        let mut phdr = None;
+        let mut seen_load = false;
+        for program_header in table {
+            match program_header.segment_type() {
+                SegmentType::LOAD => seen_load = true,
+                SegmentType::PHDR if phdr.is_some() => {
+                    return Err(ValidatePhdrSegmentError::MultipleSegments)
+                }
+                SegmentType::PHDR if seen_load => {
+                    return Err(ValidatePhdrSegmentError::FollowsLoadSegment)
+                }
+                SegmentType::PHDR => phdr = Some(program_header),
+                _ => {}
+            }
+        }
+        let Some(phdr) = phdr else {
+            return Ok(());
+        };
+
+        let header = self.header();
+        let offset: u64 = phdr.file_offset().into();
+        if offset != header.program_header_offset().into() {
+            return Err(ValidatePhdrSegmentError::OffsetMismatch);
+        }
+
+        let size =
+            u64::from(header.program_header_count()) * u64::from(header.program_header_size());
+        if phdr.file_size().into() != size || phdr.memory_size().into() != size {
+            return Err(ValidatePhdrSegmentError::SizeMismatch);
+        }
+
+        let address: u64 = phdr.virtual_address().into();
+        let mapped = table
+            .segments_of_type(SegmentType::LOAD)
+            .any(|program_header| {
+                let Some(delta) = offset.checked_sub(program_header.file_offset().into()) else {
+                    return false;
+                };
+
+                delta.saturating_add(size) <= program_header.file_size().into()
+                    && program_header
+                        .virtual_address()
+                        .into()
+                        .checked_add(delta)
+                        .is_some_and(|expected| expected == address)
+            });
+        if !mapped {
+            return Err(ValidatePhdrSegmentError::NotInLoadSegment);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the virtual address at which the [`ProgramHeaderTable`] resides when this
+    /// [`ElfFile`] is loaded.
+    ///
+    /// The address is taken from the [`SegmentType::PHDR`] segment if present, and is otherwise
+    /// computed from the [`SegmentType::LOAD`] segment containing the [`ProgramHeaderTable`].
+    pub fn program_header_table_address(&self) -> Option<u64> {
+        let table = self.program_header_table()?;
+        if let Some(phdr) = table.segments_of_type(SegmentType::PHDR).next() {
+            return Some(phdr.virtual_address().into());
+        }
+
+        let header = self.header();
+        let offset: u64 = header.program_header_offset().into();
+        let size =
+            u64::from(header.program_header_count()) * u64::from(header.program_header_size());
+        table
+            .segments_of_type(SegmentType::LOAD)
+            .find_map(|program_header| {
+                let delta = offset.checked_sub(program_header.file_offset().into())?;
+                if delta.checked_add(size)? > program_header.file_size().into() {
+                    return None;
+                }
+
+                let address: u64 = program_header.virtual_address().into();
+                address.checked_add(delta)
+            })
+    }
+
+    /// Returns the [`ProgramHeader`] of the [`SegmentType::LOAD`] segment whose memory image
+    /// contains the virtual address `address`.
+    pub fn load_segment_containing(
+        &self,
+        address: C::ClassUsize,
+    ) -> Option<ProgramHeader<'slice, C, E>> {
+        let address: u64 = address.into();
+
+        self.program_header_table()?
+            .segments_of_type(SegmentType::LOAD)
+            .find(|program_header| {
+                address
+                    .checked_sub(program_header.virtual_address().into())
+                    .is_some_and(|offset| offset < program_header.memory_size().into())
+            })
+    }
+
+    /// Returns the [`ProgramHeader`] of the [`SegmentType::LOAD`] segment containing the entry
+    /// point of this [`ElfFile`].
+    ///
+    /// Returns `None` if this [`ElfFile`] has no entry point or the entry point lies outside of
+    /// every [`SegmentType::LOAD`] segment.
+    pub fn entry_segment(&self) -> Option<ProgramHeader<'slice, C, E>> {
+        let entry = self.header().entry();
+        if entry.into() == 0 {
+            return None;
+        }
+
+        self.load_segment_containing(entry)
+    }
+
+    /// Converts this [`ElfFile`] into an [`ElfFile`] using the [`ClassParse`] implementation `T`.
+    ///
+    /// This allows code that has verified the [`Class`][c] of a file parsed with a dispatching
+    /// [`ClassParse`] implementation, such as [`AnyClass`][ac], to switch to a monomorphic
+    /// implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedClassError`] if the [`Class`][c] of this [`ElfFile`] is not supported
+    /// by `T`.
+    ///
+    /// [c]: ident::Class
+    /// [ac]: class::AnyClass
+    pub fn try_into_class<T: ClassParse>(
+        self,
+    ) -> Result<ElfFile<'slice, T, E>, UnsupportedClassError> {
+        let file = ElfFile {
+            bytes: self.bytes,
+            class: T::from_elf_class(self.header().ident().class())?,
+            encoding: self.encoding,
+        };
+
+        Ok(file)
+    }
+
+    /// Converts this [`ElfFile`] into an [`ElfFile`] using the [`EncodingParse`] implementation
+    /// `T`.
+    ///
+    /// This allows code that has verified the [`Encoding`][e] of a file parsed with a dispatching
+    /// [`EncodingParse`] implementation, such as [`AnyEndian`][ae], to switch to a monomorphic
+    /// implementation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UnsupportedEncodingError`] if the [`Encoding`][e] of this [`ElfFile`] is not
+    /// supported by `T`.
+    ///
+    /// [e]: ident::Encoding
+    /// [ae]: encoding::AnyEndian
+    pub fn try_into_encoding<T: EncodingParse>(
+        self,
+    ) -> Result<ElfFile<'slice, C, T>, UnsupportedEncodingError> {
+        let file = ElfFile {
+            bytes: self.bytes,
+            class: self.class,
+            encoding: T::from_elf_encoding(self.header().ident().encoding())?,
+        };
+
+        Ok(file)
+    }
+
+    /// Returns the range of file offsets occupied by `bytes`, which must be a subslice of this
+    /// [`ElfFile`].
+    ///
+    /// Returns `None` if `bytes` does not lie within this [`ElfFile`].
+    pub fn range_of(&self, bytes: &[u8]) -> Option<Range<usize>> {
+        let start = (bytes.as_ptr() as usize).checked_sub(self.bytes.as_ptr() as usize)?;
+        let end = start.checked_add(bytes.len())?;
+
+        (end <= self.bytes.len()).then_some(start..end)
+    }
+
+    /// Returns the range of file offsets occupied by the [`ProgramHeader`] at `index`.
+    pub fn program_header_range(&self, index: u16) -> Option<Range<usize>> {
+        let program_header = self.program_header_table()?.get(index)?;
+
+        self.range_of(program_header.raw_bytes())
+    }
+
+    /// Returns a [`BiasedElfFile`] view of this [`ElfFile`] that applies `bias` to every address
+    /// it returns.
+    pub fn with_bias(self, bias: i64) -> BiasedElfFile<'slice, C, E> {
+        BiasedElfFile::new(self, bias)
+    }
 }
 
 impl<C: ClassParse, E: EncodingParse> fmt::Debug for ElfFile<'_, C, E> {
@@ -173,8 +703,16 @@ pub enum ParseElfFileError {
     ParseElfHeaderError(ParseElfHeaderError),
     /// An error occurred while validating the [`ElfHeader`].
     ElfHeaderSpecError(ValidateElfHeaderSpecError),
+    /// A class sized value cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
     /// The [`ProgramHeaderTable`] is located out of bounds.
     ProgramHeaderTableOutOfBounds,
+    /// The size of each entry of the [`ProgramHeaderTable`] is smaller than a [`ProgramHeader`].
+    ProgramHeaderEntrySizeTooSmall(u16),
+    /// A limit of the [`ParseOptions`][po] was exceeded.
+    ///
+    /// [po]: limits::ParseOptions
+    LimitExceeded(LimitExceededError),
     /// An error occurred when validing the [`ProgramHeaderTable`].
     ProgramHeaderSpecError {
         /// The index of the [`ProgramHeader`] that fails to conform to the ELF specification.
@@ -196,6 +734,18 @@ impl From<ValidateElfHeaderSpecError> for ParseElfFileError {
     }
 }
 
+impl From<OffsetOverflow> for ParseElfFileError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl From<LimitExceededError> for ParseElfFileError {
+    fn from(value: LimitExceededError) -> Self {
+        Self::LimitExceeded(value)
+    }
+}
+
 impl fmt::Display for ParseElfFileError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -206,9 +756,17 @@ impl fmt::Display for ParseElfFileError {
                 f,
                 "error validating ELF header specification conformance: {error}"
             ),
+            Self::OffsetOverflow(error) => write!(f, "error while converting offset: {error}"),
             Self::ProgramHeaderTableOutOfBounds => {
                 write!(f, "program header table located out of bounds")
             }
+            Self::ProgramHeaderEntrySizeTooSmall(size) => {
+                write!(
+                    f,
+                    "program header entry size {size} is smaller than a program header"
+                )
+            }
+            Self::LimitExceeded(error) => write!(f, "error while parsing ELF file: {error}"),
             Self::ProgramHeaderSpecError { index, error } => write!(
                 f,
                 "program header at index {index} does not conform to ELF specification: {error}",
@@ -216,3 +774,90 @@ impl fmt::Display for ParseElfFileError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class::{AnyClass, Class64},
+        encoding::{AnyEndian, LittleEndian},
+        ElfFile, ParseElfFileError,
+    };
+
+    /// Returns a little-endian 64-bit [`ElfHeader`] with the given program header table fields,
+    /// padded with zeros to `N` bytes.
+    ///
+    /// Tests in other modules build their files on top of this header.
+    pub(crate) fn elf64_header<const N: usize>(offset: u64, count: u16, size: u16) -> [u8; N] {
+        let mut bytes = [0; N];
+        bytes[..7].copy_from_slice(&[0x7F, b'E', b'L', b'F', 2, 1, 1]);
+        bytes[20..24].copy_from_slice(&1u32.to_le_bytes());
+        bytes[32..40].copy_from_slice(&offset.to_le_bytes());
+        bytes[52..54].copy_from_slice(&64u16.to_le_bytes());
+        bytes[54..56].copy_from_slice(&size.to_le_bytes());
+        bytes[56..58].copy_from_slice(&count.to_le_bytes());
+        bytes
+    }
+
+    /// A program header table whose entries fit in the file is accepted.
+    #[test]
+    fn program_header_table_in_bounds() {
+        let bytes = elf64_header::<{ 64 + 2 * 56 }>(64, 2, 56);
+
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+        assert_eq!(file.program_header_table().unwrap().count(), 2);
+    }
+
+    /// A program header table whose count and entry size sum to less than the file size, but
+    /// whose product does not, is rejected.
+    #[test]
+    fn program_header_table_size_is_multiplied() {
+        let bytes = elf64_header::<{ 64 + 2 * 56 }>(64, 3, 56);
+
+        assert_eq!(
+            ElfFile::<AnyClass, AnyEndian>::new(&bytes).unwrap_err(),
+            ParseElfFileError::ProgramHeaderTableOutOfBounds,
+        );
+    }
+
+    /// A program header table with the largest count and entry size is rejected.
+    #[test]
+    fn program_header_table_size_maximum() {
+        let bytes = elf64_header::<{ 64 + 2 * 56 }>(64, u16::MAX, u16::MAX);
+
+        assert_eq!(
+            ElfFile::<AnyClass, AnyEndian>::new(&bytes).unwrap_err(),
+            ParseElfFileError::ProgramHeaderTableOutOfBounds,
+        );
+    }
+
+    /// A program header table whose entries are smaller than a program header is rejected
+    /// instead of being read out of bounds.
+    #[test]
+    fn program_header_entry_size_too_small() {
+        for size in [0, 8, 55] {
+            let bytes = elf64_header::<{ 64 + 2 * 56 }>(64, 2, size);
+
+            assert_eq!(
+                ElfFile::<AnyClass, AnyEndian>::new(&bytes).unwrap_err(),
+                ParseElfFileError::ProgramHeaderEntrySizeTooSmall(size),
+            );
+        }
+    }
+
+    /// A file without program headers may leave the entry size zero.
+    #[test]
+    fn program_header_entry_size_unused() {
+        let bytes = elf64_header::<64>(0, 0, 0);
+
+        let file = ElfFile::<AnyClass, AnyEndian>::new(&bytes).unwrap();
+        assert!(file.program_header_table().is_none());
+    }
+
+    /// A program header table whose end overflows the address space is rejected.
+    #[test]
+    fn program_header_table_end_overflows() {
+        let bytes = elf64_header::<{ 64 + 2 * 56 }>(u64::MAX - 8, 2, 56);
+
+        assert!(ElfFile::<AnyClass, AnyEndian>::new(&bytes).is_err());
+    }
+}
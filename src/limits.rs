@@ -0,0 +1,484 @@
+//! Definitions for limiting the amount of work performed when parsing untrusted ELF files.
+
+use core::{error, fmt, iter::FusedIterator, mem};
+
+#[cfg(feature = "dynamic")]
+use crate::dynamic::{self, Dynamic, DynamicTable};
+#[cfg(feature = "notes")]
+use crate::note::{Note, Notes};
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{self, ProgramHeader},
+    relocation::{Rel, Rela},
+    section_header,
+    symbol::{self, SymbolSource},
+    ElfFile, ParseElfFileError,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Creates a new [`ElfFile`] from the given `slice`, enforcing the limits of `options`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseElfFileError::LimitExceeded`]: Returned if the program header table contains more
+    ///   entries than [`ParseOptions::max_program_headers`], or the section header table contains
+    ///   more entries than [`ParseOptions::max_sections`].
+    ///
+    /// Otherwise, returns the same errors as [`ElfFile::new()`].
+    pub fn new_with_options(
+        slice: &'slice [u8],
+        options: &ParseOptions,
+    ) -> Result<Self, ParseElfFileError> {
+        let header = crate::header::ElfHeader::<C, E>::new(slice)?;
+        options.check(
+            Limit::ProgramHeaders,
+            options.max_program_headers,
+            header.program_header_count().into(),
+        )?;
+        options.check(
+            Limit::Sections,
+            options.max_sections,
+            header.section_header_count().into(),
+        )?;
+
+        Self::new(slice)
+    }
+
+    /// Returns an [`Iterator`] over the [`ProgramHeader`]s of this [`ElfFile`] that enforces
+    /// [`ParseOptions::max_program_headers`].
+    pub fn program_headers_with_options(
+        &self,
+        options: &ParseOptions,
+    ) -> Option<Limited<program_header::IntoIter<'slice, C, E>>> {
+        Some(Limited::new(
+            self.program_header_table()?.into_iter(),
+            Limit::ProgramHeaders,
+            options.max_program_headers,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the [`SectionHeader`][sh]s of this [`ElfFile`] that enforces
+    /// [`ParseOptions::max_sections`].
+    ///
+    /// [sh]: section_header::SectionHeader
+    pub fn sections_with_options(
+        &self,
+        options: &ParseOptions,
+    ) -> Option<Limited<section_header::IntoIter<'slice, C, E>>> {
+        Some(Limited::new(
+            self.section_header_table()?.into_iter(),
+            Limit::Sections,
+            options.max_sections,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the [`Symbol`][s]s of the [`ElfFile::symbols()`] of this
+    /// [`ElfFile`] that enforces [`ParseOptions::max_symbols`].
+    ///
+    /// [s]: symbol::Symbol
+    pub fn symbols_with_options(
+        &self,
+        options: &ParseOptions,
+    ) -> Option<(SymbolSource, Limited<symbol::IntoIter<'slice, C, E>>)> {
+        let (source, table) = self.symbols()?;
+
+        Some((
+            source,
+            Limited::new(table.into_iter(), Limit::Symbols, options.max_symbols),
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the [`Dynamic`] entries of this [`ElfFile`] that enforces
+    /// [`ParseOptions::max_dynamic_entries`].
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_entries_with_options(
+        &self,
+        options: &ParseOptions,
+    ) -> Option<Limited<dynamic::IntoIter<'slice, C, E>>> {
+        Some(Limited::new(
+            self.dynamic_table()?.into_iter(),
+            Limit::DynamicEntries,
+            options.max_dynamic_entries,
+        ))
+    }
+
+    /// Returns the [`DynamicTable`] of this [`ElfFile`], enforcing the limits of `options`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LimitExceededError`] if the [`DynamicTable`] contains more entries than
+    /// [`ParseOptions::max_dynamic_entries`].
+    #[cfg(feature = "dynamic")]
+    pub fn dynamic_table_with_options(
+        &self,
+        options: &ParseOptions,
+    ) -> Result<Option<DynamicTable<'slice, C, E>>, LimitExceededError> {
+        let Some(table) = self.dynamic_table() else {
+            return Ok(None);
+        };
+        options.check(
+            Limit::DynamicEntries,
+            options.max_dynamic_entries,
+            table.count(),
+        )?;
+
+        Ok(Some(table))
+    }
+
+    /// Returns an [`Iterator`] over the [`Note`]s of this [`ElfFile`] that enforces the limits of
+    /// `options`.
+    #[cfg(feature = "notes")]
+    pub fn notes_with_options(&self, options: ParseOptions) -> LimitedNotes<'slice, C, E> {
+        LimitedNotes {
+            notes: self.notes(),
+            options,
+            count: 0,
+            exceeded: false,
+        }
+    }
+}
+
+/// Limits on the amount of work performed when parsing an [`ElfFile`].
+///
+/// The [`Default`] limits are unlimited.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ParseOptions {
+    /// The maximum number of entries in the program header table.
+    pub max_program_headers: usize,
+    /// The maximum number of entries in the section header table.
+    pub max_sections: usize,
+    /// The maximum number of entries in the dynamic table.
+    pub max_dynamic_entries: usize,
+    /// The maximum number of entries in a symbol table.
+    pub max_symbols: usize,
+    /// The maximum number of notes.
+    pub max_notes: usize,
+    /// The maximum combined size of the name and descriptor of a single note.
+    pub max_note_size: usize,
+}
+
+impl ParseOptions {
+    /// [`ParseOptions`] that impose no limits.
+    pub const UNLIMITED: Self = Self {
+        max_program_headers: usize::MAX,
+        max_sections: usize::MAX,
+        max_dynamic_entries: usize::MAX,
+        max_symbols: usize::MAX,
+        max_notes: usize::MAX,
+        max_note_size: usize::MAX,
+    };
+
+    /// Returns an error if `value` exceeds `maximum`.
+    fn check(&self, limit: Limit, maximum: usize, value: usize) -> Result<(), LimitExceededError> {
+        if value > maximum {
+            return Err(LimitExceededError { limit, maximum });
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// A limit of [`ParseOptions`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Limit {
+    /// [`ParseOptions::max_program_headers`].
+    ProgramHeaders,
+    /// [`ParseOptions::max_sections`].
+    Sections,
+    /// [`ParseOptions::max_dynamic_entries`].
+    DynamicEntries,
+    /// [`ParseOptions::max_symbols`].
+    Symbols,
+    /// [`ParseOptions::max_notes`].
+    Notes,
+    /// [`ParseOptions::max_note_size`].
+    NoteSize,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProgramHeaders => f.pad("program header count"),
+            Self::Sections => f.pad("section count"),
+            Self::DynamicEntries => f.pad("dynamic entry count"),
+            Self::Symbols => f.pad("symbol count"),
+            Self::Notes => f.pad("note count"),
+            Self::NoteSize => f.pad("note size"),
+        }
+    }
+}
+
+/// An error returned when a [`Limit`] of [`ParseOptions`] is exceeded.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LimitExceededError {
+    /// The [`Limit`] that was exceeded.
+    pub limit: Limit,
+    /// The configured maximum of the [`Limit`].
+    pub maximum: usize,
+}
+
+impl fmt::Display for LimitExceededError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} exceeds limit of {}", self.limit, self.maximum)
+    }
+}
+
+impl error::Error for LimitExceededError {}
+
+/// An [`Iterator`] adapter that enforces a [`Limit`] of [`ParseOptions`] on the number of entries
+/// it yields.
+///
+/// Once the limit is exceeded, the [`LimitExceededError`] is returned and iteration ends.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Limited<I> {
+    /// The underlying [`Iterator`].
+    iter: I,
+    /// The [`Limit`] being enforced.
+    limit: Limit,
+    /// The configured maximum of the [`Limit`].
+    maximum: usize,
+    /// The number of entries returned so far.
+    count: usize,
+    /// Whether the [`Limit`] has been exceeded.
+    exceeded: bool,
+}
+
+impl<I: Iterator> Limited<I> {
+    /// Creates a new [`Limited`] iterator over `iter` that yields at most `maximum` entries
+    /// before reporting `limit` as exceeded.
+    pub fn new(iter: I, limit: Limit, maximum: usize) -> Self {
+        Self {
+            iter,
+            limit,
+            maximum,
+            count: 0,
+            exceeded: false,
+        }
+    }
+
+    /// Returns the underlying [`Iterator`].
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator> Iterator for Limited<I> {
+    type Item = Result<I::Item, LimitExceededError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exceeded {
+            return None;
+        }
+
+        let item = self.iter.next()?;
+        self.count += 1;
+        if self.count > self.maximum {
+            self.exceeded = true;
+            return Some(Err(LimitExceededError {
+                limit: self.limit,
+                maximum: self.maximum,
+            }));
+        }
+
+        Some(Ok(item))
+    }
+}
+
+impl<I: FusedIterator> FusedIterator for Limited<I> {}
+
+/// An [`Iterator`] over the [`Note`]s of an [`ElfFile`] that enforces the limits of
+/// [`ParseOptions`].
+///
+/// Once a limit is exceeded, the [`LimitExceededError`] is returned and iteration ends.
+#[cfg(feature = "notes")]
+#[derive(Clone, Copy)]
+pub struct LimitedNotes<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`Iterator`] over the [`Note`]s.
+    notes: Notes<'slice, C, E>,
+    /// The limits being enforced.
+    options: ParseOptions,
+    /// The number of [`Note`]s returned so far.
+    count: usize,
+    /// Whether a limit has been exceeded.
+    exceeded: bool,
+}
+
+#[cfg(feature = "notes")]
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for LimitedNotes<'slice, C, E> {
+    type Item = Result<Note<'slice>, LimitExceededError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exceeded {
+            return None;
+        }
+
+        let note = self.notes.next()?;
+        self.count += 1;
+
+        let result = self
+            .options
+            .check(Limit::Notes, self.options.max_notes, self.count)
+            .and_then(|()| {
+                self.options.check(
+                    Limit::NoteSize,
+                    self.options.max_note_size,
+                    note.name.len().saturating_add(note.descriptor.len()),
+                )
+            })
+            .map(|()| note);
+        self.exceeded = result.is_err();
+
+        Some(result)
+    }
+}
+
+#[cfg(feature = "notes")]
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for LimitedNotes<'slice, C, E> {}
+
+/// A budget of work that a [`Budgeted`] iterator may perform before pausing.
+///
+/// The [`Default`] budget is unlimited.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct WorkBudget {
+    /// The maximum number of entries to visit.
+    pub max_entries: usize,
+    /// The maximum number of bytes to touch, as reported by [`WorkCost::work_cost()`].
+    pub max_bytes: usize,
+}
+
+impl WorkBudget {
+    /// A [`WorkBudget`] that imposes no limits.
+    pub const UNLIMITED: Self = Self {
+        max_entries: usize::MAX,
+        max_bytes: usize::MAX,
+    };
+
+    /// Returns `true` if this [`WorkBudget`] permits no further work.
+    pub fn is_exhausted(&self) -> bool {
+        self.max_entries == 0 || self.max_bytes == 0
+    }
+}
+
+impl Default for WorkBudget {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// An entry whose parsing touches a known number of bytes.
+pub trait WorkCost {
+    /// Returns the number of bytes touched when parsing this entry.
+    fn work_cost(&self) -> usize;
+}
+
+impl<C: ClassParse, E: EncodingParse> WorkCost for ProgramHeader<'_, C, E> {
+    fn work_cost(&self) -> usize {
+        self.raw_bytes().len()
+    }
+}
+
+#[cfg(feature = "notes")]
+impl WorkCost for Note<'_> {
+    fn work_cost(&self) -> usize {
+        (3 * mem::size_of::<u32>())
+            .saturating_add(self.name.len())
+            .saturating_add(self.descriptor.len())
+    }
+}
+
+#[cfg(feature = "dynamic")]
+impl<C: ClassParse> WorkCost for Dynamic<C> {
+    fn work_cost(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl<C: ClassParse> WorkCost for Rel<C> {
+    fn work_cost(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+impl<C: ClassParse> WorkCost for Rela<C> {
+    fn work_cost(&self) -> usize {
+        mem::size_of::<Self>()
+    }
+}
+
+/// An [`Iterator`] adapter that pauses once its [`WorkBudget`] is exhausted.
+///
+/// A paused [`Budgeted`] iterator returns `None` until [`Budgeted::refill()`] is called, after
+/// which iteration resumes where it stopped. The budget is checked before each entry, so the
+/// final entry of a slice may overrun [`WorkBudget::max_bytes`] by its own cost.
+///
+/// Since iteration can resume after returning `None`, [`Budgeted`] does not implement
+/// [`FusedIterator`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Budgeted<I> {
+    /// The underlying [`Iterator`].
+    iter: I,
+    /// The work remaining in the current slice.
+    remaining: WorkBudget,
+    /// Whether the underlying [`Iterator`] has been exhausted.
+    finished: bool,
+}
+
+impl<I: Iterator<Item: WorkCost>> Budgeted<I> {
+    /// Creates a new [`Budgeted`] iterator over `iter` that may perform the work of `budget`
+    /// before pausing.
+    pub fn new(iter: I, budget: WorkBudget) -> Self {
+        Self {
+            iter,
+            remaining: budget,
+            finished: false,
+        }
+    }
+
+    /// Replaces the remaining work of this [`Budgeted`] iterator with `budget`, resuming
+    /// iteration if it was paused.
+    pub fn refill(&mut self, budget: WorkBudget) {
+        self.remaining = budget;
+    }
+
+    /// Returns the work remaining before this [`Budgeted`] iterator pauses.
+    pub fn remaining(&self) -> WorkBudget {
+        self.remaining
+    }
+
+    /// Returns `true` if this [`Budgeted`] iterator is paused because its [`WorkBudget`] is
+    /// exhausted, rather than because the underlying [`Iterator`] is exhausted.
+    pub fn is_paused(&self) -> bool {
+        !self.finished && self.remaining.is_exhausted()
+    }
+
+    /// Returns the underlying [`Iterator`].
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator<Item: WorkCost>> Iterator for Budgeted<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished || self.remaining.is_exhausted() {
+            return None;
+        }
+
+        let Some(item) = self.iter.next() else {
+            self.finished = true;
+            return None;
+        };
+        self.remaining.max_entries -= 1;
+        self.remaining.max_bytes = self.remaining.max_bytes.saturating_sub(item.work_cost());
+
+        Some(item)
+    }
+}
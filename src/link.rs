@@ -0,0 +1,990 @@
+//! Definitions for linking relocatable ELF files into memory, as kernel module and JIT loaders
+//! do.
+//!
+//! A [`LinkPlan`] places the [`SectionFlags::ALLOC`] sections of an [`ElfType::RELOCATABLE`]
+//! file one after another in a single block of memory, each aligned as its [`SectionHeader`]
+//! requires. The [`SectionIndex::COMMON`] symbols of the file, which no section allocates, are
+//! given zeroed storage after the sections. [`LinkPlan::link()`] copies the sections into memory
+//! provided by the caller, resolves symbols through a [`SymbolResolver`] according to the policy
+//! selected by [`LinkOptions`] and applies the [`SectionType::RELA`] relocations of the sections
+//! for [`Machine::X86_64`] and [`Machine::AARCH64`].
+//!
+//! [`SectionType::RELA`]: crate::section_header::SectionType::RELA
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_len, ClassParse, OffsetOverflow},
+    consts,
+    encoding::EncodingParse,
+    header::{ElfType, Machine},
+    relocation::Rela,
+    section::RelocationSection,
+    section_header::{self, SectionFlags, SectionHeader, SectionHeaderTable},
+    symbol::{SectionIndex, Symbol, SymbolBinding, SymbolTable, SymbolVisibility},
+    writer::ByteLayout,
+    ElfFile,
+};
+
+/// The [`ByteLayout`] of AArch64 instructions, which are little-endian in every encoding.
+const AARCH64_INSTRUCTION: ByteLayout = ByteLayout {
+    big_endian: false,
+    wide: true,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`LinkPlan`] placing the [`SectionFlags::ALLOC`] sections of this
+    /// [`ElfFile`] in memory.
+    ///
+    /// # Errors
+    ///
+    /// - [`LinkError::NotRelocatable`]: Returned if this [`ElfFile`] is not an
+    ///   [`ElfType::RELOCATABLE`] file.
+    /// - [`LinkError::UnsupportedMachine`]: Returned if the relocations of the [`Machine`] of
+    ///   this [`ElfFile`] cannot be applied.
+    /// - [`LinkError::MissingSectionHeaderTable`]: Returned if this [`ElfFile`] has no section
+    ///   header table or it is located out of bounds.
+    /// - [`LinkError::SectionOutOfBounds`]: Returned if the data of a section is located out of
+    ///   bounds.
+    /// - [`LinkError::InvalidAlignment`]: Returned if the alignment of a section is not a power
+    ///   of two.
+    /// - [`LinkError::OffsetOverflow`]: Returned if the size or alignment of a section cannot be
+    ///   represented by a [`usize`] on the host.
+    /// - [`LinkError::InvalidCommonAlignment`]: Returned if the alignment of a common symbol is
+    ///   not a power of two.
+    /// - [`LinkError::Overflow`]: Returned if the size of the placed sections and common symbols
+    ///   overflows.
+    pub fn link_plan(&self) -> Result<LinkPlan<'slice, C, E>, LinkError> {
+        let header = self.header();
+        if header.elf_type() != ElfType::RELOCATABLE {
+            return Err(LinkError::NotRelocatable);
+        }
+        let machine = header.machine();
+        if machine != Machine::X86_64 && machine != Machine::AARCH64 {
+            return Err(LinkError::UnsupportedMachine(machine));
+        }
+        let sections = self
+            .section_header_table()
+            .ok_or(LinkError::MissingSectionHeaderTable)?;
+
+        let mut plan = LinkPlan {
+            file: *self,
+            sections,
+            symbols: self.symbol_table(),
+            sections_size: 0,
+            size: 0,
+            alignment: 1,
+        };
+        for placement in plan.placements() {
+            let placement = placement?;
+            plan.sections_size = placement.offset + placement.size;
+            plan.alignment = plan.alignment.max(placement.alignment);
+        }
+        plan.size = plan.sections_size;
+        for allocation in plan.commons() {
+            let allocation = allocation?;
+            plan.size = allocation.offset + allocation.size;
+            plan.alignment = plan.alignment.max(allocation.alignment);
+        }
+
+        Ok(plan)
+    }
+}
+
+/// The placement of the [`SectionFlags::ALLOC`] sections and common symbols of a relocatable
+/// [`ElfFile`] in a single block of memory.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LinkPlan<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being linked.
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeaderTable`] of the [`ElfFile`].
+    sections: SectionHeaderTable<'slice, C, E>,
+    /// The [`SymbolTable`] defining the common symbols of the [`ElfFile`].
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The number of bytes of memory occupied by the placed sections, after which the common
+    /// symbols are allocated.
+    sections_size: usize,
+    /// The number of bytes of memory occupied by the placed sections and common symbols.
+    size: usize,
+    /// The largest alignment of the placed sections and common symbols.
+    alignment: usize,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> LinkPlan<'slice, C, E> {
+    /// Returns the number of bytes of memory required to link the [`ElfFile`].
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the alignment required of the address at which the [`ElfFile`] is linked.
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Returns an [`Iterator`] over the [`SectionPlacement`]s of the [`SectionFlags::ALLOC`]
+    /// sections, in increasing offset order.
+    pub fn placements(&self) -> SectionPlacements<'slice, C, E> {
+        SectionPlacements {
+            file: self.file,
+            sections: self.sections.into_iter(),
+            index: 0,
+            end: 0,
+            finished: false,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`CommonAllocation`]s of the [`SectionIndex::COMMON`]
+    /// symbols of the [`SectionType::SYMTAB`][st] section, in increasing offset order.
+    ///
+    /// [st]: crate::section_header::SectionType::SYMTAB
+    pub fn commons(&self) -> CommonAllocations<'slice, C, E> {
+        CommonAllocations {
+            symbols: self.symbols,
+            index: 0,
+            end: self.sections_size,
+            finished: false,
+        }
+    }
+
+    /// Returns the address of the common symbol at `symbol_index` of the
+    /// [`SectionType::SYMTAB`][st] section once the [`ElfFile`] is linked at `base`.
+    ///
+    /// Returns `None` if the symbol is not a common symbol.
+    ///
+    /// [st]: crate::section_header::SectionType::SYMTAB
+    pub fn common_address(&self, base: u64, symbol_index: u32) -> Option<u64> {
+        self.commons()
+            .map_while(Result::ok)
+            .find(|allocation| allocation.symbol_index == symbol_index)
+            .map(|allocation| base.wrapping_add(allocation.offset as u64))
+    }
+
+    /// Returns the address of the defined `symbol` once the [`ElfFile`] is linked with the
+    /// section addresses written to `addresses` by [`LinkPlan::link()`].
+    ///
+    /// Returns `None` if `symbol` is undefined, a common symbol, whose address is returned by
+    /// [`LinkPlan::common_address()`], or defined in a section that is not loaded.
+    pub fn symbol_address(
+        &self,
+        symbol: Symbol<'slice, C, E>,
+        addresses: &[Option<u64>],
+    ) -> Option<u64> {
+        let value = symbol.value().into();
+        match symbol.section_index() {
+            SectionIndex::UNDEFINED | SectionIndex::COMMON => None,
+            SectionIndex::ABSOLUTE => Some(value),
+            _ => {
+                let index = usize::try_from(symbol.section_header_index()?).ok()?;
+                let address = (*addresses.get(index)?)?;
+                Some(address.wrapping_add(value))
+            }
+        }
+    }
+
+    /// Links the [`ElfFile`] into `memory`, which will be located at `base`.
+    ///
+    /// The placed sections are copied into `memory` and the address of each section is written
+    /// to the entry of `addresses` at its index, or `None` if it is not loaded. The storage of the
+    /// common symbols is zeroed. The address of each undefined symbol is obtained from
+    /// [`SymbolResolver::resolve()`], and references to the global symbols defined by the
+    /// [`ElfFile`] are offered to [`SymbolResolver::interpose()`]. The relocations applying to the
+    /// placed sections are then applied in place. The contents of `memory` are unspecified if an
+    /// error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`LinkError::MemoryTooSmall`]: Returned if `memory` is smaller than
+    ///   [`LinkPlan::size()`].
+    /// - [`LinkError::AddressesTooSmall`]: Returned if `addresses` has fewer entries than there
+    ///   are sections.
+    /// - [`LinkError::MisalignedBase`]: Returned if `base` is not aligned to
+    ///   [`LinkPlan::alignment()`].
+    /// - [`LinkError::Overflow`]: Returned if the linked sections overflow the address space.
+    /// - [`LinkError::DuplicateSymbol`]: Returned if [`LinkOptions::reject_duplicates`] is set
+    ///   and a global symbol defined by the [`ElfFile`] is defined again by the [`ElfFile`] or
+    ///   known to `resolver`.
+    /// - [`LinkError::UnsupportedRelocationSection`]: Returned if the relocations applying to a
+    ///   placed section have implicit addends.
+    /// - [`LinkError::MissingSymbolTable`]: Returned if a relocation section does not link to a
+    ///   symbol table located in bounds.
+    /// - [`LinkError::InvalidSymbolIndex`]: Returned if a relocation refers to a symbol that does
+    ///   not exist.
+    /// - [`LinkError::UndefinedSymbol`]: Returned if a relocation refers to an undefined symbol
+    ///   that `resolver` does not know, unless the symbol is weak and
+    ///   [`LinkOptions::weak_undefined`] is [`WeakUndefined::Zero`].
+    /// - [`LinkError::UnloadedSymbol`]: Returned if a relocation refers to a symbol defined in a
+    ///   section that is not loaded.
+    /// - [`LinkError::UnsupportedRelocation`]: Returned if the type of a relocation is not
+    ///   supported.
+    /// - [`LinkError::RelocationOutOfBounds`]: Returned if a relocation targets bytes outside of
+    ///   its section.
+    /// - [`LinkError::RelocationOverflow`]: Returned if the value of a relocation cannot be
+    ///   encoded in its location.
+    pub fn link(
+        &self,
+        base: u64,
+        memory: &mut [u8],
+        addresses: &mut [Option<u64>],
+        options: LinkOptions,
+        mut resolver: impl SymbolResolver<'slice>,
+    ) -> Result<(), LinkError> {
+        let memory = memory
+            .get_mut(..self.size)
+            .ok_or(LinkError::MemoryTooSmall {
+                required: self.size,
+            })?;
+        let count = usize::from(self.sections.count());
+        let addresses = addresses
+            .get_mut(..count)
+            .ok_or(LinkError::AddressesTooSmall { required: count })?;
+        if !base.is_multiple_of(self.alignment as u64) {
+            return Err(LinkError::MisalignedBase);
+        }
+        base.checked_add(self.size as u64)
+            .ok_or(LinkError::Overflow)?;
+
+        if options.reject_duplicates {
+            self.check_duplicates(&mut resolver)?;
+        }
+
+        memory.fill(0);
+        addresses.fill(None);
+        for placement in self.placements() {
+            let placement = placement?;
+            let data = self
+                .sections
+                .get(placement.index)
+                .and_then(|section_header| self.file.section_data(section_header))
+                .ok_or(LinkError::SectionOutOfBounds(placement.index))?;
+            memory[placement.offset..placement.offset + data.len()].copy_from_slice(data);
+            addresses[usize::from(placement.index)] = Some(base + placement.offset as u64);
+        }
+
+        let mut linker = Linker {
+            plan: self,
+            base,
+            memory,
+            addresses,
+            options,
+            resolver,
+        };
+        for placement in self.placements() {
+            let placement = placement?;
+            for relocations in self.file.relocations_for_section(placement.index) {
+                let RelocationSection::Rela(relocations) = relocations else {
+                    return Err(LinkError::UnsupportedRelocationSection(placement.index));
+                };
+                let symbols = u16::try_from(relocations.section_header().link())
+                    .ok()
+                    .and_then(|index| self.sections.get(index))
+                    .and_then(|section_header| self.file.symbol_table_from_section(section_header))
+                    .ok_or(LinkError::MissingSymbolTable)?;
+                let table = relocations
+                    .table()
+                    .ok_or(LinkError::SectionOutOfBounds(placement.index))?;
+
+                for relocation in table {
+                    linker.apply(placement, symbols, relocation)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error if a global symbol defined by the [`ElfFile`] is defined again by the
+    /// [`ElfFile`] or known to `resolver`.
+    ///
+    /// This compares every pair of definitions, as no memory is available to sort them.
+    fn check_duplicates(
+        &self,
+        resolver: &mut impl SymbolResolver<'slice>,
+    ) -> Result<(), LinkError> {
+        let Some(symbols) = self.symbols else {
+            return Ok(());
+        };
+        let strong_name = |symbol: Symbol<'slice, C, E>| {
+            let defined = symbol.section_index() != SectionIndex::UNDEFINED
+                && symbol.section_index() != SectionIndex::COMMON;
+            (defined && symbol.binding() == SymbolBinding::GLOBAL)
+                .then(|| symbol.name())
+                .flatten()
+        };
+
+        for (index, symbol) in symbols.into_iter().enumerate() {
+            let Some(name) = strong_name(symbol) else {
+                continue;
+            };
+            let duplicate = resolver.resolve(name).is_some()
+                || symbols
+                    .into_iter()
+                    .skip(index + 1)
+                    .any(|other| strong_name(other) == Some(name));
+            if duplicate {
+                return Err(LinkError::DuplicateSymbol(index as u32));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A resolver of the symbols referenced by a relocatable [`ElfFile`] being linked.
+///
+/// This is implemented for closures, which resolve undefined symbols and never interpose.
+pub trait SymbolResolver<'slice> {
+    /// Returns the address of the symbol named `name`, or `None` if this [`SymbolResolver`]
+    /// does not know it.
+    fn resolve(&mut self, name: &'slice [u8]) -> Option<u64>;
+
+    /// Returns the address that references to the global symbol named `name`, which the
+    /// [`ElfFile`] defines at `address`, should use instead, or `None` to use the definition of
+    /// the [`ElfFile`].
+    fn interpose(&mut self, name: &'slice [u8], address: u64) -> Option<u64> {
+        let _ = (name, address);
+        None
+    }
+}
+
+impl<'slice, F: FnMut(&'slice [u8]) -> Option<u64>> SymbolResolver<'slice> for F {
+    fn resolve(&mut self, name: &'slice [u8]) -> Option<u64> {
+        self(name)
+    }
+}
+
+/// Options selecting the symbol resolution policy of [`LinkPlan::link()`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LinkOptions {
+    /// How references to undefined weak symbols that the [`SymbolResolver`] does not know are
+    /// resolved.
+    pub weak_undefined: WeakUndefined,
+    /// Whether to reject global symbols defined more than once, either by the [`ElfFile`] or by
+    /// the [`ElfFile`] and the [`SymbolResolver`].
+    pub reject_duplicates: bool,
+}
+
+/// The resolution of references to undefined weak symbols that the [`SymbolResolver`] does not
+/// know.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WeakUndefined {
+    /// The symbol resolves to zero, as static linkers do.
+    #[default]
+    Zero,
+    /// The reference is rejected with [`LinkError::UndefinedSymbol`].
+    Error,
+}
+
+/// The state of a [`LinkPlan::link()`] in progress.
+struct Linker<'plan, 'slice, 'memory, C: ClassParse, E: EncodingParse, R> {
+    /// The [`LinkPlan`] being linked.
+    plan: &'plan LinkPlan<'slice, C, E>,
+    /// The address at which the memory will be located.
+    base: u64,
+    /// The memory receiving the placed sections.
+    memory: &'memory mut [u8],
+    /// The address of each section, or `None` if it is not loaded.
+    addresses: &'memory [Option<u64>],
+    /// The [`LinkOptions`] selecting the symbol resolution policy.
+    options: LinkOptions,
+    /// The [`SymbolResolver`] resolving the symbols referenced by the relocations.
+    resolver: R,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse, R: SymbolResolver<'slice>>
+    Linker<'_, 'slice, '_, C, E, R>
+{
+    /// Applies `relocation`, which refers to `symbols`, to the section described by
+    /// `placement`.
+    fn apply(
+        &mut self,
+        placement: SectionPlacement,
+        symbols: SymbolTable<'slice, C, E>,
+        relocation: Rela<C>,
+    ) -> Result<(), LinkError> {
+        let file = self.plan.file;
+        let relocation_type = file.class.relocation_type_raw(relocation.info);
+        let offset = to_len(relocation.offset)?;
+        let address = (self.base + placement.offset as u64).wrapping_add(offset as u64);
+        let (symbol, size) =
+            self.resolve_symbol(symbols, file.class.symbol_raw(relocation.info))?;
+        let value = Value {
+            symbol,
+            size,
+            addend: relocation.addend.into(),
+            place: address,
+        };
+
+        let patch = match file.header().machine() {
+            Machine::X86_64 => x86_64_patch(relocation_type, value),
+            _ => aarch64_patch(relocation_type, value),
+        }
+        .ok_or(LinkError::UnsupportedRelocation(relocation_type))?;
+        let overflow = LinkError::RelocationOverflow {
+            relocation_type,
+            address,
+        };
+        let width = match patch {
+            Patch::None => return Ok(()),
+            Patch::Data { size, value, fits } => {
+                if !fits(value) {
+                    return Err(overflow);
+                }
+                size
+            }
+            Patch::Instruction { fits, .. } => {
+                if !fits {
+                    return Err(overflow);
+                }
+                4
+            }
+        };
+        if offset
+            .checked_add(width)
+            .is_none_or(|end| end > placement.size)
+        {
+            return Err(LinkError::RelocationOutOfBounds {
+                relocation_type,
+                address,
+            });
+        }
+
+        let start = placement.offset + offset;
+        match patch {
+            Patch::Data { size, value, .. } => {
+                ByteLayout::new(file.header().ident()).write(self.memory, start, size, value);
+            }
+            Patch::Instruction { mask, bits, .. } => {
+                let instruction = AARCH64_INSTRUCTION.read_u32(self.memory, start);
+                let instruction = (instruction & !mask) | (bits & mask);
+                AARCH64_INSTRUCTION.write_u32(self.memory, start, instruction);
+            }
+            Patch::None => {}
+        }
+
+        Ok(())
+    }
+
+    /// Returns the address and size of the symbol at `index` of `symbols`.
+    fn resolve_symbol(
+        &mut self,
+        symbols: SymbolTable<'slice, C, E>,
+        index: u32,
+    ) -> Result<(u64, u64), LinkError> {
+        if index == 0 {
+            return Ok((0, 0));
+        }
+
+        let symbol = usize::try_from(index)
+            .ok()
+            .and_then(|index| symbols.get(index))
+            .ok_or(LinkError::InvalidSymbolIndex(index))?;
+        let address = match symbol.section_index() {
+            SectionIndex::UNDEFINED => {
+                let resolved = symbol.name().and_then(|name| self.resolver.resolve(name));
+                match resolved {
+                    Some(address) => return Ok((address, symbol.size().into())),
+                    None if symbol.binding() == SymbolBinding::WEAK
+                        && self.options.weak_undefined == WeakUndefined::Zero =>
+                    {
+                        return Ok((0, symbol.size().into()))
+                    }
+                    None => return Err(LinkError::UndefinedSymbol(index)),
+                }
+            }
+            SectionIndex::COMMON => self.plan.common_address(self.base, index),
+            _ => self.plan.symbol_address(symbol, self.addresses),
+        }
+        .ok_or(LinkError::UnloadedSymbol(index))?;
+
+        let interposable = symbol.binding() != SymbolBinding::LOCAL
+            && symbol.visibility() == SymbolVisibility::DEFAULT;
+        let interposed = symbol
+            .name()
+            .filter(|_| interposable)
+            .and_then(|name| self.resolver.interpose(name, address));
+
+        Ok((interposed.unwrap_or(address), symbol.size().into()))
+    }
+}
+
+/// The placement of a [`SectionFlags::ALLOC`] section within the memory of a [`LinkPlan`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionPlacement {
+    /// The index of the section.
+    pub index: u16,
+    /// The offset of the section from the start of the memory.
+    pub offset: usize,
+    /// The size of the section in memory.
+    pub size: usize,
+    /// The alignment of the section.
+    pub alignment: usize,
+    /// The [`SectionFlags`] of the section, describing how its memory should be protected.
+    pub flags: SectionFlags,
+}
+
+/// An [`Iterator`] over the [`SectionPlacement`]s of a [`LinkPlan`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SectionPlacements<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being linked.
+    file: ElfFile<'slice, C, E>,
+    /// The remaining [`SectionHeader`]s.
+    sections: section_header::IntoIter<'slice, C, E>,
+    /// The index of the next [`SectionHeader`].
+    index: u16,
+    /// The offset one past the last byte of the sections placed so far.
+    end: usize,
+    /// Whether an error has been yielded.
+    finished: bool,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SectionPlacements<'slice, C, E> {
+    /// Returns the [`SectionPlacement`] of the section described by `section_header`, located
+    /// at `index`, after the sections placed so far.
+    fn place(
+        &mut self,
+        index: u16,
+        section_header: SectionHeader<'slice, C, E>,
+    ) -> Result<SectionPlacement, LinkError> {
+        let alignment = to_len(section_header.alignment())?.max(1);
+        if !alignment.is_power_of_two() {
+            return Err(LinkError::InvalidAlignment(index));
+        }
+        let size = to_len(section_header.size())?;
+        if self.file.section_data(section_header).is_none() {
+            return Err(LinkError::SectionOutOfBounds(index));
+        }
+
+        let offset = self
+            .end
+            .checked_next_multiple_of(alignment)
+            .ok_or(LinkError::Overflow)?;
+        self.end = offset.checked_add(size).ok_or(LinkError::Overflow)?;
+        Ok(SectionPlacement {
+            index,
+            offset,
+            size,
+            alignment,
+            flags: section_header.flags(),
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for SectionPlacements<'_, C, E> {
+    type Item = Result<SectionPlacement, LinkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let section_header = self.sections.next()?;
+            let index = self.index;
+            self.index = self.index.wrapping_add(1);
+            if !section_header.flags().contains(SectionFlags::ALLOC) {
+                continue;
+            }
+
+            let placement = self.place(index, section_header);
+            self.finished = placement.is_err();
+            return Some(placement);
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for SectionPlacements<'_, C, E> {}
+
+/// The storage allocated to a [`SectionIndex::COMMON`] symbol within the memory of a
+/// [`LinkPlan`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CommonAllocation {
+    /// The index of the symbol.
+    pub symbol_index: u32,
+    /// The offset of the storage from the start of the memory.
+    pub offset: usize,
+    /// The size of the storage.
+    pub size: usize,
+    /// The alignment of the storage.
+    pub alignment: usize,
+}
+
+/// An [`Iterator`] over the [`CommonAllocation`]s of a [`LinkPlan`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct CommonAllocations<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`SymbolTable`] defining the common symbols.
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The index of the next [`Symbol`].
+    index: u32,
+    /// The offset one past the last byte of the storage allocated so far.
+    end: usize,
+    /// Whether an error has been yielded.
+    finished: bool,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> CommonAllocations<'slice, C, E> {
+    /// Returns the [`CommonAllocation`] of `symbol`, located at `index`, after the storage
+    /// allocated so far.
+    ///
+    /// The value of a common symbol holds its alignment.
+    fn allocate(
+        &mut self,
+        index: u32,
+        symbol: Symbol<'slice, C, E>,
+    ) -> Result<CommonAllocation, LinkError> {
+        let alignment = to_len(symbol.value())?.max(1);
+        if !alignment.is_power_of_two() {
+            return Err(LinkError::InvalidCommonAlignment(index));
+        }
+        let size = to_len(symbol.size())?;
+
+        let offset = self
+            .end
+            .checked_next_multiple_of(alignment)
+            .ok_or(LinkError::Overflow)?;
+        self.end = offset.checked_add(size).ok_or(LinkError::Overflow)?;
+        Ok(CommonAllocation {
+            symbol_index: index,
+            offset,
+            size,
+            alignment,
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for CommonAllocations<'_, C, E> {
+    type Item = Result<CommonAllocation, LinkError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            let index = self.index;
+            let symbol = self.symbols?.get(usize::try_from(index).ok()?)?;
+            self.index = self.index.wrapping_add(1);
+            if symbol.section_index() != SectionIndex::COMMON {
+                continue;
+            }
+
+            let allocation = self.allocate(index, symbol);
+            self.finished = allocation.is_err();
+            return Some(allocation);
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for CommonAllocations<'_, C, E> {}
+
+/// The inputs to the computation of the value of a relocation.
+#[derive(Clone, Copy, Debug)]
+struct Value {
+    /// The address of the symbol.
+    symbol: u64,
+    /// The size of the symbol.
+    size: u64,
+    /// The addend of the relocation.
+    addend: i64,
+    /// The address of the location being relocated.
+    place: u64,
+}
+
+impl Value {
+    /// Returns the address of the symbol plus the addend.
+    fn absolute(self) -> u64 {
+        self.symbol.wrapping_add(self.addend as u64)
+    }
+
+    /// Returns the address of the symbol plus the addend, relative to the location.
+    fn relative(self) -> u64 {
+        self.absolute().wrapping_sub(self.place)
+    }
+}
+
+/// The modification a relocation makes to its location.
+#[derive(Clone, Copy, Debug)]
+enum Patch {
+    /// The location is left unchanged.
+    None,
+    /// The location is replaced by `value`, stored in `size` bytes, if it passes `fits`.
+    Data {
+        /// The number of bytes of the location.
+        size: usize,
+        /// The value stored in the location.
+        value: u64,
+        /// Returns `true` if the value can be stored in the location.
+        fits: fn(u64) -> bool,
+    },
+    /// The bits of the AArch64 instruction at the location selected by `mask` are replaced by
+    /// those of `bits`.
+    Instruction {
+        /// The bits of the instruction that are replaced.
+        mask: u32,
+        /// The replacement bits.
+        bits: u32,
+        /// Whether the value can be encoded in the instruction.
+        fits: bool,
+    },
+}
+
+/// Returns the [`Patch`] applying the `x86_64` relocation of `relocation_type` computed from
+/// `value`, or `None` if the relocation type is not supported.
+fn x86_64_patch(relocation_type: u32, value: Value) -> Option<Patch> {
+    let (size, result, fits): (usize, u64, fn(u64) -> bool) = match relocation_type {
+        consts::R_X86_64_NONE => return Some(Patch::None),
+        consts::R_X86_64_64 => (8, value.absolute(), |_| true),
+        consts::R_X86_64_PC64 => (8, value.relative(), |_| true),
+        consts::R_X86_64_32 => (4, value.absolute(), |value| fits_unsigned(value, 32)),
+        consts::R_X86_64_32S => (4, value.absolute(), |value| fits_signed(value, 32)),
+        consts::R_X86_64_PC32 | consts::R_X86_64_PLT32 => {
+            (4, value.relative(), |value| fits_signed(value, 32))
+        }
+        consts::R_X86_64_16 => (2, value.absolute(), |value| fits_bitfield(value, 16)),
+        consts::R_X86_64_PC16 => (2, value.relative(), |value| fits_signed(value, 16)),
+        consts::R_X86_64_8 => (1, value.absolute(), |value| fits_bitfield(value, 8)),
+        consts::R_X86_64_PC8 => (1, value.relative(), |value| fits_signed(value, 8)),
+        consts::R_X86_64_SIZE64 => (8, value.size.wrapping_add(value.addend as u64), |_| true),
+        consts::R_X86_64_SIZE32 => (4, value.size.wrapping_add(value.addend as u64), |value| {
+            fits_unsigned(value, 32)
+        }),
+        _ => return None,
+    };
+
+    Some(Patch::Data {
+        size,
+        value: result,
+        fits,
+    })
+}
+
+/// Returns the [`Patch`] applying the AArch64 relocation of `relocation_type` computed from
+/// `value`, or `None` if the relocation type is not supported.
+fn aarch64_patch(relocation_type: u32, value: Value) -> Option<Patch> {
+    let absolute = value.absolute();
+    let relative = value.relative();
+    let data = |size, value, fits| Some(Patch::Data { size, value, fits });
+
+    let patch = match relocation_type {
+        consts::R_AARCH64_NONE => Patch::None,
+        consts::R_AARCH64_ABS64 => return data(8, absolute, |_| true),
+        consts::R_AARCH64_PREL64 => return data(8, relative, |_| true),
+        consts::R_AARCH64_ABS32 => return data(4, absolute, |value| fits_bitfield(value, 32)),
+        consts::R_AARCH64_PREL32 => return data(4, relative, |value| fits_bitfield(value, 32)),
+        consts::R_AARCH64_ABS16 => return data(2, absolute, |value| fits_bitfield(value, 16)),
+        consts::R_AARCH64_PREL16 => return data(2, relative, |value| fits_bitfield(value, 16)),
+        consts::R_AARCH64_CALL26 | consts::R_AARCH64_JUMP26 => branch(relative, 26, 0),
+        consts::R_AARCH64_CONDBR19 | consts::R_AARCH64_LD_PREL_LO19 => branch(relative, 19, 5),
+        consts::R_AARCH64_TSTBR14 => branch(relative, 14, 5),
+        consts::R_AARCH64_ADR_PREL_LO21 => adr(relative, fits_signed(relative, 21)),
+        consts::R_AARCH64_ADR_PREL_PG_HI21 | consts::R_AARCH64_ADR_PREL_PG_HI21_NC => {
+            let pages = (absolute & !0xFFF).wrapping_sub(value.place & !0xFFF);
+            let fits =
+                relocation_type == consts::R_AARCH64_ADR_PREL_PG_HI21_NC || fits_signed(pages, 33);
+            adr(((pages as i64) >> 12) as u64, fits)
+        }
+        consts::R_AARCH64_ADD_ABS_LO12_NC | consts::R_AARCH64_LDST8_ABS_LO12_NC => {
+            low_12(absolute, 0)
+        }
+        consts::R_AARCH64_LDST16_ABS_LO12_NC => low_12(absolute, 1),
+        consts::R_AARCH64_LDST32_ABS_LO12_NC => low_12(absolute, 2),
+        consts::R_AARCH64_LDST64_ABS_LO12_NC => low_12(absolute, 3),
+        consts::R_AARCH64_LDST128_ABS_LO12_NC => low_12(absolute, 4),
+        consts::R_AARCH64_MOVW_UABS_G0 => movw(absolute, 0, true),
+        consts::R_AARCH64_MOVW_UABS_G0_NC => movw(absolute, 0, false),
+        consts::R_AARCH64_MOVW_UABS_G1 => movw(absolute, 1, true),
+        consts::R_AARCH64_MOVW_UABS_G1_NC => movw(absolute, 1, false),
+        consts::R_AARCH64_MOVW_UABS_G2 => movw(absolute, 2, true),
+        consts::R_AARCH64_MOVW_UABS_G2_NC => movw(absolute, 2, false),
+        consts::R_AARCH64_MOVW_UABS_G3 => movw(absolute, 3, false),
+        _ => return None,
+    };
+
+    Some(patch)
+}
+
+/// Returns the [`Patch`] storing the word offset `relative` in the `bits` wide immediate field
+/// of a branch instruction starting at bit `shift`.
+fn branch(relative: u64, bits: u32, shift: u32) -> Patch {
+    let mask = ((1 << bits) - 1) << shift;
+    Patch::Instruction {
+        mask,
+        bits: ((relative >> 2) as u32) << shift,
+        fits: relative.is_multiple_of(4) && fits_signed(relative, bits + 2),
+    }
+}
+
+/// Returns the [`Patch`] storing `immediate` in the split 21-bit immediate field of an `ADR`
+/// or `ADRP` instruction.
+fn adr(immediate: u64, fits: bool) -> Patch {
+    let immediate = immediate as u32;
+    Patch::Instruction {
+        mask: (0x3 << 29) | (0x7FFFF << 5),
+        bits: ((immediate & 0x3) << 29) | ((immediate >> 2) << 5),
+        fits,
+    }
+}
+
+/// Returns the [`Patch`] storing the low 12 bits of `absolute`, scaled down by `scale` bits, in
+/// the immediate field of an `ADD`, load or store instruction.
+fn low_12(absolute: u64, scale: u32) -> Patch {
+    let low = (absolute & 0xFFF) as u32;
+    Patch::Instruction {
+        mask: 0xFFF << 10,
+        bits: (low >> scale) << 10,
+        fits: low.is_multiple_of(1 << scale),
+    }
+}
+
+/// Returns the [`Patch`] storing the 16-bit group `group` of `absolute` in the immediate field
+/// of a `MOVZ` or `MOVK` instruction, checking that no higher bits are set if `check` is `true`.
+fn movw(absolute: u64, group: u32, check: bool) -> Patch {
+    Patch::Instruction {
+        mask: 0xFFFF << 5,
+        bits: (((absolute >> (16 * group)) & 0xFFFF) as u32) << 5,
+        fits: !check || fits_unsigned(absolute, 16 * (group + 1)),
+    }
+}
+
+/// Returns `true` if `value` is a sign-extended `bits` wide value.
+fn fits_signed(value: u64, bits: u32) -> bool {
+    let limit = 1i64 << (bits - 1);
+    (-limit..limit).contains(&(value as i64))
+}
+
+/// Returns `true` if `value` is a zero-extended `bits` wide value.
+fn fits_unsigned(value: u64, bits: u32) -> bool {
+    value >> bits == 0
+}
+
+/// Returns `true` if `value` is a sign-extended or zero-extended `bits` wide value.
+fn fits_bitfield(value: u64, bits: u32) -> bool {
+    fits_signed(value, bits) || fits_unsigned(value, bits)
+}
+
+/// Various errors that can occur while linking a relocatable [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum LinkError {
+    /// The [`ElfFile`] is not an [`ElfType::RELOCATABLE`] file.
+    NotRelocatable,
+    /// The relocations of the [`Machine`] cannot be applied.
+    UnsupportedMachine(Machine),
+    /// The [`ElfFile`] has no section header table located in bounds.
+    MissingSectionHeaderTable,
+    /// The data of the section at the given index is located out of bounds.
+    SectionOutOfBounds(u16),
+    /// The alignment of the section at the given index is not a power of two.
+    InvalidAlignment(u16),
+    /// The alignment of the common symbol at the given index is not a power of two.
+    InvalidCommonAlignment(u32),
+    /// A class sized value cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
+    /// The placed sections and common symbols overflow the address space.
+    Overflow,
+    /// The memory is too small to hold the placed sections.
+    MemoryTooSmall {
+        /// The number of bytes required to hold the placed sections.
+        required: usize,
+    },
+    /// The buffer receiving the section addresses is too small.
+    AddressesTooSmall {
+        /// The number of entries required to hold the section addresses.
+        required: usize,
+    },
+    /// The base address is not aligned to the alignment of the [`LinkPlan`].
+    MisalignedBase,
+    /// The relocations applying to the section at the given index have implicit addends.
+    UnsupportedRelocationSection(u16),
+    /// A relocation section does not link to a symbol table located in bounds.
+    MissingSymbolTable,
+    /// A relocation refers to a symbol that does not exist.
+    InvalidSymbolIndex(u32),
+    /// The global symbol at the given index is defined more than once.
+    DuplicateSymbol(u32),
+    /// The symbol at the given index is undefined and could not be resolved.
+    UndefinedSymbol(u32),
+    /// The symbol at the given index is defined in a section that is not loaded.
+    UnloadedSymbol(u32),
+    /// The relocation type is not supported.
+    UnsupportedRelocation(u32),
+    /// A relocation targets bytes outside of its section.
+    RelocationOutOfBounds {
+        /// The type of the relocation.
+        relocation_type: u32,
+        /// The address of the location being relocated.
+        address: u64,
+    },
+    /// The value of a relocation cannot be encoded in its location.
+    RelocationOverflow {
+        /// The type of the relocation.
+        relocation_type: u32,
+        /// The address of the location being relocated.
+        address: u64,
+    },
+}
+
+impl From<OffsetOverflow> for LinkError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl fmt::Display for LinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRelocatable => write!(f, "ELF file is not relocatable"),
+            Self::UnsupportedMachine(machine) => {
+                write!(f, "relocations of {machine:?} are not supported")
+            }
+            Self::MissingSectionHeaderTable => write!(f, "section header table missing"),
+            Self::SectionOutOfBounds(index) => {
+                write!(f, "section {index} is located out of bounds")
+            }
+            Self::InvalidAlignment(index) => {
+                write!(f, "alignment of section {index} is not a power of two")
+            }
+            Self::InvalidCommonAlignment(index) => {
+                write!(
+                    f,
+                    "alignment of common symbol {index} is not a power of two"
+                )
+            }
+            Self::OffsetOverflow(error) => write!(f, "{error}"),
+            Self::Overflow => write!(f, "placed sections overflow the address space"),
+            Self::MemoryTooSmall { required } => {
+                write!(f, "memory too small to hold {required} bytes")
+            }
+            Self::AddressesTooSmall { required } => {
+                write!(f, "section addresses too small to hold {required} entries")
+            }
+            Self::MisalignedBase => write!(f, "base address is misaligned"),
+            Self::UnsupportedRelocationSection(index) => {
+                write!(f, "relocations of section {index} have implicit addends")
+            }
+            Self::MissingSymbolTable => write!(f, "relocation section has no symbol table"),
+            Self::InvalidSymbolIndex(index) => write!(f, "symbol {index} does not exist"),
+            Self::DuplicateSymbol(index) => write!(f, "symbol {index} is defined more than once"),
+            Self::UndefinedSymbol(index) => write!(f, "symbol {index} is undefined"),
+            Self::UnloadedSymbol(index) => {
+                write!(
+                    f,
+                    "symbol {index} is defined in a section that is not loaded"
+                )
+            }
+            Self::UnsupportedRelocation(relocation_type) => {
+                write!(f, "relocation type {relocation_type} is not supported")
+            }
+            Self::RelocationOutOfBounds {
+                relocation_type,
+                address,
+            } => write!(
+                f,
+                "relocation of type {relocation_type} at {address:#x} is out of bounds"
+            ),
+            Self::RelocationOverflow {
+                relocation_type,
+                address,
+            } => write!(
+                f,
+                "relocation of type {relocation_type} at {address:#x} overflows"
+            ),
+        }
+    }
+}
+
+impl error::Error for LinkError {}
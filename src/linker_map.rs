@@ -0,0 +1,513 @@
+//! Definitions for checking the map file written by a linker against the ELF file it linked.
+//!
+//! Both the map files written by GNU ld with `-Map` and by LLD with `--Map` are understood. A
+//! [`LinkerMap`] yields the output sections, input sections and symbols it lists as
+//! [`MapEntry`]s, and [`ElfFile::map_discrepancies()`] reports where the output sections and
+//! symbols of the map disagree with the sections and symbols of the [`ElfFile`], which catches
+//! linker scripts that place sections other than where they were meant to go.
+//!
+//! GNU ld lists every output section statement of the linker script, so output sections of zero
+//! size, which the linker discards, are not expected to be present in the [`ElfFile`]. The
+//! expressions of assignments are not evaluated; the symbol they assign is compared at the
+//! address the map lists for it.
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    section_header::{self, SectionFlags},
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+/// The line GNU ld writes before the output sections of its map files.
+const GNU_MEMORY_MAP: &[u8] = b"Linker script and memory map";
+
+/// The number of columns each level of the `Out`, `In` and `Symbol` hierarchy of an LLD map
+/// file is indented by.
+const LLD_INDENT: usize = 8;
+
+/// A linker map file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LinkerMap<'slice> {
+    /// The lines listing the entries of the map.
+    lines: &'slice [u8],
+    /// The [`MapFormat`] of the map.
+    format: MapFormat,
+    /// The number of numeric columns preceding the names of an LLD map.
+    columns: usize,
+    /// The column at which the names of an LLD map start.
+    name_column: usize,
+}
+
+impl<'slice> LinkerMap<'slice> {
+    /// Creates a new [`LinkerMap`] from the text of a map file written by GNU ld or LLD.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseLinkerMapError::UnknownFormat`]: Returned if `slice` is not a map file written
+    ///   by GNU ld or LLD.
+    pub fn new(slice: &'slice [u8]) -> Result<Self, ParseLinkerMapError> {
+        let mut lines = Lines(slice);
+        let header = lines.find(|line| !line.trim_ascii().is_empty());
+        let out = header.and_then(|header| {
+            tokens_with_columns(header)
+                .enumerate()
+                .find(|(_, (_, token))| *token == b"Out")
+        });
+        if let Some((columns, (name_column, _))) = out {
+            return Ok(Self {
+                lines: lines.0,
+                format: MapFormat::Lld,
+                columns,
+                name_column,
+            });
+        }
+
+        let mut lines = Lines(slice);
+        if lines.any(|line| line.trim_ascii() == GNU_MEMORY_MAP) {
+            return Ok(Self {
+                lines: lines.0,
+                format: MapFormat::Gnu,
+                columns: 0,
+                name_column: 0,
+            });
+        }
+
+        Err(ParseLinkerMapError::UnknownFormat)
+    }
+
+    /// Returns the [`MapFormat`] of this [`LinkerMap`].
+    pub fn format(&self) -> MapFormat {
+        self.format
+    }
+
+    /// Returns a [`MapEntries`] over the [`MapEntry`]s listed by this [`LinkerMap`], in the
+    /// order they are listed.
+    pub fn entries(&self) -> MapEntries<'slice> {
+        MapEntries {
+            map: *self,
+            lines: Lines(self.lines),
+            pending: None,
+        }
+    }
+
+    /// Returns the [`MapEntry`] of the output section named `name`.
+    pub fn output_section(&self, name: &[u8]) -> Option<MapEntry<'slice>> {
+        self.entries()
+            .find(|entry| entry.kind == MapEntryKind::OutputSection && entry.name == name)
+    }
+}
+
+/// The linker that wrote a [`LinkerMap`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MapFormat {
+    /// The map was written by GNU ld.
+    Gnu,
+    /// The map was written by LLD.
+    Lld,
+}
+
+/// The kind of a [`MapEntry`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MapEntryKind {
+    /// An output section of the linked file.
+    OutputSection,
+    /// An input section placed in the preceding output section.
+    InputSection,
+    /// A symbol defined by the preceding input section, or assigned by the linker script.
+    Symbol,
+}
+
+/// An entry of a [`LinkerMap`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MapEntry<'slice> {
+    /// The [`MapEntryKind`] of this [`MapEntry`].
+    pub kind: MapEntryKind,
+    /// The name of the section or symbol.
+    pub name: &'slice [u8],
+    /// The virtual address of the section or symbol.
+    pub address: u64,
+    /// The size of the section or symbol, if the map lists one.
+    pub size: Option<u64>,
+    /// The file an input section was read from.
+    pub file: Option<&'slice [u8]>,
+}
+
+/// An [`Iterator`] over the [`MapEntry`]s of a [`LinkerMap`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MapEntries<'slice> {
+    /// The [`LinkerMap`] being parsed.
+    map: LinkerMap<'slice>,
+    /// The lines that have not yet been visited.
+    lines: Lines<'slice>,
+    /// The kind and name of a section whose address GNU ld wrapped onto the next line.
+    pending: Option<(MapEntryKind, &'slice [u8])>,
+}
+
+impl<'slice> MapEntries<'slice> {
+    /// Parses a line of a map written by GNU ld.
+    fn gnu_entry(&mut self, line: &'slice [u8]) -> Option<MapEntry<'slice>> {
+        let pending = self.pending.take();
+        let mut tokens = tokens_with_columns(line);
+        let (column, first) = tokens.next()?;
+        let mut tokens = tokens.map(|(_, token)| token);
+
+        let kind = match line.iter().take_while(|byte| **byte == b' ').count() {
+            0 => MapEntryKind::OutputSection,
+            1 if first[0] != b'*' => MapEntryKind::InputSection,
+            _ => {
+                let address = parse_hex(first)?;
+                if let Some((kind, name)) = pending {
+                    let size = tokens.next().and_then(parse_hex)?;
+                    return Some(MapEntry {
+                        kind,
+                        name,
+                        address,
+                        size: Some(size),
+                        file: tokens.next(),
+                    });
+                }
+
+                let name = assigned_name(&line[column + first.len()..])?;
+                return Some(MapEntry {
+                    kind: MapEntryKind::Symbol,
+                    name,
+                    address,
+                    size: None,
+                    file: None,
+                });
+            }
+        };
+
+        let Some(address) = tokens.next() else {
+            self.pending = Some((kind, first));
+            return None;
+        };
+        let size = tokens.next().and_then(parse_hex)?;
+        Some(MapEntry {
+            kind,
+            name: first,
+            address: parse_hex(address)?,
+            size: Some(size),
+            file: tokens.next(),
+        })
+    }
+
+    /// Parses a line of a map written by LLD.
+    fn lld_entry(&self, line: &'slice [u8]) -> Option<MapEntry<'slice>> {
+        let mut numbers = tokens_with_columns(line.get(..self.map.name_column)?);
+        let (_, address) = numbers.next()?;
+        let (_, size) = numbers.nth(self.map.columns.checked_sub(3)?)?;
+        let address = parse_hex(address)?;
+        let size = parse_hex(size)?;
+
+        let name = &line[self.map.name_column..];
+        let depth = name.iter().take_while(|byte| **byte == b' ').count();
+        let name = name.trim_ascii();
+        let (kind, name, file) = match depth {
+            0 => (MapEntryKind::OutputSection, name, None),
+            LLD_INDENT => {
+                let (file, section) = name
+                    .strip_suffix(b")")
+                    .and_then(|name| split_last(name, b":("))?;
+                (MapEntryKind::InputSection, section, Some(file))
+            }
+            _ => (MapEntryKind::Symbol, assigned_name(name)?, None),
+        };
+
+        Some(MapEntry {
+            kind,
+            name,
+            address,
+            size: Some(size),
+            file,
+        })
+    }
+}
+
+impl<'slice> Iterator for MapEntries<'slice> {
+    type Item = MapEntry<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.lines.next()?;
+            if line.trim_ascii().is_empty() {
+                continue;
+            }
+
+            let entry = match self.map.format {
+                MapFormat::Gnu => self.gnu_entry(line),
+                MapFormat::Lld => self.lld_entry(line),
+            };
+            if entry.is_some() {
+                return entry;
+            }
+        }
+    }
+}
+
+impl FusedIterator for MapEntries<'_> {}
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`MapDiscrepancies`] over the places where the output sections and symbols
+    /// listed by `map` disagree with the sections and symbols of this [`ElfFile`].
+    ///
+    /// The symbols are read from the table returned by [`ElfFile::symbols()`].
+    pub fn map_discrepancies<'map>(
+        &self,
+        map: LinkerMap<'map>,
+    ) -> MapDiscrepancies<'slice, 'map, C, E> {
+        MapDiscrepancies {
+            file: *self,
+            symbols: self.symbols().map(|(_, table)| table),
+            map,
+            entries: map.entries(),
+            sections: self.section_header_table().map(IntoIterator::into_iter),
+            next_section: 0,
+        }
+    }
+}
+
+/// A place where a [`LinkerMap`] disagrees with the [`ElfFile`] it describes.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum MapDiscrepancy<'slice, 'map> {
+    /// An output section of the map with a non-zero size is absent from the [`ElfFile`].
+    MissingSection(MapEntry<'map>),
+    /// An output section of the map is located at a different address in the [`ElfFile`].
+    SectionAddress {
+        /// The [`MapEntry`] of the output section.
+        entry: MapEntry<'map>,
+        /// The address of the section in the [`ElfFile`].
+        address: u64,
+    },
+    /// An output section of the map has a different size in the [`ElfFile`].
+    SectionSize {
+        /// The [`MapEntry`] of the output section.
+        entry: MapEntry<'map>,
+        /// The size of the section in the [`ElfFile`].
+        size: u64,
+    },
+    /// A symbol of the map is absent from the [`ElfFile`].
+    MissingSymbol(MapEntry<'map>),
+    /// A symbol of the map is defined at a different address in the [`ElfFile`].
+    SymbolAddress {
+        /// The [`MapEntry`] of the symbol.
+        entry: MapEntry<'map>,
+        /// The address of the symbol in the [`ElfFile`].
+        address: u64,
+    },
+    /// A [`SectionFlags::ALLOC`] section of the [`ElfFile`] is not an output section of the
+    /// map.
+    UnmappedSection {
+        /// The index of the section.
+        index: u16,
+        /// The name of the section.
+        name: &'slice [u8],
+    },
+}
+
+/// An [`Iterator`] over the [`MapDiscrepancy`]s between a [`LinkerMap`] and an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MapDiscrepancies<'slice, 'map, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] described by the map.
+    file: ElfFile<'slice, C, E>,
+    /// The [`SymbolTable`] of the [`ElfFile`].
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The [`LinkerMap`] describing the [`ElfFile`].
+    map: LinkerMap<'map>,
+    /// The [`MapEntry`]s that have not yet been visited.
+    entries: MapEntries<'map>,
+    /// The sections of the [`ElfFile`] that have not yet been visited.
+    sections: Option<section_header::IntoIter<'slice, C, E>>,
+    /// The index of the next section of the [`ElfFile`].
+    next_section: u16,
+}
+
+impl<'slice, 'map, C: ClassParse, E: EncodingParse> MapDiscrepancies<'slice, 'map, C, E> {
+    /// Returns the [`MapDiscrepancy`] of the output section described by `entry`, if any.
+    fn check_section(&self, entry: MapEntry<'map>) -> Option<MapDiscrepancy<'slice, 'map>> {
+        let section_header = self.file.section_header_table().and_then(|table| {
+            table
+                .into_iter()
+                .find(|&section_header| self.file.section_name(section_header) == Some(entry.name))
+        });
+        let Some(section_header) = section_header else {
+            return (entry.size != Some(0)).then_some(MapDiscrepancy::MissingSection(entry));
+        };
+
+        let address = section_header.address().into();
+        let size = section_header.size().into();
+        if address != entry.address {
+            Some(MapDiscrepancy::SectionAddress { entry, address })
+        } else if entry.size.is_some_and(|map_size| map_size != size) {
+            Some(MapDiscrepancy::SectionSize { entry, size })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the [`MapDiscrepancy`] of the symbol described by `entry`, if any.
+    fn check_symbol(&self, entry: MapEntry<'map>) -> Option<MapDiscrepancy<'slice, 'map>> {
+        let mut address = None;
+        let definitions = self
+            .symbols
+            .into_iter()
+            .flatten()
+            .filter(|symbol| symbol.is_defined() && symbol.name() == Some(entry.name));
+        for symbol in definitions {
+            let value = symbol.value().into();
+            if value == entry.address {
+                return None;
+            }
+            address.get_or_insert(value);
+        }
+
+        Some(match address {
+            Some(address) => MapDiscrepancy::SymbolAddress { entry, address },
+            None => MapDiscrepancy::MissingSymbol(entry),
+        })
+    }
+}
+
+impl<'slice, 'map, C: ClassParse, E: EncodingParse> Iterator
+    for MapDiscrepancies<'slice, 'map, C, E>
+{
+    type Item = MapDiscrepancy<'slice, 'map>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.entries.next() {
+            let discrepancy = match entry.kind {
+                MapEntryKind::OutputSection => self.check_section(entry),
+                MapEntryKind::InputSection => None,
+                MapEntryKind::Symbol => self.check_symbol(entry),
+            };
+            if discrepancy.is_some() {
+                return discrepancy;
+            }
+        }
+
+        for section_header in self.sections.as_mut()?.by_ref() {
+            let index = self.next_section;
+            self.next_section = self.next_section.wrapping_add(1);
+            if !section_header.flags().contains(SectionFlags::ALLOC) {
+                continue;
+            }
+
+            let name = self.file.section_name(section_header).unwrap_or_default();
+            if self.map.output_section(name).is_none() {
+                return Some(MapDiscrepancy::UnmappedSection { index, name });
+            }
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for MapDiscrepancies<'_, '_, C, E> {}
+
+/// An [`Iterator`] over the lines of a text, without their terminators.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct Lines<'slice>(&'slice [u8]);
+
+impl<'slice> Iterator for Lines<'slice> {
+    type Item = &'slice [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0.is_empty() {
+            return None;
+        }
+
+        let end = self
+            .0
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .unwrap_or(self.0.len());
+        let line = &self.0[..end];
+        self.0 = self.0.get(end + 1..).unwrap_or_default();
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+}
+
+/// Returns an [`Iterator`] over the whitespace separated tokens of `line`, along with the column
+/// each starts at.
+fn tokens_with_columns(line: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    let mut column = 0;
+    core::iter::from_fn(move || {
+        column += line[column..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_whitespace())
+            .count();
+        let length = line[column..]
+            .iter()
+            .take_while(|byte| !byte.is_ascii_whitespace())
+            .count();
+        if length == 0 {
+            return None;
+        }
+
+        let start = column;
+        column += length;
+        Some((start, &line[start..column]))
+    })
+}
+
+/// Returns the name of the symbol listed or assigned by `text`, or `None` if `text` does not
+/// name a symbol.
+fn assigned_name(text: &[u8]) -> Option<&[u8]> {
+    let text = text.trim_ascii();
+    let name = text
+        .windows(3)
+        .position(|window| window == b" = ")
+        .map_or(text, |end| text[..end].trim_ascii());
+
+    let valid = !name.is_empty()
+        && name != b"."
+        && !name
+            .iter()
+            .any(|byte| byte.is_ascii_whitespace() || matches!(byte, b'(' | b'[' | b'='));
+    valid.then_some(name)
+}
+
+/// Splits `text` around the last occurrence of `separator`.
+fn split_last<'slice>(
+    text: &'slice [u8],
+    separator: &[u8],
+) -> Option<(&'slice [u8], &'slice [u8])> {
+    let start = text
+        .windows(separator.len())
+        .rposition(|window| window == separator)?;
+
+    Some((&text[..start], &text[start + separator.len()..]))
+}
+
+/// Parses `token` as a hexadecimal number, with or without a `0x` prefix.
+fn parse_hex(token: &[u8]) -> Option<u64> {
+    let digits = token.strip_prefix(b"0x").unwrap_or(token);
+    if digits.is_empty() || digits.len() > 16 {
+        return None;
+    }
+
+    digits.iter().try_fold(0, |value, &digit| {
+        let digit = char::from(digit).to_digit(16)?;
+        Some((value << 4) | u64::from(digit))
+    })
+}
+
+/// Various errors that can occur while parsing a [`LinkerMap`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ParseLinkerMapError {
+    /// The text is not a map file written by GNU ld or LLD.
+    UnknownFormat,
+}
+
+impl fmt::Display for ParseLinkerMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFormat => write!(f, "text is not a GNU ld or LLD map file"),
+        }
+    }
+}
+
+impl error::Error for ParseLinkerMapError {}
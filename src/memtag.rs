@@ -0,0 +1,116 @@
+//! Definitions for decoding AArch64 memory tagging extension (MTE) tags stored in core dumps.
+//!
+//! Each [`SegmentType::AARCH64_MEMTAG_MTE`] segment describes the allocation tags of a range of
+//! memory. Tags are 4 bits wide, apply to a 16 byte granule, and are packed two per byte with the
+//! tag of the lower granule in the low nibble.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{ProgramHeader, SegmentType, SegmentsOfType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`MemtagSegment`]s of this [`ElfFile`].
+    pub fn memtag_segments(&self) -> MemtagSegments<'slice, C, E> {
+        MemtagSegments {
+            file: *self,
+            segments: self
+                .program_header_table()
+                .map(|table| table.segments_of_type(SegmentType::AARCH64_MEMTAG_MTE)),
+        }
+    }
+
+    /// Returns the MTE allocation tag of the granule containing `address`, if it is present in
+    /// any [`MemtagSegment`].
+    pub fn memory_tag(&self, address: u64) -> Option<u8> {
+        self.memtag_segments()
+            .find_map(|segment| segment.tag(address))
+    }
+}
+
+/// The decoded contents of a [`SegmentType::AARCH64_MEMTAG_MTE`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemtagSegment<'slice> {
+    /// The virtual address of the first byte of memory described by this [`MemtagSegment`].
+    pub address: u64,
+    /// The number of bytes of memory described by this [`MemtagSegment`].
+    pub memory_size: u64,
+    /// The packed tags of this [`MemtagSegment`].
+    pub tags: &'slice [u8],
+}
+
+impl<'slice> MemtagSegment<'slice> {
+    /// The number of bytes of memory covered by a single tag.
+    pub const GRANULE_SIZE: u64 = 16;
+
+    /// Creates a new [`MemtagSegment`] from the [`SegmentType::AARCH64_MEMTAG_MTE`] segment
+    /// controlled by `program_header` in `file`.
+    ///
+    /// Returns `None` if `program_header` is not a [`SegmentType::AARCH64_MEMTAG_MTE`] segment or
+    /// its data is located out of bounds.
+    pub fn new<C: ClassParse, E: EncodingParse>(
+        file: ElfFile<'slice, C, E>,
+        program_header: ProgramHeader<'slice, C, E>,
+    ) -> Option<Self> {
+        if program_header.segment_type() != SegmentType::AARCH64_MEMTAG_MTE {
+            return None;
+        }
+
+        let segment = Self {
+            address: program_header.virtual_address().into(),
+            memory_size: program_header.memory_size().into(),
+            tags: file.segment_data(program_header)?,
+        };
+
+        Some(segment)
+    }
+
+    /// Returns the number of granules described by this [`MemtagSegment`].
+    pub fn granule_count(&self) -> u64 {
+        self.memory_size / Self::GRANULE_SIZE
+    }
+
+    /// Returns the tag of the granule containing `address`.
+    ///
+    /// Returns `None` if `address` is not described by this [`MemtagSegment`].
+    pub fn tag(&self, address: u64) -> Option<u8> {
+        let offset = address.checked_sub(self.address)?;
+        if offset >= self.memory_size {
+            return None;
+        }
+
+        let granule = offset / Self::GRANULE_SIZE;
+        let byte = *self.tags.get(usize::try_from(granule / 2).ok()?)?;
+        if granule % 2 == 0 {
+            Some(byte & 0xF)
+        } else {
+            Some(byte >> 4)
+        }
+    }
+}
+
+/// An [`Iterator`] over the [`MemtagSegment`]s of an [`ElfFile`].
+#[derive(Clone, Copy)]
+pub struct MemtagSegments<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing the [`MemtagSegment`]s.
+    file: ElfFile<'slice, C, E>,
+    /// The [`SegmentType::AARCH64_MEMTAG_MTE`] segments that have not been visited.
+    segments: Option<SegmentsOfType<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for MemtagSegments<'slice, C, E> {
+    type Item = MemtagSegment<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let file = self.file;
+        self.segments
+            .as_mut()?
+            .find_map(|program_header| MemtagSegment::new(file, program_header))
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for MemtagSegments<'slice, C, E> {}
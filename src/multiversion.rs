@@ -0,0 +1,176 @@
+//! Definitions for inspecting functions compiled in several versions.
+//!
+//! With function multi-versioning, a compiler emits one variant of a function per target, such
+//! as `foo.avx2` and `foo.default`, along with a resolver `foo.resolver` that selects a variant
+//! when the program is loaded. References to the function go through a [`SymbolType::GNU_IFUNC`]
+//! dispatcher symbol whose value is the address of the resolver. The dispatcher is usually named
+//! after the function, except in C++, where the default variant keeps the mangled name of the
+//! function and the dispatcher receives another name.
+//!
+//! Compilers also split rarely executed parts of a function into a separate `foo.cold` function,
+//! which is placed apart from the hot code.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    symbol::{self, Symbol, SymbolTable, SymbolType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`Dispatchers`] over the [`SymbolType::GNU_IFUNC`] symbols of the table returned
+    /// by [`ElfFile::symbols()`], along with their resolvers.
+    pub fn dispatchers(&self) -> Option<Dispatchers<'slice, C, E>> {
+        let (_, table) = self.symbols()?;
+
+        Some(Dispatchers {
+            table,
+            symbols: table.into_iter(),
+        })
+    }
+
+    /// Returns a [`FunctionVariants`] over the function symbols of the table returned by
+    /// [`ElfFile::symbols()`] that are named `base_name` or `base_name` followed by a `.` and a
+    /// suffix.
+    ///
+    /// The base name of a multi-versioned function is given by [`Dispatcher::base_name`].
+    pub fn function_variants<'name>(
+        &self,
+        base_name: &'name [u8],
+    ) -> Option<FunctionVariants<'slice, 'name, C, E>> {
+        let (_, table) = self.symbols()?;
+
+        Some(FunctionVariants {
+            symbols: table.into_iter(),
+            base_name,
+        })
+    }
+}
+
+/// A [`SymbolType::GNU_IFUNC`] symbol, which dispatches to one variant of a function.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Dispatcher<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`SymbolType::GNU_IFUNC`] symbol.
+    pub symbol: Symbol<'slice, C, E>,
+    /// The [`SymbolType::FUNC`] symbol of the resolver, located at the value of
+    /// [`Dispatcher::symbol`], if present.
+    pub resolver: Option<Symbol<'slice, C, E>>,
+    /// The name the variants of the function are derived from, which is the name of the
+    /// resolver without its `.resolver` suffix, or the name of the dispatcher if there is none.
+    pub base_name: &'slice [u8],
+}
+
+/// An [`Iterator`] over the [`Dispatcher`]s of a [`SymbolTable`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Dispatchers<'slice, C, E> {
+    /// The table searched for resolvers.
+    table: SymbolTable<'slice, C, E>,
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for Dispatchers<'slice, C, E> {
+    type Item = Dispatcher<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let symbol = self
+            .symbols
+            .find(|symbol| symbol.symbol_type() == SymbolType::GNU_IFUNC && symbol.is_defined())?;
+
+        let resolvers = self.table.into_iter().filter(|resolver| {
+            resolver.symbol_type() == SymbolType::FUNC
+                && resolver.section_index() == symbol.section_index()
+                && resolver.value() == symbol.value()
+        });
+        // Prefer a resolver named after the convention, as other aliases may share its address.
+        let resolver = resolvers
+            .clone()
+            .find(|resolver| resolver.name_ends_with(b".resolver"))
+            .or_else(|| resolvers.clone().next());
+
+        let base_name = resolver
+            .and_then(|resolver| resolver.name()?.strip_suffix(b".resolver"))
+            .or_else(|| symbol.name())
+            .unwrap_or(&[]);
+
+        Some(Dispatcher {
+            symbol,
+            resolver,
+            base_name,
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for Dispatchers<'_, C, E> {}
+
+/// The role of a function symbol among the variants of a function.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum VariantRole<'slice> {
+    /// The [`SymbolType::GNU_IFUNC`] symbol that dispatches to a variant.
+    Dispatcher,
+    /// The function that selects a variant when the program is loaded.
+    Resolver,
+    /// The variant used when no other variant is supported, named with a `.default` suffix or,
+    /// in C++, named after the function.
+    Default,
+    /// The rarely executed part of the function, split from it into a `.cold` function.
+    Cold,
+    /// Another variant, with the given suffix, such as `avx2` or `arch_x86_64_v3`.
+    ///
+    /// Compiler generated specializations, such as `constprop.0` or `isra.0`, also appear as
+    /// variants.
+    Variant(&'slice [u8]),
+}
+
+/// A function symbol and its [`VariantRole`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FunctionVariant<'slice, C: ClassParse, E: EncodingParse> {
+    /// The function symbol.
+    pub symbol: Symbol<'slice, C, E>,
+    /// The [`VariantRole`] of the symbol.
+    pub role: VariantRole<'slice>,
+}
+
+/// An [`Iterator`] over the [`FunctionVariant`]s sharing a base name in a [`SymbolTable`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct FunctionVariants<'slice, 'name, C, E> {
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// The base name of the variants.
+    base_name: &'name [u8],
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for FunctionVariants<'slice, '_, C, E> {
+    type Item = FunctionVariant<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let base_name = self.base_name;
+        self.symbols.find_map(|symbol| {
+            let symbol_type = symbol.symbol_type();
+            if (symbol_type != SymbolType::FUNC && symbol_type != SymbolType::GNU_IFUNC)
+                || !symbol.is_defined()
+            {
+                return None;
+            }
+
+            let suffix = symbol.name()?.strip_prefix(base_name)?;
+            let role = match suffix {
+                [] if symbol_type == SymbolType::GNU_IFUNC => VariantRole::Dispatcher,
+                [] => VariantRole::Default,
+                [b'.', suffix @ ..] if !suffix.is_empty() => match suffix {
+                    b"resolver" => VariantRole::Resolver,
+                    b"default" => VariantRole::Default,
+                    b"cold" => VariantRole::Cold,
+                    suffix => VariantRole::Variant(suffix),
+                },
+                _ => return None,
+            };
+
+            Some(FunctionVariant { symbol, role })
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for FunctionVariants<'_, '_, C, E> {}
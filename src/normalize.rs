@@ -0,0 +1,252 @@
+//! Definitions for normalizing the layout of an ELF file, so that semantically identical files
+//! become byte-identical.
+//!
+//! The ELF header, the program header table and the sections located within segments keep their
+//! offsets, as moving them would change how the file is mapped into memory. Every other section
+//! is laid out again after them in section header table order, each aligned to its own
+//! alignment, followed by the section header table, aligned to the size of a class sized value.
+//! Every byte that is not claimed by one of these structures is zeroed, including the padding
+//! between sections, which linkers and post-processing tools do not always initialize.
+//!
+//! The file data of segments is only carried over as a whole if the file has no section header
+//! table. Otherwise the bytes of a segment that no section claims are treated as padding, and
+//! bytes following the last structure, such as appended signatures, are dropped.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_file_offset, to_len, ClassParse, OffsetOverflow},
+    encoding::EncodingParse,
+    section_header::{SectionFlags, SectionHeader, SectionType},
+    writer::ByteLayout,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Writes this [`ElfFile`] with a normalized layout into the start of `buffer`, returning the
+    /// number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`NormalizeLayoutError::SectionOutOfBounds`]: Returned if the data of a section is
+    ///   located out of bounds.
+    /// - [`NormalizeLayoutError::SegmentOutOfBounds`]: Returned if this [`ElfFile`] has no
+    ///   section header table and the file data of a segment is located out of bounds.
+    /// - [`NormalizeLayoutError::InvalidAlignment`]: Returned if the alignment of a section that
+    ///   is laid out again is not a power of two.
+    /// - [`NormalizeLayoutError::OffsetOverflow`]: Returned if an offset cannot be represented by
+    ///   a [`usize`] on the host.
+    /// - [`NormalizeLayoutError::Overflow`]: Returned if the size of the normalized file
+    ///   overflows.
+    /// - [`NormalizeLayoutError::BufferTooSmall`]: Returned if `buffer` is too small to hold the
+    ///   normalized file.
+    pub fn normalize_layout(&self, buffer: &mut [u8]) -> Result<usize, NormalizeLayoutError> {
+        let header = self.header();
+        let layout = ByteLayout::new(header.ident());
+        let sections = self.section_header_table();
+
+        // The structures that keep their offsets, and the end of the last of them.
+        let mut fixed_end = usize::from(header.header_size());
+        let program_headers = self.program_header_table().map(|table| {
+            let start = to_file_offset(header.program_header_offset())?;
+            let size = usize::from(table.count()) * usize::from(header.program_header_size());
+            Ok::<_, NormalizeLayoutError>((start, start + size))
+        });
+        let program_headers = program_headers.transpose()?;
+        if let Some((_, end)) = program_headers {
+            fixed_end = fixed_end.max(end);
+        }
+        for index in 0..self.program_header_table().map_or(0, |table| table.count()) {
+            let (start, end) = self.segment_range(index)?;
+            if sections.is_none() && self.bytes.get(start..end).is_none() {
+                return Err(NormalizeLayoutError::SegmentOutOfBounds(index));
+            }
+            fixed_end = fixed_end.max(end);
+        }
+        for (section_header, index) in sections.into_iter().flatten().zip(0..) {
+            let data = self
+                .section_data(section_header)
+                .ok_or(NormalizeLayoutError::SectionOutOfBounds(index))?;
+            if self.is_fixed(section_header) {
+                let start = to_file_offset(section_header.file_offset())?;
+                fixed_end = fixed_end.max(start + data.len());
+            }
+        }
+
+        let section_headers_offset = self.place_sections(fixed_end, |_, _, _| {})?;
+        let section_headers_offset = section_headers_offset
+            .checked_next_multiple_of(layout.word_size())
+            .ok_or(NormalizeLayoutError::Overflow)?;
+        let section_header_size = usize::from(header.section_header_size());
+        let size = match sections {
+            Some(sections) => section_headers_offset
+                .checked_add(usize::from(sections.count()) * section_header_size)
+                .ok_or(NormalizeLayoutError::Overflow)?,
+            None => fixed_end,
+        };
+
+        let buffer = buffer
+            .get_mut(..size)
+            .ok_or(NormalizeLayoutError::BufferTooSmall { required: size })?;
+        buffer.fill(0);
+
+        let header_size = usize::from(header.header_size());
+        buffer[..header_size].copy_from_slice(&self.bytes[..header_size]);
+        if let Some((start, end)) = program_headers {
+            buffer[start..end].copy_from_slice(&self.bytes[start..end]);
+        }
+
+        let Some(sections) = sections else {
+            for index in 0..self.program_header_table().map_or(0, |table| table.count()) {
+                let (start, end) = self.segment_range(index)?;
+                buffer[start..end].copy_from_slice(&self.bytes[start..end]);
+            }
+            return Ok(size);
+        };
+
+        for section_header in sections {
+            if self.is_fixed(section_header) {
+                let start = to_file_offset(section_header.file_offset())?;
+                let data = self.section_data(section_header).unwrap_or_default();
+                buffer[start..start + data.len()].copy_from_slice(data);
+            }
+        }
+
+        let table_start = to_file_offset(header.section_header_offset())?;
+        let table_size = usize::from(sections.count()) * section_header_size;
+        buffer[section_headers_offset..size]
+            .copy_from_slice(&self.bytes[table_start..table_start + table_size]);
+        layout.write_word(
+            buffer,
+            self.class.section_header_offset_offset(),
+            section_headers_offset as u64,
+        );
+
+        self.place_sections(fixed_end, |index, section_header, offset| {
+            let data = self.section_data(section_header).unwrap_or_default();
+            buffer[offset..offset + data.len()].copy_from_slice(data);
+
+            let entry = section_headers_offset + usize::from(index) * section_header_size;
+            layout.write_word(
+                buffer,
+                entry + self.class.section_file_offset_offset(),
+                offset as u64,
+            );
+        })?;
+
+        Ok(size)
+    }
+
+    /// Returns the range of file offsets occupied by the segment at `index`.
+    fn segment_range(&self, index: u16) -> Result<(usize, usize), NormalizeLayoutError> {
+        let segment = self
+            .program_header_table()
+            .and_then(|table| table.get(index))
+            .ok_or(NormalizeLayoutError::SegmentOutOfBounds(index))?;
+        let start = to_file_offset(segment.file_offset())?;
+        let end = start
+            .checked_add(to_len(segment.file_size())?)
+            .ok_or(NormalizeLayoutError::SegmentOutOfBounds(index))?;
+
+        Ok((start, end))
+    }
+
+    /// Returns `true` if the section described by `section_header` keeps its offset, because it
+    /// is located within a segment.
+    fn is_fixed(&self, section_header: SectionHeader<'slice, C, E>) -> bool {
+        let Some(table) = self.program_header_table() else {
+            return false;
+        };
+        if section_header.section_type() == SectionType::NOBITS {
+            return section_header.flags().contains(SectionFlags::ALLOC);
+        }
+
+        let start: u64 = section_header.file_offset().into();
+        table.into_iter().any(|segment| {
+            let segment_start: u64 = segment.file_offset().into();
+            let segment_end = segment_start.saturating_add(segment.file_size().into());
+            segment_start <= start && start < segment_end
+        })
+    }
+
+    /// Lays out the sections that do not keep their offsets in section header table order,
+    /// starting at `start`, calling `place` with the index, [`SectionHeader`] and new offset of
+    /// each, and returns the end of the last section.
+    fn place_sections(
+        &self,
+        start: usize,
+        mut place: impl FnMut(u16, SectionHeader<'slice, C, E>, usize),
+    ) -> Result<usize, NormalizeLayoutError> {
+        let mut end = start;
+        for (section_header, index) in self.section_header_table().into_iter().flatten().zip(0..) {
+            if index == 0 || self.is_fixed(section_header) {
+                continue;
+            }
+
+            let alignment = to_len(section_header.alignment())?.max(1);
+            if !alignment.is_power_of_two() {
+                return Err(NormalizeLayoutError::InvalidAlignment(index));
+            }
+            let offset = end
+                .checked_next_multiple_of(alignment)
+                .ok_or(NormalizeLayoutError::Overflow)?;
+            let data = self
+                .section_data(section_header)
+                .ok_or(NormalizeLayoutError::SectionOutOfBounds(index))?;
+
+            place(index, section_header, offset);
+            end = offset + data.len();
+        }
+
+        Ok(end)
+    }
+}
+
+/// Various errors that can occur while normalizing the layout of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum NormalizeLayoutError {
+    /// The data of the section at the given index is located out of bounds.
+    SectionOutOfBounds(u16),
+    /// The file data of the segment at the given index is located out of bounds.
+    SegmentOutOfBounds(u16),
+    /// The alignment of the section at the given index is not a power of two.
+    InvalidAlignment(u16),
+    /// An offset cannot be represented by a [`usize`] on the host.
+    OffsetOverflow(OffsetOverflow),
+    /// The size of the normalized file overflows.
+    Overflow,
+    /// The buffer is too small to hold the normalized file.
+    BufferTooSmall {
+        /// The number of bytes required to hold the normalized file.
+        required: usize,
+    },
+}
+
+impl From<OffsetOverflow> for NormalizeLayoutError {
+    fn from(value: OffsetOverflow) -> Self {
+        Self::OffsetOverflow(value)
+    }
+}
+
+impl fmt::Display for NormalizeLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SectionOutOfBounds(index) => {
+                write!(f, "section {index} is located out of bounds")
+            }
+            Self::SegmentOutOfBounds(index) => {
+                write!(f, "segment {index} is located out of bounds")
+            }
+            Self::InvalidAlignment(index) => {
+                write!(f, "alignment of section {index} is not a power of two")
+            }
+            Self::OffsetOverflow(error) => write!(f, "{error}"),
+            Self::Overflow => write!(f, "normalized file size overflows"),
+            Self::BufferTooSmall { required } => {
+                write!(f, "buffer too small to hold {required} bytes")
+            }
+        }
+    }
+}
+
+impl error::Error for NormalizeLayoutError {}
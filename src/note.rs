@@ -0,0 +1,607 @@
+//! Definitions for ELF notes.
+
+use core::{fmt, iter::FusedIterator, marker::PhantomData, mem, ops::Range};
+
+use crate::{
+    class::{to_len, ClassParse},
+    encoding::EncodingParse,
+    program_header::{SegmentType, SegmentsOfType},
+    search::until_nul,
+    section_header::{SectionType, SectionsOfType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`Note`]s contained in the [`SegmentType::NOTE`] segments
+    /// and [`SectionType::NOTE`] sections of this [`ElfFile`].
+    ///
+    /// The [`Note`]s of the segments are visited first. A section whose file data overlaps a
+    /// [`SegmentType::NOTE`] segment is skipped, since its [`Note`]s were already visited, so
+    /// each [`Note`] is visited once whether the file is linked or relocatable.
+    pub fn notes(&self) -> Notes<'slice, C, E> {
+        Notes {
+            file: *self,
+            segments: self
+                .program_header_table()
+                .map(|table| table.segments_of_type(SegmentType::NOTE)),
+            sections: self
+                .section_header_table()
+                .map(|table| table.sections_of_type(SectionType::NOTE)),
+            current: None,
+        }
+    }
+
+    /// Returns the range of file offsets occupied by `note`, from the start of its header to
+    /// the end of its descriptor, excluding any trailing padding.
+    ///
+    /// Returns `None` if `note` was not obtained from this [`ElfFile`].
+    pub fn note_range(&self, note: &Note<'slice>) -> Option<Range<usize>> {
+        let start = self
+            .range_of(note.name)?
+            .start
+            .checked_sub(NoteTable::<E>::HEADER_SIZE)?;
+        let end = self.range_of(note.descriptor)?.end;
+
+        (start <= end).then_some(start..end)
+    }
+
+    /// Returns the bytes of `note`, as located by [`ElfFile::note_range()`].
+    pub fn note_bytes(&self, note: &Note<'slice>) -> Option<&'slice [u8]> {
+        self.bytes.get(self.note_range(note)?)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the first [`TypedNote`] of type `T` contained in this [`ElfFile`].
+    pub fn find_note<T: TypedNote<'slice>>(&self) -> Option<T> {
+        self.notes_of().next()
+    }
+
+    /// Returns an [`Iterator`] over the [`Note`]s contained in this [`ElfFile`], decoded with
+    /// `decoder`.
+    pub fn decoded_notes<'decoder, D: NoteDecoder<'slice, E> + ?Sized>(
+        &self,
+        decoder: &'decoder D,
+    ) -> DecodedNotes<'slice, 'decoder, C, E, D> {
+        DecodedNotes {
+            notes: self.notes(),
+            decoder,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`TypedNote`]s of type `T` contained in this
+    /// [`ElfFile`].
+    pub fn notes_of<T: TypedNote<'slice>>(&self) -> NotesOf<'slice, C, E, T> {
+        NotesOf {
+            notes: self.notes(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The owner of notes defined by the GNU toolchain.
+pub const OWNER_GNU: &[u8] = b"GNU";
+/// The owner of notes defined by Android.
+pub const OWNER_ANDROID: &[u8] = b"Android";
+/// The owner of notes defined by freedesktop.org.
+pub const OWNER_FDO: &[u8] = b"FDO";
+/// The owner of notes defined by the Go toolchain.
+pub const OWNER_GO: &[u8] = b"Go";
+/// The owner of the process status notes of core dumps.
+pub const OWNER_CORE: &[u8] = b"CORE";
+/// The owner of the Linux specific notes of core dumps.
+pub const OWNER_LINUX: &[u8] = b"LINUX";
+
+/// The type of a [`Note`], interpreted according to its owner.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NoteType(pub u32);
+
+impl NoteType {
+    /// An [`OWNER_GNU`] note holding the ABI of the operating system the program targets.
+    pub const GNU_ABI_TAG: Self = Self(1);
+    /// An [`OWNER_GNU`] note holding synthetic hardware capabilities.
+    pub const GNU_HWCAP: Self = Self(2);
+    /// An [`OWNER_GNU`] note holding the unique build identifier of the program.
+    pub const GNU_BUILD_ID: Self = Self(3);
+    /// An [`OWNER_GNU`] note holding the version of the gold linker used to link the program.
+    pub const GNU_GOLD_VERSION: Self = Self(4);
+    /// An [`OWNER_GNU`] note holding GNU program properties.
+    pub const GNU_PROPERTY_TYPE_0: Self = Self(5);
+    /// An [`OWNER_GNU`] note holding build attributes that apply to a range of addresses.
+    pub const GNU_BUILD_ATTRIBUTE_OPEN: Self = Self(0x100);
+    /// An [`OWNER_GNU`] note holding build attributes that apply to a single function.
+    pub const GNU_BUILD_ATTRIBUTE_FUNC: Self = Self(0x101);
+    /// An [`OWNER_ANDROID`] note holding memory tagging settings.
+    pub const ANDROID_MEMTAG: Self = Self(4);
+    /// An [`OWNER_GO`] note holding the Go build identifier.
+    pub const GO_BUILD_ID: Self = Self(4);
+    /// An [`OWNER_FDO`] note holding JSON package metadata.
+    pub const FDO_PACKAGING_METADATA: Self = Self(0xCAFE_1A7E);
+    /// An [`OWNER_FDO`] note holding JSON metadata describing libraries opened at runtime.
+    pub const FDO_DLOPEN_METADATA: Self = Self(0x407C_0C0A);
+    /// An [`OWNER_CORE`] note holding the status and general purpose registers of a thread.
+    pub const CORE_PRSTATUS: Self = Self(1);
+    /// An [`OWNER_CORE`] note holding the floating point registers of a thread.
+    pub const CORE_FPREGSET: Self = Self(2);
+    /// An [`OWNER_CORE`] note holding information about the dumped process.
+    pub const CORE_PRPSINFO: Self = Self(3);
+    /// An [`OWNER_CORE`] note holding the auxiliary vector of the dumped process.
+    pub const CORE_AUXV: Self = Self(6);
+    /// An [`OWNER_CORE`] note holding the files mapped by the dumped process.
+    pub const CORE_FILE: Self = Self(0x4649_4C45);
+}
+
+impl From<u32> for NoteType {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<NoteType> for u32 {
+    fn from(value: NoteType) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u32> for NoteType {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A [`Note`] with a known owner and type that can be decoded into a typed representation.
+///
+/// Implementing this trait for a custom type allows it to be located with
+/// [`ElfFile::find_note()`] and [`ElfFile::notes_of()`].
+pub trait TypedNote<'slice>: Sized {
+    /// The owner of the [`Note`].
+    const OWNER: &'static [u8];
+    /// The [`NoteType`] of the [`Note`].
+    const NOTE_TYPE: NoteType;
+
+    /// Decodes the descriptor of a [`Note`] with the matching owner and type.
+    ///
+    /// Returns `None` if the descriptor is malformed.
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], encoding: E) -> Option<Self>;
+}
+
+/// A decoder mapping [`Note`]s to a typed representation.
+///
+/// This is implemented for closures and for slices of [`NoteRegistration`]s, which dispatch on
+/// the owner and [`NoteType`] of each [`Note`].
+pub trait NoteDecoder<'slice, E: EncodingParse> {
+    /// The typed representation produced by this [`NoteDecoder`].
+    type Output;
+
+    /// Decodes `note`, returning `None` if this [`NoteDecoder`] does not recognize `note` or
+    /// `note` is malformed.
+    fn decode(&self, note: Note<'slice>, encoding: E) -> Option<Self::Output>;
+}
+
+impl<'slice, E: EncodingParse, O, F: Fn(Note<'slice>, E) -> Option<O>> NoteDecoder<'slice, E>
+    for F
+{
+    type Output = O;
+
+    fn decode(&self, note: Note<'slice>, encoding: E) -> Option<Self::Output> {
+        self(note, encoding)
+    }
+}
+
+/// An association between a [`Note`] owner and [`NoteType`] and the function decoding its
+/// descriptor.
+pub struct NoteRegistration<'slice, E, O> {
+    /// The owner of the [`Note`]s decoded by this [`NoteRegistration`].
+    pub owner: &'static [u8],
+    /// The [`NoteType`] of the [`Note`]s decoded by this [`NoteRegistration`].
+    pub note_type: NoteType,
+    /// The function decoding the descriptor of a matching [`Note`].
+    pub decode: fn(&'slice [u8], E) -> Option<O>,
+}
+
+impl<E, O> Clone for NoteRegistration<'_, E, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, O> Copy for NoteRegistration<'_, E, O> {}
+
+impl<E, O> fmt::Debug for NoteRegistration<'_, E, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NoteRegistration")
+            .field("owner", &self.owner)
+            .field("note_type", &self.note_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'slice, E: EncodingParse, O> NoteDecoder<'slice, E> for [NoteRegistration<'slice, E, O>] {
+    type Output = O;
+
+    fn decode(&self, note: Note<'slice>, encoding: E) -> Option<Self::Output> {
+        self.iter()
+            .find(|registration| note.is(registration.owner, registration.note_type))
+            .and_then(|registration| (registration.decode)(note.descriptor, encoding))
+    }
+}
+
+/// A [`Note`] produced by a [`NoteDecoder`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DecodedNote<'slice, O> {
+    /// The [`Note`] was recognized and decoded.
+    Known(O),
+    /// The [`Note`] was not recognized or is malformed.
+    Unknown(Note<'slice>),
+}
+
+/// An ELF note.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Note<'slice> {
+    /// The name of the owner of this [`Note`], without the terminating null byte.
+    pub name: &'slice [u8],
+    /// The type of this [`Note`], interpreted according to its owner.
+    pub note_type: u32,
+    /// The descriptor of this [`Note`].
+    pub descriptor: &'slice [u8],
+}
+
+impl<'slice> Note<'slice> {
+    /// Returns `true` if this [`Note`] is owned by `owner` and has the given [`NoteType`].
+    pub fn is(&self, owner: &[u8], note_type: NoteType) -> bool {
+        self.name == owner && self.note_type == note_type.0
+    }
+
+    /// Returns the descriptor of this [`Note`] up to, but not including, the first null byte.
+    ///
+    /// This is useful for notes whose descriptor holds a string.
+    pub fn descriptor_str(&self) -> &'slice [u8] {
+        until_nul(self.descriptor)
+    }
+}
+
+/// The unique build identifier held by a [`NoteType::GNU_BUILD_ID`] note.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GnuBuildId<'slice>(pub &'slice [u8]);
+
+impl<'slice> TypedNote<'slice> for GnuBuildId<'slice> {
+    const OWNER: &'static [u8] = OWNER_GNU;
+    const NOTE_TYPE: NoteType = NoteType::GNU_BUILD_ID;
+
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], _: E) -> Option<Self> {
+        Some(Self(descriptor))
+    }
+}
+
+/// The gold linker version held by a [`NoteType::GNU_GOLD_VERSION`] note, without the
+/// terminating null byte.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GnuGoldVersion<'slice>(pub &'slice [u8]);
+
+impl<'slice> TypedNote<'slice> for GnuGoldVersion<'slice> {
+    const OWNER: &'static [u8] = OWNER_GNU;
+    const NOTE_TYPE: NoteType = NoteType::GNU_GOLD_VERSION;
+
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], _: E) -> Option<Self> {
+        Some(Self(until_nul(descriptor)))
+    }
+}
+
+/// The operating system ABI held by a [`NoteType::GNU_ABI_TAG`] note.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GnuAbiTag {
+    /// The operating system, where 0 is Linux, 1 is GNU Hurd, 2 is Solaris, and 3 is FreeBSD.
+    pub os: u32,
+    /// The major version of the earliest compatible kernel.
+    pub major: u32,
+    /// The minor version of the earliest compatible kernel.
+    pub minor: u32,
+    /// The patch version of the earliest compatible kernel.
+    pub patch: u32,
+}
+
+impl<'slice> TypedNote<'slice> for GnuAbiTag {
+    const OWNER: &'static [u8] = OWNER_GNU;
+    const NOTE_TYPE: NoteType = NoteType::GNU_ABI_TAG;
+
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], encoding: E) -> Option<Self> {
+        if descriptor.len() < 16 {
+            return None;
+        }
+
+        let tag = Self {
+            os: encoding.parse_u32_at(0, descriptor),
+            major: encoding.parse_u32_at(4, descriptor),
+            minor: encoding.parse_u32_at(8, descriptor),
+            patch: encoding.parse_u32_at(12, descriptor),
+        };
+
+        Some(tag)
+    }
+}
+
+/// The JSON package metadata held by a [`NoteType::FDO_PACKAGING_METADATA`] note, without the
+/// terminating null byte.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FdoPackagingMetadata<'slice>(pub &'slice [u8]);
+
+impl<'slice> TypedNote<'slice> for FdoPackagingMetadata<'slice> {
+    const OWNER: &'static [u8] = OWNER_FDO;
+    const NOTE_TYPE: NoteType = NoteType::FDO_PACKAGING_METADATA;
+
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], _: E) -> Option<Self> {
+        Some(Self(until_nul(descriptor)))
+    }
+}
+
+/// A table of [`Note`]s.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NoteTable<'slice, E> {
+    /// The underlying bytes of this [`NoteTable`].
+    pub(crate) bytes: &'slice [u8],
+    /// The alignment of the name and descriptor of each [`Note`].
+    pub(crate) alignment: usize,
+    /// The maximum number of [`Note`]s parsed from this [`NoteTable`].
+    pub(crate) max_entries: usize,
+    /// The [`EncodingParse`] of this [`NoteTable`].
+    pub(crate) encoding: E,
+}
+
+impl<'slice, E: EncodingParse> NoteTable<'slice, E> {
+    /// The size of the header of a [`Note`].
+    const HEADER_SIZE: usize = 3 * mem::size_of::<u32>();
+
+    /// Creates a new [`NoteTable`] from the given `slice`, in which the name and descriptor of
+    /// each [`Note`] are padded to `alignment` bytes.
+    ///
+    /// Returns `None` if `alignment` is not 4 or 8.
+    pub fn new(encoding: E, slice: &'slice [u8], alignment: usize) -> Option<Self> {
+        if alignment != 4 && alignment != 8 {
+            return None;
+        }
+
+        let table = Self {
+            bytes: slice,
+            alignment,
+            max_entries: usize::MAX,
+            encoding,
+        };
+
+        Some(table)
+    }
+
+    /// Limits this [`NoteTable`] to at most `max_entries` [`Note`]s.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    /// Parses the [`Note`] located at the start of `bytes`, returning the [`Note`] and the
+    /// remaining bytes.
+    fn parse(&self, bytes: &'slice [u8]) -> Option<(Note<'slice>, &'slice [u8])> {
+        if bytes.len() < Self::HEADER_SIZE {
+            return None;
+        }
+
+        let name_size = to_len(self.encoding.parse_u32_at(0, bytes)).ok()?;
+        let descriptor_size = to_len(self.encoding.parse_u32_at(4, bytes)).ok()?;
+        let note_type = self.encoding.parse_u32_at(8, bytes);
+
+        let name_start = Self::HEADER_SIZE;
+        let name_end = name_start.checked_add(name_size)?;
+        let descriptor_start = align_up(name_end, self.alignment)?;
+        let descriptor_end = descriptor_start.checked_add(descriptor_size)?;
+        let next = align_up(descriptor_end, self.alignment)?;
+
+        let note = Note {
+            name: until_nul(bytes.get(name_start..name_end)?),
+            note_type,
+            descriptor: bytes.get(descriptor_start..descriptor_end)?,
+        };
+
+        Some((note, bytes.get(next..).unwrap_or(&[])))
+    }
+}
+
+impl<'slice, E: EncodingParse> IntoIterator for NoteTable<'slice, E> {
+    type Item = Note<'slice>;
+    type IntoIter = IntoIter<'slice, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            remaining: self.bytes,
+            remaining_entries: self.max_entries,
+        }
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for NoteTable<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+/// An [`Iterator`] over the [`Note`]s in a [`NoteTable`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct IntoIter<'slice, E> {
+    /// The table to iterate over.
+    table: NoteTable<'slice, E>,
+    /// The bytes of the [`NoteTable`] that have not been parsed.
+    remaining: &'slice [u8],
+    /// The number of [`Note`]s that may still be parsed.
+    remaining_entries: usize,
+}
+
+impl<'slice, E: EncodingParse> Iterator for IntoIter<'slice, E> {
+    type Item = Note<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining_entries = self.remaining_entries.checked_sub(1)?;
+        let Some((note, remaining)) = self.table.parse(self.remaining) else {
+            self.remaining = &[];
+            self.remaining_entries = 0;
+            return None;
+        };
+
+        self.remaining = remaining;
+        Some(note)
+    }
+}
+
+impl<'slice, E: EncodingParse> FusedIterator for IntoIter<'slice, E> {}
+
+/// An [`Iterator`] over the [`Note`]s in the [`SegmentType::NOTE`] segments and
+/// [`SectionType::NOTE`] sections of an [`ElfFile`].
+#[derive(Clone, Copy)]
+pub struct Notes<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing the [`Note`]s.
+    file: ElfFile<'slice, C, E>,
+    /// The [`SegmentType::NOTE`] segments that have not been visited.
+    segments: Option<SegmentsOfType<'slice, C, E>>,
+    /// The [`SectionType::NOTE`] sections that have not been visited.
+    sections: Option<SectionsOfType<'slice, C, E>>,
+    /// The [`Iterator`] over the [`Note`]s in the current segment.
+    current: Option<IntoIter<'slice, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for Notes<'slice, C, E> {
+    type Item = Note<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(note) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(note);
+            }
+
+            if let Some(program_header) = self.segments.as_mut().and_then(Iterator::next) {
+                self.current = self
+                    .file
+                    .segment_data(program_header)
+                    .and_then(|data| {
+                        NoteTable::new(
+                            self.file.encoding,
+                            data,
+                            note_alignment(program_header.alignment().into()),
+                        )
+                    })
+                    .map(IntoIterator::into_iter);
+                continue;
+            }
+
+            let section_header = self.sections.as_mut()?.next()?;
+            self.current = self
+                .file
+                .section_data(section_header)
+                .filter(|data| !self.overlaps_note_segment(data))
+                .and_then(|data| {
+                    NoteTable::new(
+                        self.file.encoding,
+                        data,
+                        note_alignment(section_header.alignment().into()),
+                    )
+                })
+                .map(IntoIterator::into_iter);
+        }
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Notes<'slice, C, E> {
+    /// Returns `true` if `data` overlaps the file data of a [`SegmentType::NOTE`] segment.
+    fn overlaps_note_segment(&self, data: &[u8]) -> bool {
+        let Some(range) = self.file.range_of(data) else {
+            return false;
+        };
+        let Some(table) = self.file.program_header_table() else {
+            return false;
+        };
+
+        table
+            .segments_of_type(SegmentType::NOTE)
+            .filter_map(|program_header| self.file.segment_data(program_header))
+            .filter_map(|data| self.file.range_of(data))
+            .any(|segment| segment.start < range.end && range.start < segment.end)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for Notes<'slice, C, E> {}
+
+/// An [`Iterator`] over the [`Note`]s of an [`ElfFile`], decoded with a [`NoteDecoder`].
+pub struct DecodedNotes<'slice, 'decoder, C: ClassParse, E: EncodingParse, D: ?Sized> {
+    /// The [`Iterator`] over all [`Note`]s of the [`ElfFile`].
+    notes: Notes<'slice, C, E>,
+    /// The [`NoteDecoder`] applied to each [`Note`].
+    decoder: &'decoder D,
+}
+
+impl<'slice, C, E, D> Iterator for DecodedNotes<'slice, '_, C, E, D>
+where
+    C: ClassParse,
+    E: EncodingParse,
+    D: NoteDecoder<'slice, E> + ?Sized,
+{
+    type Item = DecodedNote<'slice, D::Output>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let note = self.notes.next()?;
+        match self.decoder.decode(note, self.notes.file.encoding) {
+            Some(output) => Some(DecodedNote::Known(output)),
+            None => Some(DecodedNote::Unknown(note)),
+        }
+    }
+}
+
+impl<'slice, C, E, D> FusedIterator for DecodedNotes<'slice, '_, C, E, D>
+where
+    C: ClassParse,
+    E: EncodingParse,
+    D: NoteDecoder<'slice, E> + ?Sized,
+{
+}
+
+/// An [`Iterator`] over the [`TypedNote`]s of type `T` in an [`ElfFile`].
+#[derive(Clone, Copy)]
+pub struct NotesOf<'slice, C: ClassParse, E: EncodingParse, T> {
+    /// The [`Iterator`] over all [`Note`]s of the [`ElfFile`].
+    notes: Notes<'slice, C, E>,
+    /// Marker for the [`TypedNote`] being decoded.
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse, T: TypedNote<'slice>> Iterator
+    for NotesOf<'slice, C, E, T>
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let encoding = self.notes.file.encoding;
+        self.notes.find_map(|note| {
+            if !note.is(T::OWNER, T::NOTE_TYPE) {
+                return None;
+            }
+
+            T::from_descriptor(note.descriptor, encoding)
+        })
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse, T: TypedNote<'slice>> FusedIterator
+    for NotesOf<'slice, C, E, T>
+{
+}
+
+/// Returns the alignment of the [`Note`]s in a segment or section aligned to `alignment`.
+fn note_alignment(alignment: u64) -> usize {
+    match alignment {
+        8 => 8,
+        _ => 4,
+    }
+}
+
+/// Rounds `value` up to the next multiple of `alignment`, returning `None` on overflow.
+fn align_up(value: usize, alignment: usize) -> Option<usize> {
+    value
+        .checked_add(alignment - 1)
+        .map(|value| value & !(alignment - 1))
+}
@@ -0,0 +1,133 @@
+//! Definitions for a convenient view of an ELF file for host-side tooling.
+//!
+//! The types of the rest of this crate parse lazily and leave cross-referencing to the caller,
+//! which suits kernels and loaders. An [`ElfObject`] instead locates the section name table,
+//! the symbol tables, the dynamic table and the GNU build ID once, up front, so that the common
+//! queries are single method calls.
+
+use crate::{
+    class::ClassParse,
+    dynamic::DynamicTable,
+    encoding::EncodingParse,
+    imports::NeededLibraries,
+    note::GnuBuildId,
+    section_header::{SectionHeader, SectionHeaderTable},
+    string_table::StringTable,
+    symbol::{Symbol, SymbolTable},
+    ElfFile, ParseElfFileError,
+};
+
+/// An [`ElfFile`] whose commonly used tables have been located up front.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ElfObject<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`ElfFile`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeaderTable`] of the [`ElfFile`].
+    section_headers: Option<SectionHeaderTable<'slice, C, E>>,
+    /// The section name [`StringTable`] of the [`ElfFile`].
+    section_names: Option<StringTable<'slice>>,
+    /// The [`SymbolTable`] held by the [`SectionType::SYMTAB`][st] section.
+    ///
+    /// [st]: crate::section_header::SectionType::SYMTAB
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The dynamic [`SymbolTable`].
+    dynamic_symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The [`DynamicTable`] of the [`ElfFile`].
+    dynamic: Option<DynamicTable<'slice, C, E>>,
+    /// The file names of the shared objects required by the [`ElfFile`].
+    needed: Option<NeededLibraries<'slice, C, E>>,
+    /// The descriptor of the [`GnuBuildId`] note of the [`ElfFile`].
+    build_id: Option<&'slice [u8]>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfObject<'slice, C, E> {
+    /// Parses `slice` as an [`ElfFile`] and locates its commonly used tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ParseElfFileError`] of [`ElfFile::new()`] if `slice` is not a valid
+    /// [`ElfFile`].
+    pub fn parse(slice: &'slice [u8]) -> Result<Self, ParseElfFileError> {
+        ElfFile::new(slice).map(Self::new)
+    }
+
+    /// Creates a new [`ElfObject`] from `file`, locating its commonly used tables.
+    pub fn new(file: ElfFile<'slice, C, E>) -> Self {
+        Self {
+            file,
+            section_headers: file.section_header_table(),
+            section_names: file.section_name_table(),
+            symbols: file.symbol_table(),
+            dynamic_symbols: file.dynamic_symbol_table(),
+            dynamic: file.dynamic_table(),
+            needed: file.needed_libraries(),
+            build_id: file
+                .find_note::<GnuBuildId>()
+                .map(|GnuBuildId(descriptor)| descriptor),
+        }
+    }
+
+    /// Returns the underlying [`ElfFile`].
+    pub fn file(&self) -> ElfFile<'slice, C, E> {
+        self.file
+    }
+
+    /// Returns the [`SectionHeader`] of the first section named `name`, such as `b".text"`.
+    pub fn section(&self, name: &[u8]) -> Option<SectionHeader<'slice, C, E>> {
+        let names = self.section_names?;
+
+        self.section_headers?.into_iter().find(|section_header| {
+            usize::try_from(section_header.name_offset())
+                .is_ok_and(|offset| names.get(offset) == Ok(name))
+        })
+    }
+
+    /// Returns the data of the first section named `name`.
+    pub fn section_data(&self, name: &[u8]) -> Option<&'slice [u8]> {
+        self.file.section_data(self.section(name)?)
+    }
+
+    /// Returns the [`SymbolTable`] held by the [`SectionType::SYMTAB`][st] section, or the
+    /// dynamic [`SymbolTable`] if it has been stripped.
+    ///
+    /// [st]: crate::section_header::SectionType::SYMTAB
+    pub fn symbols(&self) -> Option<SymbolTable<'slice, C, E>> {
+        self.symbols.or(self.dynamic_symbols)
+    }
+
+    /// Returns the dynamic [`SymbolTable`].
+    pub fn dynamic_symbols(&self) -> Option<SymbolTable<'slice, C, E>> {
+        self.dynamic_symbols
+    }
+
+    /// Returns the first defined [`Symbol`] named `name` in [`ElfObject::symbols()`].
+    pub fn symbol(&self, name: &[u8]) -> Option<Symbol<'slice, C, E>> {
+        self.symbols()?
+            .into_iter()
+            .find(|symbol| symbol.is_defined() && symbol.name() == Some(name))
+    }
+
+    /// Returns the [`DynamicTable`] of the [`ElfFile`].
+    pub fn dynamic(&self) -> Option<DynamicTable<'slice, C, E>> {
+        self.dynamic
+    }
+
+    /// Returns a [`NeededLibraries`] over the file names of the shared objects required by the
+    /// [`ElfFile`].
+    pub fn needed(&self) -> Option<NeededLibraries<'slice, C, E>> {
+        self.needed
+    }
+
+    /// Returns the descriptor of the [`GnuBuildId`] note of the [`ElfFile`].
+    pub fn build_id(&self) -> Option<&'slice [u8]> {
+        self.build_id
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> From<ElfFile<'slice, C, E>>
+    for ElfObject<'slice, C, E>
+{
+    fn from(file: ElfFile<'slice, C, E>) -> Self {
+        Self::new(file)
+    }
+}
@@ -0,0 +1,996 @@
+//! Definitions for planning the memory mappings required to load ELF files.
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    auxv::{AuxiliaryVector, AuxvOptions},
+    biased::BiasedElfFile,
+    class::ClassParse,
+    encoding::EncodingParse,
+    instrument,
+    program_header::{self, ProgramHeader, SegmentFlags, SegmentType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`MappingPlan`] describing the page-granular [`Mapping`]s required to load this
+    /// [`ElfFile`] at `bias` on a system with pages of `page_size` bytes.
+    ///
+    /// Returns `None` if `page_size` is not a power of two.
+    pub fn mapping_plan(&self, bias: i64, page_size: u64) -> Option<MappingPlan<'slice, C, E>> {
+        MappingPlan::new(self.with_bias(bias), Image::Executable, page_size)
+    }
+}
+
+/// The image to which a [`Mapping`] belongs.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Image {
+    /// The image of the program being executed.
+    Executable,
+    /// The image of the program interpreter.
+    Interpreter,
+}
+
+/// A page-granular memory mapping required to load a [`SegmentType::LOAD`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Mapping {
+    /// The [`Image`] to which this [`Mapping`] belongs.
+    pub image: Image,
+    /// The page-aligned virtual address at which this [`Mapping`] starts.
+    pub address: u64,
+    /// The page-aligned size of this [`Mapping`] in memory.
+    pub memory_size: u64,
+    /// The page-aligned file offset of the data mapped at the start of this [`Mapping`].
+    pub file_offset: u64,
+    /// The number of bytes of file data mapped at the start of this [`Mapping`].
+    pub file_size: u64,
+    /// The [`SegmentFlags`] with which this [`Mapping`] should be mapped.
+    pub flags: SegmentFlags,
+}
+
+impl Mapping {
+    /// Returns the virtual address of the first byte of this [`Mapping`] that must be zeroed.
+    ///
+    /// All bytes from this address to the end of this [`Mapping`] must be zeroed, including the
+    /// remainder of the page containing the last byte of file data.
+    pub fn zero_start(&self) -> u64 {
+        self.address.saturating_add(self.file_size)
+    }
+
+    /// Returns the virtual address one past the last byte of this [`Mapping`].
+    pub fn end(&self) -> u64 {
+        self.address.saturating_add(self.memory_size)
+    }
+}
+
+/// A region of address space that must be left unmapped, so that accesses overrunning an adjacent
+/// region fault.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GuardRegion {
+    /// The page-aligned virtual address at which this [`GuardRegion`] starts.
+    pub address: u64,
+    /// The page-aligned size of this [`GuardRegion`].
+    pub size: u64,
+}
+
+/// An [`Iterator`] over the [`GuardRegion`]s placed around the [`Mapping`]s of a
+/// [`MappingPlan`], in increasing address order.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct GuardRegions<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`MappingPlan`] whose [`Mapping`]s are guarded.
+    plan: MappingPlan<'slice, C, E>,
+    /// The page-aligned size of each [`GuardRegion`].
+    size: u64,
+    /// The highest end of the [`Mapping`]s visited so far.
+    end: Option<u64>,
+    /// A [`GuardRegion`] to yield before visiting the next [`Mapping`].
+    pending: Option<GuardRegion>,
+    /// Whether the [`GuardRegion`] above the last [`Mapping`] has been yielded.
+    finished: bool,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for GuardRegions<'_, C, E> {
+    type Item = Result<GuardRegion, PlanMappingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(region) = self.pending.take() {
+            return Some(Ok(region));
+        }
+
+        while !self.finished && self.size != 0 {
+            let Some(mapping) = self.plan.next() else {
+                self.finished = true;
+                let address = self.end?;
+                return Some(match address.checked_add(self.size) {
+                    Some(_) => Ok(GuardRegion {
+                        address,
+                        size: self.size,
+                    }),
+                    None => Err(PlanMappingError::Overflow),
+                });
+            };
+            let mapping = match mapping {
+                Ok(mapping) => mapping,
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            };
+
+            let previous_end = self
+                .end
+                .replace(self.end.map_or(mapping.end(), |end| end.max(mapping.end())));
+            let Some(previous_end) = previous_end else {
+                return Some(match mapping.address.checked_sub(self.size) {
+                    Some(address) => Ok(GuardRegion {
+                        address,
+                        size: self.size,
+                    }),
+                    None => {
+                        self.finished = true;
+                        Err(PlanMappingError::Overflow)
+                    }
+                });
+            };
+
+            let Some(gap) = mapping
+                .address
+                .checked_sub(previous_end)
+                .filter(|&gap| gap != 0)
+            else {
+                continue;
+            };
+            if gap <= self.size.saturating_mul(2) {
+                return Some(Ok(GuardRegion {
+                    address: previous_end,
+                    size: gap,
+                }));
+            }
+
+            self.pending = Some(GuardRegion {
+                address: mapping.address - self.size,
+                size: self.size,
+            });
+            return Some(Ok(GuardRegion {
+                address: previous_end,
+                size: self.size,
+            }));
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for GuardRegions<'_, C, E> {}
+
+/// The parameters describing where the initial stack of a program is placed.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StackOptions {
+    /// The virtual address one past the highest byte of the stack.
+    pub top: u64,
+    /// The size of the stack used if the [`SegmentType::GNU_STACK`] segment is absent or does not
+    /// specify a size.
+    pub default_size: u64,
+    /// The size of the [`GuardRegion`] placed below the stack.
+    pub guard_size: u64,
+}
+
+/// The initial stack region of a program.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct StackRegion {
+    /// The page-aligned virtual address of the lowest byte of the stack.
+    pub address: u64,
+    /// The page-aligned size of the stack.
+    pub size: u64,
+    /// The [`SegmentFlags`] with which the stack should be mapped.
+    pub flags: SegmentFlags,
+    /// The [`GuardRegion`] placed below the stack.
+    pub guard: GuardRegion,
+}
+
+impl StackRegion {
+    /// Returns the virtual address one past the highest byte of the stack.
+    pub fn end(&self) -> u64 {
+        self.address.saturating_add(self.size)
+    }
+}
+
+/// An [`Iterator`] over the [`Mapping`]s required to load an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MappingPlan<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`BiasedElfFile`] being loaded.
+    file: BiasedElfFile<'slice, C, E>,
+    /// The [`Image`] of the [`ElfFile`] being loaded.
+    image: Image,
+    /// The size of a page.
+    page_size: u64,
+    /// The underlying [`Iterator`] over the program header table.
+    iter: Option<program_header::IntoIter<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> MappingPlan<'slice, C, E> {
+    /// Creates a new [`MappingPlan`] for `file` as the given `image`, using pages of `page_size`
+    /// bytes.
+    ///
+    /// Returns `None` if `page_size` is not a power of two.
+    pub fn new(file: BiasedElfFile<'slice, C, E>, image: Image, page_size: u64) -> Option<Self> {
+        if !page_size.is_power_of_two() {
+            return None;
+        }
+
+        Some(Self {
+            file,
+            image,
+            page_size,
+            iter: file
+                .file()
+                .program_header_table()
+                .map(IntoIterator::into_iter),
+        })
+    }
+
+    /// Returns the [`Mapping`] required to load the segment controlled by `program_header`.
+    ///
+    /// # Errors
+    ///
+    /// - [`PlanMappingError::MisalignedBias`]: Returned if the load bias is not page-aligned.
+    /// - [`PlanMappingError::MisalignedSegment`]: Returned if the virtual address and file offset
+    ///   of the segment are not congruent modulo the page size.
+    /// - [`PlanMappingError::FileSizeExceedsMemorySize`]: Returned if the segment contains more
+    ///   file data than fits in its memory image.
+    /// - [`PlanMappingError::Overflow`]: Returned if an address or size overflows.
+    pub fn mapping(
+        &self,
+        program_header: ProgramHeader<'slice, C, E>,
+    ) -> Result<Mapping, PlanMappingError> {
+        let mask = self.page_size - 1;
+        if self.file.bias() as u64 & mask != 0 {
+            return Err(PlanMappingError::MisalignedBias);
+        }
+
+        let virtual_address: u64 = program_header.virtual_address().into();
+        let file_offset: u64 = program_header.file_offset().into();
+        let file_size: u64 = program_header.file_size().into();
+        let memory_size: u64 = program_header.memory_size().into();
+        if virtual_address & mask != file_offset & mask {
+            return Err(PlanMappingError::MisalignedSegment);
+        }
+        if file_size > memory_size {
+            return Err(PlanMappingError::FileSizeExceedsMemorySize);
+        }
+
+        let start = self.file.translate(program_header.virtual_address());
+        let padding = start & mask;
+        let address = start - padding;
+        let end = start
+            .checked_add(memory_size)
+            .and_then(|end| end.checked_add(mask))
+            .ok_or(PlanMappingError::Overflow)?
+            & !mask;
+
+        let mapping = Mapping {
+            image: self.image,
+            address,
+            memory_size: end - address,
+            file_offset: file_offset - padding,
+            file_size: file_size + padding,
+            flags: program_header.flags(),
+        };
+        instrument::trace!(
+            "planned mapping: {:#x} bytes at {:#x} from file offset {:#x}",
+            mapping.memory_size,
+            mapping.address,
+            mapping.file_offset,
+        );
+
+        Ok(mapping)
+    }
+
+    /// Returns an [`Iterator`] over the [`GuardRegion`]s placed around the [`Mapping`]s of this
+    /// [`MappingPlan`], each `guard_size` bytes rounded up to the page size.
+    ///
+    /// A [`GuardRegion`] is placed below the first [`Mapping`], above the last [`Mapping`], and
+    /// on both sides of every gap between consecutive [`Mapping`]s. Gaps too small to hold both
+    /// are guarded in their entirety, and [`Mapping`]s that share a page have no gap to guard.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PlanMappingError::Overflow`] if `guard_size` overflows when rounded up to the
+    /// page size.
+    pub fn guard_regions(
+        &self,
+        guard_size: u64,
+    ) -> Result<GuardRegions<'slice, C, E>, PlanMappingError> {
+        Ok(GuardRegions {
+            plan: self.restarted(),
+            size: self.page_align(guard_size)?,
+            end: None,
+            pending: None,
+            finished: false,
+        })
+    }
+
+    /// Returns the [`StackRegion`] of the program, placed below `options.top`.
+    ///
+    /// The size and [`SegmentFlags`] of the stack are taken from the
+    /// [`SegmentType::GNU_STACK`] segment. If it is absent, the stack is `options.default_size`
+    /// bytes and readable, writable and executable, matching the historical default.
+    ///
+    /// # Errors
+    ///
+    /// - [`PlanMappingError::MisalignedStack`]: Returned if `options.top` is not page-aligned.
+    /// - [`PlanMappingError::Overflow`]: Returned if the stack and its [`GuardRegion`] do not fit
+    ///   below `options.top`.
+    pub fn stack_region(&self, options: &StackOptions) -> Result<StackRegion, PlanMappingError> {
+        if options.top & (self.page_size - 1) != 0 {
+            return Err(PlanMappingError::MisalignedStack);
+        }
+
+        let stack = self
+            .file
+            .file()
+            .program_header_table()
+            .and_then(|table| table.segments_of_type(SegmentType::GNU_STACK).next());
+        let (size, flags) = match stack {
+            Some(stack) => {
+                let size: u64 = stack.memory_size().into();
+                let size = if size == 0 {
+                    options.default_size
+                } else {
+                    size
+                };
+                (size, stack.flags())
+            }
+            None => (
+                options.default_size,
+                SegmentFlags(
+                    SegmentFlags::READ.0 | SegmentFlags::WRITE.0 | SegmentFlags::EXECUTE.0,
+                ),
+            ),
+        };
+
+        let size = self.page_align(size)?;
+        let guard_size = self.page_align(options.guard_size)?;
+        let address = options
+            .top
+            .checked_sub(size)
+            .ok_or(PlanMappingError::Overflow)?;
+        let guard_address = address
+            .checked_sub(guard_size)
+            .ok_or(PlanMappingError::Overflow)?;
+
+        Ok(StackRegion {
+            address,
+            size,
+            flags,
+            guard: GuardRegion {
+                address: guard_address,
+                size: guard_size,
+            },
+        })
+    }
+
+    /// Returns a copy of this [`MappingPlan`] that starts from the first [`Mapping`].
+    fn restarted(&self) -> Self {
+        Self {
+            iter: self
+                .file
+                .file()
+                .program_header_table()
+                .map(IntoIterator::into_iter),
+            ..*self
+        }
+    }
+
+    /// Rounds `size` up to a multiple of the page size.
+    fn page_align(&self, size: u64) -> Result<u64, PlanMappingError> {
+        let mask = self.page_size - 1;
+        size.checked_add(mask)
+            .map(|size| size & !mask)
+            .ok_or(PlanMappingError::Overflow)
+    }
+
+    /// Returns an [`Iterator`] over the [`PageRun`]s covering `mapping`, using pages from
+    /// `large_page_sizes` where the alignment and size of `mapping` permit and pages of the size
+    /// used by this [`MappingPlan`] otherwise.
+    ///
+    /// Sizes in `large_page_sizes` that are not powers of two, or not larger than the page size of
+    /// this [`MappingPlan`], are ignored.
+    pub fn page_runs<'sizes>(
+        &self,
+        mapping: &Mapping,
+        large_page_sizes: &'sizes [u64],
+    ) -> PageRuns<'sizes> {
+        PageRuns {
+            address: mapping.address,
+            end: mapping.end(),
+            page_size: self.page_size,
+            large_page_sizes,
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for MappingPlan<'_, C, E> {
+    type Item = Result<Mapping, PlanMappingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let program_header = self
+            .iter
+            .as_mut()?
+            .find(|program_header| program_header.segment_type() == SegmentType::LOAD)?;
+
+        Some(self.mapping(program_header))
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for MappingPlan<'_, C, E> {}
+
+/// A run of equally sized pages covering part of a [`Mapping`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PageRun {
+    /// The virtual address at which this [`PageRun`] starts, aligned to `page_size`.
+    pub address: u64,
+    /// The size of this [`PageRun`], a multiple of `page_size`.
+    pub size: u64,
+    /// The size of the pages making up this [`PageRun`].
+    pub page_size: u64,
+}
+
+/// An [`Iterator`] over the [`PageRun`]s covering a [`Mapping`].
+///
+/// At each address the largest page size that is aligned and fits before the end of the
+/// [`Mapping`] is chosen, and a run extends until a larger page size would fit.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PageRuns<'sizes> {
+    /// The virtual address at which the next [`PageRun`] starts.
+    address: u64,
+    /// The virtual address one past the end of the [`Mapping`].
+    end: u64,
+    /// The size of a page used when no large page fits.
+    page_size: u64,
+    /// The sizes of the large pages that may be used.
+    large_page_sizes: &'sizes [u64],
+}
+
+impl PageRuns<'_> {
+    /// Returns `true` if a page of `size` bytes may be placed at `address`.
+    fn fits(&self, address: u64, size: u64) -> bool {
+        size.is_power_of_two()
+            && address & (size - 1) == 0
+            && address.checked_add(size).is_some_and(|end| end <= self.end)
+    }
+}
+
+impl Iterator for PageRuns<'_> {
+    type Item = PageRun;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.address >= self.end {
+            return None;
+        }
+
+        let large_page_sizes = self
+            .large_page_sizes
+            .iter()
+            .copied()
+            .filter(|&size| size > self.page_size && size.is_power_of_two());
+        let page_size = large_page_sizes
+            .clone()
+            .filter(|&size| self.fits(self.address, size))
+            .max()
+            .unwrap_or(self.page_size);
+
+        // Stop the run where a larger page would first fit.
+        let limit = large_page_sizes
+            .filter(|&size| size > page_size)
+            .filter_map(|size| {
+                let boundary = self.address.checked_add(size - 1)? & !(size - 1);
+                self.fits(boundary, size).then_some(boundary)
+            })
+            .fold(self.end, u64::min);
+
+        let size = (limit - self.address) & !(page_size - 1);
+        if size == 0 {
+            // The mapping was not planned with this page size.
+            self.address = self.end;
+            return None;
+        }
+
+        let run = PageRun {
+            address: self.address,
+            size,
+            page_size,
+        };
+        self.address += size;
+
+        Some(run)
+    }
+}
+
+impl FusedIterator for PageRuns<'_> {}
+
+/// Various errors that can occur while planning a [`Mapping`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PlanMappingError {
+    /// The load bias is not aligned to the page size.
+    MisalignedBias,
+    /// The virtual address and file offset of the segment are not congruent modulo the page size.
+    MisalignedSegment,
+    /// The segment contains more file data than fits in its memory image.
+    FileSizeExceedsMemorySize,
+    /// The top of the stack is not aligned to the page size.
+    MisalignedStack,
+    /// An address or size overflowed.
+    Overflow,
+}
+
+impl fmt::Display for PlanMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MisalignedBias => write!(f, "load bias is not page-aligned"),
+            Self::MisalignedSegment => write!(
+                f,
+                "segment virtual address and file offset are not congruent modulo the page size"
+            ),
+            Self::FileSizeExceedsMemorySize => {
+                write!(f, "segment file size exceeds its memory size")
+            }
+            Self::MisalignedStack => write!(f, "stack top is not page-aligned"),
+            Self::Overflow => write!(f, "segment mapping overflows the address space"),
+        }
+    }
+}
+
+impl error::Error for PlanMappingError {}
+
+/// The information required to load an executable together with its program interpreter.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct InterpreterChain<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`BiasedElfFile`] of the executable.
+    executable: BiasedElfFile<'slice, C, E>,
+    /// The [`BiasedElfFile`] of the program interpreter, if any.
+    interpreter: Option<BiasedElfFile<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> InterpreterChain<'slice, C, E> {
+    /// Creates a new [`InterpreterChain`] from the given `executable` and the program
+    /// `interpreter` located at [`ElfFile::interpreter_path()`], if any.
+    ///
+    /// # Errors
+    ///
+    /// - [`InterpreterChainError::MissingInterpreter`]: Returned if `executable` requests a
+    ///   program interpreter but `interpreter` is `None`.
+    /// - [`InterpreterChainError::UnexpectedInterpreter`]: Returned if `executable` does not
+    ///   request a program interpreter but `interpreter` is `Some`.
+    /// - [`InterpreterChainError::OverlappingImages`]: Returned if `interpreter` is loaded on top
+    ///   of `executable` when both are loaded at their biases.
+    pub fn new(
+        executable: BiasedElfFile<'slice, C, E>,
+        interpreter: Option<BiasedElfFile<'slice, C, E>>,
+    ) -> Result<Self, InterpreterChainError> {
+        match (executable.file().interpreter_path(), interpreter) {
+            (Some(_), None) => return Err(InterpreterChainError::MissingInterpreter),
+            (None, Some(_)) => return Err(InterpreterChainError::UnexpectedInterpreter),
+            _ => {}
+        }
+
+        if let Some(interpreter) = interpreter {
+            let executable_range = image_range(executable);
+            let interpreter_range = image_range(interpreter);
+            if let (Some((exe_start, exe_end)), Some((interp_start, interp_end))) =
+                (executable_range, interpreter_range)
+            {
+                if exe_start < interp_end && interp_start < exe_end {
+                    return Err(InterpreterChainError::OverlappingImages);
+                }
+            }
+        }
+
+        Ok(Self {
+            executable,
+            interpreter,
+        })
+    }
+
+    /// Returns the [`BiasedElfFile`] of the executable.
+    pub fn executable(&self) -> BiasedElfFile<'slice, C, E> {
+        self.executable
+    }
+
+    /// Returns the [`BiasedElfFile`] of the program interpreter, if any.
+    pub fn interpreter(&self) -> Option<BiasedElfFile<'slice, C, E>> {
+        self.interpreter
+    }
+
+    /// Returns the path of the program interpreter requested by the executable.
+    pub fn interpreter_path(&self) -> Option<&'slice [u8]> {
+        self.executable.file().interpreter_path()
+    }
+
+    /// Returns the biased virtual address at which execution should begin.
+    ///
+    /// This is the entry point of the program interpreter if there is one, and the entry point of
+    /// the executable otherwise.
+    pub fn entry(&self) -> u64 {
+        self.interpreter.unwrap_or(self.executable).entry()
+    }
+
+    /// Returns an [`Iterator`] over the [`Mapping`]s required to load the executable followed by
+    /// those required to load the program interpreter.
+    ///
+    /// Returns `None` if `page_size` is not a power of two.
+    pub fn mapping_plan(&self, page_size: u64) -> Option<ChainedMappingPlan<'slice, C, E>> {
+        let executable = MappingPlan::new(self.executable, Image::Executable, page_size)?;
+        let interpreter = match self.interpreter {
+            Some(interpreter) => Some(MappingPlan::new(
+                interpreter,
+                Image::Interpreter,
+                page_size,
+            )?),
+            None => None,
+        };
+
+        Some(ChainedMappingPlan {
+            executable,
+            interpreter,
+        })
+    }
+
+    /// Returns the [`AuxiliaryVector`] to pass to the program, using the given `page_size` and
+    /// `random_address`.
+    pub fn auxiliary_vector(&self, page_size: u64, random_address: u64) -> Option<AuxiliaryVector> {
+        self.executable.file().auxiliary_vector(AuxvOptions {
+            bias: self.executable.bias(),
+            interpreter_base: self.interpreter.map(|interpreter| interpreter.base()),
+            page_size,
+            random_address,
+        })
+    }
+}
+
+/// Returns the lowest and one past the highest biased virtual addresses occupied by the
+/// [`SegmentType::LOAD`] segments of `file`.
+fn image_range<C: ClassParse, E: EncodingParse>(
+    file: BiasedElfFile<'_, C, E>,
+) -> Option<(u64, u64)> {
+    file.file()
+        .program_header_table()?
+        .segments_of_type(SegmentType::LOAD)
+        .filter_map(|program_header| {
+            let start = file.translate(program_header.virtual_address());
+            let end = start.checked_add(program_header.memory_size().into())?;
+            Some((start, end))
+        })
+        .reduce(|(start, end), (other_start, other_end)| {
+            (start.min(other_start), end.max(other_end))
+        })
+}
+
+/// Various errors that can occur while creating an [`InterpreterChain`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InterpreterChainError {
+    /// The executable requests a program interpreter, but none was provided.
+    MissingInterpreter,
+    /// A program interpreter was provided, but the executable does not request one.
+    UnexpectedInterpreter,
+    /// The executable and the program interpreter overlap in memory.
+    OverlappingImages,
+}
+
+impl fmt::Display for InterpreterChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingInterpreter => write!(f, "executable requires a program interpreter"),
+            Self::UnexpectedInterpreter => {
+                write!(f, "executable does not request a program interpreter")
+            }
+            Self::OverlappingImages => {
+                write!(f, "executable and program interpreter overlap in memory")
+            }
+        }
+    }
+}
+
+impl error::Error for InterpreterChainError {}
+
+/// An [`Iterator`] over the [`Mapping`]s required to load an [`InterpreterChain`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ChainedMappingPlan<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`MappingPlan`] of the executable.
+    executable: MappingPlan<'slice, C, E>,
+    /// The [`MappingPlan`] of the program interpreter, if any.
+    interpreter: Option<MappingPlan<'slice, C, E>>,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for ChainedMappingPlan<'_, C, E> {
+    type Item = Result<Mapping, PlanMappingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.executable
+            .next()
+            .or_else(|| self.interpreter.as_mut()?.next())
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for ChainedMappingPlan<'_, C, E> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class::Class64,
+        encoding::LittleEndian,
+        plan::{
+            GuardRegion, Image, InterpreterChain, InterpreterChainError, Mapping, PlanMappingError,
+            StackOptions,
+        },
+        program_header::{SegmentFlags, SegmentType},
+        tests::elf64_header,
+        ElfFile,
+    };
+
+    /// The size of the files built by [`elf64()`].
+    const FILE_SIZE: usize = 0x400;
+    /// The file offset of the program interpreter path in the files built by [`elf64()`].
+    const INTERPRETER_OFFSET: u64 = 0x300;
+    /// The program interpreter path, including its terminating null byte.
+    const INTERPRETER_PATH: &[u8] = b"/lib/ld.so\0";
+
+    /// A program header of a file built by [`elf64()`].
+    struct Segment {
+        /// The [`SegmentType`] of the segment.
+        segment_type: SegmentType,
+        /// The [`SegmentFlags`] of the segment.
+        flags: SegmentFlags,
+        /// The file offset of the segment.
+        offset: u64,
+        /// The virtual address of the segment.
+        address: u64,
+        /// The file size of the segment.
+        file_size: u64,
+        /// The memory size of the segment.
+        memory_size: u64,
+    }
+
+    /// Returns a [`SegmentType::LOAD`] [`Segment`].
+    fn load(
+        flags: SegmentFlags,
+        offset: u64,
+        address: u64,
+        file_size: u64,
+        memory_size: u64,
+    ) -> Segment {
+        Segment {
+            segment_type: SegmentType::LOAD,
+            flags,
+            offset,
+            address,
+            file_size,
+            memory_size,
+        }
+    }
+
+    /// Returns a [`SegmentType::INTERPRETER`] [`Segment`] locating [`INTERPRETER_PATH`].
+    fn interp() -> Segment {
+        Segment {
+            segment_type: SegmentType::INTERPRETER,
+            flags: SegmentFlags::READ,
+            offset: INTERPRETER_OFFSET,
+            address: INTERPRETER_OFFSET,
+            file_size: INTERPRETER_PATH.len() as u64,
+            memory_size: INTERPRETER_PATH.len() as u64,
+        }
+    }
+
+    /// Returns a little-endian 64-bit ELF file with the given `entry` point and `segments`.
+    fn elf64(entry: u64, segments: &[Segment]) -> [u8; FILE_SIZE] {
+        let mut bytes = elf64_header::<FILE_SIZE>(64, segments.len() as u16, 56);
+        bytes[24..32].copy_from_slice(&entry.to_le_bytes());
+
+        for (index, segment) in segments.iter().enumerate() {
+            let header = &mut bytes[64 + index * 56..][..56];
+            header[0..4].copy_from_slice(&segment.segment_type.0.to_le_bytes());
+            header[4..8].copy_from_slice(&segment.flags.0.to_le_bytes());
+            header[8..16].copy_from_slice(&segment.offset.to_le_bytes());
+            header[16..24].copy_from_slice(&segment.address.to_le_bytes());
+            header[24..32].copy_from_slice(&segment.address.to_le_bytes());
+            header[32..40].copy_from_slice(&segment.file_size.to_le_bytes());
+            header[40..48].copy_from_slice(&segment.memory_size.to_le_bytes());
+        }
+
+        let path = &mut bytes[INTERPRETER_OFFSET as usize..][..INTERPRETER_PATH.len()];
+        path.copy_from_slice(INTERPRETER_PATH);
+        bytes
+    }
+
+    /// Returns an executable with a read-only segment, a partially zero-filled writable segment
+    /// a page apart, and a request for a program interpreter.
+    fn executable() -> [u8; FILE_SIZE] {
+        elf64(
+            0x100,
+            &[
+                interp(),
+                load(SegmentFlags::READ, 0, 0, 0x300, 0x300),
+                load(SegmentFlags::WRITE, 0x310, 0x2310, 0x20, 0x1000),
+            ],
+        )
+    }
+
+    /// Returns a program interpreter with a single segment.
+    fn interpreter() -> [u8; FILE_SIZE] {
+        elf64(0x80, &[load(SegmentFlags::EXECUTE, 0, 0, 0x200, 0x200)])
+    }
+
+    /// Segments are widened to whole pages, keeping their file data at the same page offsets.
+    #[test]
+    fn mapping_plan_page_aligns_segments() {
+        let bytes = executable();
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+
+        let mut plan = file.mapping_plan(0x10000, 0x1000).unwrap();
+        assert_eq!(
+            plan.next(),
+            Some(Ok(Mapping {
+                image: Image::Executable,
+                address: 0x10000,
+                memory_size: 0x1000,
+                file_offset: 0,
+                file_size: 0x300,
+                flags: SegmentFlags::READ,
+            })),
+        );
+
+        let mapping = plan.next().unwrap().unwrap();
+        assert_eq!(
+            mapping,
+            Mapping {
+                image: Image::Executable,
+                address: 0x12000,
+                memory_size: 0x2000,
+                file_offset: 0,
+                file_size: 0x330,
+                flags: SegmentFlags::WRITE,
+            },
+        );
+        assert_eq!(mapping.zero_start(), 0x12330);
+        assert_eq!(mapping.end(), 0x14000);
+        assert_eq!(plan.next(), None);
+    }
+
+    /// Planning fails for invalid page sizes, misaligned biases and misaligned segments.
+    #[test]
+    fn mapping_plan_errors() {
+        let bytes = executable();
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+
+        assert!(file.mapping_plan(0, 0x1800).is_none());
+        assert_eq!(
+            file.mapping_plan(0x800, 0x1000).unwrap().next(),
+            Some(Err(PlanMappingError::MisalignedBias)),
+        );
+
+        let bytes = elf64(0, &[load(SegmentFlags::READ, 0x10, 0x1020, 0x10, 0x10)]);
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+        assert_eq!(
+            file.mapping_plan(0, 0x1000).unwrap().next(),
+            Some(Err(PlanMappingError::MisalignedSegment)),
+        );
+    }
+
+    /// Guard regions surround the image and fill gaps too small to hold two guard regions.
+    #[test]
+    fn guard_regions() {
+        let bytes = executable();
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+        let plan = file.mapping_plan(0x10000, 0x1000).unwrap();
+
+        let mut regions = plan.guard_regions(0x800).unwrap();
+        for (address, size) in [(0xF000, 0x1000), (0x11000, 0x1000), (0x14000, 0x1000)] {
+            assert_eq!(regions.next(), Some(Ok(GuardRegion { address, size })));
+        }
+        assert_eq!(regions.next(), None);
+        assert_eq!(plan.guard_regions(0).unwrap().next(), None);
+
+        let plan = file.mapping_plan(0, 0x1000).unwrap();
+        assert_eq!(
+            plan.guard_regions(0x1000).unwrap().next(),
+            Some(Err(PlanMappingError::Overflow)),
+        );
+    }
+
+    /// Without a [`SegmentType::GNU_STACK`] segment the stack uses the default size and is
+    /// executable.
+    #[test]
+    fn stack_region_default() {
+        let bytes = executable();
+        let file = ElfFile::<Class64, LittleEndian>::new(&bytes).unwrap();
+        let plan = file.mapping_plan(0, 0x1000).unwrap();
+
+        let options = StackOptions {
+            top: 0x10_0000,
+            default_size: 0x1800,
+            guard_size: 0x1000,
+        };
+        let stack = plan.stack_region(&options).unwrap();
+        assert_eq!(stack.address, 0xFE000);
+        assert_eq!(stack.end(), 0x10_0000);
+        assert!(stack.flags.contains(SegmentFlags::EXECUTE));
+        assert_eq!(
+            stack.guard,
+            GuardRegion {
+                address: 0xFD000,
+                size: 0x1000,
+            },
+        );
+
+        let options = StackOptions {
+            top: 0x10_0800,
+            ..options
+        };
+        assert_eq!(
+            plan.stack_region(&options),
+            Err(PlanMappingError::MisalignedStack),
+        );
+    }
+
+    /// The interpreter must be provided exactly when it is requested, and must not overlap the
+    /// executable.
+    #[test]
+    fn interpreter_chain_errors() {
+        let executable_bytes = executable();
+        let executable = ElfFile::<Class64, LittleEndian>::new(&executable_bytes).unwrap();
+        let interpreter_bytes = interpreter();
+        let interpreter = ElfFile::<Class64, LittleEndian>::new(&interpreter_bytes).unwrap();
+
+        assert_eq!(
+            InterpreterChain::new(executable.with_bias(0x10000), None),
+            Err(InterpreterChainError::MissingInterpreter),
+        );
+        assert_eq!(
+            InterpreterChain::new(interpreter.with_bias(0), Some(interpreter.with_bias(0))),
+            Err(InterpreterChainError::UnexpectedInterpreter),
+        );
+        assert_eq!(
+            InterpreterChain::new(
+                executable.with_bias(0x10000),
+                Some(interpreter.with_bias(0x12000)),
+            ),
+            Err(InterpreterChainError::OverlappingImages),
+        );
+        assert!(InterpreterChain::new(interpreter.with_bias(0), None).is_ok());
+    }
+
+    /// A chain starts at the interpreter and maps the executable before the interpreter.
+    #[test]
+    fn interpreter_chain_mapping_plan() {
+        let executable_bytes = executable();
+        let executable = ElfFile::<Class64, LittleEndian>::new(&executable_bytes).unwrap();
+        let interpreter_bytes = interpreter();
+        let interpreter = ElfFile::<Class64, LittleEndian>::new(&interpreter_bytes).unwrap();
+
+        let chain = InterpreterChain::new(
+            executable.with_bias(0x10000),
+            Some(interpreter.with_bias(0x40000)),
+        )
+        .unwrap();
+        assert_eq!(
+            chain.interpreter_path(),
+            Some(&INTERPRETER_PATH[..INTERPRETER_PATH.len() - 1]),
+        );
+        assert_eq!(chain.entry(), 0x40080);
+
+        let mut plan = chain.mapping_plan(0x1000).unwrap();
+        for (image, address) in [
+            (Image::Executable, 0x10000),
+            (Image::Executable, 0x12000),
+            (Image::Interpreter, 0x40000),
+        ] {
+            let mapping = plan.next().unwrap().unwrap();
+            assert_eq!((mapping.image, mapping.address), (image, address));
+        }
+        assert_eq!(plan.next(), None);
+
+        let chain = InterpreterChain::new(interpreter.with_bias(0x40000), None).unwrap();
+        assert_eq!(chain.entry(), 0x40080);
+        assert_eq!(chain.mapping_plan(0x1000).unwrap().count(), 1);
+    }
+}
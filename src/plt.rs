@@ -0,0 +1,180 @@
+//! Definitions for mapping procedure linkage table stubs to their targets.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    relocation::{RelTable, RelaTable},
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`PltTable`] described by the [`DynamicTable`][dt] of this [`ElfFile`].
+    ///
+    /// This requires the [`ConstDynamicTag::JMP_REL`], [`ConstDynamicTag::PLT_REL_SIZE`], and
+    /// [`ConstDynamicTag::PLT_REL`] entries to be present.
+    ///
+    /// [dt]: crate::dynamic::DynamicTable
+    pub fn plt_table(&self) -> Option<PltTable<'slice, C, E>> {
+        let dynamic = self.dynamic_table()?;
+
+        let relocations_address = dynamic.find(ConstDynamicTag::JMP_REL)?;
+        let relocations_size = to_len(dynamic.find(ConstDynamicTag::PLT_REL_SIZE)?).ok()?;
+        let relocations_kind: u64 = dynamic.find(ConstDynamicTag::PLT_REL)?.into();
+
+        let relocations_data = self
+            .virtual_address_data(relocations_address)?
+            .get(..relocations_size)?;
+        let relocations = if relocations_kind == ConstDynamicTag::RELA_TABLE.0 as u64 {
+            PltRelocations::Rela(RelaTable::new(
+                self.class,
+                self.encoding,
+                relocations_data,
+                relocations_size / self.class.expected_rela_size(),
+            )?)
+        } else if relocations_kind == ConstDynamicTag::REL_TABLE.0 as u64 {
+            PltRelocations::Rel(RelTable::new(
+                self.class,
+                self.encoding,
+                relocations_data,
+                relocations_size / self.class.expected_rel_size(),
+            )?)
+        } else {
+            return None;
+        };
+
+        let table = PltTable {
+            relocations,
+            plt_got: dynamic.find(ConstDynamicTag::PLT_GOT).map(Into::into),
+            symbol_table: self.dynamic_symbol_table(),
+        };
+
+        Some(table)
+    }
+}
+
+/// A table mapping procedure linkage table stubs to their target symbols and global offset table
+/// slots.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct PltTable<'slice, C, E> {
+    /// The relocations associated with the procedure linkage table.
+    relocations: PltRelocations<'slice, C, E>,
+    /// The address of the global offset table.
+    plt_got: Option<u64>,
+    /// The dynamic symbol table.
+    symbol_table: Option<SymbolTable<'slice, C, E>>,
+}
+
+/// The relocation table associated with the procedure linkage table.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+enum PltRelocations<'slice, C, E> {
+    /// The relocations have implicit addends.
+    Rel(RelTable<'slice, C, E>),
+    /// The relocations have explicit addends.
+    Rela(RelaTable<'slice, C, E>),
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> PltTable<'slice, C, E> {
+    /// Returns the [`PltEntry`] describing the procedure linkage table stub at `index`.
+    ///
+    /// Stub indices do not include the reserved stub used to invoke the dynamic linker.
+    pub fn get(&self, index: usize) -> Option<PltEntry<'slice, C>> {
+        let (offset, info) = match self.relocations {
+            PltRelocations::Rel(table) => table.get(index).map(|rel| (rel.offset, rel.info))?,
+            PltRelocations::Rela(table) => table.get(index).map(|rela| (rela.offset, rela.info))?,
+        };
+
+        let class = match self.relocations {
+            PltRelocations::Rel(table) => table.class,
+            PltRelocations::Rela(table) => table.class,
+        };
+
+        let symbol_index = class.symbol_raw(info);
+        let entry = PltEntry {
+            index,
+            got_slot: offset,
+            symbol_index,
+            relocation_type: class.relocation_type_raw(info),
+            symbol_name: self.symbol_name(symbol_index),
+        };
+
+        Some(entry)
+    }
+
+    /// Returns the number of procedure linkage table stubs described by this [`PltTable`].
+    pub fn count(&self) -> usize {
+        match self.relocations {
+            PltRelocations::Rel(table) => table.count(),
+            PltRelocations::Rela(table) => table.count(),
+        }
+    }
+
+    /// Returns the address of the global offset table associated with the procedure linkage
+    /// table.
+    pub fn plt_got(&self) -> Option<u64> {
+        self.plt_got
+    }
+
+    /// Returns the name of the dynamic symbol at `index`, without the terminating null byte.
+    fn symbol_name(&self, index: u32) -> Option<&'slice [u8]> {
+        self.symbol_table?.get(usize::try_from(index).ok()?)?.name()
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for PltTable<'slice, C, E> {
+    type Item = PltEntry<'slice, C>;
+    type IntoIter = IntoIter<'slice, C, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            next: 0,
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for PltTable<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+/// Description of a single procedure linkage table stub.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PltEntry<'slice, C: ClassParse> {
+    /// The index of the procedure linkage table stub.
+    pub index: usize,
+    /// The address of the global offset table slot used by the procedure linkage table stub.
+    pub got_slot: C::ClassUsize,
+    /// The index of the target symbol in the dynamic symbol table.
+    pub symbol_index: u32,
+    /// The raw relocation type used to fill the global offset table slot.
+    pub relocation_type: u32,
+    /// The name of the target symbol, if it could be located.
+    pub symbol_name: Option<&'slice [u8]>,
+}
+
+/// An [`Iterator`] over the [`PltEntry`]s in a [`PltTable`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct IntoIter<'slice, C, E> {
+    /// The table to iterate over.
+    table: PltTable<'slice, C, E>,
+    /// The index in the [`PltTable`].
+    next: usize,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for IntoIter<'slice, C, E> {
+    type Item = PltEntry<'slice, C>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.table.get(self.next)?;
+
+        self.next += 1;
+        Some(item)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for IntoIter<'slice, C, E> {}
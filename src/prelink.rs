@@ -0,0 +1,718 @@
+//! Definitions for applying the relative relocations of an ELF file ahead of time.
+//!
+//! Applying the relative relocations of a position-independent [`ElfFile`] for a fixed load
+//! address, as prelink does, lets a loader on a system with read-only storage skip them at boot.
+//!
+//! The relative relocations at the start of the dynamic relocation tables, and every relocation
+//! of the RELR table, are applied. The dynamic array of the image is then edited so that the
+//! tables start after the applied relocations, which keeps the dynamic linker from applying them
+//! a second time. The returned [`AppliedRelocations`] records the original tables, so that the
+//! edits can be undone.
+//!
+//! [`ElfFile::rebase()`] goes further for images that are loaded without a dynamic linker, such
+//! as firmware for targets without a memory management unit. Once every relocation is applied,
+//! the addresses of the segments, sections, entry point and dynamic array are moved to the chosen
+//! base and the file becomes an executable that must be loaded there.
+
+use core::{error, fmt, ops::Range};
+
+use crate::{
+    class::ClassParse,
+    dynamic::{ConstDynamicTag, DynamicFlags1, DynamicTable},
+    dynamic_edit::{DynamicArrayEditor, EditDynamicError},
+    encoding::EncodingParse,
+    header::{ElfType, Machine},
+    program_header_edit::{EditProgramHeaderError, ProgramHeaderEditor},
+    relocation::{relative_relocation_type, RelTable, RelaTable},
+    section_edit::{EditSectionError, SectionHeaderEditor},
+    section_header::SectionFlags,
+    writer::ByteLayout,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Writes the relative relocations of this [`ElfFile`], applied for a load address of `base`,
+    /// into `image`, returning the [`AppliedRelocations`] needed to undo them.
+    ///
+    /// `image` must be a copy of the bytes of this [`ElfFile`]. Relocations with explicit addends
+    /// are applied to their addend, while those with implicit addends are applied to the value
+    /// stored at their target. Only the relative relocations preceding the first other relocation
+    /// of the [`ConstDynamicTag::RELA_TABLE`] and [`ConstDynamicTag::REL_TABLE`] tables are
+    /// applied, so that the remaining relocations of each table stay contiguous. Nothing is
+    /// written to `image` if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`PrelinkError::ImageMismatch`]: Returned if `image` is not the same size as this
+    ///   [`ElfFile`].
+    /// - [`PrelinkError::UnsupportedMachine`]: Returned if the relative relocation type of the
+    ///   [`Machine`] of this [`ElfFile`] is not known.
+    /// - [`PrelinkError::ValueOutOfRange`]: Returned if `base` or an edited entry of the dynamic
+    ///   array cannot be represented in the class of this [`ElfFile`].
+    /// - [`PrelinkError::TargetOutOfBounds`]: Returned if a relative relocation targets an
+    ///   address that is not backed by file data.
+    /// - [`PrelinkError::EditDynamicError`]: Returned if the dynamic array of `image` cannot be
+    ///   edited.
+    pub fn apply_relative_relocations(
+        &self,
+        base: u64,
+        image: &mut [u8],
+    ) -> Result<AppliedRelocations, PrelinkError> {
+        let mut image = Image::new(self, base, image)?;
+        let applied = AppliedRelocations {
+            base,
+            ..self.relative_relocation_tables()?
+        };
+        self.for_each_applied_relocation(&applied, |_, _| Ok(()))?;
+
+        image.edit_dynamic::<C, E>(&applied, false)?;
+        self.for_each_applied_relocation(&applied, |target, addend| {
+            let addend = match addend {
+                Some(addend) => addend as u64,
+                None => self.stored_value(target.start),
+            };
+            image
+                .layout
+                .write_word(image.bytes, target.start, base.wrapping_add(addend));
+            Ok(())
+        })?;
+
+        Ok(applied)
+    }
+
+    /// Writes the relative relocations of this [`ElfFile`], reverted from the state described by
+    /// `applied`, into `image`, returning the number of relocations reverted.
+    ///
+    /// `image` must be a copy of the bytes of this [`ElfFile`], which must have been produced by
+    /// [`ElfFile::apply_relative_relocations()`]. Targets of relocations with implicit addends
+    /// regain their original value, while targets of relocations with explicit addends are set
+    /// to their addend, since the dynamic linker ignores their stored value. The entries of the
+    /// dynamic array locating the relocation tables are restored. Nothing is written to `image`
+    /// if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ElfFile::apply_relative_relocations()`].
+    pub fn undo_relative_relocations(
+        &self,
+        applied: AppliedRelocations,
+        image: &mut [u8],
+    ) -> Result<usize, PrelinkError> {
+        let mut image = Image::new(self, applied.base, image)?;
+        self.for_each_applied_relocation(&applied, |_, _| Ok(()))?;
+
+        image.edit_dynamic::<C, E>(&applied, true)?;
+        self.for_each_applied_relocation(&applied, |target, addend| {
+            let value = match addend {
+                Some(addend) => addend as u64,
+                None => self.stored_value(target.start).wrapping_sub(applied.base),
+            };
+            image.layout.write_word(image.bytes, target.start, value);
+            Ok(())
+        })?;
+
+        Ok(applied.count)
+    }
+
+    /// Writes this [`ElfFile`], rebased to be loaded at the fixed address `base`, into `image`,
+    /// returning the [`AppliedRelocations`] applied to it.
+    ///
+    /// `image` must be a copy of the bytes of this [`ElfFile`], which must be a shared object or
+    /// position-independent executable whose dynamic relocations are all relative. The
+    /// relocations are applied as by [`ElfFile::apply_relative_relocations()`] and dropped from
+    /// the dynamic array. Then `base` is added to the addresses of the segments that occupy
+    /// memory, the sections that occupy memory, the entry point and the entries of the dynamic
+    /// array that hold non-zero addresses, and the file is marked as an executable. The values
+    /// of symbols are left unchanged. Nothing is written to `image` if an error is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [`PrelinkError::NotPositionIndependent`]: Returned if this [`ElfFile`] is not a shared
+    ///   object or position-independent executable.
+    /// - [`PrelinkError::UnappliedRelocations`]: Returned if some dynamic relocations cannot be
+    ///   applied ahead of time.
+    /// - [`PrelinkError::ValueOutOfRange`]: Returned if a rebased address cannot be represented
+    ///   in the class of this [`ElfFile`].
+    /// - [`PrelinkError::EditProgramHeaderError`]: Returned if the program header table of
+    ///   `image` cannot be edited.
+    /// - [`PrelinkError::EditSectionError`]: Returned if the section header table of `image`
+    ///   cannot be edited.
+    ///
+    /// Also returns the same errors as [`ElfFile::apply_relative_relocations()`].
+    pub fn rebase(&self, base: u64, image: &mut [u8]) -> Result<AppliedRelocations, PrelinkError> {
+        if self.header().elf_type() != ElfType::SHARED {
+            return Err(PrelinkError::NotPositionIndependent);
+        }
+        let unapplied = self
+            .relocation_count(None)
+            .saturating_sub(self.relative_relocation_tables()?.count);
+        if unapplied != 0 {
+            return Err(PrelinkError::UnappliedRelocations(unapplied));
+        }
+        self.for_each_rebased_address(|address| self.rebased(address, base).map(|_| ()))?;
+
+        let applied = self.apply_relative_relocations(base, image)?;
+
+        let len = image.len();
+        let mut editor = ProgramHeaderEditor::new::<C, E>(image, len)?;
+        for index in 0..editor.count() {
+            let Some(mut program_header) = editor.get(index) else {
+                continue;
+            };
+            if program_header.memory_size == 0 {
+                continue;
+            }
+
+            program_header.virtual_address = self.rebased(program_header.virtual_address, base)?;
+            program_header.physical_address =
+                self.rebased(program_header.physical_address, base)?;
+            editor.set(index, program_header)?;
+        }
+
+        if self.section_header_table().is_some() {
+            let mut editor = SectionHeaderEditor::new::<C, E>(image, len)?;
+            for index in 0..editor.count() {
+                let (Some(flags), Some(address)) = (editor.flags(index), editor.address(index))
+                else {
+                    continue;
+                };
+                if flags.contains(SectionFlags::ALLOC) {
+                    editor.set_address(index, self.rebased(address, base)?)?;
+                }
+            }
+        }
+
+        if self.dynamic_table().is_some() {
+            let mut editor = DynamicArrayEditor::new::<C, E>(image)?;
+            let end = editor.null_position().unwrap_or(editor.count());
+            for index in 0..end {
+                let Some(entry) = editor.get(index) else {
+                    continue;
+                };
+                if holds_address(entry.tag) && entry.value != 0 {
+                    editor.set_value(index, self.rebased(entry.value, base)?)?;
+                } else if entry.tag == i64::from(ConstDynamicTag::FLAGS_1) {
+                    editor.set_value(index, entry.value & !DynamicFlags1::PIE.0)?;
+                }
+            }
+        }
+
+        let image = Image::new(self, base, image)?;
+        let entry = self.header().entry().into();
+        if entry != 0 {
+            let entry = self.rebased(entry, base)?;
+            image
+                .layout
+                .write_word(image.bytes, self.class.entry_offset(), entry);
+        }
+        image.layout.write_u16(
+            image.bytes,
+            self.class.elf_type_offset(),
+            ElfType::EXECUTABLE.0,
+        );
+
+        Ok(applied)
+    }
+
+    /// Calls `f` with each address of this [`ElfFile`] moved by [`ElfFile::rebase()`].
+    fn for_each_rebased_address(
+        &self,
+        mut f: impl FnMut(u64) -> Result<(), PrelinkError>,
+    ) -> Result<(), PrelinkError> {
+        if let Some(table) = self.program_header_table() {
+            for program_header in (0..table.count()).filter_map(|index| table.get(index)) {
+                if program_header.memory_size().into() != 0 {
+                    f(program_header.virtual_address().into())?;
+                    f(program_header.physical_address().into())?;
+                }
+            }
+        }
+        if let Some(table) = self.section_header_table() {
+            for section_header in (0..table.count()).filter_map(|index| table.get(index)) {
+                if section_header.flags().contains(SectionFlags::ALLOC) {
+                    f(section_header.address().into())?;
+                }
+            }
+        }
+        if let Some(dynamic) = self.dynamic_table() {
+            for entry in dynamic {
+                let value = entry.val.into();
+                if holds_address(entry.tag.into()) && value != 0 {
+                    f(value)?;
+                }
+            }
+        }
+
+        f(self.header().entry().into())
+    }
+
+    /// Returns `address` moved to a load address of `base`.
+    fn rebased(&self, address: u64, base: u64) -> Result<u64, PrelinkError> {
+        address
+            .checked_add(base)
+            .filter(|&address| ByteLayout::new(self.header().ident()).fits_word(address))
+            .ok_or(PrelinkError::ValueOutOfRange)
+    }
+
+    /// Returns the [`AppliedRelocations`] describing the relative relocations of this
+    /// [`ElfFile`] that can be applied, with a `base` of zero.
+    fn relative_relocation_tables(&self) -> Result<AppliedRelocations, PrelinkError> {
+        let Some(dynamic) = self.dynamic_table() else {
+            return Ok(AppliedRelocations::default());
+        };
+        let machine = self.header().machine();
+        let relative =
+            || relative_relocation_type(machine).ok_or(PrelinkError::UnsupportedMachine(machine));
+
+        let rela = match self.relocation_data(
+            dynamic,
+            ConstDynamicTag::RELA_TABLE,
+            ConstDynamicTag::RELA_SIZE,
+        ) {
+            Some(data) => {
+                let relative = relative()?;
+                let entry_size = self.class.expected_rela_size();
+                let count =
+                    RelaTable::new(self.class, self.encoding, data, data.len() / entry_size)
+                        .map_or(0, |table| {
+                            table
+                                .into_iter()
+                                .take_while(|rela| {
+                                    self.class.relocation_type_raw(rela.info) == relative
+                                })
+                                .count()
+                        });
+                self.applied_table(
+                    dynamic,
+                    ConstDynamicTag::RELA_TABLE,
+                    Some(ConstDynamicTag::RELA_COUNT),
+                    data,
+                    count * entry_size,
+                    count,
+                )
+            }
+            None => None,
+        };
+        let rel = match self.relocation_data(
+            dynamic,
+            ConstDynamicTag::REL_TABLE,
+            ConstDynamicTag::REL_SIZE,
+        ) {
+            Some(data) => {
+                let relative = relative()?;
+                let entry_size = self.class.expected_rel_size();
+                let count = RelTable::new(self.class, self.encoding, data, data.len() / entry_size)
+                    .map_or(0, |table| {
+                        table
+                            .into_iter()
+                            .take_while(|rel| self.class.relocation_type_raw(rel.info) == relative)
+                            .count()
+                    });
+                self.applied_table(
+                    dynamic,
+                    ConstDynamicTag::REL_TABLE,
+                    Some(ConstDynamicTag::REL_COUNT),
+                    data,
+                    count * entry_size,
+                    count,
+                )
+            }
+            None => None,
+        };
+        let relr = self
+            .relocation_data(
+                dynamic,
+                ConstDynamicTag::RELR_TABLE,
+                ConstDynamicTag::RELR_SIZE,
+            )
+            .and_then(|data| {
+                let mut count = 0;
+                self.for_each_relr_relocation(data, |_| count += 1);
+                self.applied_table(
+                    dynamic,
+                    ConstDynamicTag::RELR_TABLE,
+                    None,
+                    data,
+                    data.len(),
+                    count,
+                )
+            });
+
+        let count = [rela, rel, relr]
+            .iter()
+            .flatten()
+            .map(|table| table.count)
+            .sum();
+        Ok(AppliedRelocations {
+            base: 0,
+            count,
+            rela,
+            rel,
+            relr,
+        })
+    }
+
+    /// Returns the [`AppliedTable`] describing the application of the first `applied_size` bytes
+    /// of the relocation table `data`, located by the `address` entry of `dynamic`, holding
+    /// `count` relocations and whose leading relative relocations are counted by the
+    /// `relative_count` entry, if any.
+    ///
+    /// Returns `None` if no relocation is applied.
+    fn applied_table(
+        &self,
+        dynamic: DynamicTable<'slice, C, E>,
+        address: ConstDynamicTag,
+        relative_count: Option<ConstDynamicTag>,
+        data: &[u8],
+        applied_size: usize,
+        count: usize,
+    ) -> Option<AppliedTable> {
+        if count == 0 {
+            return None;
+        }
+
+        Some(AppliedTable {
+            address: dynamic.find(address)?.into(),
+            size: data.len() as u64,
+            applied_size: applied_size as u64,
+            count,
+            relative_count: relative_count
+                .and_then(|relative_count| dynamic.find(relative_count))
+                .map(Into::into),
+        })
+    }
+
+    /// Calls `f` with the target and explicit addend, if any, of each relative relocation
+    /// described by `applied`.
+    fn for_each_applied_relocation(
+        &self,
+        applied: &AppliedRelocations,
+        mut f: impl FnMut(Range<usize>, Option<i64>) -> Result<(), PrelinkError>,
+    ) -> Result<(), PrelinkError> {
+        if let Some(table) = applied.rela {
+            let data = self.applied_table_data(table)?;
+            let count = data.len() / self.class.expected_rela_size();
+            for rela in RelaTable::new(self.class, self.encoding, data, count)
+                .into_iter()
+                .flatten()
+            {
+                f(
+                    self.relocation_target(rela.offset.into())?,
+                    Some(rela.addend.into()),
+                )?;
+            }
+        }
+        if let Some(table) = applied.rel {
+            let data = self.applied_table_data(table)?;
+            let count = data.len() / self.class.expected_rel_size();
+            for rel in RelTable::new(self.class, self.encoding, data, count)
+                .into_iter()
+                .flatten()
+            {
+                f(self.relocation_target(rel.offset.into())?, None)?;
+            }
+        }
+        if let Some(table) = applied.relr {
+            let data = self.applied_table_data(table)?;
+            let mut result = Ok(());
+            self.for_each_relr_relocation(data, |target| {
+                if result.is_ok() {
+                    result = self
+                        .relocation_target(target)
+                        .and_then(|target| f(target, None));
+                }
+            });
+            result?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bytes of the relocations applied from `table`.
+    fn applied_table_data(&self, table: AppliedTable) -> Result<&'slice [u8], PrelinkError> {
+        C::ClassUsize::try_from(table.address)
+            .ok()
+            .and_then(|address| self.virtual_address_data(address))
+            .and_then(|data| data.get(..usize::try_from(table.applied_size).ok()?))
+            .ok_or(PrelinkError::TargetOutOfBounds(table.address))
+    }
+
+    /// Returns the range of file offsets of the class sized value at `address`.
+    fn relocation_target(&self, address: u64) -> Result<Range<usize>, PrelinkError> {
+        let word_size = ByteLayout::new(self.header().ident()).word_size();
+
+        C::ClassUsize::try_from(address)
+            .ok()
+            .and_then(|address| self.virtual_address_data(address))
+            .and_then(|data| data.get(..word_size))
+            .and_then(|data| self.range_of(data))
+            .ok_or(PrelinkError::TargetOutOfBounds(address))
+    }
+
+    /// Returns the class sized value stored at the file offset `offset`.
+    fn stored_value(&self, offset: usize) -> u64 {
+        self.class
+            .parse_class_usize_at(self.encoding, offset, self.bytes)
+            .into()
+    }
+}
+
+/// The state of the relative relocations applied by [`ElfFile::apply_relative_relocations()`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AppliedRelocations {
+    /// The load address for which the relocations were applied.
+    pub base: u64,
+    /// The number of relocations applied.
+    pub count: usize,
+    /// The [`AppliedTable`] of the [`ConstDynamicTag::RELA_TABLE`] table, if any of its
+    /// relocations were applied.
+    pub rela: Option<AppliedTable>,
+    /// The [`AppliedTable`] of the [`ConstDynamicTag::REL_TABLE`] table, if any of its
+    /// relocations were applied.
+    pub rel: Option<AppliedTable>,
+    /// The [`AppliedTable`] of the [`ConstDynamicTag::RELR_TABLE`] table, if any of its
+    /// relocations were applied.
+    pub relr: Option<AppliedTable>,
+}
+
+/// The original location of a relocation table whose leading relocations were applied by
+/// [`ElfFile::apply_relative_relocations()`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AppliedTable {
+    /// The original address of the table.
+    pub address: u64,
+    /// The original size of the table in bytes.
+    pub size: u64,
+    /// The size in bytes of the applied entries at the start of the table.
+    pub applied_size: u64,
+    /// The number of relocations applied from the table.
+    pub count: usize,
+    /// The original value of the [`ConstDynamicTag::RELA_COUNT`] or
+    /// [`ConstDynamicTag::REL_COUNT`] entry of the table, if present.
+    pub relative_count: Option<u64>,
+}
+
+/// Returns `true` if entries of the dynamic array with `tag` hold an address.
+///
+/// Tags from [`ConstDynamicTag::PREINIT_ARRAY`] up to the operating system specific range hold an
+/// address if they are even, and the GNU address range holds tags such as
+/// [`ConstDynamicTag::GNU_HASH`].
+fn holds_address(tag: i64) -> bool {
+    const ADDRESS_RANGE: core::ops::RangeInclusive<i64> = 0x6FFF_FE00..=0x6FFF_FEFF;
+
+    [
+        ConstDynamicTag::PLT_GOT,
+        ConstDynamicTag::HASH,
+        ConstDynamicTag::STRING_TABLE,
+        ConstDynamicTag::SYMBOL_TABLE,
+        ConstDynamicTag::RELA_TABLE,
+        ConstDynamicTag::INIT,
+        ConstDynamicTag::FINI,
+        ConstDynamicTag::REL_TABLE,
+        ConstDynamicTag::JMP_REL,
+        ConstDynamicTag::INIT_ARRAY,
+        ConstDynamicTag::FINI_ARRAY,
+        ConstDynamicTag::VERSYM,
+        ConstDynamicTag::VERDEF,
+        ConstDynamicTag::VERNEED,
+    ]
+    .into_iter()
+    .any(|address| i64::from(address) == tag)
+        || (i64::from(ConstDynamicTag::PREINIT_ARRAY)..0x6000_0000).contains(&tag) && tag % 2 == 0
+        || ADDRESS_RANGE.contains(&tag)
+}
+
+/// A writable copy of the bytes of an [`ElfFile`].
+struct Image<'image> {
+    /// The bytes of the image.
+    bytes: &'image mut [u8],
+    /// The [`ByteLayout`] of the values of the image.
+    layout: ByteLayout,
+}
+
+impl<'image> Image<'image> {
+    /// Creates a new [`Image`] over `bytes`, which must be a copy of `file` that relocations are
+    /// applied to for a load address of `base`.
+    fn new<C: ClassParse, E: EncodingParse>(
+        file: &ElfFile<'_, C, E>,
+        base: u64,
+        bytes: &'image mut [u8],
+    ) -> Result<Self, PrelinkError> {
+        if bytes.len() != file.bytes.len() {
+            return Err(PrelinkError::ImageMismatch);
+        }
+
+        let layout = ByteLayout::new(file.header().ident());
+        if !layout.fits_word(base) {
+            return Err(PrelinkError::ValueOutOfRange);
+        }
+
+        Ok(Self { bytes, layout })
+    }
+
+    /// Edits the dynamic array of this [`Image`] so that the relocation tables exclude the
+    /// relocations described by `applied`, or include them again if `restore` is `true`.
+    ///
+    /// Every edit is validated before the first one is written.
+    fn edit_dynamic<C: ClassParse, E: EncodingParse>(
+        &mut self,
+        applied: &AppliedRelocations,
+        restore: bool,
+    ) -> Result<(), PrelinkError> {
+        let tables = [
+            (
+                applied.rela,
+                ConstDynamicTag::RELA_TABLE,
+                ConstDynamicTag::RELA_SIZE,
+                Some(ConstDynamicTag::RELA_COUNT),
+            ),
+            (
+                applied.rel,
+                ConstDynamicTag::REL_TABLE,
+                ConstDynamicTag::REL_SIZE,
+                Some(ConstDynamicTag::REL_COUNT),
+            ),
+            (
+                applied.relr,
+                ConstDynamicTag::RELR_TABLE,
+                ConstDynamicTag::RELR_SIZE,
+                None,
+            ),
+        ];
+        let mut edits = [None; 9];
+        let mut edit_slots = edits.iter_mut();
+        for (table, address, size, relative_count) in tables {
+            let Some(table) = table else {
+                continue;
+            };
+
+            let applied_values = (
+                table.address.wrapping_add(table.applied_size),
+                table.size.wrapping_sub(table.applied_size),
+                table
+                    .relative_count
+                    .map(|relative_count| relative_count.saturating_sub(table.count as u64)),
+            );
+            let values = if restore {
+                (table.address, table.size, table.relative_count)
+            } else {
+                applied_values
+            };
+
+            for edit in [
+                Some((address, values.0)),
+                Some((size, values.1)),
+                relative_count.zip(values.2),
+            ] {
+                if let Some(slot) = edit_slots.next() {
+                    *slot = edit;
+                }
+            }
+        }
+
+        if edits.iter().all(Option::is_none) {
+            return Ok(());
+        }
+
+        let mut editor = DynamicArrayEditor::new::<C, E>(self.bytes)?;
+        let mut positions = [None; 9];
+        for (position, edit) in positions.iter_mut().zip(edits) {
+            let Some((tag, value)) = edit else {
+                continue;
+            };
+            if !self.layout.fits_word(value) {
+                return Err(PrelinkError::ValueOutOfRange);
+            }
+
+            *position = Some((
+                editor
+                    .position(tag.into())
+                    .ok_or(EditDynamicError::IndexOutOfBounds)?,
+                value,
+            ));
+        }
+        for (index, value) in positions.into_iter().flatten() {
+            editor.set_value(index, value)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Various errors that can occur while applying the relative relocations of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum PrelinkError {
+    /// The image is not the same size as the [`ElfFile`].
+    ImageMismatch,
+    /// The relative relocation type of the [`Machine`] is not known.
+    UnsupportedMachine(Machine),
+    /// The load address or an entry of the dynamic array cannot be represented in the class of
+    /// the [`ElfFile`].
+    ValueOutOfRange,
+    /// A relative relocation targets an address that is not backed by file data.
+    TargetOutOfBounds(u64),
+    /// An error occurred while editing the dynamic array of the image.
+    EditDynamicError(EditDynamicError),
+    /// The [`ElfFile`] is not a shared object or position-independent executable.
+    NotPositionIndependent,
+    /// Some dynamic relocations cannot be applied ahead of time.
+    UnappliedRelocations(usize),
+    /// An error occurred while editing the program header table of the image.
+    EditProgramHeaderError(EditProgramHeaderError),
+    /// An error occurred while editing the section header table of the image.
+    EditSectionError(EditSectionError),
+}
+
+impl From<EditProgramHeaderError> for PrelinkError {
+    fn from(value: EditProgramHeaderError) -> Self {
+        Self::EditProgramHeaderError(value)
+    }
+}
+
+impl From<EditSectionError> for PrelinkError {
+    fn from(value: EditSectionError) -> Self {
+        Self::EditSectionError(value)
+    }
+}
+
+impl From<EditDynamicError> for PrelinkError {
+    fn from(value: EditDynamicError) -> Self {
+        Self::EditDynamicError(value)
+    }
+}
+
+impl fmt::Display for PrelinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ImageMismatch => write!(f, "image size does not match the ELF file"),
+            Self::UnsupportedMachine(machine) => {
+                write!(f, "relative relocation type of {machine:?} is not known")
+            }
+            Self::ValueOutOfRange => write!(f, "value out of range for the ELF class"),
+            Self::TargetOutOfBounds(address) => {
+                write!(
+                    f,
+                    "relocation target {address:#x} is not backed by file data"
+                )
+            }
+            Self::EditDynamicError(error) => {
+                write!(f, "error while editing the dynamic array: {error}")
+            }
+            Self::NotPositionIndependent => write!(f, "ELF file is not position-independent"),
+            Self::UnappliedRelocations(count) => {
+                write!(
+                    f,
+                    "{count} dynamic relocations cannot be applied ahead of time"
+                )
+            }
+            Self::EditProgramHeaderError(error) => {
+                write!(f, "error while editing the program header table: {error}")
+            }
+            Self::EditSectionError(error) => {
+                write!(f, "error while editing the section header table: {error}")
+            }
+        }
+    }
+}
+
+impl error::Error for PrelinkError {}
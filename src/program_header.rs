@@ -1,6 +1,6 @@
 //! Definitions for the ELF program headers.
 
-use core::fmt;
+use core::{fmt, iter::FusedIterator};
 
 use crate::{
     class::{ClassParse, ClassParseBase},
@@ -38,6 +38,14 @@ impl<'slice, C: ClassParse, E: EncodingParse> ProgramHeader<'slice, C, E> {
         Some(program_header)
     }
 
+    /// Returns the bytes of this [`ProgramHeader`].
+    ///
+    /// For a [`ProgramHeader`] obtained from a [`ProgramHeaderTable`], this is exactly the entry
+    /// in the table, including any bytes beyond the fields defined by the ELF specification.
+    pub fn raw_bytes(&self) -> &'slice [u8] {
+        self.bytes
+    }
+
     /// Validates that this [`ProgramHeader`] matches the ELF specification and is supported by
     /// this crate.
     ///
@@ -148,6 +156,17 @@ impl<'slice, C: ClassParse, E: EncodingParse> ProgramHeader<'slice, C, E> {
             self.bytes,
         )
     }
+
+    /// Returns the bytes of this [`ProgramHeader`] that follow the fields defined by the ELF
+    /// specification.
+    ///
+    /// These bytes are present when the size of each entry in the [`ProgramHeaderTable`] is larger
+    /// than the expected size of a [`ProgramHeader`].
+    pub fn trailing_bytes(&self) -> &'slice [u8] {
+        self.bytes
+            .get(self.class.expected_program_header_size()..)
+            .unwrap_or(&[])
+    }
 }
 
 impl<C: ClassParse, E: EncodingParse> fmt::Debug for ProgramHeader<'_, C, E> {
@@ -200,6 +219,47 @@ impl fmt::Display for ValidateProgramHeaderSpecError {
     }
 }
 
+/// Various errors that can occur when validating that the [`SegmentType::PHDR`] segment of an
+/// ELF file is consistent with the ELF header.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ValidatePhdrSegmentError {
+    /// More than one [`SegmentType::PHDR`] segment is present.
+    MultipleSegments,
+    /// The [`SegmentType::PHDR`] segment follows a [`SegmentType::LOAD`] segment.
+    FollowsLoadSegment,
+    /// The file offset of the [`SegmentType::PHDR`] segment differs from the program header
+    /// table offset in the ELF header.
+    OffsetMismatch,
+    /// The size of the [`SegmentType::PHDR`] segment differs from the size of the program header
+    /// table described by the ELF header.
+    SizeMismatch,
+    /// The [`SegmentType::PHDR`] segment is not mapped by a [`SegmentType::LOAD`] segment at its
+    /// virtual address.
+    NotInLoadSegment,
+}
+
+impl fmt::Display for ValidatePhdrSegmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MultipleSegments => write!(f, "multiple program header segments present"),
+            Self::FollowsLoadSegment => {
+                write!(f, "program header segment follows a load segment")
+            }
+            Self::OffsetMismatch => write!(
+                f,
+                "program header segment offset does not match program header table offset"
+            ),
+            Self::SizeMismatch => write!(
+                f,
+                "program header segment size does not match program header table size"
+            ),
+            Self::NotInLoadSegment => {
+                write!(f, "program header segment is not mapped by a load segment")
+            }
+        }
+    }
+}
+
 /// The type of the segment the associated [`ProgramHeader`] contains.
 #[repr(transparent)]
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -222,6 +282,23 @@ impl SegmentType {
     pub const PHDR: Self = Self(6);
     /// Thread local storage.
     pub const TLS: Self = Self(7);
+    /// GNU exception handling frame header.
+    pub const GNU_EH_FRAME: Self = Self(0x6474_E550);
+    /// GNU stack executability.
+    pub const GNU_STACK: Self = Self(0x6474_E551);
+    /// GNU read-only after relocation.
+    pub const GNU_RELRO: Self = Self(0x6474_E552);
+    /// GNU program properties.
+    pub const GNU_PROPERTY: Self = Self(0x6474_E553);
+    /// GNU SFrame stack trace information.
+    pub const GNU_SFRAME: Self = Self(0x6474_E554);
+    /// AArch64 memory tags of a core dump.
+    ///
+    /// This value is processor specific and only has this meaning for
+    /// [`Machine::AARCH64`][m].
+    ///
+    /// [m]: crate::header::Machine::AARCH64
+    pub const AARCH64_MEMTAG_MTE: Self = Self(0x7000_0002);
 }
 
 impl fmt::Debug for SegmentType {
@@ -235,11 +312,35 @@ impl fmt::Debug for SegmentType {
             Self::SHLIB => f.pad("Shlib"),
             Self::PHDR => f.pad("ProgramHeaders"),
             Self::TLS => f.pad("Tls"),
+            Self::GNU_EH_FRAME => f.pad("GnuEhFrame"),
+            Self::GNU_STACK => f.pad("GnuStack"),
+            Self::GNU_RELRO => f.pad("GnuRelro"),
+            Self::GNU_PROPERTY => f.pad("GnuProperty"),
+            Self::GNU_SFRAME => f.pad("GnuSframe"),
+            Self::AARCH64_MEMTAG_MTE => f.pad("Aarch64MemtagMte"),
             segment_type => f.debug_tuple("SegmentType").field(&segment_type.0).finish(),
         }
     }
 }
 
+impl From<u32> for SegmentType {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SegmentType> for u32 {
+    fn from(value: SegmentType) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u32> for SegmentType {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
 /// The permissions of a [`SegmentType::LOAD`] segment.
 #[repr(transparent)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -257,6 +358,11 @@ impl SegmentFlags {
     pub const MASK_OS: Self = Self(0x0FF0_FFFF);
     /// Mask of the bits reserved for processor specific semantics.
     pub const MASK_PROCESSOR: Self = Self(0xF000_0000);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`SegmentFlags`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
 }
 
 impl core::ops::BitOr for SegmentFlags {
@@ -349,8 +455,9 @@ impl<'slice, C: ClassParse, E: EncodingParse> ProgramHeaderTable<'slice, C, E> {
             return None;
         }
 
+        let start = usize::from(index) * usize::from(self.entry_size);
         let program_header = ProgramHeader {
-            bytes: &self.bytes[usize::from(index * self.entry_size)..],
+            bytes: &self.bytes[start..start + usize::from(self.entry_size)],
             class: self.class,
             encoding: self.encoding,
         };
@@ -362,6 +469,46 @@ impl<'slice, C: ClassParse, E: EncodingParse> ProgramHeaderTable<'slice, C, E> {
     pub fn count(&self) -> u16 {
         self.entry_count
     }
+
+    /// Limits this [`ProgramHeaderTable`] to at most `max_entries` [`ProgramHeader`]s.
+    pub fn with_max_entries(mut self, max_entries: u16) -> Self {
+        self.entry_count = self.entry_count.min(max_entries);
+        self
+    }
+
+    /// Writes the indices of the [`ProgramHeader`]s in this [`ProgramHeaderTable`] into `indices`,
+    /// sorted by virtual address, and returns the written indices.
+    ///
+    /// [`ProgramHeader`]s with equal virtual addresses keep their file order.
+    ///
+    /// Returns `None` if `indices` is smaller than [`ProgramHeaderTable::count()`].
+    pub fn sorted_by_virtual_address<'indices>(
+        &self,
+        indices: &'indices mut [u16],
+    ) -> Option<&'indices [u16]> {
+        let indices = indices.get_mut(..usize::from(self.entry_count))?;
+        for (slot, index) in indices.iter_mut().zip(0..) {
+            *slot = index;
+        }
+
+        indices.sort_unstable_by_key(|&index| {
+            let virtual_address: u64 = self
+                .get(index)
+                .map_or(0, |program_header| program_header.virtual_address().into());
+            (virtual_address, index)
+        });
+
+        Some(indices)
+    }
+
+    /// Returns an [`Iterator`] over the [`ProgramHeader`]s in this [`ProgramHeaderTable`] that
+    /// describe segments of the given [`SegmentType`].
+    pub fn segments_of_type(self, segment_type: SegmentType) -> SegmentsOfType<'slice, C, E> {
+        SegmentsOfType {
+            iter: self.into_iter(),
+            segment_type,
+        }
+    }
 }
 
 impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for ProgramHeaderTable<'slice, C, E> {
@@ -402,6 +549,30 @@ impl<'slice, C: ClassParse, E: EncodingParse> Iterator for IntoIter<'slice, C, E
     }
 }
 
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for IntoIter<'slice, C, E> {}
+
+/// An [`Iterator`] over the [`ProgramHeader`]s in a [`ProgramHeaderTable`] that describe segments
+/// of a particular [`SegmentType`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SegmentsOfType<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`Iterator`] over the [`ProgramHeaderTable`].
+    iter: IntoIter<'slice, C, E>,
+    /// The [`SegmentType`] to yield.
+    segment_type: SegmentType,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SegmentsOfType<'slice, C, E> {
+    type Item = ProgramHeader<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let segment_type = self.segment_type;
+        self.iter
+            .find(|program_header| program_header.segment_type() == segment_type)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for SegmentsOfType<'slice, C, E> {}
+
 /// The information required to implement class aware parsing of an ELF program header.
 pub trait ClassParseProgramHeader: ClassParseBase {
     /// The offset of the [`SegmentType`].
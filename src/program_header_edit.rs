@@ -0,0 +1,655 @@
+//! Definitions for editing the program header table of an ELF file in place.
+//!
+//! [`ProgramHeaderEditor`] inserts, removes and overwrites entries of the program header table
+//! without moving the contents of any segment. An entry is inserted in place if the bytes
+//! following the table are unused, and otherwise the table is relocated to the end of the file,
+//! which grows into the spare bytes of the buffer.
+//!
+//! A file with a [`SegmentType::PHDR`] segment expects its program header table to be loaded, so
+//! a relocated table is covered by a new [`SegmentType::LOAD`] segment placed above every other
+//! segment, and the [`SegmentType::PHDR`] segment is kept describing the table.
+
+use core::{error, fmt, ops::Range};
+
+use crate::{
+    class::{AnyClass, Class32, Class64, ClassParse},
+    encoding::{AnyEndian, EncodingParse},
+    header::ClassParseElfHeader,
+    program_header::{ClassParseProgramHeader, SegmentFlags, SegmentType},
+    writer::ByteLayout,
+    ElfFile, ParseElfFileError,
+};
+
+/// The value of `e_phnum` indicating that the number of program headers does not fit.
+const PN_XNUM: u16 = 0xFFFF;
+
+/// A raw entry of the program header table, with class sized values widened to 64 bits.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RawProgramHeader {
+    /// The [`SegmentType`] of the segment.
+    pub segment_type: SegmentType,
+    /// The [`SegmentFlags`] of the segment.
+    pub flags: SegmentFlags,
+    /// The offset of the file data of the segment.
+    pub file_offset: u64,
+    /// The virtual address of the segment.
+    pub virtual_address: u64,
+    /// The physical address of the segment.
+    pub physical_address: u64,
+    /// The size of the file data of the segment.
+    pub file_size: u64,
+    /// The size of the segment in memory.
+    pub memory_size: u64,
+    /// The alignment of the segment.
+    pub alignment: u64,
+}
+
+/// An editor over the program header table of an ELF file.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct ProgramHeaderEditor<'buffer> {
+    /// The buffer holding the ELF file, followed by spare bytes the file may grow into.
+    bytes: &'buffer mut [u8],
+    /// The number of bytes of the buffer occupied by the ELF file.
+    len: usize,
+    /// The offset of the program header table.
+    table_offset: usize,
+    /// The number of entries in the program header table.
+    count: u16,
+    /// The stride of each entry in the program header table.
+    entry_size: usize,
+    /// The [`ByteLayout`] of the values of the ELF file.
+    layout: ByteLayout,
+}
+
+impl<'buffer> ProgramHeaderEditor<'buffer> {
+    /// Creates a new [`ProgramHeaderEditor`] over the program header table of the ELF file
+    /// occupying the first `len` bytes of `bytes`.
+    ///
+    /// Any bytes of `bytes` following the ELF file are used when an insertion requires the
+    /// program header table to be relocated.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditProgramHeaderError::BufferTooSmall`]: Returned if `bytes` is smaller than `len`.
+    /// - [`EditProgramHeaderError::ParseElfFileError`]: Returned if the first `len` bytes of
+    ///   `bytes` do not contain a valid [`ElfFile`].
+    /// - [`EditProgramHeaderError::InvalidEntrySize`]: Returned if the entries of the program
+    ///   header table are smaller than a program header.
+    pub fn new<C: ClassParse, E: EncodingParse>(
+        bytes: &'buffer mut [u8],
+        len: usize,
+    ) -> Result<Self, EditProgramHeaderError> {
+        let file = ElfFile::<C, E>::new(
+            bytes
+                .get(..len)
+                .ok_or(EditProgramHeaderError::BufferTooSmall { required: len })?,
+        )?;
+        let header = file.header();
+        let ident = header.ident();
+        let layout = ByteLayout::new(ident);
+        let expected_size = if layout.wide {
+            Class64.expected_program_header_size()
+        } else {
+            Class32.expected_program_header_size()
+        };
+
+        let count = header.program_header_count();
+        let (table_offset, entry_size) = match file.program_header_table() {
+            Some(table) => (
+                table
+                    .get(0)
+                    .and_then(|program_header| file.range_of(program_header.raw_bytes()))
+                    .map_or(0, |range| range.start),
+                usize::from(header.program_header_size()),
+            ),
+            None => (0, expected_size),
+        };
+        if entry_size < expected_size {
+            return Err(EditProgramHeaderError::InvalidEntrySize);
+        }
+
+        Ok(Self {
+            table_offset,
+            count,
+            entry_size,
+            layout,
+            bytes,
+            len,
+        })
+    }
+
+    /// Returns the bytes of the edited ELF file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Returns the number of bytes of the buffer occupied by the edited ELF file.
+    pub fn file_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of entries in the program header table.
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns the offset of the program header table.
+    pub fn table_offset(&self) -> usize {
+        self.table_offset
+    }
+
+    /// Returns the [`RawProgramHeader`] located at `index`.
+    pub fn get(&self, index: u16) -> Option<RawProgramHeader> {
+        if index >= self.count {
+            return None;
+        }
+
+        let start = self.entry_offset(index);
+        let fields = self.fields();
+        Some(RawProgramHeader {
+            segment_type: SegmentType(
+                self.layout.read(self.bytes, start + fields.segment_type, 4) as u32
+            ),
+            flags: SegmentFlags(self.layout.read(self.bytes, start + fields.flags, 4) as u32),
+            file_offset: self
+                .layout
+                .read_word(self.bytes, start + fields.file_offset),
+            virtual_address: self
+                .layout
+                .read_word(self.bytes, start + fields.virtual_address),
+            physical_address: self
+                .layout
+                .read_word(self.bytes, start + fields.physical_address),
+            file_size: self.layout.read_word(self.bytes, start + fields.file_size),
+            memory_size: self
+                .layout
+                .read_word(self.bytes, start + fields.memory_size),
+            alignment: self.layout.read_word(self.bytes, start + fields.alignment),
+        })
+    }
+
+    /// Returns the index of the first entry of the given [`SegmentType`].
+    pub fn position(&self, segment_type: SegmentType) -> Option<u16> {
+        (0..self.count).find(|&index| {
+            self.get(index)
+                .is_some_and(|program_header| program_header.segment_type == segment_type)
+        })
+    }
+
+    /// Overwrites the entry located at `index` with `program_header`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditProgramHeaderError::IndexOutOfBounds`]: Returned if `index` is out of bounds.
+    /// - [`EditProgramHeaderError::ValueOutOfRange`]: Returned if `program_header` cannot be
+    ///   represented in the class of the ELF file.
+    pub fn set(
+        &mut self,
+        index: u16,
+        program_header: RawProgramHeader,
+    ) -> Result<(), EditProgramHeaderError> {
+        if index >= self.count {
+            return Err(EditProgramHeaderError::IndexOutOfBounds);
+        }
+        self.check_representable(&program_header)?;
+
+        self.write_entry(index, &program_header);
+        Ok(())
+    }
+
+    /// Inserts `program_header` at `index`, moving the following entries up by one.
+    ///
+    /// The program header table grows in place if the bytes following it are zero and unused by
+    /// the ELF header, the section header table, any section and any segment other than the
+    /// [`SegmentType::LOAD`] segment containing the table. Otherwise, the table is relocated to
+    /// the end of the file, adding a [`SegmentType::LOAD`] segment that maps it if the file has a
+    /// [`SegmentType::PHDR`] segment.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditProgramHeaderError::IndexOutOfBounds`]: Returned if `index` is greater than the
+    ///   number of entries.
+    /// - [`EditProgramHeaderError::ValueOutOfRange`]: Returned if `program_header`, the number of
+    ///   entries or the relocated table cannot be represented in the class of the ELF file.
+    /// - [`EditProgramHeaderError::BufferTooSmall`]: Returned if the buffer has too few spare
+    ///   bytes to hold the relocated table.
+    pub fn insert(
+        &mut self,
+        index: u16,
+        program_header: RawProgramHeader,
+    ) -> Result<(), EditProgramHeaderError> {
+        if index > self.count {
+            return Err(EditProgramHeaderError::IndexOutOfBounds);
+        }
+        self.check_representable(&program_header)?;
+
+        if self.has_slack() {
+            self.check_count(1)?;
+            self.insert_in_place(index, &program_header);
+        } else if self.phdr_index().is_some() {
+            let start = self.relocated_offset()?;
+            let load = self.table_load(start, &program_header);
+            self.check_representable(&load)?;
+
+            self.relocate(start, 2)?;
+            self.insert_in_place(index, &program_header);
+            let load_index = (0..self.count)
+                .rfind(|&index| {
+                    self.get(index).is_some_and(|program_header| {
+                        program_header.segment_type == SegmentType::LOAD
+                    })
+                })
+                .map_or(self.count, |index| index + 1);
+            self.insert_in_place(load_index, &load);
+        } else {
+            let start = self.relocated_offset()?;
+            self.relocate(start, 1)?;
+            self.insert_in_place(index, &program_header);
+        }
+
+        self.update_phdr();
+        Ok(())
+    }
+
+    /// Removes the entry located at `index`, moving the following entries down by one.
+    ///
+    /// The entry slot freed at the end of the program header table is zeroed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditProgramHeaderError::IndexOutOfBounds`] if `index` is out of bounds.
+    pub fn remove(&mut self, index: u16) -> Result<RawProgramHeader, EditProgramHeaderError> {
+        let removed = self
+            .get(index)
+            .ok_or(EditProgramHeaderError::IndexOutOfBounds)?;
+
+        let start = self.entry_offset(index);
+        let end = self.entry_offset(self.count);
+        self.bytes.copy_within(start + self.entry_size..end, start);
+        self.bytes[end - self.entry_size..end].fill(0);
+        self.set_count(self.count - 1);
+
+        self.update_phdr();
+        Ok(removed)
+    }
+
+    /// Returns `true` if the entry slot following the program header table is unused.
+    fn has_slack(&self) -> bool {
+        let start = self.entry_offset(self.count);
+        let Some(slack) = start
+            .checked_add(self.entry_size)
+            .filter(|&end| self.count != 0 && end <= self.len)
+            .map(|end| start..end)
+        else {
+            return false;
+        };
+        if self.bytes[slack.clone()].iter().any(|&byte| byte != 0) {
+            return false;
+        }
+
+        let Ok(file) = ElfFile::<AnyClass, AnyEndian>::new(self.bytes()) else {
+            return false;
+        };
+        let overlaps = |range: Range<usize>| range.start < slack.end && slack.start < range.end;
+
+        let header_size = usize::from(file.header().header_size());
+        if overlaps(0..header_size) {
+            return false;
+        }
+        if let Some(table) = file.section_header_table() {
+            let section_header_table = table
+                .get(0)
+                .and_then(|section_header| file.range_of(section_header.raw_bytes()))
+                .map(|range| range.start..range.start + usize::from(table.count()) * range.len());
+            if section_header_table.is_some_and(overlaps)
+                || table
+                    .into_iter()
+                    .filter_map(|section_header| file.section_data(section_header))
+                    .filter_map(|data| file.range_of(data))
+                    .any(overlaps)
+            {
+                return false;
+            }
+        }
+
+        // The slack must be loaded along with the table if the table is loaded.
+        let mut containing_load = self.phdr_index().is_none();
+        let Some(table) = file.program_header_table() else {
+            return false;
+        };
+        for program_header in table {
+            let Some(range) = file
+                .segment_data(program_header)
+                .and_then(|data| file.range_of(data))
+            else {
+                continue;
+            };
+
+            let segment_type = program_header.segment_type();
+            if segment_type == SegmentType::LOAD {
+                containing_load |= range.start <= self.table_offset && slack.end <= range.end;
+            } else if segment_type != SegmentType::PHDR && overlaps(range) {
+                return false;
+            }
+        }
+
+        containing_load
+    }
+
+    /// Returns the offset to which the program header table is relocated.
+    fn relocated_offset(&self) -> Result<usize, EditProgramHeaderError> {
+        self.len
+            .checked_next_multiple_of(self.layout.word_size())
+            .ok_or(EditProgramHeaderError::ValueOutOfRange)
+    }
+
+    /// Moves the program header table to `start`, at the end of the file, leaving room for
+    /// `extra` more entries.
+    fn relocate(&mut self, start: usize, extra: u16) -> Result<(), EditProgramHeaderError> {
+        self.check_count(extra)?;
+
+        let table_size = usize::from(self.count + extra) * self.entry_size;
+        let required = start
+            .checked_add(table_size)
+            .ok_or(EditProgramHeaderError::ValueOutOfRange)?;
+        if self.bytes.len() < required {
+            return Err(EditProgramHeaderError::BufferTooSmall { required });
+        }
+        if !self.layout.fits_word(required as u64) {
+            return Err(EditProgramHeaderError::ValueOutOfRange);
+        }
+
+        let old_table = self.table_offset..self.entry_offset(self.count);
+        self.bytes[self.len..required].fill(0);
+        self.bytes.copy_within(old_table, start);
+        self.len = required;
+        self.table_offset = start;
+
+        let offset = self.header_field(
+            Class64.program_header_offset_offset(),
+            Class32.program_header_offset_offset(),
+        );
+        self.layout.write_word(self.bytes, offset, start as u64);
+        let entry_size = self.header_field(
+            Class64.program_header_size_offset(),
+            Class32.program_header_size_offset(),
+        );
+        self.layout
+            .write(self.bytes, entry_size, 2, self.entry_size as u64);
+        Ok(())
+    }
+
+    /// Returns the [`SegmentType::LOAD`] segment mapping the program header table relocated to
+    /// `offset` after `inserted` is added to it, placed above every other segment.
+    fn table_load(&self, offset: usize, inserted: &RawProgramHeader) -> RawProgramHeader {
+        let loads = (0..self.count)
+            .filter_map(|index| self.get(index))
+            .chain([*inserted])
+            .filter(|program_header| program_header.segment_type == SegmentType::LOAD);
+        let alignment = loads
+            .clone()
+            .map(|program_header| program_header.alignment)
+            .filter(|alignment| alignment.is_power_of_two())
+            .max()
+            .unwrap_or(1);
+        let end = loads
+            .map(|program_header| {
+                program_header
+                    .virtual_address
+                    .saturating_add(program_header.memory_size)
+            })
+            .max()
+            .unwrap_or(0);
+
+        let offset = offset as u64;
+        let virtual_address = end
+            .checked_next_multiple_of(alignment)
+            .and_then(|address| address.checked_add(offset % alignment))
+            .unwrap_or(u64::MAX);
+        let size = (u64::from(self.count) + 2) * self.entry_size as u64;
+        RawProgramHeader {
+            segment_type: SegmentType::LOAD,
+            flags: SegmentFlags::READ,
+            file_offset: offset,
+            virtual_address,
+            physical_address: virtual_address,
+            file_size: size,
+            memory_size: size,
+            alignment,
+        }
+    }
+
+    /// Updates the [`SegmentType::PHDR`] segment, if any, to describe the program header table.
+    fn update_phdr(&mut self) {
+        let Some(index) = self.phdr_index() else {
+            return;
+        };
+        let Some(mut phdr) = self.get(index) else {
+            return;
+        };
+
+        let offset = self.table_offset as u64;
+        let size = u64::from(self.count) * self.entry_size as u64;
+        if phdr.file_offset != offset {
+            // The table was relocated, so it is mapped by the segment added by `table_load`.
+            if let Some(load) = (0..self.count)
+                .filter_map(|index| self.get(index))
+                .find(|load| load.segment_type == SegmentType::LOAD && load.file_offset == offset)
+            {
+                phdr.virtual_address = load.virtual_address;
+                phdr.physical_address = load.physical_address;
+            }
+            phdr.file_offset = offset;
+        }
+        phdr.file_size = size;
+        phdr.memory_size = size;
+
+        if self.check_representable(&phdr).is_ok() {
+            self.write_entry(index, &phdr);
+        }
+    }
+
+    /// Inserts `program_header` at `index`, which must be valid, into the room following the
+    /// program header table.
+    fn insert_in_place(&mut self, index: u16, program_header: &RawProgramHeader) {
+        let start = self.entry_offset(index);
+        let end = self.entry_offset(self.count);
+        self.bytes.copy_within(start..end, start + self.entry_size);
+        self.bytes[start..start + self.entry_size].fill(0);
+        self.set_count(self.count + 1);
+        self.write_entry(index, program_header);
+    }
+
+    /// Returns the index of the [`SegmentType::PHDR`] segment.
+    fn phdr_index(&self) -> Option<u16> {
+        self.position(SegmentType::PHDR)
+    }
+
+    /// Returns an error if `additional` entries cannot be added to the program header table.
+    fn check_count(&self, additional: u16) -> Result<(), EditProgramHeaderError> {
+        match self.count.checked_add(additional) {
+            Some(count) if count < PN_XNUM => Ok(()),
+            _ => Err(EditProgramHeaderError::ValueOutOfRange),
+        }
+    }
+
+    /// Returns an error if `program_header` cannot be represented in the class of the file.
+    fn check_representable(
+        &self,
+        program_header: &RawProgramHeader,
+    ) -> Result<(), EditProgramHeaderError> {
+        let words = [
+            program_header.file_offset,
+            program_header.virtual_address,
+            program_header.physical_address,
+            program_header.file_size,
+            program_header.memory_size,
+            program_header.alignment,
+        ];
+        if !words.iter().all(|&word| self.layout.fits_word(word)) {
+            return Err(EditProgramHeaderError::ValueOutOfRange);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the number of entries in the program header table to `count`.
+    fn set_count(&mut self, count: u16) {
+        self.count = count;
+        let offset = self.header_field(
+            Class64.program_header_count_offset(),
+            Class32.program_header_count_offset(),
+        );
+        self.layout.write(self.bytes, offset, 2, u64::from(count));
+    }
+
+    /// Writes `program_header` into the entry located at `index`.
+    fn write_entry(&mut self, index: u16, program_header: &RawProgramHeader) {
+        let start = self.entry_offset(index);
+        let fields = self.fields();
+        self.layout.write(
+            self.bytes,
+            start + fields.segment_type,
+            4,
+            u64::from(program_header.segment_type.0),
+        );
+        self.layout.write(
+            self.bytes,
+            start + fields.flags,
+            4,
+            u64::from(program_header.flags.0),
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.file_offset,
+            program_header.file_offset,
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.virtual_address,
+            program_header.virtual_address,
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.physical_address,
+            program_header.physical_address,
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.file_size,
+            program_header.file_size,
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.memory_size,
+            program_header.memory_size,
+        );
+        self.layout.write_word(
+            self.bytes,
+            start + fields.alignment,
+            program_header.alignment,
+        );
+    }
+
+    /// Returns the offset of the entry located at `index`.
+    fn entry_offset(&self, index: u16) -> usize {
+        self.table_offset + usize::from(index) * self.entry_size
+    }
+
+    /// Returns the offsets of the fields within a program header.
+    fn fields(&self) -> ProgramHeaderFields {
+        if self.layout.wide {
+            ProgramHeaderFields::new(Class64)
+        } else {
+            ProgramHeaderFields::new(Class32)
+        }
+    }
+
+    /// Returns `wide` if class sized values are 64 bits wide, and `narrow` otherwise.
+    fn header_field(&self, wide: usize, narrow: usize) -> usize {
+        if self.layout.wide {
+            wide
+        } else {
+            narrow
+        }
+    }
+}
+
+/// The offsets of the fields within a program header.
+pub(crate) struct ProgramHeaderFields {
+    /// The offset of the [`SegmentType`].
+    pub(crate) segment_type: usize,
+    /// The offset of the [`SegmentFlags`].
+    pub(crate) flags: usize,
+    /// The offset of the file offset.
+    pub(crate) file_offset: usize,
+    /// The offset of the virtual address.
+    pub(crate) virtual_address: usize,
+    /// The offset of the physical address.
+    pub(crate) physical_address: usize,
+    /// The offset of the file size.
+    pub(crate) file_size: usize,
+    /// The offset of the memory size.
+    pub(crate) memory_size: usize,
+    /// The offset of the alignment.
+    pub(crate) alignment: usize,
+}
+
+impl ProgramHeaderFields {
+    /// Returns the [`ProgramHeaderFields`] of `class`.
+    pub(crate) fn new<C: ClassParseProgramHeader>(class: C) -> Self {
+        Self {
+            segment_type: class.segment_type_offset(),
+            flags: class.segment_flags_offset(),
+            file_offset: class.segment_file_offset_offset(),
+            virtual_address: class.segment_virtual_address_offset(),
+            physical_address: class.segment_physical_address_offset(),
+            file_size: class.segment_file_size_offset(),
+            memory_size: class.segment_memory_size_offset(),
+            alignment: class.segment_alignment_offset(),
+        }
+    }
+}
+
+/// Various errors that can occur while editing the program header table of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum EditProgramHeaderError {
+    /// An error occurred while parsing the [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// The entries of the program header table are smaller than a program header.
+    InvalidEntrySize,
+    /// The index of an entry is out of bounds.
+    IndexOutOfBounds,
+    /// A value cannot be represented in the class of the [`ElfFile`].
+    ValueOutOfRange,
+    /// The buffer is too small to hold the edited [`ElfFile`].
+    BufferTooSmall {
+        /// The number of bytes required to hold the edited [`ElfFile`].
+        required: usize,
+    },
+}
+
+impl From<ParseElfFileError> for EditProgramHeaderError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for EditProgramHeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::InvalidEntrySize => write!(f, "program header table entries are too small"),
+            Self::IndexOutOfBounds => write!(f, "program header index out of bounds"),
+            Self::ValueOutOfRange => write!(f, "value out of range for the ELF class"),
+            Self::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "buffer too small to hold edited file of {required} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for EditProgramHeaderError {}
@@ -0,0 +1,346 @@
+//! Definitions for GNU program properties and related notes.
+//!
+//! GNU program properties are stored in [`NoteType::GNU_PROPERTY_TYPE_0`] notes owned by `GNU`
+//! and describe features that the loader must enable for the program, such as AArch64 branch
+//! target identification or x86 shadow stacks.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    ident::Class,
+    note::{Note, NoteType, Notes, TypedNote, OWNER_ANDROID, OWNER_GNU},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`GnuProperty`]s of this [`ElfFile`].
+    pub fn gnu_properties(&self) -> GnuProperties<'slice, C, E> {
+        let alignment = if self.header().ident().class() == Class::CLASS64 {
+            8
+        } else {
+            4
+        };
+
+        GnuProperties {
+            notes: self.notes(),
+            current: None,
+            alignment,
+            encoding: self.encoding,
+        }
+    }
+
+    /// Returns the [`X86IsaReport`] describing the x86-64 microarchitecture levels required and
+    /// used by this [`ElfFile`].
+    pub fn x86_isa_report(&self) -> X86IsaReport {
+        let mut report = X86IsaReport::default();
+        for property in self.gnu_properties() {
+            if property.data.len() < 4 {
+                continue;
+            }
+
+            let levels = X86IsaLevels(self.encoding.parse_u32_at(0, property.data));
+            match property.property_type {
+                GnuPropertyType::X86_ISA_1_NEEDED => {
+                    report.needed = Some(report.needed.unwrap_or_default() | levels);
+                }
+                GnuPropertyType::X86_ISA_1_USED => {
+                    report.used = Some(report.used.unwrap_or_default() | levels);
+                }
+                _ => {}
+            }
+        }
+
+        report
+    }
+
+    /// Returns the [`AndroidMemtag`] settings of this [`ElfFile`], if present.
+    pub fn android_memtag(&self) -> Option<AndroidMemtag> {
+        self.notes().find_map(|note| {
+            if note.name != b"Android" || note.note_type != NoteType::ANDROID_MEMTAG.0 {
+                return None;
+            }
+            if note.descriptor.len() < 4 {
+                return None;
+            }
+
+            Some(AndroidMemtag(
+                self.encoding.parse_u32_at(0, note.descriptor),
+            ))
+        })
+    }
+}
+
+/// A GNU program property.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GnuProperty<'slice> {
+    /// The [`GnuPropertyType`] of this [`GnuProperty`].
+    pub property_type: GnuPropertyType,
+    /// The data of this [`GnuProperty`].
+    pub data: &'slice [u8],
+}
+
+/// The type of a [`GnuProperty`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GnuPropertyType(pub u32);
+
+impl GnuPropertyType {
+    /// The AArch64 features required by the program, as [`Aarch64Feature1`] flags.
+    pub const AARCH64_FEATURE_1_AND: Self = Self(0xC000_0000);
+    /// The AArch64 pointer authentication ABI used by the program.
+    pub const AARCH64_FEATURE_PAUTH: Self = Self(0xC000_0001);
+    /// The x86 instruction set levels required by the program, as [`X86IsaLevels`] flags.
+    pub const X86_ISA_1_NEEDED: Self = Self(0xC000_8002);
+    /// The x86 instruction set levels used by the program, as [`X86IsaLevels`] flags.
+    pub const X86_ISA_1_USED: Self = Self(0xC001_0002);
+}
+
+impl fmt::Debug for GnuPropertyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::AARCH64_FEATURE_1_AND => f.pad("Aarch64Feature1And"),
+            Self::AARCH64_FEATURE_PAUTH => f.pad("Aarch64FeaturePauth"),
+            Self::X86_ISA_1_NEEDED => f.pad("X86Isa1Needed"),
+            Self::X86_ISA_1_USED => f.pad("X86Isa1Used"),
+            property_type => f
+                .debug_tuple("GnuPropertyType")
+                .field(&property_type.0)
+                .finish(),
+        }
+    }
+}
+
+/// The AArch64 features held by a [`GnuPropertyType::AARCH64_FEATURE_1_AND`] property.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Aarch64Feature1(pub u32);
+
+impl Aarch64Feature1 {
+    /// The program is compatible with branch target identification.
+    pub const BTI: Self = Self(0x1);
+    /// The program signs return addresses using pointer authentication.
+    pub const PAC: Self = Self(0x2);
+    /// The program is compatible with the guarded control stack.
+    pub const GCS: Self = Self(0x4);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`Aarch64Feature1`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The x86-64 microarchitecture levels held by a [`GnuPropertyType::X86_ISA_1_NEEDED`] or
+/// [`GnuPropertyType::X86_ISA_1_USED`] property.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct X86IsaLevels(pub u32);
+
+impl X86IsaLevels {
+    /// The x86-64 baseline instruction set.
+    pub const BASELINE: Self = Self(0x1);
+    /// The x86-64-v2 instruction set.
+    pub const V2: Self = Self(0x2);
+    /// The x86-64-v3 instruction set.
+    pub const V3: Self = Self(0x4);
+    /// The x86-64-v4 instruction set.
+    pub const V4: Self = Self(0x8);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`X86IsaLevels`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns the highest [`X86IsaLevel`] present in this [`X86IsaLevels`].
+    pub const fn highest(self) -> Option<X86IsaLevel> {
+        if self.contains(Self::V4) {
+            Some(X86IsaLevel::V4)
+        } else if self.contains(Self::V3) {
+            Some(X86IsaLevel::V3)
+        } else if self.contains(Self::V2) {
+            Some(X86IsaLevel::V2)
+        } else if self.contains(Self::BASELINE) {
+            Some(X86IsaLevel::V1)
+        } else {
+            None
+        }
+    }
+}
+
+impl core::ops::BitOr for X86IsaLevels {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// An x86-64 microarchitecture level.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum X86IsaLevel {
+    /// The x86-64 baseline.
+    V1,
+    /// x86-64-v2, adding SSE4.2, SSSE3, and POPCNT.
+    V2,
+    /// x86-64-v3, adding AVX2, BMI2, and FMA.
+    V3,
+    /// x86-64-v4, adding AVX-512.
+    V4,
+}
+
+/// The x86-64 microarchitecture levels required and used by an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct X86IsaReport {
+    /// The levels required by the [`ElfFile`], if a [`GnuPropertyType::X86_ISA_1_NEEDED`]
+    /// property is present.
+    pub needed: Option<X86IsaLevels>,
+    /// The levels used by the [`ElfFile`], if a [`GnuPropertyType::X86_ISA_1_USED`] property is
+    /// present.
+    pub used: Option<X86IsaLevels>,
+}
+
+impl X86IsaReport {
+    /// Returns the minimum [`X86IsaLevel`] a CPU must support to run the [`ElfFile`].
+    ///
+    /// Returns [`X86IsaLevel::V1`] if no requirement is recorded.
+    pub fn required_level(&self) -> X86IsaLevel {
+        self.needed
+            .and_then(X86IsaLevels::highest)
+            .unwrap_or(X86IsaLevel::V1)
+    }
+
+    /// Returns `true` if the [`ElfFile`] can run on a CPU supporting `level`.
+    pub fn runs_on(&self, level: X86IsaLevel) -> bool {
+        self.required_level() <= level
+    }
+}
+
+/// The memory tagging settings held by an [`NoteType::ANDROID_MEMTAG`] note.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AndroidMemtag(pub u32);
+
+impl AndroidMemtag {
+    /// Mask of the bits holding the [`AndroidMemtagLevel`].
+    pub const LEVEL_MASK: u32 = 0x3;
+    /// Heap allocations should be tagged.
+    pub const HEAP: u32 = 0x4;
+    /// Stack allocations should be tagged.
+    pub const STACK: u32 = 0x8;
+
+    /// Returns the [`AndroidMemtagLevel`] requested by the program.
+    pub const fn level(self) -> AndroidMemtagLevel {
+        AndroidMemtagLevel(self.0 & Self::LEVEL_MASK)
+    }
+
+    /// Returns `true` if heap allocations should be tagged.
+    pub const fn heap(self) -> bool {
+        self.0 & Self::HEAP != 0
+    }
+
+    /// Returns `true` if stack allocations should be tagged.
+    pub const fn stack(self) -> bool {
+        self.0 & Self::STACK != 0
+    }
+}
+
+impl<'slice> TypedNote<'slice> for AndroidMemtag {
+    const OWNER: &'static [u8] = OWNER_ANDROID;
+    const NOTE_TYPE: NoteType = NoteType::ANDROID_MEMTAG;
+
+    fn from_descriptor<E: EncodingParse>(descriptor: &'slice [u8], encoding: E) -> Option<Self> {
+        if descriptor.len() < 4 {
+            return None;
+        }
+
+        Some(Self(encoding.parse_u32_at(0, descriptor)))
+    }
+}
+
+/// The manner in which tag check faults are reported, as requested by an [`AndroidMemtag`] note.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AndroidMemtagLevel(pub u32);
+
+impl AndroidMemtagLevel {
+    /// Memory tagging is disabled.
+    pub const NONE: Self = Self(0);
+    /// Tag check faults are reported asynchronously.
+    pub const ASYNC: Self = Self(1);
+    /// Tag check faults are reported synchronously.
+    pub const SYNC: Self = Self(2);
+}
+
+impl fmt::Debug for AndroidMemtagLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("None"),
+            Self::ASYNC => f.pad("Async"),
+            Self::SYNC => f.pad("Sync"),
+            level => f.debug_tuple("AndroidMemtagLevel").field(&level.0).finish(),
+        }
+    }
+}
+
+/// An [`Iterator`] over the [`GnuProperty`]s of an [`ElfFile`].
+#[derive(Clone, Copy)]
+pub struct GnuProperties<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`Iterator`] over the [`Note`]s of the [`ElfFile`].
+    notes: Notes<'slice, C, E>,
+    /// The unparsed properties of the current [`NoteType::GNU_PROPERTY_TYPE_0`] note.
+    current: Option<&'slice [u8]>,
+    /// The alignment of each [`GnuProperty`].
+    alignment: usize,
+    /// The [`EncodingParse`] of the [`ElfFile`].
+    encoding: E,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> GnuProperties<'slice, C, E> {
+    /// Parses the [`GnuProperty`] located at the start of `bytes`, returning the [`GnuProperty`]
+    /// and the remaining bytes.
+    fn parse(&self, bytes: &'slice [u8]) -> Option<(GnuProperty<'slice>, &'slice [u8])> {
+        if bytes.len() < 8 {
+            return None;
+        }
+
+        let property_type = GnuPropertyType(self.encoding.parse_u32_at(0, bytes));
+        let size = usize::try_from(self.encoding.parse_u32_at(4, bytes)).ok()?;
+        let end = size.checked_add(8)?;
+        let next = end
+            .checked_add(self.alignment - 1)
+            .map(|value| value & !(self.alignment - 1))?;
+
+        let property = GnuProperty {
+            property_type,
+            data: bytes.get(8..end)?,
+        };
+
+        Some((property, bytes.get(next..).unwrap_or(&[])))
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for GnuProperties<'slice, C, E> {
+    type Item = GnuProperty<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(bytes) = self.current {
+                match self.parse(bytes) {
+                    Some((property, remaining)) => {
+                        self.current = Some(remaining);
+                        return Some(property);
+                    }
+                    None => self.current = None,
+                }
+            }
+
+            let note: Note<'slice> = self.notes.next()?;
+            if note.is(OWNER_GNU, NoteType::GNU_PROPERTY_TYPE_0) {
+                self.current = Some(note.descriptor);
+            }
+        }
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for GnuProperties<'slice, C, E> {}
@@ -0,0 +1,243 @@
+//! Definitions for reconstructing section-like regions of an ELF file from its program headers
+//! and dynamic array.
+//!
+//! This is intended for ELF files whose section header table is missing or corrupt, such as
+//! those recovered from memory dumps. The reconstruction is best-effort: regions whose size
+//! cannot be determined from the dynamic array are reported without a size.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    dynamic::{ConstDynamicTag, DynamicTable},
+    encoding::EncodingParse,
+    program_header::{self, SegmentType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`ReconstructedRegion`]s of this [`ElfFile`].
+    pub fn reconstructed_regions(&self) -> ReconstructedRegions<'slice, C, E> {
+        ReconstructedRegions {
+            file: *self,
+            program_headers: self.program_header_table().map(IntoIterator::into_iter),
+            dynamic: self.dynamic_table(),
+            next_dynamic: 0,
+        }
+    }
+
+    /// Returns the file offset at which the byte at virtual address `address` is stored.
+    fn virtual_address_offset(&self, address: u64) -> Option<u64> {
+        self.program_header_table()?
+            .segments_of_type(SegmentType::LOAD)
+            .find_map(|program_header| {
+                let offset = address.checked_sub(program_header.virtual_address().into())?;
+                if offset >= program_header.file_size().into() {
+                    return None;
+                }
+
+                offset.checked_add(program_header.file_offset().into())
+            })
+    }
+
+    /// Returns the size of the dynamic symbol table, if its entry size and symbol count are
+    /// known and consistent.
+    fn dynamic_symbol_table_size(&self) -> Option<u64> {
+        let entry_size = self.dynamic_symbol_entry_size().ok()?;
+        let count = self.dynamic_symbol_count().ok()??;
+
+        u64::try_from(count.checked_mul(entry_size)?).ok()
+    }
+}
+
+/// The kind of a [`ReconstructedRegion`], named after the section it usually corresponds to.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RegionKind {
+    /// The program interpreter path (`.interp`).
+    Interp,
+    /// The dynamic array (`.dynamic`).
+    Dynamic,
+    /// A note region (`.note`).
+    Note,
+    /// The thread local storage template (`.tdata` and `.tbss`).
+    Tls,
+    /// The exception handling frame header (`.eh_frame_hdr`).
+    EhFrameHeader,
+    /// The symbol hash table (`.hash`).
+    Hash,
+    /// The dynamic string table (`.dynstr`).
+    DynamicStringTable,
+    /// The dynamic symbol table (`.dynsym`).
+    DynamicSymbolTable,
+    /// The relocations with explicit addends (`.rela.dyn`).
+    RelaDyn,
+    /// The relocations without explicit addends (`.rel.dyn`).
+    RelDyn,
+    /// The procedure linkage table relocations (`.rela.plt` or `.rel.plt`).
+    PltRelocations,
+    /// The global offset table used by the procedure linkage table (`.got.plt`).
+    PltGot,
+    /// The array of pre-initialization functions (`.preinit_array`).
+    PreinitArray,
+    /// The array of initialization functions (`.init_array`).
+    InitArray,
+    /// The array of termination functions (`.fini_array`).
+    FiniArray,
+}
+
+impl RegionKind {
+    /// Returns the name of the section this [`RegionKind`] usually corresponds to.
+    pub const fn section_name(self) -> &'static str {
+        match self {
+            Self::Interp => ".interp",
+            Self::Dynamic => ".dynamic",
+            Self::Note => ".note",
+            Self::Tls => ".tdata",
+            Self::EhFrameHeader => ".eh_frame_hdr",
+            Self::Hash => ".hash",
+            Self::DynamicStringTable => ".dynstr",
+            Self::DynamicSymbolTable => ".dynsym",
+            Self::RelaDyn => ".rela.dyn",
+            Self::RelDyn => ".rel.dyn",
+            Self::PltRelocations => ".rela.plt",
+            Self::PltGot => ".got.plt",
+            Self::PreinitArray => ".preinit_array",
+            Self::InitArray => ".init_array",
+            Self::FiniArray => ".fini_array",
+        }
+    }
+}
+
+/// A section-like region of an ELF file reconstructed from its program headers and dynamic
+/// array.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReconstructedRegion {
+    /// The [`RegionKind`] of the region.
+    pub kind: RegionKind,
+    /// The virtual address at which the region resides in memory.
+    pub virtual_address: u64,
+    /// The number of bytes of the region, if it could be determined.
+    pub size: Option<u64>,
+    /// The offset within the file at which the region starts, if it is backed by the file.
+    pub file_offset: Option<u64>,
+}
+
+/// The regions located by the dynamic array, along with the tags holding their address and size.
+const DYNAMIC_REGIONS: [(RegionKind, ConstDynamicTag, Option<ConstDynamicTag>); 10] = [
+    (RegionKind::Hash, ConstDynamicTag::HASH, None),
+    (
+        RegionKind::DynamicStringTable,
+        ConstDynamicTag::STRING_TABLE,
+        Some(ConstDynamicTag::STRING_TABLE_SIZE),
+    ),
+    (
+        RegionKind::DynamicSymbolTable,
+        ConstDynamicTag::SYMBOL_TABLE,
+        None,
+    ),
+    (
+        RegionKind::RelaDyn,
+        ConstDynamicTag::RELA_TABLE,
+        Some(ConstDynamicTag::RELA_SIZE),
+    ),
+    (
+        RegionKind::RelDyn,
+        ConstDynamicTag::REL_TABLE,
+        Some(ConstDynamicTag::REL_SIZE),
+    ),
+    (
+        RegionKind::PltRelocations,
+        ConstDynamicTag::JMP_REL,
+        Some(ConstDynamicTag::PLT_REL_SIZE),
+    ),
+    (RegionKind::PltGot, ConstDynamicTag::PLT_GOT, None),
+    (
+        RegionKind::PreinitArray,
+        ConstDynamicTag::PREINIT_ARRAY,
+        Some(ConstDynamicTag::PREINIT_ARRAY_SIZE),
+    ),
+    (
+        RegionKind::InitArray,
+        ConstDynamicTag::INIT_ARRAY,
+        Some(ConstDynamicTag::INIT_ARRAY_SIZE),
+    ),
+    (
+        RegionKind::FiniArray,
+        ConstDynamicTag::FINI_ARRAY,
+        Some(ConstDynamicTag::FINI_ARRAY_SIZE),
+    ),
+];
+
+/// An [`Iterator`] over the [`ReconstructedRegion`]s of an [`ElfFile`].
+///
+/// Regions described by program headers are yielded first, followed by regions described by the
+/// dynamic array.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ReconstructedRegions<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being reconstructed.
+    file: ElfFile<'slice, C, E>,
+    /// The underlying [`Iterator`] over the program header table.
+    program_headers: Option<program_header::IntoIter<'slice, C, E>>,
+    /// The dynamic array of the [`ElfFile`].
+    dynamic: Option<DynamicTable<'slice, C, E>>,
+    /// The index of the next entry of [`DYNAMIC_REGIONS`] to check.
+    next_dynamic: usize,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for ReconstructedRegions<'_, C, E> {
+    type Item = ReconstructedRegion;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(program_headers) = self.program_headers.as_mut() {
+            for program_header in program_headers.by_ref() {
+                let kind = match program_header.segment_type() {
+                    SegmentType::INTERPRETER => RegionKind::Interp,
+                    SegmentType::DYNAMIC => RegionKind::Dynamic,
+                    SegmentType::NOTE => RegionKind::Note,
+                    SegmentType::TLS => RegionKind::Tls,
+                    SegmentType::GNU_EH_FRAME => RegionKind::EhFrameHeader,
+                    _ => continue,
+                };
+
+                let file_size: u64 = program_header.file_size().into();
+                return Some(ReconstructedRegion {
+                    kind,
+                    virtual_address: program_header.virtual_address().into(),
+                    size: Some(program_header.memory_size().into()),
+                    file_offset: (file_size != 0).then(|| program_header.file_offset().into()),
+                });
+            }
+        }
+
+        let dynamic = self.dynamic?;
+        while let Some(&(kind, address_tag, size_tag)) = DYNAMIC_REGIONS.get(self.next_dynamic) {
+            self.next_dynamic += 1;
+
+            let Some(address) = dynamic.find(address_tag) else {
+                continue;
+            };
+            let virtual_address: u64 = address.into();
+            let size = match kind {
+                RegionKind::DynamicSymbolTable => self.file.dynamic_symbol_table_size(),
+                _ => size_tag.and_then(|tag| dynamic.find(tag)).map(Into::into),
+            };
+
+            return Some(ReconstructedRegion {
+                kind,
+                virtual_address,
+                size,
+                file_offset: self.file.virtual_address_offset(virtual_address),
+            });
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for ReconstructedRegions<'_, C, E> {}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for ReconstructedRegions<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
@@ -0,0 +1,212 @@
+//! Definitions for asserting that the [`SegmentType::LOAD`] segments of an ELF file lie within
+//! declared memory regions, in the style of the `MEMORY` command of a linker script.
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{self, ProgramHeader, SegmentFlags, SegmentType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`RegionViolation`]s of the [`SegmentType::LOAD`]
+    /// segments of this [`ElfFile`] against `regions`.
+    ///
+    /// The memory image of each segment must lie within a single [`MemoryRegion`] that permits
+    /// its [`SegmentFlags`], and its file-backed contents must lie within a single
+    /// [`MemoryRegion`] at its physical address.
+    pub fn region_violations<'regions>(
+        &self,
+        regions: &'regions [MemoryRegion<'regions>],
+    ) -> RegionViolations<'slice, 'regions, C, E> {
+        RegionViolations {
+            regions,
+            iter: self.program_header_table().map(IntoIterator::into_iter),
+            index: 0,
+            pending: None,
+        }
+    }
+
+    /// Validates that the [`SegmentType::LOAD`] segments of this [`ElfFile`] lie within
+    /// `regions`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`RegionViolation`] yielded by [`ElfFile::region_violations()`].
+    pub fn validate_regions(&self, regions: &[MemoryRegion<'_>]) -> Result<(), RegionViolation> {
+        match self.region_violations(regions).next() {
+            Some(violation) => Err(violation),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A named region of memory into which segments may be placed.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct MemoryRegion<'name> {
+    /// The name of this [`MemoryRegion`].
+    pub name: &'name str,
+    /// The address at which this [`MemoryRegion`] starts.
+    pub origin: u64,
+    /// The size of this [`MemoryRegion`] in bytes.
+    pub length: u64,
+    /// The [`SegmentFlags`] permitted for segments placed in this [`MemoryRegion`].
+    pub attributes: SegmentFlags,
+}
+
+impl MemoryRegion<'_> {
+    /// Returns `true` if the `size` bytes starting at `address` lie within this
+    /// [`MemoryRegion`].
+    pub fn contains(&self, address: u64, size: u64) -> bool {
+        address
+            .checked_sub(self.origin)
+            .and_then(|offset| offset.checked_add(size))
+            .is_some_and(|end| end <= self.length)
+    }
+}
+
+/// A [`SegmentType::LOAD`] segment that violates the declared [`MemoryRegion`]s.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RegionViolation {
+    /// The index of the segment in the program header table.
+    pub index: u16,
+    /// The [`RegionViolationKind`] of this [`RegionViolation`].
+    pub kind: RegionViolationKind,
+}
+
+/// The ways in which a segment can violate the declared [`MemoryRegion`]s.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RegionViolationKind {
+    /// The memory image of the segment does not lie within any [`MemoryRegion`].
+    VirtualOutsideRegions {
+        /// The virtual address of the segment.
+        address: u64,
+        /// The size of the segment in memory.
+        size: u64,
+    },
+    /// The file-backed contents of the segment do not lie within any [`MemoryRegion`] at its
+    /// physical address.
+    PhysicalOutsideRegions {
+        /// The physical address of the segment.
+        address: u64,
+        /// The size of the file-backed contents of the segment.
+        size: u64,
+    },
+    /// The segment lies within a [`MemoryRegion`] that does not permit its [`SegmentFlags`].
+    PermissionsExceeded {
+        /// The index of the [`MemoryRegion`] containing the segment.
+        region: usize,
+        /// The [`SegmentFlags`] of the segment.
+        flags: SegmentFlags,
+    },
+}
+
+impl fmt::Display for RegionViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.index;
+        match self.kind {
+            RegionViolationKind::VirtualOutsideRegions { address, size } => write!(
+                f,
+                "segment {index} at virtual address {address:#x} ({size:#x} bytes) lies outside \
+                 every memory region"
+            ),
+            RegionViolationKind::PhysicalOutsideRegions { address, size } => write!(
+                f,
+                "segment {index} at physical address {address:#x} ({size:#x} bytes) lies outside \
+                 every memory region"
+            ),
+            RegionViolationKind::PermissionsExceeded { region, flags } => write!(
+                f,
+                "segment {index} with flags {:#x} exceeds the attributes of memory region {region}",
+                flags.0
+            ),
+        }
+    }
+}
+
+impl error::Error for RegionViolation {}
+
+/// An [`Iterator`] over the [`RegionViolation`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug)]
+pub struct RegionViolations<'slice, 'regions, C: ClassParse, E: EncodingParse> {
+    /// The declared [`MemoryRegion`]s.
+    regions: &'regions [MemoryRegion<'regions>],
+    /// The underlying [`Iterator`] over the program header table.
+    iter: Option<program_header::IntoIter<'slice, C, E>>,
+    /// The index of the next segment.
+    index: u16,
+    /// A second [`RegionViolation`] of the previous segment that has not been yielded.
+    pending: Option<RegionViolation>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> RegionViolations<'slice, '_, C, E> {
+    /// Returns the [`RegionViolationKind`]s of the memory image and of the file-backed contents
+    /// of the segment controlled by `program_header`.
+    fn check(
+        &self,
+        program_header: ProgramHeader<'slice, C, E>,
+    ) -> [Option<RegionViolationKind>; 2] {
+        let address: u64 = program_header.virtual_address().into();
+        let size: u64 = program_header.memory_size().into();
+        let flags = program_header.flags();
+        let permissions = SegmentFlags::READ | SegmentFlags::WRITE | SegmentFlags::EXECUTE;
+        let flags = SegmentFlags(flags.0 & permissions.0);
+
+        let virtual_violation = match self
+            .regions
+            .iter()
+            .position(|region| region.contains(address, size))
+        {
+            Some(region) if !self.regions[region].attributes.contains(flags) => {
+                Some(RegionViolationKind::PermissionsExceeded { region, flags })
+            }
+            Some(_) => None,
+            None => Some(RegionViolationKind::VirtualOutsideRegions { address, size }),
+        };
+
+        let address: u64 = program_header.physical_address().into();
+        let size: u64 = program_header.file_size().into();
+        let physical_violation = (size != 0
+            && !self
+                .regions
+                .iter()
+                .any(|region| region.contains(address, size)))
+        .then_some(RegionViolationKind::PhysicalOutsideRegions { address, size });
+
+        [virtual_violation, physical_violation]
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for RegionViolations<'_, '_, C, E> {
+    type Item = RegionViolation;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(violation) = self.pending.take() {
+            return Some(violation);
+        }
+
+        loop {
+            let program_header = self.iter.as_mut()?.next()?;
+            let index = self.index;
+            self.index += 1;
+
+            if program_header.segment_type() != SegmentType::LOAD {
+                continue;
+            }
+
+            let mut violations = self
+                .check(program_header)
+                .into_iter()
+                .flatten()
+                .map(|kind| RegionViolation { index, kind });
+            if let Some(violation) = violations.next() {
+                self.pending = violations.next();
+                return Some(violation);
+            }
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for RegionViolations<'_, '_, C, E> {}
@@ -1,8 +1,12 @@
 //! Definitions for ELF relocation entries.
 
+use core::iter::FusedIterator;
+
 use crate::{
     class::{ClassParse, ClassParseBase},
+    consts,
     encoding::EncodingParse,
+    header::Machine,
 };
 
 /// An ELF relocation entry without an explicit addend.
@@ -42,7 +46,7 @@ impl<'slice, C: ClassParse, E: EncodingParse> RelTable<'slice, C, E> {
     /// Creates a new [`RelTable`] from the given `slice`.
     pub fn new(class: C, encoding: E, slice: &'slice [u8], count: usize) -> Option<Self> {
         if count
-            .checked_add(class.expected_rel_size())
+            .checked_mul(class.expected_rel_size())
             .is_none_or(|total_size| slice.len() < total_size)
         {
             return None;
@@ -85,6 +89,12 @@ impl<'slice, C: ClassParse, E: EncodingParse> RelTable<'slice, C, E> {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Limits this [`RelTable`] to at most `max_entries` [`Rel`] entries.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.count = self.count.min(max_entries);
+        self
+    }
 }
 
 impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for RelTable<'slice, C, E> {
@@ -119,6 +129,8 @@ impl<C: ClassParse, E: EncodingParse> Iterator for RelIntoIter<'_, C, E> {
     }
 }
 
+impl<C: ClassParse, E: EncodingParse> FusedIterator for RelIntoIter<'_, C, E> {}
+
 /// A table of [`Rela`] entries.
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub struct RelaTable<'slice, C, E> {
@@ -136,7 +148,7 @@ impl<'slice, C: ClassParse, E: EncodingParse> RelaTable<'slice, C, E> {
     /// Creates a new [`RelaTable`] from the given `slice`.
     pub fn new(class: C, encoding: E, slice: &'slice [u8], count: usize) -> Option<Self> {
         if count
-            .checked_add(class.expected_rel_size())
+            .checked_mul(class.expected_rela_size())
             .is_none_or(|total_size| slice.len() < total_size)
         {
             return None;
@@ -158,7 +170,7 @@ impl<'slice, C: ClassParse, E: EncodingParse> RelaTable<'slice, C, E> {
             return None;
         }
 
-        let rela_bytes = &self.bytes[index * self.class.expected_rel_size()..];
+        let rela_bytes = &self.bytes[index * self.class.expected_rela_size()..];
         let rela = Rela {
             offset: self.class.parse_class_usize_at(
                 self.encoding,
@@ -184,6 +196,12 @@ impl<'slice, C: ClassParse, E: EncodingParse> RelaTable<'slice, C, E> {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Limits this [`RelaTable`] to at most `max_entries` [`Rela`] entries.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.count = self.count.min(max_entries);
+        self
+    }
 }
 
 impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for RelaTable<'slice, C, E> {
@@ -218,11 +236,32 @@ impl<C: ClassParse, E: EncodingParse> Iterator for RelaIntoIter<'_, C, E> {
     }
 }
 
+impl<C: ClassParse, E: EncodingParse> FusedIterator for RelaIntoIter<'_, C, E> {}
+
+/// Returns the raw relocation type that adjusts a location by the load bias of an object on
+/// `machine`, or `None` if it is not known.
+pub fn relative_relocation_type(machine: Machine) -> Option<u32> {
+    let relative = match machine {
+        Machine::INTEL_386 => consts::R_386_RELATIVE,
+        Machine::X86_64 => consts::R_X86_64_RELATIVE,
+        Machine::ARM => consts::R_ARM_RELATIVE,
+        Machine::AARCH64 => consts::R_AARCH64_RELATIVE,
+        Machine::RISCV => consts::R_RISCV_RELATIVE,
+        _ => return None,
+    };
+
+    Some(relative)
+}
+
 /// The requirements to implement class aware parsing of ELF relocation entries.
 pub trait ClassParseRelocation: ClassParseBase {
     /// Returns the relocation type extracted from `info`.
+    ///
+    /// Implementations must not panic, truncating `info` if it is wider than the class allows.
     fn relocation_type_raw(self, info: Self::ClassUsize) -> u32;
     /// Returns the symbol index extracted from `info`.
+    ///
+    /// Implementations must not panic, truncating `info` if it is wider than the class allows.
     fn symbol_raw(self, info: Self::ClassUsize) -> u32;
 
     /// The offset of the location at which to apply the relocation.
@@ -242,3 +281,43 @@ pub trait ClassParseRelocation: ClassParseBase {
     /// The expected size of an ELF rela entry.
     fn expected_rela_size(self) -> usize;
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class::{Class32, Class64},
+        encoding::{BigEndian, LittleEndian},
+        relocation::{RelTable, RelaTable},
+    };
+
+    /// Relocation tables whose entries fit in their bytes are accepted.
+    #[test]
+    fn tables_in_bounds() {
+        let bytes = [0; 2 * 24];
+
+        let rel = RelTable::new(Class64, LittleEndian, &bytes, 3).unwrap();
+        assert_eq!(rel.into_iter().count(), 3);
+        let rela = RelaTable::new(Class64, LittleEndian, &bytes, 2).unwrap();
+        assert_eq!(rela.into_iter().count(), 2);
+    }
+
+    /// Relocation tables with more entries than their bytes can hold are rejected.
+    #[test]
+    fn tables_too_small() {
+        let bytes = [0; 2 * 24];
+
+        assert!(RelTable::new(Class64, LittleEndian, &bytes, 4).is_none());
+        assert!(RelaTable::new(Class64, LittleEndian, &bytes, 3).is_none());
+    }
+
+    /// Relocation tables whose count times the entry size overflows are rejected.
+    #[test]
+    fn table_sizes_overflow() {
+        let bytes = [0; 24];
+
+        assert!(RelTable::new(Class64, LittleEndian, &bytes, usize::MAX).is_none());
+        assert!(RelaTable::new(Class64, LittleEndian, &bytes, usize::MAX / 8).is_none());
+        assert!(RelTable::new(Class32, BigEndian, &bytes, usize::MAX / 4).is_none());
+        assert!(RelaTable::new(Class32, BigEndian, &bytes, usize::MAX / 6).is_none());
+    }
+}
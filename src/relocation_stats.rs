@@ -0,0 +1,310 @@
+//! Definitions for estimating the load time cost of the dynamic relocations of an ELF file.
+//!
+//! Every page targeted by a dynamic relocation is written by the dynamic linker, so it stops
+//! being shared with the file and other processes. Counting the distinct target pages therefore
+//! estimates the memory dirtied at load time.
+//!
+//! Counting distinct pages requires sorting the targets, which are written into a buffer
+//! provided by the caller that must hold one element per relocation.
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    ident::Class,
+    relocation::{relative_relocation_type, RelTable, RelaTable},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the number of dynamic relocations of this [`ElfFile`] of the given
+    /// [`RelocationKind`], or of every kind if `kind` is `None`.
+    pub fn relocation_count(&self, kind: Option<RelocationKind>) -> usize {
+        let mut count = 0;
+        self.for_each_dynamic_relocation(|relocation_kind, _| {
+            if kind.is_none_or(|kind| kind == relocation_kind) {
+                count += 1;
+            }
+        });
+
+        count
+    }
+
+    /// Returns a [`PageCounts`] over the pages of `page_size` bytes targeted by the dynamic
+    /// relocations of this [`ElfFile`] of the given [`RelocationKind`], or of every kind if
+    /// `kind` is `None`.
+    ///
+    /// The target page of each relocation is written into `pages`.
+    ///
+    /// # Errors
+    ///
+    /// - [`RelocationStatsError::InvalidPageSize`]: Returned if `page_size` is not a power of
+    ///   two.
+    /// - [`RelocationStatsError::BufferTooSmall`]: Returned if `pages` is smaller than
+    ///   [`ElfFile::relocation_count()`].
+    pub fn relocation_pages<'pages>(
+        &self,
+        kind: Option<RelocationKind>,
+        page_size: u64,
+        pages: &'pages mut [u64],
+    ) -> Result<PageCounts<'pages>, RelocationStatsError> {
+        if !page_size.is_power_of_two() {
+            return Err(RelocationStatsError::InvalidPageSize);
+        }
+
+        let required = self.relocation_count(kind);
+        let pages = pages
+            .get_mut(..required)
+            .ok_or(RelocationStatsError::BufferTooSmall { required })?;
+
+        let mut next = 0;
+        self.for_each_dynamic_relocation(|relocation_kind, target| {
+            if kind.is_none_or(|kind| kind == relocation_kind) {
+                pages[next] = target & !(page_size - 1);
+                next += 1;
+            }
+        });
+        pages.sort_unstable();
+
+        Ok(PageCounts { pages })
+    }
+
+    /// Returns the [`RelocationStats`] of the dynamic relocations of this [`ElfFile`] for pages
+    /// of `page_size` bytes, using `pages` to sort the targeted pages.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ElfFile::relocation_pages()`] with a `kind` of `None`.
+    pub fn relocation_stats(
+        &self,
+        page_size: u64,
+        pages: &mut [u64],
+    ) -> Result<RelocationStats, RelocationStatsError> {
+        let mut summarize = |kind| {
+            self.relocation_pages(kind, page_size, pages)
+                .map(KindStats::from_page_counts)
+        };
+
+        let total = summarize(None)?;
+        Ok(RelocationStats {
+            relative: summarize(Some(RelocationKind::Relative))?,
+            symbolic: summarize(Some(RelocationKind::Symbolic))?,
+            plt: summarize(Some(RelocationKind::Plt))?,
+            total,
+        })
+    }
+
+    /// Calls `f` with the [`RelocationKind`] and target address of each dynamic relocation.
+    fn for_each_dynamic_relocation(&self, mut f: impl FnMut(RelocationKind, u64)) {
+        let Some(dynamic) = self.dynamic_table() else {
+            return;
+        };
+        let relative = relative_relocation_type(self.header().machine());
+        let kind = |info| {
+            if Some(self.class.relocation_type_raw(info)) == relative {
+                RelocationKind::Relative
+            } else {
+                RelocationKind::Symbolic
+            }
+        };
+
+        if let Some(table) = self
+            .relocation_data(
+                dynamic,
+                ConstDynamicTag::RELA_TABLE,
+                ConstDynamicTag::RELA_SIZE,
+            )
+            .and_then(|data| {
+                RelaTable::new(
+                    self.class,
+                    self.encoding,
+                    data,
+                    data.len() / self.class.expected_rela_size(),
+                )
+            })
+        {
+            for rela in table {
+                f(kind(rela.info), rela.offset.into());
+            }
+        }
+        if let Some(table) = self
+            .relocation_data(
+                dynamic,
+                ConstDynamicTag::REL_TABLE,
+                ConstDynamicTag::REL_SIZE,
+            )
+            .and_then(|data| {
+                RelTable::new(
+                    self.class,
+                    self.encoding,
+                    data,
+                    data.len() / self.class.expected_rel_size(),
+                )
+            })
+        {
+            for rel in table {
+                f(kind(rel.info), rel.offset.into());
+            }
+        }
+        if let Some(data) = self.relocation_data(
+            dynamic,
+            ConstDynamicTag::RELR_TABLE,
+            ConstDynamicTag::RELR_SIZE,
+        ) {
+            self.for_each_relr_relocation(data, |target| f(RelocationKind::Relative, target));
+        }
+        if let Some(table) = self.plt_table() {
+            for entry in table {
+                f(RelocationKind::Plt, entry.got_slot.into());
+            }
+        }
+    }
+
+    /// Calls `f` with the target address of each relocation encoded in the RELR table `data`.
+    ///
+    /// Each even entry is the target of a relocation, and each odd entry is a bitmap whose bits
+    /// above the lowest mark relocations at the words following the previous target.
+    pub(crate) fn for_each_relr_relocation(&self, data: &[u8], mut f: impl FnMut(u64)) {
+        let word_size: usize = if self.header().ident().class() == Class::CLASS64 {
+            8
+        } else {
+            4
+        };
+        let word = word_size as u64;
+        let bits = word * 8;
+
+        let mut next = 0u64;
+        for entry in data.chunks_exact(word_size) {
+            let entry = if word_size == 8 {
+                self.encoding.parse_u64_at(0, entry)
+            } else {
+                u64::from(self.encoding.parse_u32_at(0, entry))
+            };
+
+            if entry & 1 == 0 {
+                f(entry);
+                next = entry.wrapping_add(word);
+            } else {
+                for bit in (1..bits).filter(|bit| (entry >> bit) & 1 != 0) {
+                    f(next.wrapping_add((bit - 1) * word));
+                }
+                next = next.wrapping_add((bits - 1) * word);
+            }
+        }
+    }
+}
+
+/// The kinds of dynamic relocations distinguished by [`RelocationStats`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelocationKind {
+    /// A relocation that adjusts its target by the load bias of the object.
+    ///
+    /// Relocations from the RELR table are always relative, while those from the other tables
+    /// are only recognized as relative on machines known to [`relative_relocation_type()`].
+    Relative,
+    /// A relocation from the dynamic relocation tables that is not [`RelocationKind::Relative`],
+    /// which usually requires a symbol lookup.
+    Symbolic,
+    /// A relocation that fills a global offset table slot used by the procedure linkage table.
+    Plt,
+}
+
+/// The number of relocations targeting a single page.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PageCount {
+    /// The address of the page.
+    pub page: u64,
+    /// The number of relocations targeting the page.
+    pub relocations: usize,
+}
+
+/// An [`Iterator`] over the pages targeted by relocations, in increasing address order.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PageCounts<'pages> {
+    /// The sorted page of each relocation that has not yet been visited.
+    pages: &'pages [u64],
+}
+
+impl Iterator for PageCounts<'_> {
+    type Item = PageCount;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let page = *self.pages.first()?;
+        let relocations = self
+            .pages
+            .iter()
+            .position(|&other| other != page)
+            .unwrap_or(self.pages.len());
+
+        self.pages = &self.pages[relocations..];
+        Some(PageCount { page, relocations })
+    }
+}
+
+impl FusedIterator for PageCounts<'_> {}
+
+/// Summary of the pages targeted by relocations of a single [`RelocationKind`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct KindStats {
+    /// The number of relocations.
+    pub relocations: usize,
+    /// The number of distinct pages targeted, which estimates the number of pages dirtied at
+    /// load time.
+    pub pages: usize,
+    /// The largest number of relocations targeting a single page.
+    pub max_per_page: usize,
+}
+
+impl KindStats {
+    /// Returns the [`KindStats`] summarizing `page_counts`.
+    fn from_page_counts(page_counts: PageCounts<'_>) -> Self {
+        page_counts.fold(Self::default(), |stats, page_count| Self {
+            relocations: stats.relocations + page_count.relocations,
+            pages: stats.pages + 1,
+            max_per_page: stats.max_per_page.max(page_count.relocations),
+        })
+    }
+}
+
+/// Summary of the pages targeted by the dynamic relocations of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RelocationStats {
+    /// The [`KindStats`] of [`RelocationKind::Relative`] relocations.
+    pub relative: KindStats,
+    /// The [`KindStats`] of [`RelocationKind::Symbolic`] relocations.
+    pub symbolic: KindStats,
+    /// The [`KindStats`] of [`RelocationKind::Plt`] relocations.
+    pub plt: KindStats,
+    /// The [`KindStats`] of all relocations.
+    ///
+    /// Pages targeted by relocations of several kinds are only counted once, so
+    /// [`KindStats::pages`] is the estimated number of pages dirtied at load time.
+    pub total: KindStats,
+}
+
+/// Various errors that can occur while computing [`RelocationStats`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RelocationStatsError {
+    /// The page size is not a power of two.
+    InvalidPageSize,
+    /// The buffer is too small to hold the page of each relocation.
+    BufferTooSmall {
+        /// The number of elements required to hold the page of each relocation.
+        required: usize,
+    },
+}
+
+impl fmt::Display for RelocationStatsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPageSize => write!(f, "page size is not a power of two"),
+            Self::BufferTooSmall { required } => {
+                write!(f, "buffer too small to hold {required} relocation pages")
+            }
+        }
+    }
+}
+
+impl error::Error for RelocationStatsError {}
@@ -0,0 +1,158 @@
+//! Definitions for analyzing the read-only after relocation coverage of an ELF file.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    dynamic::{ConstDynamicTag, DynamicFlags, DynamicFlags1},
+    encoding::EncodingParse,
+    plt,
+    program_header::SegmentType,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`RelroCoverage`] of this [`ElfFile`].
+    pub fn relro_coverage(&self) -> RelroCoverage<'slice, C, E> {
+        let range = self
+            .program_header_table()
+            .and_then(|table| table.segments_of_type(SegmentType::GNU_RELRO).next())
+            .and_then(|program_header| {
+                let start: u64 = program_header.virtual_address().into();
+                let end = start.checked_add(program_header.memory_size().into())?;
+
+                Some((start, end))
+            });
+
+        let dynamic = self.dynamic_table();
+        let bind_now = dynamic.is_some_and(|dynamic| {
+            dynamic.find(ConstDynamicTag::BIND_NOW).is_some()
+                || dynamic.find(ConstDynamicTag::FLAGS).is_some_and(|flags| {
+                    DynamicFlags(flags.into()).contains(DynamicFlags::BIND_NOW)
+                })
+                || dynamic
+                    .find(ConstDynamicTag::FLAGS_1)
+                    .is_some_and(|flags| DynamicFlags1(flags.into()).contains(DynamicFlags1::NOW))
+        });
+
+        RelroCoverage {
+            file: *self,
+            range,
+            bind_now,
+            plt_got: dynamic
+                .and_then(|dynamic| dynamic.find(ConstDynamicTag::PLT_GOT))
+                .map(Into::into),
+        }
+    }
+}
+
+/// The level of read-only after relocation protection applied to an ELF file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelroLevel {
+    /// No [`SegmentType::GNU_RELRO`] segment is present.
+    None,
+    /// A [`SegmentType::GNU_RELRO`] segment is present, but part of the global offset table
+    /// remains writable after relocation.
+    Partial,
+    /// A [`SegmentType::GNU_RELRO`] segment is present and covers the entire global offset table.
+    Full,
+}
+
+/// Description of the addresses protected by the [`SegmentType::GNU_RELRO`] segment of an
+/// [`ElfFile`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct RelroCoverage<'slice, C, E> {
+    /// The [`ElfFile`] being analyzed.
+    file: ElfFile<'slice, C, E>,
+    /// The start and end virtual addresses of the [`SegmentType::GNU_RELRO`] segment.
+    range: Option<(u64, u64)>,
+    /// Whether all relocations are processed before control is transferred to the program.
+    bind_now: bool,
+    /// The address of the global offset table.
+    plt_got: Option<u64>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> RelroCoverage<'slice, C, E> {
+    /// Returns the start and end virtual addresses of the region made read-only after relocation.
+    pub fn range(&self) -> Option<(u64, u64)> {
+        self.range
+    }
+
+    /// Returns `true` if all relocations are processed before control is transferred to the
+    /// program.
+    pub fn bind_now(&self) -> bool {
+        self.bind_now
+    }
+
+    /// Returns `true` if `address` is made read-only after relocation.
+    pub fn contains(&self, address: u64) -> bool {
+        self.range
+            .is_some_and(|(start, end)| start <= address && address < end)
+    }
+
+    /// Returns `true` if the entire global offset table is made read-only after relocation.
+    ///
+    /// Lazily bound global offset table slots are only made read-only if all relocations are
+    /// processed before control is transferred to the program.
+    pub fn is_got_covered(&self) -> bool {
+        self.plt_got.is_none_or(|address| self.contains(address))
+            && self.uncovered_got_slots().next().is_none()
+    }
+
+    /// Returns the [`RelroLevel`] of the [`ElfFile`].
+    pub fn level(&self) -> RelroLevel {
+        if self.range.is_none() {
+            RelroLevel::None
+        } else if self.is_got_covered() {
+            RelroLevel::Full
+        } else {
+            RelroLevel::Partial
+        }
+    }
+
+    /// Returns an [`Iterator`] over the addresses of the global offset table slots used by the
+    /// procedure linkage table that remain writable after relocation.
+    pub fn uncovered_got_slots(&self) -> UncoveredGotSlots<'slice, C, E> {
+        UncoveredGotSlots {
+            coverage: *self,
+            iter: self.file.plt_table().map(IntoIterator::into_iter),
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for RelroCoverage<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelroCoverage")
+            .field("range", &self.range)
+            .field("bind_now", &self.bind_now)
+            .field("plt_got", &self.plt_got)
+            .field("level", &self.level())
+            .finish()
+    }
+}
+
+/// An [`Iterator`] over the global offset table slots that remain writable after relocation.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct UncoveredGotSlots<'slice, C, E> {
+    /// The [`RelroCoverage`] being checked against.
+    coverage: RelroCoverage<'slice, C, E>,
+    /// The underlying [`Iterator`] over the procedure linkage table.
+    iter: Option<plt::IntoIter<'slice, C, E>>,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for UncoveredGotSlots<'_, C, E> {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.as_mut()?.find_map(|entry| {
+            let address = entry.got_slot.into();
+            if self.coverage.bind_now && self.coverage.contains(address) {
+                return None;
+            }
+
+            Some(address)
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for UncoveredGotSlots<'_, C, E> {}
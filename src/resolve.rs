@@ -0,0 +1,135 @@
+//! Definitions for resolving symbol references the way the GNU dynamic linker does.
+//!
+//! A reference is resolved by searching the symbol tables of the loaded objects in a lookup
+//! order, usually the executable followed by its dependencies in breadth-first order, and
+//! binding to the first acceptable definition. [`SymbolBinding::WEAK`] definitions are accepted
+//! like [`SymbolBinding::GLOBAL`] ones, so a later global definition does not override an earlier
+//! weak one.
+//!
+//! [`SymbolBinding::GNU_UNIQUE`] definitions, which compilers emit for static data of inline
+//! functions and template members, are additionally shared across lookup orders: the first unique
+//! definition the dynamic linker encounters is used by every object in the process, even those
+//! loaded into separate namespaces or that would otherwise bind to their own definition. Since
+//! other objects may refer to it, an object defining a unique symbol is never unloaded.
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    symbol::{Symbol, SymbolBinding, SymbolTable, SymbolType, SymbolVisibility},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns `true` if the [`ElfFile::dynamic_symbol_table()`] of this [`ElfFile`] defines a
+    /// [`SymbolBinding::GNU_UNIQUE`] symbol, in which case the dynamic linker never unloads it.
+    pub fn defines_unique_symbols(&self) -> bool {
+        self.dynamic_symbol_table().is_some_and(|table| {
+            table
+                .into_iter()
+                .any(|symbol| symbol.binding() == SymbolBinding::GNU_UNIQUE && symbol.is_defined())
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Symbol<'_, C, E> {
+    /// Returns the [`DefinitionKind`] of this [`Symbol`] when it is found while resolving a
+    /// reference from another object.
+    pub fn definition_kind(&self) -> DefinitionKind {
+        if !self.is_defined() {
+            return DefinitionKind::Ignored;
+        }
+
+        let visibility = self.visibility();
+        if visibility == SymbolVisibility::HIDDEN || visibility == SymbolVisibility::INTERNAL {
+            return DefinitionKind::Ignored;
+        }
+
+        // The dynamic linker skips section, file and processor specific symbols.
+        let symbol_type = self.symbol_type();
+        if !matches!(
+            symbol_type,
+            SymbolType::NOTYPE
+                | SymbolType::OBJECT
+                | SymbolType::FUNC
+                | SymbolType::COMMON
+                | SymbolType::TLS
+                | SymbolType::GNU_IFUNC
+        ) {
+            return DefinitionKind::Ignored;
+        }
+
+        match self.binding() {
+            SymbolBinding::GLOBAL => DefinitionKind::Global,
+            SymbolBinding::WEAK => DefinitionKind::Weak,
+            SymbolBinding::GNU_UNIQUE => DefinitionKind::Unique,
+            _ => DefinitionKind::Ignored,
+        }
+    }
+}
+
+/// The treatment of a [`Symbol`] by the dynamic linker when it is found while resolving a
+/// reference from another object.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DefinitionKind {
+    /// The [`Symbol`] cannot satisfy the reference, because it is undefined, local, not visible
+    /// to other objects, or of a [`SymbolType`] the dynamic linker skips.
+    Ignored,
+    /// The [`Symbol`] is a [`SymbolBinding::WEAK`] definition, which satisfies the reference like
+    /// a [`DefinitionKind::Global`] one.
+    Weak,
+    /// The [`Symbol`] is a [`SymbolBinding::GLOBAL`] definition.
+    Global,
+    /// The [`Symbol`] is a [`SymbolBinding::GNU_UNIQUE`] definition, which satisfies the
+    /// reference and every later reference to the same name in the process.
+    Unique,
+}
+
+/// The definition a reference resolved to.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Resolution<'slice, C: ClassParse, E: EncodingParse> {
+    /// The index of the [`SymbolTable`] holding the definition in the lookup order.
+    pub table: usize,
+    /// The index of the definition in its [`SymbolTable`].
+    pub index: usize,
+    /// The definition.
+    pub symbol: Symbol<'slice, C, E>,
+    /// The [`DefinitionKind`] of the definition.
+    pub kind: DefinitionKind,
+}
+
+/// Resolves a reference to the symbol named `name` against `tables`, the symbol tables of the
+/// loaded objects in lookup order.
+///
+/// If `unique` holds the [`Resolution`] of an earlier lookup of `name` that bound to a
+/// [`DefinitionKind::Unique`] definition, possibly in another lookup order, it is returned
+/// without searching, as the dynamic linker does, so its indices refer to the earlier lookup
+/// order. Otherwise, the first definition whose
+/// [`DefinitionKind`] is not [`DefinitionKind::Ignored`] is returned. Symbol versions are not
+/// considered.
+pub fn resolve<'slice, C: ClassParse, E: EncodingParse>(
+    name: &[u8],
+    tables: &[SymbolTable<'slice, C, E>],
+    unique: Option<Resolution<'slice, C, E>>,
+) -> Option<Resolution<'slice, C, E>> {
+    if let Some(resolution) = unique.filter(|resolution| {
+        resolution.kind == DefinitionKind::Unique && resolution.symbol.name() == Some(name)
+    }) {
+        return Some(resolution);
+    }
+
+    tables.iter().enumerate().find_map(|(table, symbols)| {
+        symbols.into_iter().enumerate().find_map(|(index, symbol)| {
+            let kind = symbol.definition_kind();
+            if kind == DefinitionKind::Ignored || symbol.name() != Some(name) {
+                return None;
+            }
+
+            Some(Resolution {
+                table,
+                index,
+                symbol,
+                kind,
+            })
+        })
+    })
+}
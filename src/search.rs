@@ -0,0 +1,262 @@
+//! Definitions for bounded searches over the contents of ELF segments and sections.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse, encoding::EncodingParse, program_header::ProgramHeader,
+    section_header::SectionHeader, ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns an [`Iterator`] over the occurrences of `pattern` in the file data of the segment
+    /// controlled by `program_header`.
+    ///
+    /// Returns `None` if the segment's file data is located out of bounds.
+    pub fn find_in_segment<'pattern>(
+        &self,
+        program_header: ProgramHeader<'slice, C, E>,
+        pattern: &'pattern [u8],
+    ) -> Option<PatternMatches<'slice, 'pattern>> {
+        let data = self.segment_data(program_header)?;
+
+        Some(PatternMatches::new(
+            data,
+            program_header.virtual_address().into(),
+            pattern,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the null-terminated strings of at least `minimum_length`
+    /// bytes in the file data of the segment controlled by `program_header`.
+    ///
+    /// Returns `None` if the segment's file data is located out of bounds.
+    pub fn strings_in_segment(
+        &self,
+        program_header: ProgramHeader<'slice, C, E>,
+        minimum_length: usize,
+    ) -> Option<Strings<'slice>> {
+        let data = self.segment_data(program_header)?;
+
+        Some(Strings::new(
+            data,
+            program_header.virtual_address().into(),
+            minimum_length,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the occurrences of `pattern` in the data of the section
+    /// described by `section_header`.
+    ///
+    /// The virtual addresses of the occurrences are relative to the address of the section,
+    /// which is zero for sections that are not loaded into memory.
+    ///
+    /// Returns `None` if the section's data is located out of bounds.
+    pub fn find_in_section<'pattern>(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+        pattern: &'pattern [u8],
+    ) -> Option<PatternMatches<'slice, 'pattern>> {
+        let data = self.section_data(section_header)?;
+
+        Some(PatternMatches::new(
+            data,
+            section_header.address().into(),
+            pattern,
+        ))
+    }
+
+    /// Returns an [`Iterator`] over the null-terminated strings of at least `minimum_length`
+    /// bytes in the data of the section described by `section_header`.
+    ///
+    /// The virtual addresses of the strings are relative to the address of the section, which
+    /// is zero for sections that are not loaded into memory.
+    ///
+    /// Returns `None` if the section's data is located out of bounds.
+    pub fn strings_in_section(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+        minimum_length: usize,
+    ) -> Option<Strings<'slice>> {
+        let data = self.section_data(section_header)?;
+
+        Some(Strings::new(
+            data,
+            section_header.address().into(),
+            minimum_length,
+        ))
+    }
+}
+
+/// An occurrence of a pattern found by [`PatternMatches`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PatternMatch {
+    /// The offset of the occurrence from the start of the searched data.
+    pub offset: usize,
+    /// The virtual address of the occurrence.
+    pub virtual_address: u64,
+}
+
+/// An [`Iterator`] over the occurrences of a byte pattern in a slice.
+///
+/// Occurrences may overlap. An empty pattern matches nothing.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct PatternMatches<'slice, 'pattern> {
+    /// The data being searched.
+    data: &'slice [u8],
+    /// The virtual address of the first byte of `data`.
+    address: u64,
+    /// The pattern being searched for.
+    pattern: &'pattern [u8],
+    /// The offset at which the search resumes.
+    offset: usize,
+}
+
+impl<'slice, 'pattern> PatternMatches<'slice, 'pattern> {
+    /// Creates a new [`PatternMatches`] searching `data`, whose first byte resides at `address`,
+    /// for `pattern`.
+    pub fn new(data: &'slice [u8], address: u64, pattern: &'pattern [u8]) -> Self {
+        Self {
+            data,
+            address,
+            pattern,
+            offset: 0,
+        }
+    }
+}
+
+impl Iterator for PatternMatches<'_, '_> {
+    type Item = PatternMatch;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (&first, _) = self.pattern.split_first()?;
+
+        while let Some(remaining) = self.data.get(self.offset..) {
+            let position = find_byte(first, remaining)?;
+            let offset = self.offset + position;
+            self.offset = offset + 1;
+
+            if self.data[offset..].starts_with(self.pattern) {
+                return Some(PatternMatch {
+                    offset,
+                    virtual_address: self.address.wrapping_add(offset as u64),
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl FusedIterator for PatternMatches<'_, '_> {}
+
+/// A null-terminated string found by [`Strings`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FoundString<'slice> {
+    /// The offset of the string from the start of the searched data.
+    pub offset: usize,
+    /// The virtual address of the string.
+    pub virtual_address: u64,
+    /// The bytes of the string, without the terminating null byte.
+    pub bytes: &'slice [u8],
+}
+
+/// An [`Iterator`] over the null-terminated strings of printable ASCII characters in a slice.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Strings<'slice> {
+    /// The data being searched.
+    data: &'slice [u8],
+    /// The virtual address of the first byte of `data`.
+    address: u64,
+    /// The minimum length of a string to yield.
+    minimum_length: usize,
+    /// The offset at which the search resumes.
+    offset: usize,
+}
+
+impl<'slice> Strings<'slice> {
+    /// Creates a new [`Strings`] searching `data`, whose first byte resides at `address`, for
+    /// null-terminated strings of at least `minimum_length` bytes.
+    pub fn new(data: &'slice [u8], address: u64, minimum_length: usize) -> Self {
+        Self {
+            data,
+            address,
+            minimum_length: minimum_length.max(1),
+            offset: 0,
+        }
+    }
+}
+
+impl<'slice> Iterator for Strings<'slice> {
+    type Item = FoundString<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(remaining) = self.data.get(self.offset..) {
+            let start = self.offset;
+            let length = remaining
+                .iter()
+                .position(|&byte| !is_printable(byte))
+                .unwrap_or(remaining.len());
+            let terminated = remaining.get(length) == Some(&0);
+            self.offset = start + length + 1;
+
+            if terminated && length >= self.minimum_length {
+                return Some(FoundString {
+                    offset: start,
+                    virtual_address: self.address.wrapping_add(start as u64),
+                    bytes: &remaining[..length],
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl<'slice> FusedIterator for Strings<'slice> {}
+
+/// Returns `true` if `byte` is a printable ASCII character or whitespace.
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Returns the index of the first null byte in `data`.
+///
+/// Long runs of non-null bytes, such as those in string tables, are scanned a word at a time.
+pub fn find_nul(data: &[u8]) -> Option<usize> {
+    find_byte(0, data)
+}
+
+/// Returns `data` up to, but not including, the first null byte.
+///
+/// Returns all of `data` if it contains no null byte.
+pub fn until_nul(data: &[u8]) -> &[u8] {
+    match find_nul(data) {
+        Some(length) => &data[..length],
+        None => data,
+    }
+}
+
+/// Returns the index of the first occurrence of `byte` in `data`.
+///
+/// Long runs of bytes not equal to `byte` are scanned a word at a time.
+pub fn find_byte(byte: u8, data: &[u8]) -> Option<usize> {
+    const WORD: usize = core::mem::size_of::<usize>();
+    const LOW_BITS: usize = usize::MAX / 0xFF;
+    const HIGH_BITS: usize = LOW_BITS << 7;
+
+    let repeated = LOW_BITS * usize::from(byte);
+    let mut offset = 0;
+    while let Some(chunk) = data.get(offset..).and_then(<[u8]>::first_chunk::<WORD>) {
+        let word = usize::from_ne_bytes(*chunk) ^ repeated;
+        // Nonzero if and only if some byte of `word` is zero.
+        if word.wrapping_sub(LOW_BITS) & !word & HIGH_BITS != 0 {
+            break;
+        }
+        offset += WORD;
+    }
+
+    data[offset..]
+        .iter()
+        .position(|&candidate| candidate == byte)
+        .map(|position| offset + position)
+}
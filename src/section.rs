@@ -0,0 +1,380 @@
+//! Definitions for typed views of ELF sections.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    dynamic::DynamicTable,
+    encoding::EncodingParse,
+    relocation::{RelTable, RelaTable},
+    section_header::{self, SectionHeader, SectionType},
+    string_table::StringTable,
+    symbol::SymbolTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the typed [`Section`] described by `section_header`.
+    pub fn section(&self, section_header: SectionHeader<'slice, C, E>) -> Section<'slice, C, E> {
+        Section::new(*self, section_header)
+    }
+
+    /// Returns an [`Iterator`] over the typed [`Section`]s of this [`ElfFile`].
+    pub fn sections(&self) -> Sections<'slice, C, E> {
+        Sections {
+            file: *self,
+            iter: self.section_header_table().map(IntoIterator::into_iter),
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`RelocationSection`]s of this [`ElfFile`] whose
+    /// [`SectionHeader::info()`] refers to the section at `section_index`, which hold the
+    /// relocations that apply to that section.
+    pub fn relocations_for_section(&self, section_index: u16) -> RelocationSections<'slice, C, E> {
+        RelocationSections {
+            sections: self.sections(),
+            section_index,
+        }
+    }
+}
+
+/// A typed view of an ELF section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Section<'slice, C: ClassParse, E: EncodingParse> {
+    /// A [`SectionType::SYMTAB`] or [`SectionType::DYNSYM`] section.
+    SymbolTable(SymbolTableSection<'slice, C, E>),
+    /// A [`SectionType::STRTAB`] section.
+    StringTable(StringTableSection<'slice, C, E>),
+    /// A [`SectionType::REL`] section.
+    Rel(RelSection<'slice, C, E>),
+    /// A [`SectionType::RELA`] section.
+    Rela(RelaSection<'slice, C, E>),
+    /// A [`SectionType::DYNAMIC`] section.
+    Dynamic(DynamicSection<'slice, C, E>),
+    /// A [`SectionType::NOTE`] section.
+    Note(NoteSection<'slice, C, E>),
+    /// A [`SectionType::NOBITS`] section.
+    NoBits(NoBitsSection<'slice, C, E>),
+    /// A section of any other [`SectionType`].
+    Other(SectionHeader<'slice, C, E>),
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Section<'slice, C, E> {
+    /// Creates a new [`Section`] from the given `section_header` located in `file`.
+    pub fn new(file: ElfFile<'slice, C, E>, section_header: SectionHeader<'slice, C, E>) -> Self {
+        match section_header.section_type() {
+            SectionType::SYMTAB | SectionType::DYNSYM => Self::SymbolTable(SymbolTableSection {
+                file,
+                section_header,
+            }),
+            SectionType::STRTAB => Self::StringTable(StringTableSection {
+                file,
+                section_header,
+            }),
+            SectionType::REL => Self::Rel(RelSection {
+                file,
+                section_header,
+            }),
+            SectionType::RELA => Self::Rela(RelaSection {
+                file,
+                section_header,
+            }),
+            SectionType::DYNAMIC => Self::Dynamic(DynamicSection {
+                file,
+                section_header,
+            }),
+            SectionType::NOTE => Self::Note(NoteSection {
+                file,
+                section_header,
+            }),
+            SectionType::NOBITS => Self::NoBits(NoBitsSection { section_header }),
+            _ => Self::Other(section_header),
+        }
+    }
+
+    /// Returns the [`SectionHeader`] describing this [`Section`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        match self {
+            Self::SymbolTable(section) => section.section_header,
+            Self::StringTable(section) => section.section_header,
+            Self::Rel(section) => section.section_header,
+            Self::Rela(section) => section.section_header,
+            Self::Dynamic(section) => section.section_header,
+            Self::Note(section) => section.section_header,
+            Self::NoBits(section) => section.section_header,
+            Self::Other(section_header) => *section_header,
+        }
+    }
+}
+
+/// A [`SectionType::SYMTAB`] or [`SectionType::DYNSYM`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SymbolTableSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`SymbolTableSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`SymbolTableSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SymbolTableSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`SymbolTableSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the [`SymbolTable`] contained in this [`SymbolTableSection`], with names located
+    /// in the section its [`SectionHeader::link()`] refers to.
+    pub fn table(&self) -> Option<SymbolTable<'slice, C, E>> {
+        self.file.symbol_table_from_section(self.section_header)
+    }
+}
+
+/// A [`SectionType::STRTAB`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct StringTableSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`StringTableSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`StringTableSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> StringTableSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`StringTableSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the [`StringTable`] contained in this [`StringTableSection`].
+    pub fn table(&self) -> Option<StringTable<'slice>> {
+        self.file
+            .section_data(self.section_header)
+            .map(StringTable::new)
+    }
+}
+
+/// A [`SectionType::REL`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RelSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`RelSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`RelSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> RelSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`RelSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the index of the section the relocations of this [`RelSection`] apply to.
+    pub fn target_index(&self) -> u32 {
+        self.section_header.info()
+    }
+
+    /// Returns the [`RelTable`] contained in this [`RelSection`].
+    pub fn table(&self) -> Option<RelTable<'slice, C, E>> {
+        let data = self.file.section_data(self.section_header)?;
+
+        RelTable::new(
+            self.file.class,
+            self.file.encoding,
+            data,
+            data.len() / self.file.class.expected_rel_size(),
+        )
+    }
+}
+
+/// A [`SectionType::RELA`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RelaSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`RelaSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`RelaSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> RelaSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`RelaSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the index of the section the relocations of this [`RelaSection`] apply to.
+    pub fn target_index(&self) -> u32 {
+        self.section_header.info()
+    }
+
+    /// Returns the [`RelaTable`] contained in this [`RelaSection`].
+    pub fn table(&self) -> Option<RelaTable<'slice, C, E>> {
+        let data = self.file.section_data(self.section_header)?;
+
+        RelaTable::new(
+            self.file.class,
+            self.file.encoding,
+            data,
+            data.len() / self.file.class.expected_rela_size(),
+        )
+    }
+}
+
+/// A [`SectionType::REL`] or [`SectionType::RELA`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum RelocationSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// A [`SectionType::REL`] section.
+    Rel(RelSection<'slice, C, E>),
+    /// A [`SectionType::RELA`] section.
+    Rela(RelaSection<'slice, C, E>),
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> RelocationSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`RelocationSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        match self {
+            Self::Rel(section) => section.section_header,
+            Self::Rela(section) => section.section_header,
+        }
+    }
+
+    /// Returns the index of the section the relocations of this [`RelocationSection`] apply to.
+    pub fn target_index(&self) -> u32 {
+        self.section_header().info()
+    }
+}
+
+/// A [`SectionType::DYNAMIC`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DynamicSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`DynamicSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`DynamicSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> DynamicSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`DynamicSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the [`DynamicTable`] contained in this [`DynamicSection`].
+    pub fn table(&self) -> Option<DynamicTable<'slice, C, E>> {
+        let data = self.file.section_data(self.section_header)?;
+
+        DynamicTable::new(
+            self.file.class,
+            self.file.encoding,
+            data,
+            data.len() / self.file.class.expected_dynamic_size(),
+        )
+    }
+}
+
+/// A [`SectionType::NOTE`] section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct NoteSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`NoteSection`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`SectionHeader`] describing this [`NoteSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> NoteSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`NoteSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the raw bytes of this [`NoteSection`].
+    pub fn data(&self) -> Option<&'slice [u8]> {
+        self.file.section_data(self.section_header)
+    }
+
+    /// Returns the [`NoteTable`][nt] contained in this [`NoteSection`].
+    ///
+    /// [nt]: crate::note::NoteTable
+    #[cfg(feature = "notes")]
+    pub fn notes(&self) -> Option<crate::note::NoteTable<'slice, E>> {
+        let alignment = match self.section_header.alignment().into() {
+            8 => 8,
+            _ => 4,
+        };
+
+        crate::note::NoteTable::new(self.file.encoding, self.data()?, alignment)
+    }
+}
+
+/// A [`SectionType::NOBITS`] section, which occupies memory but no space in the file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct NoBitsSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`SectionHeader`] describing this [`NoBitsSection`].
+    section_header: SectionHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> NoBitsSection<'slice, C, E> {
+    /// Returns the [`SectionHeader`] describing this [`NoBitsSection`].
+    pub fn section_header(&self) -> SectionHeader<'slice, C, E> {
+        self.section_header
+    }
+
+    /// Returns the virtual address at which this [`NoBitsSection`] should reside in memory.
+    pub fn address(&self) -> C::ClassUsize {
+        self.section_header.address()
+    }
+
+    /// Returns the number of zeroed bytes this [`NoBitsSection`] occupies in memory.
+    pub fn memory_size(&self) -> C::ClassUsize {
+        self.section_header.size()
+    }
+}
+
+/// An [`Iterator`] over the typed [`Section`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Sections<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing the [`Section`]s.
+    file: ElfFile<'slice, C, E>,
+    /// The underlying [`Iterator`] over the section header table.
+    iter: Option<section_header::IntoIter<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for Sections<'slice, C, E> {
+    type Item = Section<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section_header = self.iter.as_mut()?.next()?;
+
+        Some(Section::new(self.file, section_header))
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for Sections<'slice, C, E> {}
+
+/// An [`Iterator`] over the [`RelocationSection`]s of an [`ElfFile`] that apply to a single
+/// section.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct RelocationSections<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`Section`]s that have not yet been visited.
+    sections: Sections<'slice, C, E>,
+    /// The index of the section the relocations apply to.
+    section_index: u16,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for RelocationSections<'slice, C, E> {
+    type Item = RelocationSection<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section_index = u32::from(self.section_index);
+
+        self.sections.find_map(|section| {
+            let section = match section {
+                Section::Rel(section) => RelocationSection::Rel(section),
+                Section::Rela(section) => RelocationSection::Rela(section),
+                _ => return None,
+            };
+
+            (section.target_index() == section_index).then_some(section)
+        })
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for RelocationSections<'slice, C, E> {}
@@ -0,0 +1,388 @@
+//! Definitions for editing the section headers of an ELF file in place.
+//!
+//! [`SectionHeaderEditor`] changes individual fields of the section header table without moving
+//! the contents of any section, so the rest of the file keeps its exact bytes. Names that are
+//! not already present in the section name string table are added by appending a grown copy of
+//! the string table to the end of the file.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{Class32, Class64, ClassParse},
+    encoding::EncodingParse,
+    section_header::{ClassParseSectionHeader, SectionFlags},
+    string_table::StringTable,
+    writer::ByteLayout,
+    ElfFile, ParseElfFileError,
+};
+
+/// An editor over the section header table of an ELF file.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct SectionHeaderEditor<'buffer> {
+    /// The buffer holding the ELF file, followed by spare bytes the file may grow into.
+    bytes: &'buffer mut [u8],
+    /// The number of bytes of the buffer occupied by the ELF file.
+    len: usize,
+    /// The offset of the section header table.
+    table_offset: usize,
+    /// The number of entries in the section header table.
+    count: u16,
+    /// The stride of each entry in the section header table.
+    entry_size: usize,
+    /// The index of the section name string table.
+    string_table_index: u16,
+    /// The [`ByteLayout`] of the values of the ELF file.
+    layout: ByteLayout,
+}
+
+impl<'buffer> SectionHeaderEditor<'buffer> {
+    /// Creates a new [`SectionHeaderEditor`] over the section header table of the ELF file
+    /// occupying the first `len` bytes of `bytes`.
+    ///
+    /// Any bytes of `bytes` following the ELF file are used when a rename requires the section
+    /// name string table to grow.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSectionError::BufferTooSmall`]: Returned if `bytes` is smaller than `len`.
+    /// - [`EditSectionError::ParseElfFileError`]: Returned if the first `len` bytes of `bytes`
+    ///   do not contain a valid [`ElfFile`].
+    /// - [`EditSectionError::MissingSectionHeaderTable`]: Returned if the [`ElfFile`] has no
+    ///   section header table or it is located out of bounds.
+    pub fn new<C: ClassParse, E: EncodingParse>(
+        bytes: &'buffer mut [u8],
+        len: usize,
+    ) -> Result<Self, EditSectionError> {
+        let file = ElfFile::<C, E>::new(
+            bytes
+                .get(..len)
+                .ok_or(EditSectionError::BufferTooSmall { required: len })?,
+        )?;
+        let header = file.header();
+        let ident = header.ident();
+        let table_offset = file
+            .section_header_table()
+            .and_then(|table| table.get(0))
+            .and_then(|section_header| file.range_of(section_header.raw_bytes()))
+            .ok_or(EditSectionError::MissingSectionHeaderTable)?
+            .start;
+
+        Ok(Self {
+            table_offset,
+            count: header.section_header_count(),
+            entry_size: usize::from(header.section_header_size()),
+            string_table_index: header.section_header_string_table_index(),
+            layout: ByteLayout::new(ident),
+            bytes,
+            len,
+        })
+    }
+
+    /// Returns the bytes of the edited ELF file.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Returns the number of bytes of the buffer occupied by the edited ELF file.
+    pub fn file_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of entries in the section header table.
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns the name of the section at `index`, without the terminating null byte.
+    pub fn name(&self, index: u16) -> Option<&[u8]> {
+        let offset = self
+            .layout
+            .read_u32(self.bytes, self.field(index, Self::name_offset())?);
+        let offset = usize::try_from(offset).ok()?;
+
+        self.string_table()?.get(offset).ok()
+    }
+
+    /// Returns the [`SectionFlags`] of the section at `index`.
+    pub fn flags(&self, index: u16) -> Option<SectionFlags> {
+        let offset = self.field(index, self.flags_offset())?;
+
+        Some(SectionFlags(self.layout.read_word(self.bytes, offset)))
+    }
+
+    /// Overwrites the [`SectionFlags`] of the section at `index` with `flags`.
+    ///
+    /// Only the flags are changed. In particular, the address of a section is kept when
+    /// [`SectionFlags::ALLOC`] is cleared, as `objcopy --set-section-flags` does.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSectionError::IndexOutOfBounds`]: Returned if `index` is out of bounds.
+    /// - [`EditSectionError::ValueOutOfRange`]: Returned if `flags` cannot be represented in the
+    ///   class of the ELF file.
+    pub fn set_flags(&mut self, index: u16, flags: SectionFlags) -> Result<(), EditSectionError> {
+        let offset = self
+            .field(index, self.flags_offset())
+            .ok_or(EditSectionError::IndexOutOfBounds)?;
+        if !self.layout.fits_word(flags.0) {
+            return Err(EditSectionError::ValueOutOfRange);
+        }
+
+        self.layout.write_word(self.bytes, offset, flags.0);
+
+        Ok(())
+    }
+
+    /// Sets the bits of `flags` in the [`SectionFlags`] of the section at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SectionHeaderEditor::set_flags()`].
+    pub fn insert_flags(
+        &mut self,
+        index: u16,
+        flags: SectionFlags,
+    ) -> Result<(), EditSectionError> {
+        let current = self
+            .flags(index)
+            .ok_or(EditSectionError::IndexOutOfBounds)?;
+
+        self.set_flags(index, current | flags)
+    }
+
+    /// Clears the bits of `flags` in the [`SectionFlags`] of the section at `index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`SectionHeaderEditor::set_flags()`].
+    pub fn remove_flags(
+        &mut self,
+        index: u16,
+        flags: SectionFlags,
+    ) -> Result<(), EditSectionError> {
+        let current = self
+            .flags(index)
+            .ok_or(EditSectionError::IndexOutOfBounds)?;
+
+        self.set_flags(index, SectionFlags(current.0 & !flags.0))
+    }
+
+    /// Returns the address of the section at `index`.
+    pub fn address(&self, index: u16) -> Option<u64> {
+        let offset = self.field(index, self.address_offset())?;
+
+        Some(self.layout.read_word(self.bytes, offset))
+    }
+
+    /// Overwrites the address of the section at `index` with `address`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSectionError::IndexOutOfBounds`]: Returned if `index` is out of bounds.
+    /// - [`EditSectionError::ValueOutOfRange`]: Returned if `address` cannot be represented in
+    ///   the class of the ELF file.
+    pub fn set_address(&mut self, index: u16, address: u64) -> Result<(), EditSectionError> {
+        let offset = self
+            .field(index, self.address_offset())
+            .ok_or(EditSectionError::IndexOutOfBounds)?;
+        if !self.layout.fits_word(address) {
+            return Err(EditSectionError::ValueOutOfRange);
+        }
+
+        self.layout.write_word(self.bytes, offset, address);
+
+        Ok(())
+    }
+
+    /// Renames the section at `index` to `name`, which must not contain null bytes.
+    ///
+    /// If `name` is already present in the section name string table, including as the tail of
+    /// another name, it is reused. Otherwise, a copy of the string table with `name` appended is
+    /// written to the end of the file, and the file grows into the spare bytes of the buffer.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSectionError::IndexOutOfBounds`]: Returned if `index` is out of bounds.
+    /// - [`EditSectionError::InteriorNul`]: Returned if `name` contains a null byte.
+    /// - [`EditSectionError::MissingStringTable`]: Returned if the ELF file has no section name
+    ///   string table or it is located out of bounds.
+    /// - [`EditSectionError::ValueOutOfRange`]: Returned if the grown string table cannot be
+    ///   described in the class of the ELF file.
+    /// - [`EditSectionError::BufferTooSmall`]: Returned if the buffer has too few spare bytes to
+    ///   hold the grown string table.
+    pub fn rename(&mut self, index: u16, name: &[u8]) -> Result<(), EditSectionError> {
+        let name_field = self
+            .field(index, Self::name_offset())
+            .ok_or(EditSectionError::IndexOutOfBounds)?;
+        if name.contains(&0) {
+            return Err(EditSectionError::InteriorNul);
+        }
+
+        let table = self
+            .string_table()
+            .ok_or(EditSectionError::MissingStringTable)?
+            .bytes();
+        if let Some(position) = table
+            .windows(name.len() + 1)
+            .position(|window| window[..name.len()] == *name && window[name.len()] == 0)
+        {
+            let position =
+                u32::try_from(position).map_err(|_| EditSectionError::ValueOutOfRange)?;
+            self.layout.write_u32(self.bytes, name_field, position);
+            return Ok(());
+        }
+
+        let table_size = table.len();
+        let grown_size = table_size + name.len() + 1;
+        let name_offset =
+            u32::try_from(table_size).map_err(|_| EditSectionError::ValueOutOfRange)?;
+        let required = self
+            .len
+            .checked_add(grown_size)
+            .ok_or(EditSectionError::ValueOutOfRange)?;
+        if self.bytes.len() < required {
+            return Err(EditSectionError::BufferTooSmall { required });
+        }
+        if !self.layout.fits_word(required as u64) {
+            return Err(EditSectionError::ValueOutOfRange);
+        }
+
+        let offset_field = self
+            .field(self.string_table_index, self.file_offset_offset())
+            .ok_or(EditSectionError::MissingStringTable)?;
+        let size_field = self
+            .field(self.string_table_index, self.size_offset())
+            .ok_or(EditSectionError::MissingStringTable)?;
+        let string_table_start = self.layout.read_word(self.bytes, offset_field) as usize;
+
+        let start = self.len;
+        self.bytes
+            .copy_within(string_table_start..string_table_start + table_size, start);
+        self.bytes[start + table_size..start + table_size + name.len()].copy_from_slice(name);
+        self.bytes[required - 1] = 0;
+        self.len = required;
+
+        self.layout
+            .write_word(self.bytes, offset_field, start as u64);
+        self.layout
+            .write_word(self.bytes, size_field, grown_size as u64);
+        self.layout.write_u32(self.bytes, name_field, name_offset);
+
+        Ok(())
+    }
+
+    /// Returns the section name string table.
+    fn string_table(&self) -> Option<StringTable<'_>> {
+        if self.string_table_index == 0 {
+            return None;
+        }
+
+        let start = self.field(self.string_table_index, self.file_offset_offset())?;
+        let size = self.field(self.string_table_index, self.size_offset())?;
+        let start = usize::try_from(self.layout.read_word(self.bytes, start)).ok()?;
+        let size = usize::try_from(self.layout.read_word(self.bytes, size)).ok()?;
+
+        self.bytes()
+            .get(start..start.checked_add(size)?)
+            .map(StringTable::new)
+    }
+
+    /// Returns the offset of the field at `field_offset` within the section header at `index`.
+    fn field(&self, index: u16, field_offset: usize) -> Option<usize> {
+        if index >= self.count {
+            return None;
+        }
+
+        Some(self.table_offset + usize::from(index) * self.entry_size + field_offset)
+    }
+
+    /// Returns the offset of the name offset within a section header.
+    fn name_offset() -> usize {
+        Class64.section_name_offset()
+    }
+
+    /// Returns the offset of the flags within a section header.
+    fn flags_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_flags_offset()
+        } else {
+            Class32.section_flags_offset()
+        }
+    }
+
+    /// Returns the offset of the address within a section header.
+    fn address_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_address_offset()
+        } else {
+            Class32.section_address_offset()
+        }
+    }
+
+    /// Returns the offset of the file offset within a section header.
+    fn file_offset_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_file_offset_offset()
+        } else {
+            Class32.section_file_offset_offset()
+        }
+    }
+
+    /// Returns the offset of the size within a section header.
+    fn size_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_size_offset()
+        } else {
+            Class32.section_size_offset()
+        }
+    }
+}
+
+/// Various errors that can occur while editing the section headers of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum EditSectionError {
+    /// An error occurred while parsing the [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// The [`ElfFile`] has no section header table located in bounds.
+    MissingSectionHeaderTable,
+    /// The [`ElfFile`] has no section name string table located in bounds.
+    MissingStringTable,
+    /// The index of a section is out of bounds.
+    IndexOutOfBounds,
+    /// A value cannot be represented in the class of the [`ElfFile`].
+    ValueOutOfRange,
+    /// A section name contains a null byte.
+    InteriorNul,
+    /// The buffer is too small to hold the edited [`ElfFile`].
+    BufferTooSmall {
+        /// The number of bytes required to hold the edited [`ElfFile`].
+        required: usize,
+    },
+}
+
+impl From<ParseElfFileError> for EditSectionError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for EditSectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::MissingSectionHeaderTable => write!(f, "ELF file has no section header table"),
+            Self::MissingStringTable => write!(f, "ELF file has no section name string table"),
+            Self::IndexOutOfBounds => write!(f, "section index out of bounds"),
+            Self::ValueOutOfRange => write!(f, "value out of range for the ELF class"),
+            Self::InteriorNul => write!(f, "section name contains a null byte"),
+            Self::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "buffer too small to hold edited file of {required} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for EditSectionError {}
@@ -0,0 +1,548 @@
+//! Definitions for the ELF section headers.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::{ClassParse, ClassParseBase},
+    encoding::EncodingParse,
+};
+
+/// View of an ELF section header.
+///
+/// Structure that describes how to locate and interpret a section of an ELF file, the unit used
+/// by linkers and other tools that operate on the file rather than the loaded program.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SectionHeader<'slice, C, E> {
+    /// The underlying bytes of the [`SectionHeader`].
+    pub(crate) bytes: &'slice [u8],
+    /// The [`ClassParseSectionHeader`] of this [`SectionHeader`].
+    pub(crate) class: C,
+    /// The [`EncodingParse`] of this [`SectionHeader`].
+    pub(crate) encoding: E,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SectionHeader<'slice, C, E> {
+    /// Creates a new [`SectionHeader`] from the given `slice`, returning `None` if the `slice` is
+    /// too small to contain a [`SectionHeader`].
+    pub fn new(class: C, encoding: E, slice: &'slice [u8]) -> Option<Self> {
+        if slice.len() < class.expected_section_header_size() {
+            return None;
+        }
+
+        let section_header = Self {
+            bytes: slice,
+            class,
+            encoding,
+        };
+
+        Some(section_header)
+    }
+
+    /// Returns the bytes of this [`SectionHeader`].
+    ///
+    /// For a [`SectionHeader`] obtained from a [`SectionHeaderTable`], this is exactly the entry
+    /// in the table, including any bytes beyond the fields defined by the ELF specification.
+    pub fn raw_bytes(&self) -> &'slice [u8] {
+        self.bytes
+    }
+
+    /// Returns the offset of the name of this section within the section name string table.
+    pub fn name_offset(&self) -> u32 {
+        self.encoding
+            .parse_u32_at(self.class.section_name_offset(), self.bytes)
+    }
+
+    /// Returns the [`SectionType`] of the section this [`SectionHeader`] describes.
+    pub fn section_type(&self) -> SectionType {
+        SectionType(
+            self.encoding
+                .parse_u32_at(self.class.section_type_offset(), self.bytes),
+        )
+    }
+
+    /// Returns the [`SectionFlags`] associated with this [`SectionHeader`].
+    pub fn flags(&self) -> SectionFlags {
+        SectionFlags(
+            self.class
+                .parse_class_usize_at(self.encoding, self.class.section_flags_offset(), self.bytes)
+                .into(),
+        )
+    }
+
+    /// Returns the virtual address at which the first byte of the section should reside in
+    /// memory, or zero if the section is not loaded.
+    pub fn address(&self) -> C::ClassUsize {
+        self.class.parse_class_usize_at(
+            self.encoding,
+            self.class.section_address_offset(),
+            self.bytes,
+        )
+    }
+
+    /// Returns the offset within the file at which the section this [`SectionHeader`] describes
+    /// starts.
+    pub fn file_offset(&self) -> C::ClassUsize {
+        self.class.parse_class_usize_at(
+            self.encoding,
+            self.class.section_file_offset_offset(),
+            self.bytes,
+        )
+    }
+
+    /// Returns the number of bytes in the section.
+    ///
+    /// A [`SectionType::NOBITS`] section occupies no bytes in the file, regardless of its size.
+    pub fn size(&self) -> C::ClassUsize {
+        self.class
+            .parse_class_usize_at(self.encoding, self.class.section_size_offset(), self.bytes)
+    }
+
+    /// Returns the section header table index link, whose interpretation depends on the
+    /// [`SectionType`].
+    pub fn link(&self) -> u32 {
+        self.encoding
+            .parse_u32_at(self.class.section_link_offset(), self.bytes)
+    }
+
+    /// Returns the extra information, whose interpretation depends on the [`SectionType`].
+    pub fn info(&self) -> u32 {
+        self.encoding
+            .parse_u32_at(self.class.section_info_offset(), self.bytes)
+    }
+
+    /// Returns the alignment constraint of the address of the section.
+    pub fn alignment(&self) -> C::ClassUsize {
+        self.class.parse_class_usize_at(
+            self.encoding,
+            self.class.section_alignment_offset(),
+            self.bytes,
+        )
+    }
+
+    /// Returns the size of each entry of the section if it holds a table of fixed-size entries,
+    /// or zero otherwise.
+    pub fn entry_size(&self) -> C::ClassUsize {
+        self.class.parse_class_usize_at(
+            self.encoding,
+            self.class.section_entry_size_offset(),
+            self.bytes,
+        )
+    }
+
+    /// Returns the bytes of this [`SectionHeader`] that follow the fields defined by the ELF
+    /// specification.
+    ///
+    /// These bytes are present when the size of each entry in the [`SectionHeaderTable`] is larger
+    /// than the expected size of a [`SectionHeader`].
+    pub fn trailing_bytes(&self) -> &'slice [u8] {
+        &self.bytes[self.class.expected_section_header_size()..]
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for SectionHeader<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("SectionHeader");
+
+        debug_struct.field("name_offset", &self.name_offset());
+        debug_struct.field("section_type", &self.section_type());
+        debug_struct.field("flags", &self.flags());
+
+        debug_struct.field("address", &self.address());
+        debug_struct.field("file_offset", &self.file_offset());
+        debug_struct.field("size", &self.size());
+
+        debug_struct.field("link", &self.link());
+        debug_struct.field("info", &self.info());
+
+        debug_struct.field("alignment", &self.alignment());
+        debug_struct.field("entry_size", &self.entry_size());
+
+        debug_struct.finish()
+    }
+}
+
+/// The type of the section the associated [`SectionHeader`] describes.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionType(pub u32);
+
+impl SectionType {
+    /// Unused [`SectionHeader`].
+    pub const NULL: Self = Self(0);
+    /// Information defined by the program.
+    pub const PROGBITS: Self = Self(1);
+    /// Symbol table.
+    pub const SYMTAB: Self = Self(2);
+    /// String table.
+    pub const STRTAB: Self = Self(3);
+    /// Relocation entries with explicit addends.
+    pub const RELA: Self = Self(4);
+    /// Symbol hash table.
+    pub const HASH: Self = Self(5);
+    /// Dynamic linking information.
+    pub const DYNAMIC: Self = Self(6);
+    /// Auxiliary information.
+    pub const NOTE: Self = Self(7);
+    /// Section that occupies no space in the file.
+    pub const NOBITS: Self = Self(8);
+    /// Relocation entries without explicit addends.
+    pub const REL: Self = Self(9);
+    /// Reserved.
+    pub const SHLIB: Self = Self(10);
+    /// Dynamic linking symbol table.
+    pub const DYNSYM: Self = Self(11);
+    /// Array of initialization functions.
+    pub const INIT_ARRAY: Self = Self(14);
+    /// Array of termination functions.
+    pub const FINI_ARRAY: Self = Self(15);
+    /// Array of functions run before all other initialization functions.
+    pub const PREINIT_ARRAY: Self = Self(16);
+    /// Section group.
+    pub const GROUP: Self = Self(17);
+    /// Extended section indices of a symbol table.
+    pub const SYMTAB_SHNDX: Self = Self(18);
+    /// Relative relocation entries in the compact RELR format.
+    pub const RELR: Self = Self(19);
+    /// GNU symbol hash table.
+    pub const GNU_HASH: Self = Self(0x6FFF_FFF6);
+    /// GNU symbol version definitions.
+    pub const GNU_VERDEF: Self = Self(0x6FFF_FFFD);
+    /// GNU symbol version requirements.
+    pub const GNU_VERNEED: Self = Self(0x6FFF_FFFE);
+    /// GNU symbol version table.
+    pub const GNU_VERSYM: Self = Self(0x6FFF_FFFF);
+}
+
+impl fmt::Debug for SectionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NULL => f.pad("Null"),
+            Self::PROGBITS => f.pad("ProgBits"),
+            Self::SYMTAB => f.pad("SymbolTable"),
+            Self::STRTAB => f.pad("StringTable"),
+            Self::RELA => f.pad("Rela"),
+            Self::HASH => f.pad("Hash"),
+            Self::DYNAMIC => f.pad("Dynamic"),
+            Self::NOTE => f.pad("Note"),
+            Self::NOBITS => f.pad("NoBits"),
+            Self::REL => f.pad("Rel"),
+            Self::SHLIB => f.pad("Shlib"),
+            Self::DYNSYM => f.pad("DynamicSymbolTable"),
+            Self::INIT_ARRAY => f.pad("InitArray"),
+            Self::FINI_ARRAY => f.pad("FiniArray"),
+            Self::PREINIT_ARRAY => f.pad("PreinitArray"),
+            Self::GROUP => f.pad("Group"),
+            Self::SYMTAB_SHNDX => f.pad("SymbolTableIndices"),
+            Self::RELR => f.pad("Relr"),
+            Self::GNU_HASH => f.pad("GnuHash"),
+            Self::GNU_VERDEF => f.pad("GnuVerdef"),
+            Self::GNU_VERNEED => f.pad("GnuVerneed"),
+            Self::GNU_VERSYM => f.pad("GnuVersym"),
+            section_type => f.debug_tuple("SectionType").field(&section_type.0).finish(),
+        }
+    }
+}
+
+impl From<u32> for SectionType {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<SectionType> for u32 {
+    fn from(value: SectionType) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<u32> for SectionType {
+    fn eq(&self, other: &u32) -> bool {
+        self.0 == *other
+    }
+}
+
+/// The attributes of a section.
+///
+/// 32-bit ELF files store these flags in 32 bits, which are zero-extended.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionFlags(pub u64);
+
+impl SectionFlags {
+    /// The section contains data that should be writable during execution.
+    pub const WRITE: Self = Self(0x1);
+    /// The section occupies memory during execution.
+    pub const ALLOC: Self = Self(0x2);
+    /// The section contains executable instructions.
+    pub const EXECINSTR: Self = Self(0x4);
+    /// The section contains data that may be merged to eliminate duplication.
+    pub const MERGE: Self = Self(0x10);
+    /// The section contains null-terminated strings.
+    pub const STRINGS: Self = Self(0x20);
+    /// The [`SectionHeader::info()`] of the section holds a section header table index.
+    pub const INFO_LINK: Self = Self(0x40);
+    /// The section must keep its ordering relative to the section it links to.
+    pub const LINK_ORDER: Self = Self(0x80);
+    /// The section requires operating system specific processing.
+    pub const OS_NONCONFORMING: Self = Self(0x100);
+    /// The section is a member of a section group.
+    pub const GROUP: Self = Self(0x200);
+    /// The section holds thread local storage.
+    pub const TLS: Self = Self(0x400);
+    /// The section contains compressed data.
+    pub const COMPRESSED: Self = Self(0x800);
+
+    /// Mask of the bits reserved for operating system specific semantics.
+    pub const MASK_OS: Self = Self(0x0FF0_0000);
+    /// Mask of the bits reserved for processor specific semantics.
+    pub const MASK_PROCESSOR: Self = Self(0xF000_0000);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`SectionFlags`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for SectionFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for SectionFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl core::ops::BitAnd for SectionFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl core::ops::BitAndAssign for SectionFlags {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+impl core::ops::BitXor for SectionFlags {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl core::ops::BitXorAssign for SectionFlags {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        *self = *self ^ rhs;
+    }
+}
+
+/// A table of [`SectionHeader`]s.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionHeaderTable<'slice, C, E> {
+    /// The underlying bytes of the ELF section header table.
+    pub(crate) bytes: &'slice [u8],
+    /// The number of entries in the [`SectionHeaderTable`].
+    pub(crate) entry_count: u16,
+    /// The stride of each [`SectionHeader`] in the [`SectionHeaderTable`].
+    pub(crate) entry_size: u16,
+    /// The [`ClassParse`] of this [`SectionHeaderTable`].
+    pub(crate) class: C,
+    /// The [`EncodingParse`] of this [`SectionHeaderTable`].
+    pub(crate) encoding: E,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SectionHeaderTable<'slice, C, E> {
+    /// Creates a new [`SectionHeaderTable`] from the given `slice`.
+    ///
+    /// The generated [`SectionHeaderTable`] has `count` [`SectionHeader`]s.
+    pub fn new(class: C, encoding: E, slice: &'slice [u8], count: u16, size: u16) -> Option<Self> {
+        if usize::from(size) < class.expected_section_header_size() {
+            return None;
+        }
+
+        if usize::from(count)
+            .checked_mul(usize::from(size))
+            .is_none_or(|total_size| slice.len() < total_size)
+        {
+            return None;
+        }
+
+        let table = Self {
+            bytes: slice,
+            entry_count: count,
+            entry_size: size,
+            class,
+            encoding,
+        };
+
+        Some(table)
+    }
+
+    /// Returns the [`SectionHeader`] located at `index`.
+    pub fn get(&self, index: u16) -> Option<SectionHeader<'slice, C, E>> {
+        if index >= self.entry_count {
+            return None;
+        }
+
+        let start = usize::from(index) * usize::from(self.entry_size);
+        let section_header = SectionHeader {
+            bytes: &self.bytes[start..start + usize::from(self.entry_size)],
+            class: self.class,
+            encoding: self.encoding,
+        };
+
+        Some(section_header)
+    }
+
+    /// Returns the number of [`SectionHeader`]s in this [`SectionHeaderTable`].
+    pub fn count(&self) -> u16 {
+        self.entry_count
+    }
+
+    /// Limits this [`SectionHeaderTable`] to at most `max_entries` [`SectionHeader`]s.
+    pub fn with_max_entries(mut self, max_entries: u16) -> Self {
+        self.entry_count = self.entry_count.min(max_entries);
+        self
+    }
+
+    /// Returns an [`Iterator`] over the [`SectionHeader`]s in this [`SectionHeaderTable`] that
+    /// describe sections of the given [`SectionType`].
+    pub fn sections_of_type(self, section_type: SectionType) -> SectionsOfType<'slice, C, E> {
+        SectionsOfType {
+            iter: self.into_iter(),
+            section_type,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`SectionHeader`]s in this [`SectionHeaderTable`] that
+    /// describe sections with all of the given [`SectionFlags`] set.
+    pub fn sections_with_flags(self, flags: SectionFlags) -> SectionsWithFlags<'slice, C, E> {
+        SectionsWithFlags {
+            iter: self.into_iter(),
+            flags,
+        }
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for SectionHeaderTable<'slice, C, E> {
+    type Item = SectionHeader<'slice, C, E>;
+    type IntoIter = IntoIter<'slice, C, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            next: 0,
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for SectionHeaderTable<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+/// An [`Iterator`] over the [`SectionHeader`]s in a [`SectionHeaderTable`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct IntoIter<'slice, C: ClassParse, E: EncodingParse> {
+    /// The table to iterate over.
+    table: SectionHeaderTable<'slice, C, E>,
+    /// The index in the [`SectionHeaderTable`].
+    next: u16,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for IntoIter<'slice, C, E> {
+    type Item = SectionHeader<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.table.get(self.next)?;
+
+        self.next += 1;
+        Some(item)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for IntoIter<'slice, C, E> {}
+
+/// An [`Iterator`] over the [`SectionHeader`]s in a [`SectionHeaderTable`] that describe sections
+/// of a particular [`SectionType`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SectionsOfType<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`Iterator`] over the [`SectionHeaderTable`].
+    iter: IntoIter<'slice, C, E>,
+    /// The [`SectionType`] to yield.
+    section_type: SectionType,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SectionsOfType<'slice, C, E> {
+    type Item = SectionHeader<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section_type = self.section_type;
+        self.iter
+            .find(|section_header| section_header.section_type() == section_type)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for SectionsOfType<'slice, C, E> {}
+
+/// An [`Iterator`] over the [`SectionHeader`]s in a [`SectionHeaderTable`] that describe sections
+/// with particular [`SectionFlags`] set.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SectionsWithFlags<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`Iterator`] over the [`SectionHeaderTable`].
+    iter: IntoIter<'slice, C, E>,
+    /// The [`SectionFlags`] that must be set.
+    flags: SectionFlags,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SectionsWithFlags<'slice, C, E> {
+    type Item = SectionHeader<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let flags = self.flags;
+        self.iter
+            .find(|section_header| section_header.flags().contains(flags))
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for SectionsWithFlags<'slice, C, E> {}
+
+/// The information required to implement class aware parsing of an ELF section header.
+pub trait ClassParseSectionHeader: ClassParseBase {
+    /// The offset of the offset of the section name in the section name string table.
+    fn section_name_offset(self) -> usize;
+    /// The offset of the [`SectionType`].
+    fn section_type_offset(self) -> usize;
+    /// The offset of the section flags.
+    fn section_flags_offset(self) -> usize;
+
+    /// The offset of the virtual address of the section.
+    fn section_address_offset(self) -> usize;
+    /// The offset of the file offset of the section.
+    fn section_file_offset_offset(self) -> usize;
+    /// The offset of the number of bytes in the section.
+    fn section_size_offset(self) -> usize;
+
+    /// The offset of the section header table index link.
+    fn section_link_offset(self) -> usize;
+    /// The offset of the extra information of the section.
+    fn section_info_offset(self) -> usize;
+
+    /// The offset of the alignment of the section.
+    fn section_alignment_offset(self) -> usize;
+    /// The offset of the size of each entry in the section.
+    fn section_entry_size_offset(self) -> usize;
+
+    /// The expected size of an ELF section header.
+    fn expected_section_header_size(self) -> usize;
+}
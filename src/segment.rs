@@ -0,0 +1,278 @@
+//! Definitions for typed views of ELF segments.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    dynamic::DynamicTable,
+    encoding::EncodingParse,
+    program_header::{self, ProgramHeader, SegmentFlags, SegmentType},
+    search::find_nul,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the typed [`Segment`] controlled by `program_header`.
+    pub fn segment(&self, program_header: ProgramHeader<'slice, C, E>) -> Segment<'slice, C, E> {
+        Segment::new(*self, program_header)
+    }
+
+    /// Returns an [`Iterator`] over the typed [`Segment`]s of this [`ElfFile`].
+    pub fn segments(&self) -> Segments<'slice, C, E> {
+        Segments {
+            file: *self,
+            iter: self.program_header_table().map(IntoIterator::into_iter),
+        }
+    }
+
+    /// Returns the path of the program interpreter requested by this [`ElfFile`], without the
+    /// terminating null byte.
+    ///
+    /// Returns `None` if this [`ElfFile`] has no [`SegmentType::INTERPRETER`] segment or its path
+    /// is malformed.
+    pub fn interpreter_path(&self) -> Option<&'slice [u8]> {
+        self.segments().find_map(|segment| match segment {
+            Segment::Interp(segment) => segment.path(),
+            _ => None,
+        })
+    }
+}
+
+/// A typed view of an ELF segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Segment<'slice, C: ClassParse, E: EncodingParse> {
+    /// A [`SegmentType::LOAD`] segment.
+    Load(LoadSegment<'slice, C, E>),
+    /// A [`SegmentType::DYNAMIC`] segment.
+    Dynamic(DynamicSegment<'slice, C, E>),
+    /// A [`SegmentType::INTERPRETER`] segment.
+    Interp(InterpSegment<'slice, C, E>),
+    /// A [`SegmentType::NOTE`] segment.
+    Note(NoteSegment<'slice, C, E>),
+    /// A [`SegmentType::TLS`] segment.
+    Tls(TlsSegment<'slice, C, E>),
+    /// A segment of any other [`SegmentType`].
+    Other(ProgramHeader<'slice, C, E>),
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Segment<'slice, C, E> {
+    /// Creates a new [`Segment`] from the given `program_header` located in `file`.
+    pub fn new(file: ElfFile<'slice, C, E>, program_header: ProgramHeader<'slice, C, E>) -> Self {
+        match program_header.segment_type() {
+            SegmentType::LOAD => Self::Load(LoadSegment {
+                file,
+                program_header,
+            }),
+            SegmentType::DYNAMIC => Self::Dynamic(DynamicSegment {
+                file,
+                program_header,
+            }),
+            SegmentType::INTERPRETER => Self::Interp(InterpSegment {
+                file,
+                program_header,
+            }),
+            SegmentType::NOTE => Self::Note(NoteSegment {
+                file,
+                program_header,
+            }),
+            SegmentType::TLS => Self::Tls(TlsSegment {
+                file,
+                program_header,
+            }),
+            _ => Self::Other(program_header),
+        }
+    }
+
+    /// Returns the [`ProgramHeader`] controlling this [`Segment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        match self {
+            Self::Load(segment) => segment.program_header,
+            Self::Dynamic(segment) => segment.program_header,
+            Self::Interp(segment) => segment.program_header,
+            Self::Note(segment) => segment.program_header,
+            Self::Tls(segment) => segment.program_header,
+            Self::Other(program_header) => *program_header,
+        }
+    }
+}
+
+/// A [`SegmentType::LOAD`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct LoadSegment<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`LoadSegment`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`ProgramHeader`] controlling this [`LoadSegment`].
+    program_header: ProgramHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> LoadSegment<'slice, C, E> {
+    /// Returns the [`ProgramHeader`] controlling this [`LoadSegment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        self.program_header
+    }
+
+    /// Returns the virtual address at which this [`LoadSegment`] should reside in memory.
+    pub fn virtual_address(&self) -> C::ClassUsize {
+        self.program_header.virtual_address()
+    }
+
+    /// Returns the number of bytes of this [`LoadSegment`] when loaded into memory.
+    pub fn memory_size(&self) -> C::ClassUsize {
+        self.program_header.memory_size()
+    }
+
+    /// Returns the [`SegmentFlags`] with which this [`LoadSegment`] should be mapped.
+    pub fn flags(&self) -> SegmentFlags {
+        self.program_header.flags()
+    }
+
+    /// Returns the file data that should be copied to the start of this [`LoadSegment`] when
+    /// loaded.
+    ///
+    /// The remaining bytes of the loaded segment should be zeroed.
+    pub fn data(&self) -> Option<&'slice [u8]> {
+        self.file.segment_data(self.program_header)
+    }
+}
+
+/// A [`SegmentType::DYNAMIC`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct DynamicSegment<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`DynamicSegment`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`ProgramHeader`] controlling this [`DynamicSegment`].
+    program_header: ProgramHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> DynamicSegment<'slice, C, E> {
+    /// Returns the [`ProgramHeader`] controlling this [`DynamicSegment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        self.program_header
+    }
+
+    /// Returns the [`DynamicTable`] contained in this [`DynamicSegment`].
+    pub fn table(&self) -> Option<DynamicTable<'slice, C, E>> {
+        let data = self.file.segment_data(self.program_header)?;
+
+        DynamicTable::new(
+            self.file.class,
+            self.file.encoding,
+            data,
+            data.len() / self.file.class.expected_dynamic_size(),
+        )
+    }
+}
+
+/// A [`SegmentType::INTERPRETER`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct InterpSegment<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`InterpSegment`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`ProgramHeader`] controlling this [`InterpSegment`].
+    program_header: ProgramHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> InterpSegment<'slice, C, E> {
+    /// Returns the [`ProgramHeader`] controlling this [`InterpSegment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        self.program_header
+    }
+
+    /// Returns the path of the program interpreter, without the terminating null byte.
+    ///
+    /// Returns `None` if the path is located out of bounds or is not null-terminated.
+    pub fn path(&self) -> Option<&'slice [u8]> {
+        let data = self.file.segment_data(self.program_header)?;
+        let length = find_nul(data)?;
+
+        Some(&data[..length])
+    }
+}
+
+/// A [`SegmentType::NOTE`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct NoteSegment<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`NoteSegment`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`ProgramHeader`] controlling this [`NoteSegment`].
+    program_header: ProgramHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> NoteSegment<'slice, C, E> {
+    /// Returns the [`ProgramHeader`] controlling this [`NoteSegment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        self.program_header
+    }
+
+    /// Returns the raw bytes of this [`NoteSegment`].
+    pub fn data(&self) -> Option<&'slice [u8]> {
+        self.file.segment_data(self.program_header)
+    }
+
+    /// Returns the [`NoteTable`][nt] contained in this [`NoteSegment`].
+    ///
+    /// [nt]: crate::note::NoteTable
+    #[cfg(feature = "notes")]
+    pub fn notes(&self) -> Option<crate::note::NoteTable<'slice, E>> {
+        let alignment = match self.program_header.alignment().into() {
+            8 => 8,
+            _ => 4,
+        };
+
+        crate::note::NoteTable::new(self.file.encoding, self.data()?, alignment)
+    }
+}
+
+/// A [`SegmentType::TLS`] segment.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct TlsSegment<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing this [`TlsSegment`].
+    file: ElfFile<'slice, C, E>,
+    /// The [`ProgramHeader`] controlling this [`TlsSegment`].
+    program_header: ProgramHeader<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> TlsSegment<'slice, C, E> {
+    /// Returns the [`ProgramHeader`] controlling this [`TlsSegment`].
+    pub fn program_header(&self) -> ProgramHeader<'slice, C, E> {
+        self.program_header
+    }
+
+    /// Returns the initialization image of the thread local storage.
+    ///
+    /// The remaining bytes of each thread's block should be zeroed.
+    pub fn template(&self) -> Option<&'slice [u8]> {
+        self.file.segment_data(self.program_header)
+    }
+
+    /// Returns the total size of each thread's block of thread local storage.
+    pub fn block_size(&self) -> C::ClassUsize {
+        self.program_header.memory_size()
+    }
+
+    /// Returns the required alignment of each thread's block of thread local storage.
+    pub fn block_alignment(&self) -> C::ClassUsize {
+        self.program_header.alignment()
+    }
+}
+
+/// An [`Iterator`] over the typed [`Segment`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Segments<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] containing the [`Segment`]s.
+    file: ElfFile<'slice, C, E>,
+    /// The underlying [`Iterator`] over the program header table.
+    iter: Option<program_header::IntoIter<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for Segments<'slice, C, E> {
+    type Item = Segment<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let program_header = self.iter.as_mut()?.next()?;
+
+        Some(Segment::new(self.file, program_header))
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> FusedIterator for Segments<'slice, C, E> {}
@@ -0,0 +1,540 @@
+//! Definitions for parsing SFrame stack trace information.
+//!
+//! SFrame describes, for each function, how to recover the canonical frame address (CFA), the
+//! return address, and the frame pointer at any program counter within the function. It is
+//! stored in the `.sframe` section, which is located by the [`SegmentType::GNU_SFRAME`] segment.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_len, ClassParse},
+    encoding::EncodingParse,
+    program_header::SegmentType,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`SFrameSection`] described by the [`SegmentType::GNU_SFRAME`] segment of this
+    /// [`ElfFile`].
+    ///
+    /// Returns `None` if no [`SegmentType::GNU_SFRAME`] segment is present or the section fails
+    /// to parse.
+    pub fn sframe(&self) -> Option<SFrameSection<'slice, E>> {
+        let program_header = self
+            .program_header_table()?
+            .segments_of_type(SegmentType::GNU_SFRAME)
+            .next()?;
+        let data = self.segment_data(program_header)?;
+
+        SFrameSection::new(self.encoding, data, program_header.virtual_address().into()).ok()
+    }
+}
+
+/// A parsed SFrame section.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SFrameSection<'slice, E> {
+    /// The underlying bytes of this [`SFrameSection`].
+    bytes: &'slice [u8],
+    /// The virtual address at which this [`SFrameSection`] resides in memory.
+    address: u64,
+    /// The offset of the function descriptor entries.
+    fde_offset: usize,
+    /// The number of function descriptor entries.
+    fde_count: usize,
+    /// The bytes of the frame row entries, to which every frame row entry is bounded.
+    fres: &'slice [u8],
+    /// The [`EncodingParse`] of this [`SFrameSection`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> SFrameSection<'slice, E> {
+    /// The value of the magic number identifying an [`SFrameSection`].
+    pub const MAGIC: u16 = 0xDEE2;
+    /// The first version of the SFrame format.
+    pub const VERSION_1: u8 = 1;
+    /// The second version of the SFrame format.
+    pub const VERSION_2: u8 = 2;
+
+    /// The size of the SFrame header, excluding the auxiliary header.
+    const HEADER_SIZE: usize = 28;
+
+    /// Creates a new [`SFrameSection`] from the given `slice`, which resides in memory at
+    /// `address`.
+    ///
+    /// # Errors
+    ///
+    /// - [`ParseSFrameError::TooSmall`]: Returned if `slice` is too small to contain the SFrame
+    ///   header.
+    /// - [`ParseSFrameError::InvalidMagic`]: Returned if the magic number is incorrect.
+    /// - [`ParseSFrameError::UnsupportedVersion`]: Returned if the SFrame version is not
+    ///   [`SFrameSection::VERSION_1`] or [`SFrameSection::VERSION_2`].
+    /// - [`ParseSFrameError::OutOfBounds`]: Returned if the function descriptor entries or frame
+    ///   row entries are located out of bounds.
+    pub fn new(encoding: E, slice: &'slice [u8], address: u64) -> Result<Self, ParseSFrameError> {
+        if slice.len() < Self::HEADER_SIZE {
+            return Err(ParseSFrameError::TooSmall);
+        }
+
+        let magic = encoding.parse_u16_at(0, slice);
+        if magic != Self::MAGIC {
+            return Err(ParseSFrameError::InvalidMagic(magic));
+        }
+
+        let version = encoding.parse_u8_at(2, slice);
+        if version != Self::VERSION_1 && version != Self::VERSION_2 {
+            return Err(ParseSFrameError::UnsupportedVersion(version));
+        }
+
+        let header_size = Self::HEADER_SIZE + usize::from(encoding.parse_u8_at(7, slice));
+        let fde_count =
+            to_len(encoding.parse_u32_at(8, slice)).map_err(|_| ParseSFrameError::OutOfBounds)?;
+        let fre_length =
+            to_len(encoding.parse_u32_at(16, slice)).map_err(|_| ParseSFrameError::OutOfBounds)?;
+        let fde_offset = to_len(encoding.parse_u32_at(20, slice))
+            .ok()
+            .and_then(|offset| offset.checked_add(header_size))
+            .ok_or(ParseSFrameError::OutOfBounds)?;
+        let fre_offset = to_len(encoding.parse_u32_at(24, slice))
+            .ok()
+            .and_then(|offset| offset.checked_add(header_size))
+            .ok_or(ParseSFrameError::OutOfBounds)?;
+
+        let fde_end = fde_count
+            .checked_mul(fde_size(version))
+            .and_then(|size| size.checked_add(fde_offset));
+        if fde_end.is_none_or(|end| end > slice.len()) {
+            return Err(ParseSFrameError::OutOfBounds);
+        }
+        let fres = fre_offset
+            .checked_add(fre_length)
+            .and_then(|fre_end| slice.get(fre_offset..fre_end))
+            .ok_or(ParseSFrameError::OutOfBounds)?;
+
+        let section = Self {
+            bytes: slice,
+            address,
+            fde_offset,
+            fde_count,
+            fres,
+            encoding,
+        };
+
+        Ok(section)
+    }
+
+    /// Returns the version of the SFrame format used by this [`SFrameSection`].
+    pub fn version(&self) -> u8 {
+        self.encoding.parse_u8_at(2, self.bytes)
+    }
+
+    /// Returns the [`SFrameFlags`] of this [`SFrameSection`].
+    pub fn flags(&self) -> SFrameFlags {
+        SFrameFlags(self.encoding.parse_u8_at(3, self.bytes))
+    }
+
+    /// Returns the [`SFrameAbi`] of this [`SFrameSection`].
+    pub fn abi(&self) -> SFrameAbi {
+        SFrameAbi(self.encoding.parse_u8_at(4, self.bytes))
+    }
+
+    /// Returns the fixed offset of the frame pointer from the CFA, if the frame pointer is not
+    /// tracked per frame row entry.
+    pub fn fixed_fp_offset(&self) -> Option<i8> {
+        Some(self.encoding.parse_u8_at(5, self.bytes) as i8).filter(|&offset| offset != 0)
+    }
+
+    /// Returns the fixed offset of the return address from the CFA, if the return address is not
+    /// tracked per frame row entry.
+    pub fn fixed_ra_offset(&self) -> Option<i8> {
+        Some(self.encoding.parse_u8_at(6, self.bytes) as i8).filter(|&offset| offset != 0)
+    }
+
+    /// Returns the number of [`SFrameFde`]s in this [`SFrameSection`].
+    pub fn fde_count(&self) -> usize {
+        self.fde_count
+    }
+
+    /// Returns the [`SFrameFde`] at `index`.
+    pub fn fde(&self, index: usize) -> Option<SFrameFde> {
+        if index >= self.fde_count() {
+            return None;
+        }
+
+        let offset = self.fde_offset + index * fde_size(self.version());
+        let start = i64::from(self.encoding.parse_i32_at(offset, self.bytes));
+        let base = if self.flags().contains(SFrameFlags::FDE_FUNC_START_PCREL) {
+            self.address.checked_add(offset as u64)?
+        } else {
+            self.address
+        };
+
+        let fde = SFrameFde {
+            start_address: base.checked_add_signed(start)?,
+            size: self.encoding.parse_u32_at(offset + 4, self.bytes),
+            fre_offset: self.encoding.parse_u32_at(offset + 8, self.bytes),
+            fre_count: self.encoding.parse_u32_at(offset + 12, self.bytes),
+            info: self.encoding.parse_u8_at(offset + 16, self.bytes),
+            repetition_size: if self.version() == Self::VERSION_1 {
+                0
+            } else {
+                self.encoding.parse_u8_at(offset + 17, self.bytes)
+            },
+        };
+
+        Some(fde)
+    }
+
+    /// Returns the [`SFrameFde`] describing the function containing `pc`.
+    pub fn find_fde(&self, pc: u64) -> Option<SFrameFde> {
+        let contains = |fde: &SFrameFde| {
+            pc.checked_sub(fde.start_address)
+                .is_some_and(|offset| offset < u64::from(fde.size))
+        };
+
+        if !self.flags().contains(SFrameFlags::FDE_SORTED) {
+            return (0..self.fde_count())
+                .filter_map(|index| self.fde(index))
+                .find(contains);
+        }
+
+        let (mut low, mut high) = (0, self.fde_count());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let fde = self.fde(middle)?;
+            if pc < fde.start_address {
+                high = middle;
+            } else if contains(&fde) {
+                return Some(fde);
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        None
+    }
+
+    /// Returns the [`SFrameFre`] at `index` within the frame row entries of `fde`.
+    pub fn fre(&self, fde: &SFrameFde, index: u32) -> Option<SFrameFre> {
+        if index >= fde.fre_count {
+            return None;
+        }
+
+        let mut offset = to_len(fde.fre_offset).ok()?;
+        for _ in 0..index {
+            offset = offset.checked_add(self.fre_size_at(fde, offset)?)?;
+        }
+
+        self.parse_fre(fde, offset)
+    }
+
+    /// Returns the [`SFrameRow`] describing how to recover the caller's frame at `pc`.
+    pub fn lookup(&self, pc: u64) -> Option<SFrameRow> {
+        let fde = self.find_fde(pc)?;
+        let mut function_offset = pc - fde.start_address;
+        if fde.fde_type() == SFrameFdeType::PC_MASK {
+            function_offset %= u64::from(fde.repetition_size).max(1);
+        }
+
+        let mut offset = to_len(fde.fre_offset).ok()?;
+        let mut found = None;
+        for _ in 0..fde.fre_count {
+            let fre = self.parse_fre(&fde, offset)?;
+            if u64::from(fre.start_offset) > function_offset {
+                break;
+            }
+
+            offset = offset.checked_add(self.fre_size_at(&fde, offset)?)?;
+            found = Some(fre);
+        }
+
+        let fre = found?;
+        let row = SFrameRow {
+            cfa_base: fre.cfa_base(),
+            cfa_offset: fre.offset(0)?,
+            ra_offset: match self.fixed_ra_offset() {
+                Some(offset) => Some(i32::from(offset)),
+                None => fre.offset(1),
+            },
+            fp_offset: match (self.fixed_fp_offset(), self.fixed_ra_offset()) {
+                (Some(offset), _) => Some(i32::from(offset)),
+                (None, Some(_)) => fre.offset(1),
+                (None, None) => fre.offset(2),
+            },
+            mangled_ra: fre.mangled_ra(),
+        };
+
+        Some(row)
+    }
+
+    /// Returns the size of the frame row entry of `fde` at `offset` within the frame row entries.
+    fn fre_size_at(&self, fde: &SFrameFde, offset: usize) -> Option<usize> {
+        let address_size = fde.fre_address_size()?;
+        let info = *self.fres.get(offset.checked_add(address_size)?)?;
+        let (count, size) = fre_offset_layout(info)?;
+
+        Some(address_size + 1 + count * size)
+    }
+
+    /// Parses the frame row entry of `fde` at `offset` within the frame row entries.
+    fn parse_fre(&self, fde: &SFrameFde, offset: usize) -> Option<SFrameFre> {
+        let address_size = fde.fre_address_size()?;
+        let end = offset.checked_add(self.fre_size_at(fde, offset)?)?;
+        let bytes = self.fres.get(offset..end)?;
+
+        let start_offset = match address_size {
+            1 => u32::from(self.encoding.parse_u8_at(0, bytes)),
+            2 => u32::from(self.encoding.parse_u16_at(0, bytes)),
+            _ => self.encoding.parse_u32_at(0, bytes),
+        };
+        let info = bytes[address_size];
+        let (count, size) = fre_offset_layout(info)?;
+
+        let mut offsets = [0; 3];
+        for (index, value) in offsets.iter_mut().enumerate().take(count.min(3)) {
+            let at = address_size + 1 + index * size;
+            *value = match size {
+                1 => i32::from(self.encoding.parse_u8_at(at, bytes) as i8),
+                2 => i32::from(self.encoding.parse_u16_at(at, bytes) as i16),
+                _ => self.encoding.parse_i32_at(at, bytes),
+            };
+        }
+
+        let fre = SFrameFre {
+            start_offset,
+            info,
+            offsets,
+            offset_count: count.min(3) as u8,
+        };
+
+        Some(fre)
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for SFrameSection<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SFrameSection")
+            .field("flags", &self.flags())
+            .field("abi", &self.abi())
+            .field("fixed_fp_offset", &self.fixed_fp_offset())
+            .field("fixed_ra_offset", &self.fixed_ra_offset())
+            .field("fde_count", &self.fde_count())
+            .finish()
+    }
+}
+
+/// Returns the size of a function descriptor entry in the given SFrame `version`.
+fn fde_size(version: u8) -> usize {
+    if version == 1 {
+        17
+    } else {
+        20
+    }
+}
+
+/// Returns the number and size of the stack offsets of a frame row entry with the given `info`.
+fn fre_offset_layout(info: u8) -> Option<(usize, usize)> {
+    let count = usize::from((info >> 1) & 0xF);
+    let size = match (info >> 5) & 0x3 {
+        0 => 1,
+        1 => 2,
+        2 => 4,
+        _ => return None,
+    };
+
+    Some((count, size))
+}
+
+/// Various errors that can occur while parsing an [`SFrameSection`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ParseSFrameError {
+    /// The given slice is too small to contain the SFrame header.
+    TooSmall,
+    /// The magic number is incorrect.
+    InvalidMagic(u16),
+    /// The SFrame version is not supported.
+    UnsupportedVersion(u8),
+    /// The function descriptor entries or frame row entries are located out of bounds.
+    OutOfBounds,
+}
+
+impl fmt::Display for ParseSFrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooSmall => write!(f, "slice too small to contain SFrame header"),
+            Self::InvalidMagic(magic) => write!(f, "invalid SFrame magic: {magic:#x}"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported SFrame version: {version}")
+            }
+            Self::OutOfBounds => write!(f, "SFrame entries located out of bounds"),
+        }
+    }
+}
+
+impl error::Error for ParseSFrameError {}
+
+/// The flags of an [`SFrameSection`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameFlags(pub u8);
+
+impl SFrameFlags {
+    /// The function descriptor entries are sorted by start address.
+    pub const FDE_SORTED: Self = Self(0x1);
+    /// All functions preserve the frame pointer.
+    pub const FRAME_POINTER: Self = Self(0x2);
+    /// Function start addresses are relative to the function descriptor entry rather than the
+    /// start of the section.
+    pub const FDE_FUNC_START_PCREL: Self = Self(0x4);
+
+    /// Returns `true` if all of the flags in `other` are set in this [`SFrameFlags`].
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+/// The ABI and architecture described by an [`SFrameSection`].
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameAbi(pub u8);
+
+impl SFrameAbi {
+    /// Big endian AArch64.
+    pub const AARCH64_BIG_ENDIAN: Self = Self(1);
+    /// Little endian AArch64.
+    pub const AARCH64_LITTLE_ENDIAN: Self = Self(2);
+    /// Little endian x86-64.
+    pub const AMD64_LITTLE_ENDIAN: Self = Self(3);
+}
+
+impl fmt::Debug for SFrameAbi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::AARCH64_BIG_ENDIAN => f.pad("Aarch64BigEndian"),
+            Self::AARCH64_LITTLE_ENDIAN => f.pad("Aarch64LittleEndian"),
+            Self::AMD64_LITTLE_ENDIAN => f.pad("Amd64LittleEndian"),
+            abi => f.debug_tuple("SFrameAbi").field(&abi.0).finish(),
+        }
+    }
+}
+
+/// An SFrame function descriptor entry.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameFde {
+    /// The virtual address of the start of the function.
+    pub start_address: u64,
+    /// The size of the function in bytes.
+    pub size: u32,
+    /// The offset of the first frame row entry of the function within the frame row entries.
+    pub fre_offset: u32,
+    /// The number of frame row entries of the function.
+    pub fre_count: u32,
+    /// The raw information byte of the function descriptor entry.
+    pub info: u8,
+    /// The size of the repeating code block for [`SFrameFdeType::PC_MASK`] entries.
+    ///
+    /// This is always zero for version 1 sections.
+    pub repetition_size: u8,
+}
+
+impl SFrameFde {
+    /// Returns the [`SFrameFdeType`] of this [`SFrameFde`].
+    pub const fn fde_type(&self) -> SFrameFdeType {
+        SFrameFdeType((self.info >> 4) & 0x1)
+    }
+
+    /// Returns the size in bytes of the start address of each frame row entry of this
+    /// [`SFrameFde`].
+    pub const fn fre_address_size(&self) -> Option<usize> {
+        match self.info & 0xF {
+            0 => Some(1),
+            1 => Some(2),
+            2 => Some(4),
+            _ => None,
+        }
+    }
+}
+
+/// The manner in which the frame row entries of an [`SFrameFde`] are matched against a program
+/// counter.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameFdeType(pub u8);
+
+impl SFrameFdeType {
+    /// Frame row entries are matched against the offset of the program counter from the start of
+    /// the function.
+    pub const PC_INCREMENT: Self = Self(0);
+    /// Frame row entries are matched against the offset of the program counter modulo the
+    /// repetition size, as used for procedure linkage tables.
+    pub const PC_MASK: Self = Self(1);
+}
+
+impl fmt::Debug for SFrameFdeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::PC_INCREMENT => f.pad("PcIncrement"),
+            Self::PC_MASK => f.pad("PcMask"),
+            fde_type => f.debug_tuple("SFrameFdeType").field(&fde_type.0).finish(),
+        }
+    }
+}
+
+/// An SFrame frame row entry.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameFre {
+    /// The offset from the start of the function at which this [`SFrameFre`] begins to apply.
+    pub start_offset: u32,
+    /// The raw information byte of the frame row entry.
+    pub info: u8,
+    /// The stack offsets of the frame row entry.
+    offsets: [i32; 3],
+    /// The number of valid stack offsets.
+    offset_count: u8,
+}
+
+impl SFrameFre {
+    /// Returns the [`SFrameCfaBase`] from which the CFA is computed.
+    pub const fn cfa_base(&self) -> SFrameCfaBase {
+        if self.info & 0x1 == 0 {
+            SFrameCfaBase::FramePointer
+        } else {
+            SFrameCfaBase::StackPointer
+        }
+    }
+
+    /// Returns `true` if the return address is mangled, such as by pointer authentication.
+    pub const fn mangled_ra(&self) -> bool {
+        self.info & 0x80 != 0
+    }
+
+    /// Returns the stack offset at `index`.
+    pub fn offset(&self, index: usize) -> Option<i32> {
+        self.offsets[..usize::from(self.offset_count)]
+            .get(index)
+            .copied()
+    }
+}
+
+/// The register from which the CFA is computed.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SFrameCfaBase {
+    /// The CFA is computed from the frame pointer.
+    FramePointer,
+    /// The CFA is computed from the stack pointer.
+    StackPointer,
+}
+
+/// The rules for recovering the caller's frame at a particular program counter.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SFrameRow {
+    /// The register from which the CFA is computed.
+    pub cfa_base: SFrameCfaBase,
+    /// The offset of the CFA from [`SFrameRow::cfa_base`].
+    pub cfa_offset: i32,
+    /// The offset from the CFA at which the return address is saved, if it is saved on the stack.
+    pub ra_offset: Option<i32>,
+    /// The offset from the CFA at which the frame pointer is saved, if it is saved on the stack.
+    pub fp_offset: Option<i32>,
+    /// Whether the return address is mangled, such as by pointer authentication.
+    pub mangled_ra: bool,
+}
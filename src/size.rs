@@ -0,0 +1,326 @@
+//! Definitions for attributing the size of an ELF file to its segments, sections and symbols.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    program_header::{self, SegmentFlags, SegmentType},
+    section_header::{self, SectionFlags, SectionType},
+    symbol::{SymbolTable, SymbolType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`SizeReport`] of this [`ElfFile`].
+    pub fn size_report(&self) -> SizeReport<'slice, C, E> {
+        SizeReport { file: *self }
+    }
+}
+
+/// A breakdown of the file and memory size of an [`ElfFile`] by segment, section and symbol.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SizeReport<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being measured.
+    file: ElfFile<'slice, C, E>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SizeReport<'slice, C, E> {
+    /// Returns an [`Iterator`] over the [`SegmentSize`] of each segment of the measured
+    /// [`ElfFile`].
+    ///
+    /// The yielded [`SegmentSize`]s can be collected and sorted to rank segments by size.
+    pub fn segments(&self) -> SegmentSizes<'slice, C, E> {
+        SegmentSizes {
+            iter: self
+                .file
+                .program_header_table()
+                .map(IntoIterator::into_iter),
+            index: 0,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`SectionSize`] of each section of the measured
+    /// [`ElfFile`].
+    ///
+    /// The yielded [`SectionSize`]s can be collected and sorted to rank sections by size.
+    pub fn sections(&self) -> SectionSizes<'slice, C, E> {
+        SectionSizes {
+            file: self.file,
+            iter: self
+                .file
+                .section_header_table()
+                .map(IntoIterator::into_iter),
+            index: 0,
+        }
+    }
+
+    /// Returns an [`Iterator`] over the [`SymbolSize`] of each defined symbol of the table
+    /// returned by [`ElfFile::symbols()`].
+    ///
+    /// The yielded [`SymbolSize`]s can be collected and sorted to rank symbols by size.
+    pub fn symbols(&self) -> SymbolSizes<'slice, C, E> {
+        SymbolSizes {
+            table: self.file.symbols().map(|(_, table)| table),
+            index: 0,
+        }
+    }
+
+    /// Returns the [`SizeTotals`] of the [`SegmentType::LOAD`] segments of the measured
+    /// [`ElfFile`].
+    pub fn totals(&self) -> SizeTotals {
+        let mut totals = SizeTotals {
+            file_size: self.file.bytes.len() as u64,
+            ..SizeTotals::default()
+        };
+
+        for segment in self.segments() {
+            if segment.segment_type != SegmentType::LOAD {
+                continue;
+            }
+
+            let zero_fill = segment.memory_size.saturating_sub(segment.file_size);
+            if segment.flags.contains(SegmentFlags::WRITE) {
+                totals.data = totals.data.saturating_add(segment.file_size);
+                totals.bss = totals.bss.saturating_add(zero_fill);
+            } else {
+                totals.text = totals
+                    .text
+                    .saturating_add(segment.file_size)
+                    .saturating_add(zero_fill);
+            }
+        }
+
+        totals
+    }
+}
+
+/// The size of a single segment of an [`ElfFile`].
+///
+/// [`SegmentSize`]s are ordered by memory size, then file size, then index.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SegmentSize {
+    /// The index of the segment in the program header table.
+    pub index: u16,
+    /// The [`SegmentType`] of the segment.
+    pub segment_type: SegmentType,
+    /// The [`SegmentFlags`] of the segment.
+    pub flags: SegmentFlags,
+    /// The number of bytes the segment occupies in the file.
+    pub file_size: u64,
+    /// The number of bytes the segment occupies in memory.
+    pub memory_size: u64,
+}
+
+impl PartialOrd for SegmentSize {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SegmentSize {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.memory_size, self.file_size, self.index).cmp(&(
+            other.memory_size,
+            other.file_size,
+            other.index,
+        ))
+    }
+}
+
+/// The size of a single section of an [`ElfFile`].
+///
+/// [`SectionSize`]s are ordered by memory size, then file size, then index.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SectionSize<'slice> {
+    /// The index of the section in the section header table.
+    pub index: u16,
+    /// The name of the section, if the section name table is present.
+    pub name: Option<&'slice [u8]>,
+    /// The [`SectionType`] of the section.
+    pub section_type: SectionType,
+    /// The [`SectionFlags`] of the section.
+    pub flags: SectionFlags,
+    /// The number of bytes the section occupies in the file, zero for
+    /// [`SectionType::NOBITS`] sections.
+    pub file_size: u64,
+    /// The number of bytes the section occupies in memory, zero for sections without
+    /// [`SectionFlags::ALLOC`].
+    pub memory_size: u64,
+}
+
+impl PartialOrd for SectionSize<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SectionSize<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.memory_size, self.file_size, self.index).cmp(&(
+            other.memory_size,
+            other.file_size,
+            other.index,
+        ))
+    }
+}
+
+/// The size of a single defined symbol of an [`ElfFile`].
+///
+/// [`SymbolSize`]s are ordered by size, then index.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SymbolSize<'slice> {
+    /// The index of the symbol in its symbol table.
+    pub index: usize,
+    /// The name of the symbol, if the associated string table is present.
+    pub name: Option<&'slice [u8]>,
+    /// The [`SymbolType`] of the symbol.
+    pub symbol_type: SymbolType,
+    /// The index of the section defining the symbol, or `None` if the symbol is absolute or
+    /// common.
+    pub section_index: Option<u32>,
+    /// The size of the symbol, as recorded in the symbol table.
+    pub size: u64,
+}
+
+impl PartialOrd for SymbolSize<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SymbolSize<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.size, self.index).cmp(&(other.size, other.index))
+    }
+}
+
+/// Totals of the sizes of the [`SegmentType::LOAD`] segments of an [`ElfFile`], in the style of
+/// the Berkeley `size` utility.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SizeTotals {
+    /// The number of bytes in non-writable [`SegmentType::LOAD`] segments.
+    pub text: u64,
+    /// The number of file-backed bytes in writable [`SegmentType::LOAD`] segments.
+    pub data: u64,
+    /// The number of zero-filled bytes in writable [`SegmentType::LOAD`] segments.
+    pub bss: u64,
+    /// The total size of the [`ElfFile`].
+    pub file_size: u64,
+}
+
+impl SizeTotals {
+    /// Returns the total number of bytes occupied in memory by the [`SegmentType::LOAD`]
+    /// segments.
+    pub fn memory_size(&self) -> u64 {
+        self.text.saturating_add(self.data).saturating_add(self.bss)
+    }
+}
+
+/// An [`Iterator`] over the [`SegmentSize`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SegmentSizes<'slice, C: ClassParse, E: EncodingParse> {
+    /// The underlying [`Iterator`] over the program header table.
+    iter: Option<program_header::IntoIter<'slice, C, E>>,
+    /// The index of the next segment.
+    index: u16,
+}
+
+impl<C: ClassParse, E: EncodingParse> Iterator for SegmentSizes<'_, C, E> {
+    type Item = SegmentSize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let program_header = self.iter.as_mut()?.next()?;
+        let index = self.index;
+        self.index += 1;
+
+        Some(SegmentSize {
+            index,
+            segment_type: program_header.segment_type(),
+            flags: program_header.flags(),
+            file_size: program_header.file_size().into(),
+            memory_size: program_header.memory_size().into(),
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for SegmentSizes<'_, C, E> {}
+
+/// An [`Iterator`] over the [`SectionSize`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SectionSizes<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being measured.
+    file: ElfFile<'slice, C, E>,
+    /// The underlying [`Iterator`] over the section header table.
+    iter: Option<section_header::IntoIter<'slice, C, E>>,
+    /// The index of the next section.
+    index: u16,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SectionSizes<'slice, C, E> {
+    type Item = SectionSize<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section_header = self.iter.as_mut()?.next()?;
+        let index = self.index;
+        self.index = self.index.wrapping_add(1);
+
+        let size = section_header.size().into();
+        let section_type = section_header.section_type();
+        let flags = section_header.flags();
+        Some(SectionSize {
+            index,
+            name: self.file.section_name(section_header),
+            section_type,
+            flags,
+            file_size: if section_type == SectionType::NOBITS {
+                0
+            } else {
+                size
+            },
+            memory_size: if flags.contains(SectionFlags::ALLOC) {
+                size
+            } else {
+                0
+            },
+        })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for SectionSizes<'_, C, E> {}
+
+/// An [`Iterator`] over the [`SymbolSize`]s of the defined symbols of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SymbolSizes<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`SymbolTable`] being measured.
+    table: Option<SymbolTable<'slice, C, E>>,
+    /// The index of the next symbol.
+    index: usize,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SymbolSizes<'slice, C, E> {
+    type Item = SymbolSize<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table?;
+        loop {
+            let symbol = table.get(self.index)?;
+            let index = self.index;
+            self.index += 1;
+
+            if !symbol.is_defined() {
+                continue;
+            }
+
+            return Some(SymbolSize {
+                index,
+                name: symbol.name(),
+                symbol_type: symbol.symbol_type(),
+                section_index: symbol.section_header_index(),
+                size: symbol.size().into(),
+            });
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for SymbolSizes<'_, C, E> {}
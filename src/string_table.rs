@@ -0,0 +1,85 @@
+//! Definitions for reading ELF string tables.
+//!
+//! Section names, symbol names and the strings referenced by the dynamic array are all stored as
+//! offsets into a [`StringTable`], which holds null-terminated strings back to back.
+
+use core::{error, fmt, str};
+
+use crate::search::find_nul;
+
+/// A table of null-terminated strings referenced by offset.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StringTable<'slice> {
+    /// The underlying bytes of the [`StringTable`].
+    bytes: &'slice [u8],
+}
+
+impl<'slice> StringTable<'slice> {
+    /// Creates a new [`StringTable`] from the given `slice`.
+    pub const fn new(slice: &'slice [u8]) -> Self {
+        Self { bytes: slice }
+    }
+
+    /// Returns the underlying bytes of this [`StringTable`].
+    pub const fn bytes(&self) -> &'slice [u8] {
+        self.bytes
+    }
+
+    /// Returns the string starting at `offset`, without the terminating null byte.
+    ///
+    /// # Errors
+    ///
+    /// - [`StringTableError::OffsetOutOfBounds`]: Returned if `offset` is not within this
+    ///   [`StringTable`].
+    /// - [`StringTableError::Unterminated`]: Returned if the string is not terminated by a null
+    ///   byte before the end of this [`StringTable`].
+    pub fn get(&self, offset: usize) -> Result<&'slice [u8], StringTableError> {
+        let string = self
+            .bytes
+            .get(offset..)
+            .filter(|string| !string.is_empty())
+            .ok_or(StringTableError::OffsetOutOfBounds(offset))?;
+        let length = find_nul(string).ok_or(StringTableError::Unterminated(offset))?;
+
+        Ok(&string[..length])
+    }
+
+    /// Returns the string starting at `offset` as a [`str`](prim@str), without the terminating null byte.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`StringTable::get()`], along with
+    /// [`StringTableError::InvalidUtf8`] if the string is not valid UTF-8.
+    pub fn get_str(&self, offset: usize) -> Result<&'slice str, StringTableError> {
+        str::from_utf8(self.get(offset)?).map_err(|_| StringTableError::InvalidUtf8(offset))
+    }
+}
+
+/// Various errors that can occur when reading a string from a [`StringTable`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum StringTableError {
+    /// The offset of the string is not within the [`StringTable`].
+    OffsetOutOfBounds(usize),
+    /// The string at the offset is not terminated by a null byte.
+    Unterminated(usize),
+    /// The string at the offset is not valid UTF-8.
+    InvalidUtf8(usize),
+}
+
+impl fmt::Display for StringTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OffsetOutOfBounds(offset) => {
+                write!(f, "string table offset {offset:#x} out of bounds")
+            }
+            Self::Unterminated(offset) => {
+                write!(f, "string at offset {offset:#x} is not null-terminated")
+            }
+            Self::InvalidUtf8(offset) => {
+                write!(f, "string at offset {offset:#x} is not valid UTF-8")
+            }
+        }
+    }
+}
+
+impl error::Error for StringTableError {}
@@ -0,0 +1,171 @@
+//! Definitions for building ELF string tables.
+//!
+//! By default, [`StringTableBuilder`] stores a string that is the tail of another string, such
+//! as `name` in `longname`, as part of the longer string, which matches the string tables
+//! produced by GNU `ld` and `strip`. Disabling [`StringTableOptions::merge_suffixes`] instead
+//! stores every string in the given order, so the output only depends on that order.
+//!
+//! Finding shared tails compares every pair of strings, so building a table takes time quadratic
+//! in the number of strings.
+
+use core::{error, fmt};
+
+/// Options controlling how a [`StringTableBuilder`] lays out its strings.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct StringTableOptions {
+    /// Whether to store strings that are the tail of another string as part of that string.
+    pub merge_suffixes: bool,
+}
+
+impl Default for StringTableOptions {
+    fn default() -> Self {
+        Self {
+            merge_suffixes: true,
+        }
+    }
+}
+
+/// A builder of an ELF string table holding a set of strings.
+///
+/// The table starts with a null byte, so empty strings are located at offset zero.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct StringTableBuilder<'strings> {
+    /// The strings to store in the string table, without null terminators.
+    strings: &'strings [&'strings [u8]],
+    /// The [`StringTableOptions`] used to lay out the string table.
+    options: StringTableOptions,
+}
+
+impl<'strings> StringTableBuilder<'strings> {
+    /// Creates a new [`StringTableBuilder`] storing `strings`, which must not contain null bytes.
+    ///
+    /// # Errors
+    ///
+    /// - [`BuildStringTableError::InteriorNul`]: Returned if a string contains a null byte.
+    pub fn new(
+        strings: &'strings [&'strings [u8]],
+        options: StringTableOptions,
+    ) -> Result<Self, BuildStringTableError> {
+        if let Some(index) = strings.iter().position(|string| string.contains(&0)) {
+            return Err(BuildStringTableError::InteriorNul { index });
+        }
+
+        Ok(Self { strings, options })
+    }
+
+    /// Returns the number of bytes required to hold the string table.
+    pub fn size(&self) -> usize {
+        (0..self.strings.len())
+            .filter(|&index| self.is_stored(index))
+            .map(|index| self.strings[index].len() + 1)
+            .sum::<usize>()
+            + 1
+    }
+
+    /// Writes the string table into the start of `buffer`, and the offset of each string into the
+    /// corresponding element of `offsets`, returning the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// - [`BuildStringTableError::OffsetsTooSmall`]: Returned if `offsets` has fewer elements
+    ///   than there are strings.
+    /// - [`BuildStringTableError::BufferTooSmall`]: Returned if `buffer` is smaller than
+    ///   [`StringTableBuilder::size()`].
+    pub fn build(
+        &self,
+        buffer: &mut [u8],
+        offsets: &mut [usize],
+    ) -> Result<usize, BuildStringTableError> {
+        let offsets = offsets
+            .get_mut(..self.strings.len())
+            .ok_or(BuildStringTableError::OffsetsTooSmall)?;
+        let size = self.size();
+        let buffer = buffer
+            .get_mut(..size)
+            .ok_or(BuildStringTableError::BufferTooSmall { required: size })?;
+
+        buffer[0] = 0;
+        let mut cursor = 1;
+        for (index, string) in self.strings.iter().enumerate() {
+            if !self.is_stored(index) {
+                continue;
+            }
+
+            offsets[index] = cursor;
+            buffer[cursor..cursor + string.len()].copy_from_slice(string);
+            buffer[cursor + string.len()] = 0;
+            cursor += string.len() + 1;
+        }
+
+        for (index, string) in self.strings.iter().enumerate() {
+            if string.is_empty() {
+                offsets[index] = 0;
+            } else if !self.is_stored(index) {
+                let container = self.container(index);
+                offsets[index] = offsets[container] + self.strings[container].len() - string.len();
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Returns `true` if the string at `index` is stored on its own rather than as part of
+    /// another string.
+    fn is_stored(&self, index: usize) -> bool {
+        !self.strings[index].is_empty()
+            && (!self.options.merge_suffixes || self.container(index) == index)
+    }
+
+    /// Returns the index of the string that holds the string at `index` as its tail.
+    ///
+    /// This is the longest such string, with ties broken by the lowest index, so the string it
+    /// returns is never held by another string.
+    fn container(&self, index: usize) -> usize {
+        let string = self.strings[index];
+
+        let mut container = index;
+        for (other_index, other) in self.strings.iter().enumerate() {
+            let longer = other.len() > self.strings[container].len();
+            let earlier = other.len() == self.strings[container].len() && other_index < container;
+            if (longer || earlier) && other.ends_with(string) {
+                container = other_index;
+            }
+        }
+
+        container
+    }
+}
+
+/// Various errors that can occur while building a string table.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum BuildStringTableError {
+    /// A string contains a null byte.
+    InteriorNul {
+        /// The index of the string.
+        index: usize,
+    },
+    /// There are fewer offsets than strings.
+    OffsetsTooSmall,
+    /// The buffer is too small to hold the string table.
+    BufferTooSmall {
+        /// The number of bytes required to hold the string table.
+        required: usize,
+    },
+}
+
+impl fmt::Display for BuildStringTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InteriorNul { index } => write!(f, "string {index} contains a null byte"),
+            Self::OffsetsTooSmall => write!(f, "fewer offsets than strings"),
+            Self::BufferTooSmall { required } => {
+                write!(
+                    f,
+                    "buffer too small to hold string table of {required} bytes"
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for BuildStringTableError {}
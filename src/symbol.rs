@@ -0,0 +1,705 @@
+//! Definitions for the ELF symbol tables.
+//!
+//! A symbol table is an array of fixed-size entries, each naming a location or value by an offset
+//! into an associated [`StringTable`]. The [`SectionType::SYMTAB`] section holds every symbol
+//! known to the linker, while the [`SectionType::DYNSYM`] section holds the subset used for
+//! dynamic linking, which is also located by the dynamic array when the section header table has
+//! been stripped.
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_file_offset, to_len, ClassParse, ClassParseBase},
+    encoding::EncodingParse,
+    section_header::{SectionHeader, SectionType},
+    string_table::StringTable,
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`SymbolTable`] held by the [`SectionType::SYMTAB`] section of this
+    /// [`ElfFile`], with names located in the section it links to.
+    ///
+    /// Returns `None` if there is no such section or it is located out of bounds.
+    pub fn symbol_table(&self) -> Option<SymbolTable<'slice, C, E>> {
+        let section_header = self
+            .section_header_table()?
+            .sections_of_type(SectionType::SYMTAB)
+            .next()?;
+
+        self.symbol_table_from_section(section_header)
+    }
+
+    /// Returns the [`SymbolTable`] held by the [`SectionType::DYNSYM`] section of this
+    /// [`ElfFile`], with names located in the section it links to.
+    ///
+    /// If there is no such section, the table located by the dynamic array is returned instead,
+    /// which requires the number of symbols to be recoverable from the symbol hash tables.
+    pub fn dynamic_symbol_table(&self) -> Option<SymbolTable<'slice, C, E>> {
+        let section_header = self
+            .section_header_table()
+            .and_then(|table| table.sections_of_type(SectionType::DYNSYM).next());
+
+        match section_header {
+            Some(section_header) => self.symbol_table_from_section(section_header),
+            #[cfg(feature = "dynamic")]
+            None => self.dynamic_symbol_table_from_dynamic(),
+            #[cfg(not(feature = "dynamic"))]
+            None => None,
+        }
+    }
+
+    /// Returns the most complete [`SymbolTable`] of this [`ElfFile`], along with the
+    /// [`SymbolSource`] it was read from.
+    ///
+    /// The sources are tried in the following order, so that stripped and unstripped files can
+    /// be handled alike:
+    ///
+    /// 1. [`SymbolSource::SymbolTable`]: the [`SectionType::SYMTAB`] section, which is usually
+    ///    removed when the file is stripped.
+    /// 2. [`SymbolSource::DynamicSymbolTable`]: the [`SectionType::DYNSYM`] section, which is
+    ///    absent from static executables.
+    /// 3. [`SymbolSource::DynamicArray`]: the table located by the dynamic array, which remains
+    ///    when the section header table has been removed.
+    pub fn symbols(&self) -> Option<(SymbolSource, SymbolTable<'slice, C, E>)> {
+        if let Some(table) = self.symbol_table() {
+            return Some((SymbolSource::SymbolTable, table));
+        }
+
+        let section_header = self
+            .section_header_table()
+            .and_then(|table| table.sections_of_type(SectionType::DYNSYM).next());
+        if let Some(table) =
+            section_header.and_then(|section_header| self.symbol_table_from_section(section_header))
+        {
+            return Some((SymbolSource::DynamicSymbolTable, table));
+        }
+
+        #[cfg(feature = "dynamic")]
+        if let Some(table) = self.dynamic_symbol_table_from_dynamic() {
+            return Some((SymbolSource::DynamicArray, table));
+        }
+
+        None
+    }
+
+    /// Returns a [`SymbolsInSection`] over the symbols of the table returned by
+    /// [`ElfFile::symbols()`] that are defined in the section at `section_index` in the section
+    /// header table.
+    pub fn symbols_in_section(&self, section_index: u16) -> Option<SymbolsInSection<'slice, C, E>> {
+        let (_, table) = self.symbols()?;
+
+        Some(table.in_section(section_index))
+    }
+
+    /// Returns the [`SymbolTable`] held by the section `section_header` describes, with names
+    /// located in the section its [`SectionHeader::link()`] refers to.
+    ///
+    /// Returns `None` if the section is located out of bounds or its entry size is neither zero
+    /// nor at least the size of a symbol. The [`SymbolTable`] has no [`StringTable`] if the
+    /// linked section cannot be located. The [`SectionType::SYMTAB_SHNDX`] section linked to the
+    /// section, if any, is attached as the extended section index table, and `None` is returned
+    /// if it holds fewer entries than the [`SymbolTable`].
+    pub fn symbol_table_from_section(
+        &self,
+        section_header: SectionHeader<'slice, C, E>,
+    ) -> Option<SymbolTable<'slice, C, E>> {
+        let expected = self.class.expected_symbol_size();
+        let entry_size = match to_len(section_header.entry_size()).ok()? {
+            0 => expected,
+            entry_size => entry_size,
+        };
+
+        let data = self.section_data(section_header)?;
+        let table = SymbolTable::new(
+            self.class,
+            self.encoding,
+            data,
+            data.len() / entry_size,
+            entry_size,
+        )?;
+
+        let strings = u16::try_from(section_header.link())
+            .ok()
+            .and_then(|index| self.section_header_table()?.get(index))
+            .and_then(|string_section| self.section_data(string_section))
+            .map(StringTable::new);
+
+        let table = match strings {
+            Some(strings) => table.with_strings(strings),
+            None => table,
+        };
+
+        let Some(section_table) = self.section_header_table() else {
+            return Some(table);
+        };
+        let indices = section_table
+            .sections_of_type(SectionType::SYMTAB_SHNDX)
+            .find(|indices| {
+                u16::try_from(indices.link())
+                    .ok()
+                    .and_then(|index| section_table.get(index))
+                    .is_some_and(|linked| core::ptr::eq(linked.bytes, section_header.bytes))
+            })
+            .and_then(|indices| self.section_data(indices));
+        Some(match indices {
+            Some(indices) => table.with_extended_indices(indices)?,
+            None => table,
+        })
+    }
+}
+
+/// The location a [`SymbolTable`] returned by [`ElfFile::symbols()`] was read from.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SymbolSource {
+    /// The [`SectionType::SYMTAB`] section, which holds every symbol known to the linker.
+    SymbolTable,
+    /// The [`SectionType::DYNSYM`] section, which holds only the symbols used for dynamic
+    /// linking.
+    DynamicSymbolTable,
+    /// The table located by the [`ConstDynamicTag::SYMBOL_TABLE`][st] entry of the dynamic
+    /// array, which holds the same symbols as [`SymbolSource::DynamicSymbolTable`].
+    ///
+    /// [st]: crate::dynamic::ConstDynamicTag::SYMBOL_TABLE
+    DynamicArray,
+}
+
+/// View of an ELF symbol.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Symbol<'slice, C, E> {
+    /// The underlying bytes of the [`Symbol`].
+    pub(crate) bytes: &'slice [u8],
+    /// The [`StringTable`] holding the name of the [`Symbol`].
+    pub(crate) strings: Option<StringTable<'slice>>,
+    /// The entry of the extended section index table for the [`Symbol`], if present.
+    pub(crate) extended_index: Option<u32>,
+    /// The [`ClassParseSymbol`] of this [`Symbol`].
+    pub(crate) class: C,
+    /// The [`EncodingParse`] of this [`Symbol`].
+    pub(crate) encoding: E,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Symbol<'slice, C, E> {
+    /// Creates a new [`Symbol`] from the given `slice`, returning `None` if the `slice` is too
+    /// small to contain a [`Symbol`].
+    pub fn new(class: C, encoding: E, slice: &'slice [u8]) -> Option<Self> {
+        if slice.len() < class.expected_symbol_size() {
+            return None;
+        }
+
+        let symbol = Self {
+            bytes: slice,
+            strings: None,
+            extended_index: None,
+            class,
+            encoding,
+        };
+
+        Some(symbol)
+    }
+
+    /// Returns the bytes of this [`Symbol`].
+    ///
+    /// For a [`Symbol`] obtained from a [`SymbolTable`], this is exactly the entry in the table,
+    /// including any bytes beyond the fields defined by the ELF specification.
+    pub fn raw_bytes(&self) -> &'slice [u8] {
+        self.bytes
+    }
+
+    /// Returns the offset of the name of this [`Symbol`] within its [`StringTable`].
+    pub fn name_offset(&self) -> u32 {
+        self.encoding
+            .parse_u32_at(self.class.symbol_name_offset(), self.bytes)
+    }
+
+    /// Returns the name of this [`Symbol`], without the terminating null byte.
+    ///
+    /// Returns `None` if this [`Symbol`] has no [`StringTable`] or the name is not a
+    /// null-terminated string within it.
+    pub fn name(&self) -> Option<&'slice [u8]> {
+        let offset = to_file_offset(self.name_offset()).ok()?;
+
+        self.strings?.get(offset).ok()
+    }
+
+    /// Returns the value of this [`Symbol`], which is usually an address.
+    ///
+    /// In relocatable files, the value of a symbol defined in a section is an offset from the
+    /// start of the section, and the value of a [`SectionIndex::COMMON`] symbol is its alignment.
+    pub fn value(&self) -> C::ClassUsize {
+        self.class
+            .parse_class_usize_at(self.encoding, self.class.symbol_value_offset(), self.bytes)
+    }
+
+    /// Returns the size of the object this [`Symbol`] describes, or zero if it has no size or
+    /// the size is unknown.
+    pub fn size(&self) -> C::ClassUsize {
+        self.class
+            .parse_class_usize_at(self.encoding, self.class.symbol_size_offset(), self.bytes)
+    }
+
+    /// Returns the raw type and binding attributes of this [`Symbol`].
+    pub fn info(&self) -> u8 {
+        self.encoding
+            .parse_u8_at(self.class.symbol_info_offset(), self.bytes)
+    }
+
+    /// Returns the [`SymbolBinding`] of this [`Symbol`].
+    pub fn binding(&self) -> SymbolBinding {
+        SymbolBinding(self.info() >> 4)
+    }
+
+    /// Returns the [`SymbolType`] of this [`Symbol`].
+    pub fn symbol_type(&self) -> SymbolType {
+        SymbolType(self.info() & 0xF)
+    }
+
+    /// Returns the raw visibility attribute of this [`Symbol`].
+    pub fn other(&self) -> u8 {
+        self.encoding
+            .parse_u8_at(self.class.symbol_other_offset(), self.bytes)
+    }
+
+    /// Returns the [`SymbolVisibility`] of this [`Symbol`].
+    pub fn visibility(&self) -> SymbolVisibility {
+        SymbolVisibility(self.other() & 0x3)
+    }
+
+    /// Returns the [`SectionIndex`] of the section this [`Symbol`] is defined in.
+    pub fn section_index(&self) -> SectionIndex {
+        SectionIndex(
+            self.encoding
+                .parse_u16_at(self.class.symbol_section_index_offset(), self.bytes),
+        )
+    }
+
+    /// Returns the index in the section header table of the section this [`Symbol`] is defined
+    /// in.
+    ///
+    /// A [`SectionIndex::EXTENDED`] index is resolved through the extended section index table
+    /// of the [`SymbolTable`] this [`Symbol`] was obtained from. Returns `None` if this
+    /// [`Symbol`] is not defined in a section, or its index is [`SectionIndex::EXTENDED`] and
+    /// there is no extended section index table.
+    pub fn section_header_index(&self) -> Option<u32> {
+        match self.section_index() {
+            SectionIndex::EXTENDED => self.extended_index,
+            section_index if section_index.is_reserved() => None,
+            section_index => Some(u32::from(section_index.0)),
+        }
+    }
+
+    /// Returns `true` if this [`Symbol`] is defined in the section at `section_index`, directly
+    /// or through the extended section index table.
+    pub(crate) fn is_in_section(&self, section_index: u16) -> bool {
+        self.section_index() == SectionIndex(section_index)
+            || self.section_header_index() == Some(u32::from(section_index))
+    }
+
+    /// Returns `true` if this [`Symbol`] is defined, as opposed to referencing a symbol defined
+    /// in another file.
+    pub fn is_defined(&self) -> bool {
+        self.section_index() != SectionIndex::UNDEFINED
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for Symbol<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("Symbol");
+
+        debug_struct.field("name_offset", &self.name_offset());
+        debug_struct.field("value", &self.value());
+        debug_struct.field("size", &self.size());
+
+        debug_struct.field("binding", &self.binding());
+        debug_struct.field("symbol_type", &self.symbol_type());
+        debug_struct.field("visibility", &self.visibility());
+        debug_struct.field("section_index", &self.section_index());
+
+        debug_struct.finish()
+    }
+}
+
+/// The linkage visibility of a [`Symbol`] to other files.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolBinding(pub u8);
+
+impl SymbolBinding {
+    /// The symbol is not visible outside the file defining it.
+    pub const LOCAL: Self = Self(0);
+    /// The symbol is visible to all files being combined.
+    pub const GLOBAL: Self = Self(1);
+    /// The symbol is visible to all files being combined, but with lower precedence than
+    /// [`SymbolBinding::GLOBAL`] symbols.
+    pub const WEAK: Self = Self(2);
+    /// The symbol is visible to all files being combined, and the dynamic linker ensures that
+    /// the whole process uses a single definition of it, even across otherwise isolated lookup
+    /// scopes.
+    pub const GNU_UNIQUE: Self = Self(10);
+}
+
+impl fmt::Debug for SymbolBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LOCAL => f.pad("Local"),
+            Self::GLOBAL => f.pad("Global"),
+            Self::WEAK => f.pad("Weak"),
+            Self::GNU_UNIQUE => f.pad("GnuUnique"),
+            binding => f.debug_tuple("SymbolBinding").field(&binding.0).finish(),
+        }
+    }
+}
+
+/// The kind of entity a [`Symbol`] is associated with.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolType(pub u8);
+
+impl SymbolType {
+    /// The type of the symbol is not specified.
+    pub const NOTYPE: Self = Self(0);
+    /// The symbol is associated with a data object.
+    pub const OBJECT: Self = Self(1);
+    /// The symbol is associated with a function or other executable code.
+    pub const FUNC: Self = Self(2);
+    /// The symbol is associated with a section.
+    pub const SECTION: Self = Self(3);
+    /// The symbol names the source file associated with the file.
+    pub const FILE: Self = Self(4);
+    /// The symbol labels an uninitialized common block.
+    pub const COMMON: Self = Self(5);
+    /// The symbol specifies a thread local storage entity.
+    pub const TLS: Self = Self(6);
+    /// The symbol is associated with a function returning the address of the implementation to
+    /// use, selected when the symbol is resolved.
+    pub const GNU_IFUNC: Self = Self(10);
+}
+
+impl fmt::Debug for SymbolType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NOTYPE => f.pad("NoType"),
+            Self::OBJECT => f.pad("Object"),
+            Self::FUNC => f.pad("Func"),
+            Self::SECTION => f.pad("Section"),
+            Self::FILE => f.pad("File"),
+            Self::COMMON => f.pad("Common"),
+            Self::TLS => f.pad("Tls"),
+            Self::GNU_IFUNC => f.pad("GnuIfunc"),
+            symbol_type => f.debug_tuple("SymbolType").field(&symbol_type.0).finish(),
+        }
+    }
+}
+
+/// The visibility of a [`Symbol`] once it has been included in an executable file or shared
+/// object.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SymbolVisibility(pub u8);
+
+impl SymbolVisibility {
+    /// The visibility is specified by the [`SymbolBinding`].
+    pub const DEFAULT: Self = Self(0);
+    /// The symbol is [`SymbolVisibility::HIDDEN`] with processor specific semantics.
+    pub const INTERNAL: Self = Self(1);
+    /// The symbol is not visible to other components.
+    pub const HIDDEN: Self = Self(2);
+    /// The symbol is visible to other components, but references from within its component
+    /// always resolve to it.
+    pub const PROTECTED: Self = Self(3);
+}
+
+impl fmt::Debug for SymbolVisibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::DEFAULT => f.pad("Default"),
+            Self::INTERNAL => f.pad("Internal"),
+            Self::HIDDEN => f.pad("Hidden"),
+            Self::PROTECTED => f.pad("Protected"),
+            visibility => f
+                .debug_tuple("SymbolVisibility")
+                .field(&visibility.0)
+                .finish(),
+        }
+    }
+}
+
+/// The section header table index of the section a [`Symbol`] is defined in, or one of the
+/// reserved indices giving the [`Symbol`] special meaning.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionIndex(pub u16);
+
+impl SectionIndex {
+    /// The symbol is undefined.
+    pub const UNDEFINED: Self = Self(0);
+    /// The value of the symbol is absolute and not affected by relocation.
+    pub const ABSOLUTE: Self = Self(0xFFF1);
+    /// The symbol labels a common block that has not yet been allocated.
+    pub const COMMON: Self = Self(0xFFF2);
+    /// The section index is too large to fit in this field and is held in the associated
+    /// [`SectionType::SYMTAB_SHNDX`] section.
+    pub const EXTENDED: Self = Self(0xFFFF);
+
+    /// Returns `true` if this [`SectionIndex`] is reserved rather than an index into the section
+    /// header table.
+    pub const fn is_reserved(self) -> bool {
+        self.0 == Self::UNDEFINED.0 || self.0 >= 0xFF00
+    }
+}
+
+impl fmt::Debug for SectionIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UNDEFINED => f.pad("Undefined"),
+            Self::ABSOLUTE => f.pad("Absolute"),
+            Self::COMMON => f.pad("Common"),
+            Self::EXTENDED => f.pad("Extended"),
+            index => f.debug_tuple("SectionIndex").field(&index.0).finish(),
+        }
+    }
+}
+
+/// A table of [`Symbol`]s.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SymbolTable<'slice, C, E> {
+    /// The underlying bytes of the symbol table.
+    pub(crate) bytes: &'slice [u8],
+    /// The number of entries in the [`SymbolTable`].
+    pub(crate) entry_count: usize,
+    /// The stride of each [`Symbol`] in the [`SymbolTable`].
+    pub(crate) entry_size: usize,
+    /// The [`StringTable`] holding the names of the [`Symbol`]s.
+    pub(crate) strings: Option<StringTable<'slice>>,
+    /// The extended section index table, holding one [`u32`] per [`Symbol`].
+    pub(crate) extended_indices: Option<&'slice [u8]>,
+    /// The [`ClassParse`] of this [`SymbolTable`].
+    pub(crate) class: C,
+    /// The [`EncodingParse`] of this [`SymbolTable`].
+    pub(crate) encoding: E,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> SymbolTable<'slice, C, E> {
+    /// Creates a new [`SymbolTable`] from the given `slice`, without a [`StringTable`].
+    ///
+    /// The generated [`SymbolTable`] has `count` [`Symbol`]s placed `size` bytes apart. Returns
+    /// `None` if `size` is smaller than a [`Symbol`] or `slice` is too small to hold the table.
+    pub fn new(
+        class: C,
+        encoding: E,
+        slice: &'slice [u8],
+        count: usize,
+        size: usize,
+    ) -> Option<Self> {
+        if size < class.expected_symbol_size() {
+            return None;
+        }
+
+        if count
+            .checked_mul(size)
+            .is_none_or(|total_size| slice.len() < total_size)
+        {
+            return None;
+        }
+
+        let table = Self {
+            bytes: slice,
+            entry_count: count,
+            entry_size: size,
+            strings: None,
+            extended_indices: None,
+            class,
+            encoding,
+        };
+
+        Some(table)
+    }
+
+    /// Locates the names of the [`Symbol`]s of this [`SymbolTable`] in `strings`.
+    pub fn with_strings(mut self, strings: StringTable<'slice>) -> Self {
+        self.strings = Some(strings);
+        self
+    }
+
+    /// Returns the [`StringTable`] holding the names of the [`Symbol`]s of this [`SymbolTable`].
+    pub fn strings(&self) -> Option<StringTable<'slice>> {
+        self.strings
+    }
+
+    /// Resolves the [`SectionIndex::EXTENDED`] indices of the [`Symbol`]s of this
+    /// [`SymbolTable`] through `indices`, the contents of the associated
+    /// [`SectionType::SYMTAB_SHNDX`] section.
+    ///
+    /// Returns `None` if `indices` is too small to hold an entry for every [`Symbol`].
+    pub fn with_extended_indices(mut self, indices: &'slice [u8]) -> Option<Self> {
+        if self
+            .entry_count
+            .checked_mul(4)
+            .is_none_or(|total_size| indices.len() < total_size)
+        {
+            return None;
+        }
+
+        self.extended_indices = Some(indices);
+        Some(self)
+    }
+
+    /// Returns the [`Symbol`] located at `index`.
+    pub fn get(&self, index: usize) -> Option<Symbol<'slice, C, E>> {
+        if index >= self.entry_count {
+            return None;
+        }
+
+        let start = index * self.entry_size;
+        let symbol = Symbol {
+            bytes: &self.bytes[start..start + self.entry_size],
+            strings: self.strings,
+            extended_index: self
+                .extended_indices
+                .map(|indices| self.encoding.parse_u32_at(index * 4, indices)),
+            class: self.class,
+            encoding: self.encoding,
+        };
+
+        Some(symbol)
+    }
+
+    /// Returns the number of [`Symbol`]s in this [`SymbolTable`].
+    pub fn count(&self) -> usize {
+        self.entry_count
+    }
+
+    /// Returns the stride of each [`Symbol`] in this [`SymbolTable`].
+    pub fn entry_size(&self) -> usize {
+        self.entry_size
+    }
+
+    /// Limits this [`SymbolTable`] to at most `max_entries` [`Symbol`]s.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.entry_count = self.entry_count.min(max_entries);
+        self
+    }
+
+    /// Returns an [`Iterator`] over the [`Symbol`]s in this [`SymbolTable`] that are defined in
+    /// the section at `section_index` in the section header table.
+    ///
+    /// Symbols whose [`SectionIndex`] is [`SectionIndex::EXTENDED`] are matched through the
+    /// extended section index table, if present.
+    pub fn in_section(self, section_index: u16) -> SymbolsInSection<'slice, C, E> {
+        SymbolsInSection {
+            iter: self.into_iter(),
+            section_index,
+        }
+    }
+
+    /// Writes the indices of the [`Symbol`]s in this [`SymbolTable`] that are defined in the
+    /// section at `section_index` into `indices`, sorted by value, and returns the written
+    /// indices.
+    ///
+    /// This labels the contents of a section, such as the functions of a code section, in
+    /// address order. [`Symbol`]s with equal values keep their table order.
+    ///
+    /// Returns `None` if `indices` is smaller than the number of [`Symbol`]s defined in the
+    /// section.
+    pub fn sorted_in_section<'indices>(
+        &self,
+        section_index: u16,
+        indices: &'indices mut [usize],
+    ) -> Option<&'indices [usize]> {
+        let mut count = 0;
+        for (index, symbol) in self.into_iter().enumerate() {
+            if symbol.is_in_section(section_index) {
+                *indices.get_mut(count)? = index;
+                count += 1;
+            }
+        }
+
+        let indices = &mut indices[..count];
+        indices.sort_unstable_by_key(|&index| {
+            let value: u64 = self.get(index).map_or(0, |symbol| symbol.value().into());
+            (value, index)
+        });
+
+        Some(indices)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> IntoIterator for SymbolTable<'slice, C, E> {
+    type Item = Symbol<'slice, C, E>;
+    type IntoIter = IntoIter<'slice, C, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            table: self,
+            next: 0,
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for SymbolTable<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+/// An [`Iterator`] over the [`Symbol`]s in a [`SymbolTable`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct IntoIter<'slice, C, E> {
+    /// The table to iterate over.
+    table: SymbolTable<'slice, C, E>,
+    /// The index in the [`SymbolTable`].
+    next: usize,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for IntoIter<'slice, C, E> {
+    type Item = Symbol<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.table.get(self.next)?;
+
+        self.next += 1;
+        Some(item)
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for IntoIter<'_, C, E> {}
+
+/// An [`Iterator`] over the [`Symbol`]s in a [`SymbolTable`] that are defined in a particular
+/// section.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct SymbolsInSection<'slice, C, E> {
+    /// The underlying [`Iterator`] over the [`SymbolTable`].
+    iter: IntoIter<'slice, C, E>,
+    /// The index of the section the [`Symbol`]s to yield are defined in.
+    section_index: u16,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for SymbolsInSection<'slice, C, E> {
+    type Item = Symbol<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section_index = self.section_index;
+        self.iter.find(|symbol| symbol.is_in_section(section_index))
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for SymbolsInSection<'_, C, E> {}
+
+/// The information required to implement class aware parsing of an ELF symbol.
+pub trait ClassParseSymbol: ClassParseBase {
+    /// The offset of the offset of the symbol name in the associated string table.
+    fn symbol_name_offset(self) -> usize;
+    /// The offset of the value of the symbol.
+    fn symbol_value_offset(self) -> usize;
+    /// The offset of the size of the symbol.
+    fn symbol_size_offset(self) -> usize;
+    /// The offset of the type and binding attributes of the symbol.
+    fn symbol_info_offset(self) -> usize;
+    /// The offset of the visibility attribute of the symbol.
+    fn symbol_other_offset(self) -> usize;
+    /// The offset of the section header table index of the section the symbol is defined in.
+    fn symbol_section_index_offset(self) -> usize;
+
+    /// The expected size of an ELF symbol structure.
+    fn expected_symbol_size(self) -> usize;
+}
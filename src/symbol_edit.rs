@@ -0,0 +1,613 @@
+//! Definitions for pruning the symbol table of an ELF file in place.
+//!
+//! [`SymbolTableEditor`] edits the [`SectionType::SYMTAB`] section, which holds the symbols that
+//! are not needed for dynamic linking, without moving the contents of any section. The symbols
+//! needed for dynamic linking are held in the [`SectionType::DYNSYM`] section, which is never
+//! edited.
+//!
+//! The ELF specification requires local symbols to precede the other symbols, so localizing or
+//! removing a symbol moves the symbols between it and the first non-local symbol by one entry.
+//! References to moved symbols from the relocation sections, section groups and extended section
+//! index table associated with the symbol table are updated accordingly.
+//!
+//! The names of removed symbols are overwritten with null bytes in the string table, unless they
+//! are shared with another symbol, so they cannot be recovered from the edited file.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{Class32, Class64, ClassParse},
+    encoding::EncodingParse,
+    section_header::{ClassParseSectionHeader, SectionType},
+    symbol::{ClassParseSymbol, SymbolBinding},
+    writer::ByteLayout,
+    ElfFile, ParseElfFileError,
+};
+
+/// An editor over the [`SectionType::SYMTAB`] section of an ELF file.
+#[derive(Debug, Hash, PartialEq, Eq)]
+pub struct SymbolTableEditor<'buffer> {
+    /// The buffer holding the ELF file.
+    bytes: &'buffer mut [u8],
+    /// The offset of the section header table.
+    section_table_offset: usize,
+    /// The number of entries in the section header table.
+    section_count: u16,
+    /// The stride of each entry in the section header table.
+    section_entry_size: usize,
+    /// The index of the [`SectionType::SYMTAB`] section.
+    section: u16,
+    /// The offset of the symbol table.
+    table_offset: usize,
+    /// The number of entries in the symbol table.
+    count: usize,
+    /// The stride of each entry in the symbol table.
+    entry_size: usize,
+    /// The offset of the string table holding the names of the symbols.
+    strings_offset: usize,
+    /// The size of the string table holding the names of the symbols.
+    strings_size: usize,
+    /// Whether the string table also holds the names of the sections.
+    strings_shared: bool,
+    /// The [`ByteLayout`] of the values of the ELF file.
+    layout: ByteLayout,
+}
+
+impl<'buffer> SymbolTableEditor<'buffer> {
+    /// Creates a new [`SymbolTableEditor`] over the [`SectionType::SYMTAB`] section of the ELF
+    /// file held by `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSymbolError::ParseElfFileError`]: Returned if `bytes` does not contain a valid
+    ///   [`ElfFile`].
+    /// - [`EditSymbolError::MissingSymbolTable`]: Returned if the [`ElfFile`] has no
+    ///   [`SectionType::SYMTAB`] section, or it or its string table is located out of bounds.
+    pub fn new<C: ClassParse, E: EncodingParse>(
+        bytes: &'buffer mut [u8],
+    ) -> Result<Self, EditSymbolError> {
+        let file = ElfFile::<C, E>::new(bytes)?;
+        let header = file.header();
+        let ident = header.ident();
+        let table = file
+            .section_header_table()
+            .ok_or(EditSymbolError::MissingSymbolTable)?;
+        let section_table_offset = table
+            .get(0)
+            .and_then(|section_header| file.range_of(section_header.raw_bytes()))
+            .ok_or(EditSymbolError::MissingSymbolTable)?
+            .start;
+
+        let (section, symbol_table) = (0..table.count())
+            .filter_map(|index| Some((index, table.get(index)?)))
+            .find(|(_, section_header)| section_header.section_type() == SectionType::SYMTAB)
+            .ok_or(EditSymbolError::MissingSymbolTable)?;
+        let symbols = file
+            .symbol_table_from_section(symbol_table)
+            .ok_or(EditSymbolError::MissingSymbolTable)?;
+        let strings = symbols
+            .strings()
+            .and_then(|strings| file.range_of(strings.bytes()))
+            .ok_or(EditSymbolError::MissingSymbolTable)?;
+        let table_offset = file
+            .section_data(symbol_table)
+            .and_then(|data| file.range_of(data))
+            .ok_or(EditSymbolError::MissingSymbolTable)?
+            .start;
+
+        Ok(Self {
+            section_table_offset,
+            section_count: table.count(),
+            section_entry_size: usize::from(header.section_header_size()),
+            section,
+            table_offset,
+            count: symbols.count(),
+            entry_size: symbols.entry_size(),
+            strings_offset: strings.start,
+            strings_size: strings.len(),
+            strings_shared: u32::from(header.section_header_string_table_index())
+                == symbol_table.link(),
+            layout: ByteLayout::new(ident),
+            bytes,
+        })
+    }
+
+    /// Returns the bytes of the edited ELF file.
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes
+    }
+
+    /// Returns the number of entries in the symbol table, including the null symbol at index
+    /// zero.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the name of the symbol at `index`, without the terminating null byte.
+    pub fn name(&self, index: usize) -> Option<&[u8]> {
+        let offset = usize::try_from(
+            self.layout
+                .read_u32(self.bytes, self.symbol_field(index, 0)?),
+        )
+        .ok()?;
+        let strings = &self.bytes[self.strings_offset..self.strings_offset + self.strings_size];
+        let name = strings.get(offset..)?;
+
+        name.iter()
+            .position(|&byte| byte == 0)
+            .map(|end| &name[..end])
+    }
+
+    /// Removes every symbol except the null symbol at index zero, and overwrites the string
+    /// table with null bytes.
+    ///
+    /// The symbols needed for dynamic linking are kept in the [`SectionType::DYNSYM`] section,
+    /// so this matches stripping all symbols that are not needed for dynamic linking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditSymbolError::SymbolReferenced`] if a relocation section or section group
+    /// refers to the symbol table, as is the case for relocatable files.
+    pub fn strip_all(&mut self) -> Result<(), EditSymbolError> {
+        if self.linked_sections().next().is_some() {
+            return Err(EditSymbolError::SymbolReferenced);
+        }
+
+        let start = self.table_offset + self.entry_size.min(self.count * self.entry_size);
+        let end = self.table_offset + self.count * self.entry_size;
+        self.bytes[start..end].fill(0);
+        self.count = self.count.min(1);
+        self.write_symbol_table_size();
+        self.write_first_global(self.count as u32);
+
+        if !self.strings_shared && self.strings_size > 1 {
+            self.bytes[self.strings_offset + 1..self.strings_offset + self.strings_size].fill(0);
+            self.strings_size = 1;
+            let field = self.section_field(self.strings_section(), self.section_size_offset());
+            self.layout.write_word(self.bytes, field, 1);
+        }
+
+        Ok(())
+    }
+
+    /// Changes the binding of every non-local symbol named `name` to [`SymbolBinding::LOCAL`],
+    /// returning the number of symbols changed.
+    ///
+    /// Each changed symbol is moved to the end of the local symbols.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EditSymbolError::SymbolNotFound`] if there is no non-local symbol named `name`.
+    pub fn localize(&mut self, name: &[u8]) -> Result<usize, EditSymbolError> {
+        let mut changed = 0;
+        while let Some(index) =
+            (self.first_global()..self.count).find(|&index| self.name(index) == Some(name))
+        {
+            let first_global = self.first_global();
+            self.move_symbol(index, first_global);
+
+            let info_offset = self.symbol_info_offset();
+            let info = self.table_offset + first_global * self.entry_size + info_offset;
+            self.bytes[info] = (SymbolBinding::LOCAL.0 << 4) | (self.bytes[info] & 0xF);
+            self.write_first_global(first_global as u32 + 1);
+            changed += 1;
+        }
+
+        if changed == 0 {
+            return Err(EditSymbolError::SymbolNotFound);
+        }
+
+        Ok(changed)
+    }
+
+    /// Removes every symbol named `name`, returning the number of symbols removed.
+    ///
+    /// The names of the removed symbols are overwritten with null bytes in the string table,
+    /// unless they are shared with another symbol or the string table also holds the names of
+    /// the sections.
+    ///
+    /// # Errors
+    ///
+    /// - [`EditSymbolError::SymbolNotFound`]: Returned if there is no symbol named `name`.
+    /// - [`EditSymbolError::SymbolReferenced`]: Returned if a relocation or section group
+    ///   refers to a symbol named `name`. No symbol is removed in this case.
+    pub fn remove(&mut self, name: &[u8]) -> Result<usize, EditSymbolError> {
+        let mut found = false;
+        for index in 1..self.count {
+            if self.name(index) == Some(name) {
+                found = true;
+                if self.is_referenced(index) {
+                    return Err(EditSymbolError::SymbolReferenced);
+                }
+            }
+        }
+        if !found {
+            return Err(EditSymbolError::SymbolNotFound);
+        }
+
+        let mut removed = 0;
+        while let Some(index) = (1..self.count).find(|&index| self.name(index) == Some(name)) {
+            self.scrub_name(index);
+
+            let last = self.count - 1;
+            self.move_symbol(index, last);
+            let start = self.table_offset + last * self.entry_size;
+            self.bytes[start..start + self.entry_size].fill(0);
+            self.count = last;
+            self.write_symbol_table_size();
+
+            let first_global = self.first_global();
+            if index < first_global {
+                self.write_first_global(first_global as u32 - 1);
+            }
+            removed += 1;
+        }
+
+        Ok(removed)
+    }
+
+    /// Moves the symbol at `from` to `to`, shifting the symbols in between by one entry, and
+    /// updates the references to every moved symbol.
+    fn move_symbol(&mut self, from: usize, to: usize) {
+        if from == to {
+            return;
+        }
+
+        let (low, high) = (from.min(to), from.max(to));
+        let range = self.table_offset + low * self.entry_size
+            ..self.table_offset + (high + 1) * self.entry_size;
+        if from > to {
+            self.bytes[range].rotate_right(self.entry_size);
+        } else {
+            self.bytes[range].rotate_left(self.entry_size);
+        }
+
+        let remap = |index: usize| match index {
+            index if index == from => to,
+            index if index < low || index > high => index,
+            index if from > to => index + 1,
+            index => index - 1,
+        };
+        self.remap_references(remap, low, high, from > to);
+    }
+
+    /// Rewrites the references to the symbols from `low` to `high` according to `remap`, which
+    /// moved them by rotating them by one entry, rightwards if `right` is `true`.
+    fn remap_references(
+        &mut self,
+        remap: impl Fn(usize) -> usize,
+        low: usize,
+        high: usize,
+        right: bool,
+    ) {
+        for index in 0..self.section_count {
+            let section_type = self.section_type(index);
+            if self.layout.read_u32(
+                self.bytes,
+                self.section_field(index, self.section_link_offset()),
+            ) != u32::from(self.section)
+            {
+                continue;
+            }
+
+            match section_type {
+                SectionType::REL | SectionType::RELA => {
+                    let Some((start, size, entry_size)) = self.section_extent(index) else {
+                        continue;
+                    };
+                    let entry_size = match entry_size {
+                        0 if section_type == SectionType::REL => self.layout.word_size() * 2,
+                        0 => self.layout.word_size() * 3,
+                        entry_size => entry_size,
+                    };
+
+                    for entry in (start..start + size).step_by(entry_size) {
+                        if entry + entry_size > start + size {
+                            break;
+                        }
+
+                        let info_offset = entry + self.layout.word_size();
+                        let info = self.layout.read_word(self.bytes, info_offset);
+                        let (symbol, kind) = if self.layout.wide {
+                            (info >> 32, info & 0xFFFF_FFFF)
+                        } else {
+                            (info >> 8, info & 0xFF)
+                        };
+                        let symbol = remap(symbol as usize) as u64;
+                        let info = if self.layout.wide {
+                            (symbol << 32) | kind
+                        } else {
+                            (symbol << 8) | kind
+                        };
+                        self.layout.write_word(self.bytes, info_offset, info);
+                    }
+                }
+                SectionType::GROUP => {
+                    let field = self.section_field(index, self.section_info_offset());
+                    let symbol = remap(self.layout.read_u32(self.bytes, field) as usize);
+                    self.layout.write_u32(self.bytes, field, symbol as u32);
+                }
+                SectionType::SYMTAB_SHNDX => {
+                    let Some((start, size, _)) = self.section_extent(index) else {
+                        continue;
+                    };
+                    if size < (high + 1) * 4 {
+                        continue;
+                    }
+
+                    let range = start + low * 4..start + (high + 1) * 4;
+                    if right {
+                        self.bytes[range].rotate_right(4);
+                    } else {
+                        self.bytes[range].rotate_left(4);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns `true` if a relocation or section group refers to the symbol at `index`.
+    fn is_referenced(&self, index: usize) -> bool {
+        self.linked_sections().any(|section| {
+            let section_type = self.section_type(section);
+            if section_type == SectionType::GROUP {
+                let field = self.section_field(section, self.section_info_offset());
+                return self.layout.read_u32(self.bytes, field) as usize == index;
+            }
+
+            let Some((start, size, entry_size)) = self.section_extent(section) else {
+                return false;
+            };
+            let entry_size = match entry_size {
+                0 if section_type == SectionType::REL => self.layout.word_size() * 2,
+                0 => self.layout.word_size() * 3,
+                entry_size => entry_size,
+            };
+            (start..start + size)
+                .step_by(entry_size)
+                .take_while(|&entry| entry + entry_size <= start + size)
+                .any(|entry| {
+                    let info = self
+                        .layout
+                        .read_word(self.bytes, entry + self.layout.word_size());
+                    let symbol = if self.layout.wide {
+                        info >> 32
+                    } else {
+                        info >> 8
+                    };
+                    symbol as usize == index
+                })
+        })
+    }
+
+    /// Returns an [`Iterator`] over the indices of the relocation sections and section groups
+    /// that refer to the symbol table.
+    fn linked_sections(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..self.section_count).filter(|&index| {
+            let section_type = self.section_type(index);
+            let link = self.layout.read_u32(
+                self.bytes,
+                self.section_field(index, self.section_link_offset()),
+            );
+            link == u32::from(self.section)
+                && (section_type == SectionType::REL
+                    || section_type == SectionType::RELA
+                    || section_type == SectionType::GROUP)
+        })
+    }
+
+    /// Overwrites the bytes of the name of the symbol at `index` with null bytes, unless they are
+    /// shared with another symbol or the section names.
+    fn scrub_name(&mut self, index: usize) {
+        if self.strings_shared {
+            return;
+        }
+        let Some(field) = self.symbol_field(index, 0) else {
+            return;
+        };
+        let offset = self.layout.read_u32(self.bytes, field) as usize;
+        if offset == 0 || offset >= self.strings_size {
+            return;
+        }
+
+        let strings = &self.bytes[self.strings_offset..self.strings_offset + self.strings_size];
+        let Some(end) = strings[offset..]
+            .iter()
+            .position(|&byte| byte == 0)
+            .map(|length| offset + length)
+        else {
+            return;
+        };
+        let string_start = strings[..offset]
+            .iter()
+            .rposition(|&byte| byte == 0)
+            .map_or(0, |nul| nul + 1);
+
+        // Another symbol whose name starts at or before this one shares all of its bytes, while
+        // one whose name starts within it only shares its tail.
+        let mut scrub_end = end;
+        for other in (1..self.count).filter(|&other| other != index) {
+            let Some(field) = self.symbol_field(other, 0) else {
+                continue;
+            };
+            let other_offset = self.layout.read_u32(self.bytes, field) as usize;
+            if (string_start..=offset).contains(&other_offset) {
+                return;
+            }
+            if (offset..end).contains(&other_offset) {
+                scrub_end = scrub_end.min(other_offset);
+            }
+        }
+
+        self.bytes[self.strings_offset + offset..self.strings_offset + scrub_end].fill(0);
+    }
+
+    /// Returns the file offset, size and entry size of the section at `index`, if it is located
+    /// in bounds.
+    fn section_extent(&self, index: u16) -> Option<(usize, usize, usize)> {
+        let start = usize::try_from(self.layout.read_word(
+            self.bytes,
+            self.section_field(index, self.section_file_offset_offset()),
+        ))
+        .ok()?;
+        let size = usize::try_from(self.layout.read_word(
+            self.bytes,
+            self.section_field(index, self.section_size_offset()),
+        ))
+        .ok()?;
+        let entry_size = usize::try_from(self.layout.read_word(
+            self.bytes,
+            self.section_field(index, self.section_entry_size_offset()),
+        ))
+        .ok()?;
+        if start.checked_add(size)? > self.bytes.len() {
+            return None;
+        }
+
+        Some((start, size, entry_size))
+    }
+
+    /// Returns the [`SectionType`] of the section at `index`.
+    fn section_type(&self, index: u16) -> SectionType {
+        let field_offset = if self.layout.wide {
+            Class64.section_type_offset()
+        } else {
+            Class32.section_type_offset()
+        };
+        SectionType(
+            self.layout
+                .read_u32(self.bytes, self.section_field(index, field_offset)),
+        )
+    }
+
+    /// Returns the index of the string table holding the names of the symbols.
+    fn strings_section(&self) -> u16 {
+        self.layout.read_u32(
+            self.bytes,
+            self.section_field(self.section, self.section_link_offset()),
+        ) as u16
+    }
+
+    /// Returns the index of the first non-local symbol.
+    fn first_global(&self) -> usize {
+        let field = self.section_field(self.section, self.section_info_offset());
+        (self.layout.read_u32(self.bytes, field) as usize).min(self.count)
+    }
+
+    /// Writes `first_global` as the index of the first non-local symbol.
+    fn write_first_global(&mut self, first_global: u32) {
+        let field = self.section_field(self.section, self.section_info_offset());
+        self.layout.write_u32(self.bytes, field, first_global);
+    }
+
+    /// Writes the size of the symbol table from its number of entries.
+    fn write_symbol_table_size(&mut self) {
+        let field = self.section_field(self.section, self.section_size_offset());
+        self.layout
+            .write_word(self.bytes, field, (self.count * self.entry_size) as u64);
+    }
+
+    /// Returns the offset of the field at `field_offset` within the symbol at `index`.
+    fn symbol_field(&self, index: usize, field_offset: usize) -> Option<usize> {
+        if index >= self.count {
+            return None;
+        }
+
+        Some(self.table_offset + index * self.entry_size + field_offset)
+    }
+
+    /// Returns the offset of the field at `field_offset` within the section header at `index`.
+    ///
+    /// The index must be less than the number of section headers.
+    fn section_field(&self, index: u16, field_offset: usize) -> usize {
+        self.section_table_offset + usize::from(index) * self.section_entry_size + field_offset
+    }
+
+    /// Returns the offset of the type and binding attributes within a symbol.
+    fn symbol_info_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.symbol_info_offset()
+        } else {
+            Class32.symbol_info_offset()
+        }
+    }
+
+    /// Returns the offset of the file offset within a section header.
+    fn section_file_offset_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_file_offset_offset()
+        } else {
+            Class32.section_file_offset_offset()
+        }
+    }
+
+    /// Returns the offset of the size within a section header.
+    fn section_size_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_size_offset()
+        } else {
+            Class32.section_size_offset()
+        }
+    }
+
+    /// Returns the offset of the link within a section header.
+    fn section_link_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_link_offset()
+        } else {
+            Class32.section_link_offset()
+        }
+    }
+
+    /// Returns the offset of the extra information within a section header.
+    fn section_info_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_info_offset()
+        } else {
+            Class32.section_info_offset()
+        }
+    }
+
+    /// Returns the offset of the entry size within a section header.
+    fn section_entry_size_offset(&self) -> usize {
+        if self.layout.wide {
+            Class64.section_entry_size_offset()
+        } else {
+            Class32.section_entry_size_offset()
+        }
+    }
+}
+
+/// Various errors that can occur while editing the symbol table of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum EditSymbolError {
+    /// An error occurred while parsing the [`ElfFile`].
+    ParseElfFileError(ParseElfFileError),
+    /// The [`ElfFile`] has no [`SectionType::SYMTAB`] section and string table located in
+    /// bounds.
+    MissingSymbolTable,
+    /// No symbol matches the given name.
+    SymbolNotFound,
+    /// A relocation or section group refers to a symbol that would be removed.
+    SymbolReferenced,
+}
+
+impl From<ParseElfFileError> for EditSymbolError {
+    fn from(value: ParseElfFileError) -> Self {
+        Self::ParseElfFileError(value)
+    }
+}
+
+impl fmt::Display for EditSymbolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ParseElfFileError(error) => write!(f, "error while parsing ELF file: {error}"),
+            Self::MissingSymbolTable => write!(f, "ELF file has no symbol table"),
+            Self::SymbolNotFound => write!(f, "no symbol matches the given name"),
+            Self::SymbolReferenced => write!(f, "symbol is referenced by a relocation or group"),
+        }
+    }
+}
+
+impl error::Error for EditSymbolError {}
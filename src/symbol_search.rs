@@ -0,0 +1,175 @@
+//! Definitions for searching the symbols of an ELF file without collecting them first.
+//!
+//! [`ElfFile::find_symbols_matching()`] lazily filters the symbols of an [`ElfFile`] by a
+//! predicate, which is usually built from the matchers on [`Symbol`], such as
+//! [`Symbol::name_ends_with()`].
+//!
+//! With the `demangle` feature enabled, [`Symbol::demangled_path_ends_with()`] matches symbols by
+//! the path they were declared at rather than their mangled name. Only the names of C++ and
+//! legacy Rust symbols mangled according to the Itanium C++ ABI are understood, and only those
+//! whose path consists of plain identifiers, as is the case for most free functions and methods.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    symbol::{self, Symbol},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`MatchingSymbols`] over the symbols of this [`ElfFile`] for which `predicate`
+    /// returns `true`.
+    ///
+    /// The symbols are read from the table returned by [`ElfFile::symbols()`]. Returns `None` if
+    /// this [`ElfFile`] has no symbol table.
+    pub fn find_symbols_matching<P: FnMut(&Symbol<'slice, C, E>) -> bool>(
+        &self,
+        predicate: P,
+    ) -> Option<MatchingSymbols<'slice, C, E, P>> {
+        let (_, table) = self.symbols()?;
+        let symbols = table.into_iter();
+
+        Some(MatchingSymbols { symbols, predicate })
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> Symbol<'_, C, E> {
+    /// Returns `true` if the name of this [`Symbol`] ends with `suffix`.
+    pub fn name_ends_with(&self, suffix: &[u8]) -> bool {
+        self.name().is_some_and(|name| name.ends_with(suffix))
+    }
+
+    /// Returns `true` if this [`Symbol`] is declared at a path ending with the identifiers in
+    /// `path`, such as `[b"Vec", b"push"]`.
+    ///
+    /// Names that are not mangled are treated as a path with a single identifier. Returns
+    /// `false` if the name is mangled in a form that is not understood.
+    #[cfg(feature = "demangle")]
+    pub fn demangled_path_ends_with(&self, path: &[&[u8]]) -> bool {
+        self.name()
+            .is_some_and(|name| demangled_path_ends_with(name, path))
+    }
+}
+
+/// An [`Iterator`] over the [`Symbol`]s of a [`SymbolTable`][table] that match a predicate.
+///
+/// [table]: crate::symbol::SymbolTable
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct MatchingSymbols<'slice, C, E, P> {
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// The predicate symbols must match.
+    predicate: P,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse, P: FnMut(&Symbol<'slice, C, E>) -> bool> Iterator
+    for MatchingSymbols<'slice, C, E, P>
+{
+    type Item = Symbol<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.symbols.find(&mut self.predicate)
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse, P: FnMut(&Symbol<'slice, C, E>) -> bool> FusedIterator
+    for MatchingSymbols<'slice, C, E, P>
+{
+}
+
+/// Returns `true` if the symbol named `name` is declared at a path ending with the identifiers
+/// in `path`.
+///
+/// Names starting with `_Z` are decoded as Itanium C++ ABI mangled names, dropping the hash that
+/// legacy Rust mangling appends to the path. Other names are treated as a path with a single
+/// identifier. Returns `false` if the name is mangled in a form that is not understood.
+#[cfg(feature = "demangle")]
+pub fn demangled_path_ends_with(name: &[u8], path: &[&[u8]]) -> bool {
+    let Some(mangled) = name.strip_prefix(b"_Z") else {
+        return path == [name];
+    };
+
+    let Some(count) = for_each_component(mangled, |_, _| true) else {
+        return false;
+    };
+    let Some(skip) = count.checked_sub(path.len()) else {
+        return false;
+    };
+
+    for_each_component(mangled, |index, component| {
+        index < skip || path[index - skip] == component
+    }) == Some(count)
+}
+
+/// Calls `f` with the index and identifier of each component of the path encoded by the
+/// Itanium C++ ABI mangled name `mangled`, without its `_Z` prefix, until `f` returns `false`.
+///
+/// Returns the number of components visited, or `None` if the encoding is not understood.
+#[cfg(feature = "demangle")]
+fn for_each_component(
+    mut mangled: &[u8],
+    mut f: impl FnMut(usize, &[u8]) -> bool,
+) -> Option<usize> {
+    let nested = match mangled.first() {
+        Some(b'N') => {
+            mangled = &mangled[1..];
+            // Skip the qualifiers of the implicit object parameter of a method.
+            while let Some(b'r' | b'V' | b'K' | b'R' | b'O') = mangled.first() {
+                mangled = &mangled[1..];
+            }
+            true
+        }
+        Some(b'L') => {
+            mangled = &mangled[1..];
+            false
+        }
+        _ => false,
+    };
+
+    let mut count = 0;
+    loop {
+        let component = match mangled {
+            [b'E', ..] if nested && count != 0 => return Some(count),
+            [b'S', b't', rest @ ..] if count == 0 => {
+                mangled = rest;
+                &b"std"[..]
+            }
+            [b'0'..=b'9', ..] => {
+                let digits = mangled
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_digit())
+                    .count();
+                let length = core::str::from_utf8(&mangled[..digits])
+                    .ok()?
+                    .parse::<usize>()
+                    .ok()?;
+                let end = digits.checked_add(length)?;
+                let component = mangled.get(digits..end)?;
+                mangled = &mangled[end..];
+                component
+            }
+            _ => return None,
+        };
+
+        // Legacy Rust mangling ends the path with a hash: `h` followed by 16 hex digits.
+        let is_hash = nested
+            && mangled.first() == Some(&b'E')
+            && component.len() == 17
+            && component[0] == b'h'
+            && component[1..].iter().all(u8::is_ascii_hexdigit);
+        if is_hash && count != 0 {
+            return Some(count);
+        }
+
+        if !f(count, component) {
+            return Some(count);
+        }
+        count += 1;
+
+        if !nested {
+            return Some(count);
+        }
+    }
+}
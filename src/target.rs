@@ -0,0 +1,153 @@
+//! Definitions for checking that an ELF file was built for a particular target.
+//!
+//! A [`TargetSpec`] can be declared as a `const`, so tools that program a single kind of board
+//! can describe it once and refuse images built for anything else.
+
+use core::{error, fmt};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    header::Machine,
+    ident::{Class, Encoding},
+    ElfFile,
+};
+
+impl<C: ClassParse, E: EncodingParse> ElfFile<'_, C, E> {
+    /// Checks that this [`ElfFile`] was built for the target described by `spec`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`TargetMismatch`] found, checking the [`Class`], the [`Encoding`], the
+    /// [`Machine`] and the processor specific flags in that order.
+    pub fn matches(&self, spec: &TargetSpec) -> Result<(), TargetMismatch> {
+        let header = self.header();
+        let ident = header.ident();
+
+        if ident.class() != spec.class {
+            return Err(TargetMismatch::Class {
+                expected: spec.class,
+                found: ident.class(),
+            });
+        }
+        if ident.encoding() != spec.encoding {
+            return Err(TargetMismatch::Encoding {
+                expected: spec.encoding,
+                found: ident.encoding(),
+            });
+        }
+        if header.machine() != spec.machine {
+            return Err(TargetMismatch::Machine {
+                expected: spec.machine,
+                found: header.machine(),
+            });
+        }
+        if header.flags() & spec.flags_mask != spec.flags & spec.flags_mask {
+            return Err(TargetMismatch::Flags {
+                expected: spec.flags & spec.flags_mask,
+                found: header.flags() & spec.flags_mask,
+                mask: spec.flags_mask,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Description of the target an ELF file must be built for.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TargetSpec {
+    /// The required [`Class`].
+    pub class: Class,
+    /// The required [`Encoding`].
+    pub encoding: Encoding,
+    /// The required [`Machine`].
+    pub machine: Machine,
+    /// The required values of the processor specific flags selected by
+    /// [`TargetSpec::flags_mask`].
+    pub flags: u32,
+    /// The processor specific flags that must match [`TargetSpec::flags`].
+    pub flags_mask: u32,
+}
+
+impl TargetSpec {
+    /// Creates a new [`TargetSpec`] that accepts any processor specific flags.
+    pub const fn new(class: Class, encoding: Encoding, machine: Machine) -> Self {
+        Self {
+            class,
+            encoding,
+            machine,
+            flags: 0,
+            flags_mask: 0,
+        }
+    }
+
+    /// Returns this [`TargetSpec`], requiring the processor specific flags selected by `mask` to
+    /// equal those of `flags`.
+    pub const fn with_flags(mut self, flags: u32, mask: u32) -> Self {
+        self.flags = flags;
+        self.flags_mask = mask;
+        self
+    }
+}
+
+/// Various ways in which an ELF file can differ from a [`TargetSpec`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TargetMismatch {
+    /// The [`Class`] of the ELF file differs.
+    Class {
+        /// The [`Class`] required by the [`TargetSpec`].
+        expected: Class,
+        /// The [`Class`] of the ELF file.
+        found: Class,
+    },
+    /// The [`Encoding`] of the ELF file differs.
+    Encoding {
+        /// The [`Encoding`] required by the [`TargetSpec`].
+        expected: Encoding,
+        /// The [`Encoding`] of the ELF file.
+        found: Encoding,
+    },
+    /// The [`Machine`] of the ELF file differs.
+    Machine {
+        /// The [`Machine`] required by the [`TargetSpec`].
+        expected: Machine,
+        /// The [`Machine`] of the ELF file.
+        found: Machine,
+    },
+    /// The processor specific flags of the ELF file differ within the mask.
+    Flags {
+        /// The masked flags required by the [`TargetSpec`].
+        expected: u32,
+        /// The masked flags of the ELF file.
+        found: u32,
+        /// The mask applied to both sets of flags.
+        mask: u32,
+    },
+}
+
+impl fmt::Display for TargetMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Class { expected, found } => {
+                write!(f, "expected class {expected:?}, found {found:?}")
+            }
+            Self::Encoding { expected, found } => {
+                write!(f, "expected encoding {expected:?}, found {found:?}")
+            }
+            Self::Machine { expected, found } => {
+                write!(f, "expected machine {expected:?}, found {found:?}")
+            }
+            Self::Flags {
+                expected,
+                found,
+                mask,
+            } => write!(
+                f,
+                "expected flags {expected:#010x}, found {found:#010x} (mask {mask:#010x})"
+            ),
+        }
+    }
+}
+
+impl error::Error for TargetMismatch {}
@@ -0,0 +1,311 @@
+//! Definitions for describing the [`SegmentType::LOAD`] segments of an ELF file in a form suited
+//! to wrapping into U-Boot legacy or FIT images.
+
+use core::{error, fmt, iter::FusedIterator};
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    header::{ElfType, Machine},
+    ident::Class,
+    program_header::{ProgramHeader, SegmentType, SegmentsOfType},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`UImageMetadata`] of this [`ElfFile`].
+    ///
+    /// # Errors
+    ///
+    /// - [`UImageError::NotExecutable`]: Returned if this [`ElfFile`] is not an executable.
+    /// - [`UImageError::UnsupportedMachine`]: Returned if the [`Machine`] of this [`ElfFile`] has
+    ///   no U-Boot architecture.
+    /// - [`UImageError::ClassMismatch`]: Returned if the [`Class`] of this [`ElfFile`] does not
+    ///   match its [`Machine`].
+    /// - [`UImageError::NoLoadSegments`]: Returned if this [`ElfFile`] has no
+    ///   [`SegmentType::LOAD`] segments with file data.
+    /// - [`UImageError::EntryOutsideLoadSegments`]: Returned if the entry point of this
+    ///   [`ElfFile`] does not lie within a [`SegmentType::LOAD`] segment.
+    pub fn uimage_metadata(&self) -> Result<UImageMetadata<'slice, C, E>, UImageError> {
+        let header = self.header();
+        if header.elf_type() != ElfType::EXECUTABLE {
+            return Err(UImageError::NotExecutable);
+        }
+
+        let class = header.ident().class();
+        let architecture = match (header.machine(), class) {
+            (Machine::INTEL_386, Class::CLASS32) => UImageArchitecture::I386,
+            (Machine::X86_64, Class::CLASS64) => UImageArchitecture::X86_64,
+            (Machine::ARM, Class::CLASS32) => UImageArchitecture::ARM,
+            (Machine::AARCH64, Class::CLASS64) => UImageArchitecture::ARM64,
+            (Machine::MIPS, Class::CLASS32) => UImageArchitecture::MIPS,
+            (Machine::MIPS, Class::CLASS64) => UImageArchitecture::MIPS64,
+            (Machine::PPC64, Class::CLASS64) => UImageArchitecture::PPC,
+            (Machine::RISCV, _) => UImageArchitecture::RISCV,
+            (
+                Machine::INTEL_386
+                | Machine::X86_64
+                | Machine::ARM
+                | Machine::AARCH64
+                | Machine::PPC64,
+                _,
+            ) => return Err(UImageError::ClassMismatch),
+            _ => return Err(UImageError::UnsupportedMachine),
+        };
+
+        let metadata = UImageMetadata {
+            file: *self,
+            architecture,
+            entry: header.entry().into(),
+        };
+        if metadata.segments().next().is_none() {
+            return Err(UImageError::NoLoadSegments);
+        }
+        if self.entry_segment().is_none() {
+            return Err(UImageError::EntryOutsideLoadSegments);
+        }
+
+        Ok(metadata)
+    }
+}
+
+/// The metadata required to wrap an [`ElfFile`] into a U-Boot image.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct UImageMetadata<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being described.
+    file: ElfFile<'slice, C, E>,
+    /// The [`UImageArchitecture`] of the [`ElfFile`].
+    architecture: UImageArchitecture,
+    /// The entry point of the [`ElfFile`].
+    entry: u64,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> UImageMetadata<'slice, C, E> {
+    /// Returns the [`UImageArchitecture`] of the described [`ElfFile`].
+    pub fn architecture(&self) -> UImageArchitecture {
+        self.architecture
+    }
+
+    /// Returns the virtual address of the entry point of the described [`ElfFile`].
+    pub fn entry(&self) -> u64 {
+        self.entry
+    }
+
+    /// Returns an [`Iterator`] over the [`UImageSegment`]s of the described [`ElfFile`].
+    pub fn segments(&self) -> UImageSegments<'slice, C, E> {
+        UImageSegments {
+            file: self.file,
+            entry: self.entry,
+            segments: self
+                .file
+                .program_header_table()
+                .map(|table| table.segments_of_type(SegmentType::LOAD)),
+        }
+    }
+
+    /// Returns `true` if the described [`ElfFile`] can be wrapped into a U-Boot legacy image.
+    ///
+    /// Legacy images hold a single payload with 32-bit load and entry addresses.
+    pub fn fits_legacy(&self) -> bool {
+        let mut segments = self.segments();
+        let Some(segment) = segments.next() else {
+            return false;
+        };
+
+        segments.next().is_none()
+            && u32::try_from(segment.load_address).is_ok()
+            && u32::try_from(self.entry).is_ok()
+    }
+}
+
+/// The description of a [`SegmentType::LOAD`] segment as a U-Boot image payload.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct UImageSegment<'slice> {
+    /// The physical address at which the payload should be loaded.
+    pub load_address: u64,
+    /// The entry point of the [`ElfFile`], if it lies within this [`UImageSegment`].
+    pub entry: Option<u64>,
+    /// The [`UImageCompression`] of the payload, detected from its leading bytes.
+    pub compression: UImageCompression,
+    /// The payload.
+    pub data: &'slice [u8],
+}
+
+impl<'slice> UImageSegment<'slice> {
+    /// Creates a new [`UImageSegment`] describing the segment controlled by `program_header`.
+    fn new<C: ClassParse, E: EncodingParse>(
+        file: ElfFile<'slice, C, E>,
+        program_header: ProgramHeader<'slice, C, E>,
+        entry: u64,
+    ) -> Option<Self> {
+        if program_header.file_size().into() == 0 {
+            return None;
+        }
+
+        let data = file.segment_data(program_header)?;
+        let virtual_address: u64 = program_header.virtual_address().into();
+        let contains_entry = entry
+            .checked_sub(virtual_address)
+            .is_some_and(|offset| offset < program_header.memory_size().into());
+
+        let segment = Self {
+            load_address: program_header.physical_address().into(),
+            entry: contains_entry.then_some(entry),
+            compression: UImageCompression::detect(data),
+            data,
+        };
+
+        Some(segment)
+    }
+}
+
+/// An [`Iterator`] over the [`UImageSegment`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct UImageSegments<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being described.
+    file: ElfFile<'slice, C, E>,
+    /// The entry point of the [`ElfFile`].
+    entry: u64,
+    /// The [`SegmentType::LOAD`] segments that have not been visited.
+    segments: Option<SegmentsOfType<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for UImageSegments<'slice, C, E> {
+    type Item = UImageSegment<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (file, entry) = (self.file, self.entry);
+        self.segments
+            .as_mut()?
+            .find_map(|program_header| UImageSegment::new(file, program_header, entry))
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for UImageSegments<'_, C, E> {}
+
+/// The architecture of a U-Boot image.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UImageArchitecture(pub u8);
+
+impl UImageArchitecture {
+    /// The ARM architecture.
+    pub const ARM: Self = Self(2);
+    /// The Intel 80386 architecture.
+    pub const I386: Self = Self(3);
+    /// The 32-bit MIPS architecture.
+    pub const MIPS: Self = Self(5);
+    /// The 64-bit MIPS architecture.
+    pub const MIPS64: Self = Self(6);
+    /// The PowerPC architecture.
+    pub const PPC: Self = Self(7);
+    /// The AArch64 architecture.
+    pub const ARM64: Self = Self(22);
+    /// The AMD x86_64 architecture.
+    pub const X86_64: Self = Self(24);
+    /// The RISC-V architecture.
+    pub const RISCV: Self = Self(26);
+}
+
+impl fmt::Debug for UImageArchitecture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::ARM => f.pad("Arm"),
+            Self::I386 => f.pad("I386"),
+            Self::MIPS => f.pad("Mips"),
+            Self::MIPS64 => f.pad("Mips64"),
+            Self::PPC => f.pad("PowerPc"),
+            Self::ARM64 => f.pad("Arm64"),
+            Self::X86_64 => f.pad("x86_64"),
+            Self::RISCV => f.pad("RiscV"),
+            architecture => f
+                .debug_tuple("UImageArchitecture")
+                .field(&architecture.0)
+                .finish(),
+        }
+    }
+}
+
+/// The compression of a U-Boot image payload.
+#[repr(transparent)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UImageCompression(pub u8);
+
+impl UImageCompression {
+    /// The payload is not compressed.
+    pub const NONE: Self = Self(0);
+    /// The payload is compressed with gzip.
+    pub const GZIP: Self = Self(1);
+    /// The payload is compressed with bzip2.
+    pub const BZIP2: Self = Self(2);
+    /// The payload is compressed with LZMA.
+    pub const LZMA: Self = Self(3);
+    /// The payload is compressed with LZO.
+    pub const LZO: Self = Self(4);
+    /// The payload is compressed with LZ4.
+    pub const LZ4: Self = Self(5);
+    /// The payload is compressed with Zstandard.
+    pub const ZSTD: Self = Self(6);
+
+    /// Returns the [`UImageCompression`] of `data`, detected from its leading magic bytes.
+    pub fn detect(data: &[u8]) -> Self {
+        match data {
+            [0x1F, 0x8B, ..] => Self::GZIP,
+            [b'B', b'Z', b'h', ..] => Self::BZIP2,
+            [0x5D, 0x00, 0x00, ..] => Self::LZMA,
+            [0x89, b'L', b'Z', b'O', ..] => Self::LZO,
+            [0x02, 0x21, 0x4C, 0x18, ..] | [0x04, 0x22, 0x4D, 0x18, ..] => Self::LZ4,
+            [0x28, 0xB5, 0x2F, 0xFD, ..] => Self::ZSTD,
+            _ => Self::NONE,
+        }
+    }
+}
+
+impl fmt::Debug for UImageCompression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NONE => f.pad("None"),
+            Self::GZIP => f.pad("Gzip"),
+            Self::BZIP2 => f.pad("Bzip2"),
+            Self::LZMA => f.pad("Lzma"),
+            Self::LZO => f.pad("Lzo"),
+            Self::LZ4 => f.pad("Lz4"),
+            Self::ZSTD => f.pad("Zstd"),
+            compression => f
+                .debug_tuple("UImageCompression")
+                .field(&compression.0)
+                .finish(),
+        }
+    }
+}
+
+/// Various errors that can occur while describing an [`ElfFile`] for a U-Boot image.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UImageError {
+    /// The [`ElfFile`] is not an executable.
+    NotExecutable,
+    /// The [`Machine`] of the [`ElfFile`] has no U-Boot architecture.
+    UnsupportedMachine,
+    /// The [`Class`] of the [`ElfFile`] does not match its [`Machine`].
+    ClassMismatch,
+    /// The [`ElfFile`] has no [`SegmentType::LOAD`] segments with file data.
+    NoLoadSegments,
+    /// The entry point of the [`ElfFile`] does not lie within a [`SegmentType::LOAD`] segment.
+    EntryOutsideLoadSegments,
+}
+
+impl fmt::Display for UImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotExecutable => write!(f, "ELF file is not an executable"),
+            Self::UnsupportedMachine => write!(f, "machine has no U-Boot architecture"),
+            Self::ClassMismatch => write!(f, "ELF class does not match machine"),
+            Self::NoLoadSegments => write!(f, "no load segments with file data"),
+            Self::EntryOutsideLoadSegments => {
+                write!(f, "entry point lies outside of every load segment")
+            }
+        }
+    }
+}
+
+impl error::Error for UImageError {}
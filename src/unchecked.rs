@@ -0,0 +1,200 @@
+//! Definitions for reading a validated ELF file without revalidating it.
+//!
+//! [`ValidatedElfFile`] checks once that every segment and dynamic relocation table of an
+//! [`ElfFile`] lies within the file. Its `unchecked` accessors then skip the bounds and
+//! conversion checks that [`ElfFile`] repeats on every call, which adds up in hot loops such as
+//! applying millions of relocations.
+//!
+//! The accessors contain no `unsafe` code. Passing them an index that is out of range panics.
+
+use core::{error, fmt};
+
+use crate::{
+    class::{to_file_offset, to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    program_header::ProgramHeaderTable,
+    relocation::{Rel, Rela},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Validates that every segment and dynamic relocation table of this [`ElfFile`] is located
+    /// in bounds, returning a [`ValidatedElfFile`] that can be read without further checks.
+    ///
+    /// # Errors
+    ///
+    /// - [`ValidateElfFileError::SegmentOutOfBounds`]: Returned if the file data of a segment is
+    ///   located out of bounds.
+    /// - [`ValidateElfFileError::RelocationTableOutOfBounds`]: Returned if the dynamic array
+    ///   describes a relocation table that is located out of bounds.
+    pub fn validated(&self) -> Result<ValidatedElfFile<'slice, C, E>, ValidateElfFileError> {
+        let program_headers = self.program_header_table();
+        if let Some(table) = program_headers {
+            for (index, program_header) in (0..).zip(table) {
+                if self.segment_data(program_header).is_none() {
+                    return Err(ValidateElfFileError::SegmentOutOfBounds { index });
+                }
+            }
+        }
+
+        let mut validated = ValidatedElfFile {
+            file: *self,
+            program_headers,
+            rela: &[],
+            rel: &[],
+        };
+        let Some(dynamic) = self.dynamic_table() else {
+            return Ok(validated);
+        };
+
+        if dynamic.find(ConstDynamicTag::RELA_TABLE).is_some() {
+            let data = self
+                .relocation_data(
+                    dynamic,
+                    ConstDynamicTag::RELA_TABLE,
+                    ConstDynamicTag::RELA_SIZE,
+                )
+                .ok_or(ValidateElfFileError::RelocationTableOutOfBounds)?;
+            let size = self.class.expected_rela_size();
+            validated.rela = &data[..data.len() / size * size];
+        }
+        if dynamic.find(ConstDynamicTag::REL_TABLE).is_some() {
+            let data = self
+                .relocation_data(
+                    dynamic,
+                    ConstDynamicTag::REL_TABLE,
+                    ConstDynamicTag::REL_SIZE,
+                )
+                .ok_or(ValidateElfFileError::RelocationTableOutOfBounds)?;
+            let size = self.class.expected_rel_size();
+            validated.rel = &data[..data.len() / size * size];
+        }
+
+        Ok(validated)
+    }
+}
+
+/// An [`ElfFile`] whose segments and dynamic relocation tables are known to be located in
+/// bounds.
+///
+/// A [`ValidatedElfFile`] can only be obtained from [`ElfFile::validated()`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct ValidatedElfFile<'slice, C, E> {
+    /// The validated [`ElfFile`].
+    file: ElfFile<'slice, C, E>,
+    /// The validated [`ProgramHeaderTable`], if present.
+    program_headers: Option<ProgramHeaderTable<'slice, C, E>>,
+    /// The bytes of the relocation table with explicit addends, truncated to whole entries.
+    rela: &'slice [u8],
+    /// The bytes of the relocation table with implicit addends, truncated to whole entries.
+    rel: &'slice [u8],
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> ValidatedElfFile<'slice, C, E> {
+    /// Returns the underlying [`ElfFile`].
+    pub fn file(&self) -> ElfFile<'slice, C, E> {
+        self.file
+    }
+
+    /// Returns the number of segments of this [`ValidatedElfFile`].
+    pub fn segment_count(&self) -> u16 {
+        self.program_headers.map_or(0, |table| table.count())
+    }
+
+    /// Returns the file data of the segment at `index`, which must be less than
+    /// [`ValidatedElfFile::segment_count()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of range.
+    pub fn segment_data_unchecked(&self, index: u16) -> &'slice [u8] {
+        let program_header = self
+            .program_headers
+            .and_then(|table| table.get(index))
+            .expect("segment index out of range");
+        let start = to_file_offset(program_header.file_offset()).expect("validated segment");
+        let size = to_len(program_header.file_size()).expect("validated segment");
+
+        &self.file.bytes[start..start + size]
+    }
+
+    /// Returns the number of entries in the dynamic relocation table with explicit addends.
+    pub fn rela_count(&self) -> usize {
+        self.rela.len() / self.file.class.expected_rela_size()
+    }
+
+    /// Returns the [`Rela`] entry located at `index`, which must be less than
+    /// [`ValidatedElfFile::rela_count()`].
+    pub fn rela_unchecked(&self, index: usize) -> Rela<C> {
+        let class = self.file.class;
+        let encoding = self.file.encoding;
+        let rela_bytes = &self.rela[index * class.expected_rela_size()..];
+
+        Rela {
+            offset: class.parse_class_usize_at(encoding, class.rela_offset_offset(), rela_bytes),
+            info: class.parse_class_usize_at(encoding, class.rela_info_offset(), rela_bytes),
+            addend: class.parse_class_isize_at(encoding, class.rela_addend_offset(), rela_bytes),
+        }
+    }
+
+    /// Returns the number of entries in the dynamic relocation table with implicit addends.
+    pub fn rel_count(&self) -> usize {
+        self.rel.len() / self.file.class.expected_rel_size()
+    }
+
+    /// Returns the [`Rel`] entry located at `index`, which must be less than
+    /// [`ValidatedElfFile::rel_count()`].
+    pub fn rel_unchecked(&self, index: usize) -> Rel<C> {
+        let class = self.file.class;
+        let encoding = self.file.encoding;
+        let rel_bytes = &self.rel[index * class.expected_rel_size()..];
+
+        Rel {
+            offset: class.parse_class_usize_at(encoding, class.rel_offset_offset(), rel_bytes),
+            info: class.parse_class_usize_at(encoding, class.rel_info_offset(), rel_bytes),
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> fmt::Debug for ValidatedElfFile<'_, C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("ValidatedElfFile");
+
+        debug_struct.field("file", &self.file);
+        debug_struct.field("segment_count", &self.segment_count());
+        debug_struct.field("rela_count", &self.rela_count());
+        debug_struct.field("rel_count", &self.rel_count());
+
+        debug_struct.finish()
+    }
+}
+
+/// Various errors that can occur when validating an [`ElfFile`] for unchecked access.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ValidateElfFileError {
+    /// The file data of a segment is located out of bounds.
+    SegmentOutOfBounds {
+        /// The index of the [`ProgramHeader`][ph] describing the segment.
+        ///
+        /// [ph]: crate::program_header::ProgramHeader
+        index: u16,
+    },
+    /// A relocation table described by the dynamic array is located out of bounds.
+    RelocationTableOutOfBounds,
+}
+
+impl fmt::Display for ValidateElfFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SegmentOutOfBounds { index } => {
+                write!(f, "segment {index} is located out of bounds")
+            }
+            Self::RelocationTableOutOfBounds => {
+                write!(f, "relocation table is located out of bounds")
+            }
+        }
+    }
+}
+
+impl error::Error for ValidateElfFileError {}
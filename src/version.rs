@@ -0,0 +1,440 @@
+//! Definitions for the GNU symbol version requirement (`.gnu.version_r`) and definition
+//! (`.gnu.version_d`) tables.
+//!
+//! The requirement table holds one [`VersionRequirement`] per shared object a file takes
+//! versioned symbols from, each followed by the [`VersionNeeded`] entries naming the versions
+//! required from that shared object. The definition table holds one [`VersionDefinition`] per
+//! version a file defines for its own symbols. The index of each [`VersionNeeded`] and
+//! [`VersionDefinition`] entry is the value that the [`VersymTable`][versym] holds for the
+//! symbols bound to that version.
+//!
+//! The entries have the same layout in 32-bit and 64-bit files, and are linked together by
+//! offsets relative to the start of each entry.
+//!
+//! [versym]: crate::versym::VersymTable
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{
+    class::{to_len, ClassParse},
+    dynamic::ConstDynamicTag,
+    encoding::EncodingParse,
+    string_table::StringTable,
+    ElfFile,
+};
+
+/// The size of a [`VersionRequirement`] entry.
+const REQUIREMENT_SIZE: usize = 16;
+/// The size of a [`VersionNeeded`] entry.
+const NEEDED_SIZE: usize = 16;
+/// The size of a [`VersionDefinition`] entry.
+const DEFINITION_SIZE: usize = 20;
+/// The size of an entry naming a [`VersionDefinition`] or one of its parents.
+const DEFINITION_NAME_SIZE: usize = 8;
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`VersionRequirements`] over the version requirement table located by the
+    /// [`ConstDynamicTag::VERNEED`] entry of this [`ElfFile`].
+    ///
+    /// The number of entries is limited by the [`ConstDynamicTag::VERNEED_COUNT`] entry, if
+    /// present. Names are read from the [`ElfFile::dynamic_string_table()`].
+    pub fn version_requirements(&self) -> Option<VersionRequirements<'slice, E>> {
+        let dynamic = self.dynamic_table()?;
+        let bytes = self.virtual_address_data(dynamic.find(ConstDynamicTag::VERNEED)?)?;
+        let remaining = dynamic
+            .find(ConstDynamicTag::VERNEED_COUNT)
+            .map_or(Some(usize::MAX), |count| to_len(count).ok())?;
+
+        Some(VersionRequirements {
+            bytes,
+            remaining,
+            strings: self.dynamic_string_table(),
+            encoding: self.encoding,
+        })
+    }
+
+    /// Returns the name of the version with the given [`VersionNeeded::index`] and the file name
+    /// of the shared object it is required from.
+    pub fn required_version(&self, index: u16) -> Option<(&'slice [u8], &'slice [u8])> {
+        self.version_requirements()?.find_map(|requirement| {
+            let needed = requirement.needed().find(|needed| needed.index == index)?;
+            Some((requirement.file()?, needed.name?))
+        })
+    }
+
+    /// Returns a [`VersionDefinitions`] over the version definition table located by the
+    /// [`ConstDynamicTag::VERDEF`] entry of this [`ElfFile`].
+    ///
+    /// The number of entries is limited by the [`ConstDynamicTag::VERDEF_COUNT`] entry, if
+    /// present. Names are read from the [`ElfFile::dynamic_string_table()`].
+    pub fn version_definitions(&self) -> Option<VersionDefinitions<'slice, E>> {
+        let dynamic = self.dynamic_table()?;
+        let bytes = self.virtual_address_data(dynamic.find(ConstDynamicTag::VERDEF)?)?;
+        let remaining = dynamic
+            .find(ConstDynamicTag::VERDEF_COUNT)
+            .map_or(Some(usize::MAX), |count| to_len(count).ok())?;
+
+        Some(VersionDefinitions {
+            bytes,
+            remaining,
+            strings: self.dynamic_string_table(),
+            encoding: self.encoding,
+        })
+    }
+
+    /// Returns the name of the version with the given [`VersionDefinition::index()`].
+    pub fn defined_version(&self, index: u16) -> Option<&'slice [u8]> {
+        self.version_definitions()?
+            .find(|definition| definition.index() == index)?
+            .name()
+    }
+}
+
+/// An entry of the GNU symbol version requirement table, naming a shared object that versioned
+/// symbols are required from.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct VersionRequirement<'slice, E> {
+    /// The bytes of the table, starting at this entry.
+    bytes: &'slice [u8],
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of this [`VersionRequirement`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> VersionRequirement<'slice, E> {
+    /// Returns the version of the structure of this entry, which is `1`.
+    pub fn version(&self) -> u16 {
+        self.encoding.parse_u16_at(0, self.bytes)
+    }
+
+    /// Returns the number of [`VersionNeeded`] entries following this entry.
+    pub fn count(&self) -> u16 {
+        self.encoding.parse_u16_at(2, self.bytes)
+    }
+
+    /// Returns the offset of the file name of the shared object in the string table.
+    pub fn file_offset(&self) -> u32 {
+        self.encoding.parse_u32_at(4, self.bytes)
+    }
+
+    /// Returns the file name of the shared object, as it appears in a
+    /// [`ConstDynamicTag::NEEDED`] entry, without the terminating null byte.
+    pub fn file(&self) -> Option<&'slice [u8]> {
+        self.strings?.get(to_len(self.file_offset()).ok()?).ok()
+    }
+
+    /// Returns an [`Iterator`] over the [`VersionNeeded`] entries of this entry.
+    pub fn needed(&self) -> NeededVersions<'slice, E> {
+        let offset = self.encoding.parse_u32_at(8, self.bytes);
+
+        NeededVersions {
+            bytes: to_len(offset)
+                .ok()
+                .and_then(|offset| self.bytes.get(offset..))
+                .unwrap_or(&[]),
+            remaining: self.count(),
+            strings: self.strings,
+            encoding: self.encoding,
+        }
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for VersionRequirement<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("VersionRequirement");
+
+        debug_struct.field("version", &self.version());
+        debug_struct.field("count", &self.count());
+        debug_struct.field("file_offset", &self.file_offset());
+
+        debug_struct.finish()
+    }
+}
+
+/// An [`Iterator`] over the [`VersionRequirement`]s of a version requirement table.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct VersionRequirements<'slice, E> {
+    /// The bytes of the table, starting at the next entry.
+    bytes: &'slice [u8],
+    /// The maximum number of entries remaining.
+    remaining: usize,
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of the table.
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> Iterator for VersionRequirements<'slice, E> {
+    type Item = VersionRequirement<'slice, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.bytes.len() < REQUIREMENT_SIZE {
+            return None;
+        }
+
+        let requirement = VersionRequirement {
+            bytes: self.bytes,
+            strings: self.strings,
+            encoding: self.encoding,
+        };
+
+        let next = self.encoding.parse_u32_at(12, self.bytes);
+        self.remaining = match next {
+            0 => 0,
+            _ => self.remaining - 1,
+        };
+        self.bytes = to_len(next)
+            .ok()
+            .and_then(|next| self.bytes.get(next..))
+            .unwrap_or(&[]);
+
+        Some(requirement)
+    }
+}
+
+impl<E: EncodingParse> FusedIterator for VersionRequirements<'_, E> {}
+
+/// A version required from the shared object named by a [`VersionRequirement`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct VersionNeeded<'slice> {
+    /// The ELF hash of the name of the version.
+    pub hash: u32,
+    /// The flags of the version, such as [`VersionNeeded::WEAK`].
+    pub flags: u16,
+    /// The index of the version, as held by the [`VersymTable`][versym] for the symbols bound to
+    /// it.
+    ///
+    /// [versym]: crate::versym::VersymTable
+    pub index: u16,
+    /// The name of the version, such as `GLIBC_2.34`, if it could be read.
+    pub name: Option<&'slice [u8]>,
+}
+
+impl VersionNeeded<'_> {
+    /// The flag marking a version whose absence is not an error.
+    pub const WEAK: u16 = 0x2;
+}
+
+/// An [`Iterator`] over the [`VersionNeeded`] entries of a [`VersionRequirement`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct NeededVersions<'slice, E> {
+    /// The bytes of the table, starting at the next entry.
+    bytes: &'slice [u8],
+    /// The maximum number of entries remaining.
+    remaining: u16,
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of the table.
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> Iterator for NeededVersions<'slice, E> {
+    type Item = VersionNeeded<'slice>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.bytes.len() < NEEDED_SIZE {
+            return None;
+        }
+
+        let name = self.encoding.parse_u32_at(8, self.bytes);
+        let needed = VersionNeeded {
+            hash: self.encoding.parse_u32_at(0, self.bytes),
+            flags: self.encoding.parse_u16_at(4, self.bytes),
+            index: self.encoding.parse_u16_at(6, self.bytes),
+            name: self
+                .strings
+                .zip(to_len(name).ok())
+                .and_then(|(strings, name)| strings.get(name).ok()),
+        };
+
+        let next = self.encoding.parse_u32_at(12, self.bytes);
+        self.remaining = match next {
+            0 => 0,
+            _ => self.remaining - 1,
+        };
+        self.bytes = to_len(next)
+            .ok()
+            .and_then(|next| self.bytes.get(next..))
+            .unwrap_or(&[]);
+
+        Some(needed)
+    }
+}
+
+impl<E: EncodingParse> FusedIterator for NeededVersions<'_, E> {}
+
+/// An entry of the GNU symbol version definition table, defining a version of the symbols of a
+/// file.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct VersionDefinition<'slice, E> {
+    /// The bytes of the table, starting at this entry.
+    bytes: &'slice [u8],
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of this [`VersionDefinition`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> VersionDefinition<'slice, E> {
+    /// The flag marking the definition of the file itself, named after its shared object name.
+    pub const BASE: u16 = 0x1;
+    /// The flag marking a weak version.
+    pub const WEAK: u16 = 0x2;
+
+    /// Returns the version of the structure of this entry, which is `1`.
+    pub fn version(&self) -> u16 {
+        self.encoding.parse_u16_at(0, self.bytes)
+    }
+
+    /// Returns the flags of this entry, such as [`VersionDefinition::BASE`].
+    pub fn flags(&self) -> u16 {
+        self.encoding.parse_u16_at(2, self.bytes)
+    }
+
+    /// Returns the index of the version, as held by the [`VersymTable`][versym] for the symbols
+    /// bound to it.
+    ///
+    /// [versym]: crate::versym::VersymTable
+    pub fn index(&self) -> u16 {
+        self.encoding.parse_u16_at(4, self.bytes)
+    }
+
+    /// Returns the number of names of this entry, which is one more than its number of parents.
+    pub fn count(&self) -> u16 {
+        self.encoding.parse_u16_at(6, self.bytes)
+    }
+
+    /// Returns the ELF hash of the name of the version.
+    pub fn hash(&self) -> u32 {
+        self.encoding.parse_u32_at(8, self.bytes)
+    }
+
+    /// Returns the name of the version, such as `GLIBC_2.34`, without the terminating null byte.
+    pub fn name(&self) -> Option<&'slice [u8]> {
+        self.names().next()?
+    }
+
+    /// Returns an [`Iterator`] over the names of the versions this version inherits from.
+    pub fn parents(&self) -> DefinitionNames<'slice, E> {
+        let mut names = self.names();
+        names.next();
+        names
+    }
+
+    /// Returns an [`Iterator`] over the name of this version followed by those of its parents.
+    fn names(&self) -> DefinitionNames<'slice, E> {
+        let offset = self.encoding.parse_u32_at(12, self.bytes);
+
+        DefinitionNames {
+            bytes: to_len(offset)
+                .ok()
+                .and_then(|offset| self.bytes.get(offset..))
+                .unwrap_or(&[]),
+            remaining: self.count(),
+            strings: self.strings,
+            encoding: self.encoding,
+        }
+    }
+}
+
+impl<E: EncodingParse> fmt::Debug for VersionDefinition<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug_struct = f.debug_struct("VersionDefinition");
+
+        debug_struct.field("version", &self.version());
+        debug_struct.field("flags", &self.flags());
+        debug_struct.field("index", &self.index());
+        debug_struct.field("count", &self.count());
+        debug_struct.field("hash", &self.hash());
+
+        debug_struct.finish()
+    }
+}
+
+/// An [`Iterator`] over the [`VersionDefinition`]s of a version definition table.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct VersionDefinitions<'slice, E> {
+    /// The bytes of the table, starting at the next entry.
+    bytes: &'slice [u8],
+    /// The maximum number of entries remaining.
+    remaining: usize,
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of the table.
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> Iterator for VersionDefinitions<'slice, E> {
+    type Item = VersionDefinition<'slice, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.bytes.len() < DEFINITION_SIZE {
+            return None;
+        }
+
+        let definition = VersionDefinition {
+            bytes: self.bytes,
+            strings: self.strings,
+            encoding: self.encoding,
+        };
+
+        let next = self.encoding.parse_u32_at(16, self.bytes);
+        self.remaining = match next {
+            0 => 0,
+            _ => self.remaining - 1,
+        };
+        self.bytes = to_len(next)
+            .ok()
+            .and_then(|next| self.bytes.get(next..))
+            .unwrap_or(&[]);
+
+        Some(definition)
+    }
+}
+
+impl<E: EncodingParse> FusedIterator for VersionDefinitions<'_, E> {}
+
+/// An [`Iterator`] over the names of a [`VersionDefinition`], yielding `None` for names that
+/// could not be read.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct DefinitionNames<'slice, E> {
+    /// The bytes of the table, starting at the next entry.
+    bytes: &'slice [u8],
+    /// The maximum number of entries remaining.
+    remaining: u16,
+    /// The [`StringTable`] holding the names.
+    strings: Option<StringTable<'slice>>,
+    /// The [`EncodingParse`] of the table.
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> Iterator for DefinitionNames<'slice, E> {
+    type Item = Option<&'slice [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 || self.bytes.len() < DEFINITION_NAME_SIZE {
+            return None;
+        }
+
+        let name = self.encoding.parse_u32_at(0, self.bytes);
+        let name = self
+            .strings
+            .zip(to_len(name).ok())
+            .and_then(|(strings, name)| strings.get(name).ok());
+
+        let next = self.encoding.parse_u32_at(4, self.bytes);
+        self.remaining = match next {
+            0 => 0,
+            _ => self.remaining - 1,
+        };
+        self.bytes = to_len(next)
+            .ok()
+            .and_then(|next| self.bytes.get(next..))
+            .unwrap_or(&[]);
+
+        Some(name)
+    }
+}
+
+impl<E: EncodingParse> FusedIterator for DefinitionNames<'_, E> {}
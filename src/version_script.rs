@@ -0,0 +1,186 @@
+//! Definitions for checking linker version scripts against the dynamic symbol table.
+//!
+//! A version script assigns the symbols exported by a shared object to versions, or hides them,
+//! using the glob patterns understood by the linker: `*` matches any sequence of bytes, `?`
+//! matches a single byte, and `[...]` matches a single byte from a set, which is negated by a
+//! leading `!` or `^` and may contain ranges such as `a-z`. A backslash matches the following
+//! byte literally.
+//!
+//! Quoted names in a version script are matched literally rather than as patterns, and patterns
+//! in `extern "C++"` blocks are matched against demangled names, so neither is handled here.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    symbol::{self, Symbol, SymbolBinding},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`GlobMatches`] over the symbols exported by the
+    /// [`ElfFile::dynamic_symbol_table()`] of this [`ElfFile`] whose names match any of
+    /// `patterns`.
+    ///
+    /// Calling this with a single pattern reports the symbols that pattern captures.
+    pub fn version_script_matches<'patterns>(
+        &self,
+        patterns: &'patterns [&'patterns [u8]],
+    ) -> Option<GlobMatches<'slice, 'patterns, C, E>> {
+        let symbols = self.dynamic_symbol_table()?.into_iter();
+
+        Some(GlobMatches {
+            symbols,
+            next: 0,
+            patterns,
+            matching: true,
+        })
+    }
+
+    /// Returns a [`GlobMatches`] over the symbols exported by the
+    /// [`ElfFile::dynamic_symbol_table()`] of this [`ElfFile`] whose names match none of
+    /// `patterns`.
+    ///
+    /// These are the symbols a version script listing `patterns` fails to account for.
+    pub fn version_script_unmatched<'patterns>(
+        &self,
+        patterns: &'patterns [&'patterns [u8]],
+    ) -> Option<GlobMatches<'slice, 'patterns, C, E>> {
+        let symbols = self.dynamic_symbol_table()?.into_iter();
+
+        Some(GlobMatches {
+            symbols,
+            next: 0,
+            patterns,
+            matching: false,
+        })
+    }
+}
+
+/// Returns `true` if `name` matches the version script glob `pattern`.
+pub fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    let mut pattern_index = 0;
+    let mut name_index = 0;
+    // The position after the last `*` and the byte of `name` it is currently matched up to.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while name_index < name.len() {
+        let step = match pattern.get(pattern_index) {
+            Some(b'*') => {
+                pattern_index += 1;
+                backtrack = Some((pattern_index, name_index));
+                continue;
+            }
+            Some(b'?') => Some(pattern_index + 1),
+            Some(b'[') => match_class(pattern, pattern_index, name[name_index]),
+            Some(b'\\') if pattern_index + 1 < pattern.len() => {
+                (pattern[pattern_index + 1] == name[name_index]).then_some(pattern_index + 2)
+            }
+            Some(&byte) => (byte == name[name_index]).then_some(pattern_index + 1),
+            None => None,
+        };
+
+        match (step, backtrack) {
+            (Some(next), _) => {
+                pattern_index = next;
+                name_index += 1;
+            }
+            (None, Some((star_pattern, star_name))) => {
+                pattern_index = star_pattern;
+                name_index = star_name + 1;
+                backtrack = Some((star_pattern, star_name + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+
+    pattern[pattern_index..].iter().all(|&byte| byte == b'*')
+}
+
+/// Matches `byte` against the bracketed class starting at `start` in `pattern`, returning the
+/// index following the class if it matches.
+///
+/// An unterminated class matches a literal `[`.
+fn match_class(pattern: &[u8], start: usize, byte: u8) -> Option<usize> {
+    let mut index = start + 1;
+    let negated = matches!(pattern.get(index), Some(b'!' | b'^'));
+    if negated {
+        index += 1;
+    }
+
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let low = match pattern.get(index) {
+            None => return (byte == b'[').then_some(start + 1),
+            Some(b']') if !first => break,
+            Some(b'\\') if index + 1 < pattern.len() => {
+                index += 1;
+                pattern[index]
+            }
+            Some(&low) => low,
+        };
+        first = false;
+        index += 1;
+
+        let high = match (pattern.get(index), pattern.get(index + 1)) {
+            (Some(b'-'), Some(&high)) if high != b']' => {
+                index += 2;
+                high
+            }
+            _ => low,
+        };
+        matched |= low <= byte && byte <= high;
+    }
+
+    (matched != negated).then_some(index + 1)
+}
+
+/// An [`Iterator`] over the exported [`Symbol`]s of a [`SymbolTable`][table] whose names match,
+/// or fail to match, a set of version script glob patterns, along with their indices.
+///
+/// A symbol is exported if it is defined, named and not [`SymbolBinding::LOCAL`].
+///
+/// [table]: crate::symbol::SymbolTable
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct GlobMatches<'slice, 'patterns, C, E> {
+    /// The symbols that have not yet been visited.
+    symbols: symbol::IntoIter<'slice, C, E>,
+    /// The index of the next symbol.
+    next: usize,
+    /// The patterns to match names against.
+    patterns: &'patterns [&'patterns [u8]],
+    /// Whether symbols matching any pattern are yielded, rather than those matching none.
+    matching: bool,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for GlobMatches<'slice, '_, C, E> {
+    type Item = (usize, Symbol<'slice, C, E>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for symbol in self.symbols.by_ref() {
+            let index = self.next;
+            self.next += 1;
+
+            if !symbol.is_defined() || symbol.binding() == SymbolBinding::LOCAL {
+                continue;
+            }
+            let Some(name) = symbol.name().filter(|name| !name.is_empty()) else {
+                continue;
+            };
+
+            let matched = self
+                .patterns
+                .iter()
+                .any(|pattern| glob_match(pattern, name));
+            if matched == self.matching {
+                return Some((index, symbol));
+            }
+        }
+
+        None
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for GlobMatches<'_, '_, C, E> {}
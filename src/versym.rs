@@ -0,0 +1,151 @@
+//! Definitions for the GNU symbol version table (`.gnu.version`).
+
+use core::{fmt, iter::FusedIterator};
+
+use crate::{class::ClassParse, dynamic::ConstDynamicTag, encoding::EncodingParse, ElfFile};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns the [`VersymTable`] located by the [`ConstDynamicTag::VERSYM`] entry of this
+    /// [`ElfFile`], holding `count` entries.
+    ///
+    /// The symbol version table has one entry per dynamic symbol, so `count` should be the number
+    /// of entries in the dynamic symbol table.
+    pub fn versym_table(&self, count: usize) -> Option<VersymTable<'slice, E>> {
+        let address = self.dynamic_table()?.find(ConstDynamicTag::VERSYM)?;
+        let data = self.virtual_address_data(address)?;
+
+        VersymTable::new(self.encoding, data, count)
+    }
+}
+
+/// An entry of the GNU symbol version table.
+#[repr(transparent)]
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Versym(pub u16);
+
+impl Versym {
+    /// The symbol is local and unversioned.
+    pub const LOCAL: Self = Self(0);
+    /// The symbol is global and has the base version.
+    pub const GLOBAL: Self = Self(1);
+
+    /// The bit marking the symbol as hidden.
+    pub const HIDDEN: u16 = 0x8000;
+
+    /// Returns `true` if the symbol is hidden, meaning it cannot be bound to by references
+    /// that do not request its version explicitly.
+    pub const fn is_hidden(self) -> bool {
+        self.0 & Self::HIDDEN != 0
+    }
+
+    /// Returns the version index of the symbol, without the [`Versym::HIDDEN`] bit.
+    pub const fn index(self) -> u16 {
+        self.0 & !Self::HIDDEN
+    }
+}
+
+impl fmt::Debug for Versym {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::LOCAL => f.pad("Local"),
+            Self::GLOBAL => f.pad("Global"),
+            versym => f
+                .debug_struct("Versym")
+                .field("index", &versym.index())
+                .field("hidden", &versym.is_hidden())
+                .finish(),
+        }
+    }
+}
+
+/// A table of [`Versym`] entries.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct VersymTable<'slice, E> {
+    /// The underlying bytes of this [`VersymTable`].
+    bytes: &'slice [u8],
+    /// The number of [`Versym`] entries in this [`VersymTable`].
+    count: usize,
+    /// The [`EncodingParse`] of this [`VersymTable`].
+    encoding: E,
+}
+
+impl<'slice, E: EncodingParse> VersymTable<'slice, E> {
+    /// Creates a new [`VersymTable`] from the given `slice`.
+    pub fn new(encoding: E, slice: &'slice [u8], count: usize) -> Option<Self> {
+        if count
+            .checked_mul(2)
+            .is_none_or(|total_size| slice.len() < total_size)
+        {
+            return None;
+        }
+
+        let table = Self {
+            bytes: slice,
+            count,
+            encoding,
+        };
+
+        Some(table)
+    }
+
+    /// Returns the [`Versym`] entry located at `index`.
+    pub fn get(&self, index: usize) -> Option<Versym> {
+        if index >= self.count {
+            return None;
+        }
+
+        Some(Versym(self.encoding.parse_u16_at(index * 2, self.bytes)))
+    }
+
+    /// Returns the number of [`Versym`] entries in this [`VersymTable`].
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Limits this [`VersymTable`] to at most `max_entries` [`Versym`] entries.
+    pub fn with_max_entries(mut self, max_entries: usize) -> Self {
+        self.count = self.count.min(max_entries);
+        self
+    }
+}
+
+impl<'slice, E: EncodingParse> IntoIterator for VersymTable<'slice, E> {
+    type Item = Versym;
+    type IntoIter = VersymIntoIter<'slice, E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        VersymIntoIter {
+            table: self,
+            next: 0,
+        }
+    }
+}
+
+/// An [`Iterator`] over the [`Versym`] entries in a [`VersymTable`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct VersymIntoIter<'slice, E> {
+    /// The table to iterate over.
+    table: VersymTable<'slice, E>,
+    /// The index in the [`VersymTable`].
+    next: usize,
+}
+
+impl<E: EncodingParse> Iterator for VersymIntoIter<'_, E> {
+    type Item = Versym;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.table.get(self.next)?;
+
+        self.next += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.table.count - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<E: EncodingParse> ExactSizeIterator for VersymIntoIter<'_, E> {}
+
+impl<E: EncodingParse> FusedIterator for VersymIntoIter<'_, E> {}
@@ -0,0 +1,118 @@
+//! Crate-private helpers for reading and writing class and encoding aware values in a buffer.
+
+use crate::ident::{Class, ElfIdent, Encoding};
+
+/// The byte order and width of the values stored in an ELF file.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct ByteLayout {
+    /// Whether values are stored in big-endian byte order.
+    pub(crate) big_endian: bool,
+    /// Whether class sized values are 64 bits wide.
+    pub(crate) wide: bool,
+}
+
+impl ByteLayout {
+    /// Returns the [`ByteLayout`] described by `ident`.
+    pub(crate) fn new(ident: ElfIdent<'_>) -> Self {
+        Self {
+            big_endian: ident.encoding() == Encoding::MSB2,
+            wide: ident.class() == Class::CLASS64,
+        }
+    }
+
+    /// Returns the size of a class sized value.
+    pub(crate) fn word_size(self) -> usize {
+        if self.wide {
+            8
+        } else {
+            4
+        }
+    }
+
+    /// Returns `true` if `value` can be stored as a class sized value.
+    pub(crate) fn fits_word(self, value: u64) -> bool {
+        self.wide || u32::try_from(value).is_ok()
+    }
+
+    /// Reads the value of `size` bytes at `offset` in `bytes`.
+    pub(crate) fn read(self, bytes: &[u8], offset: usize, size: usize) -> u64 {
+        let bytes = &bytes[offset..offset + size];
+        let fold = |value: u64, &byte: &u8| (value << 8) | u64::from(byte);
+        if self.big_endian {
+            bytes.iter().fold(0, fold)
+        } else {
+            bytes.iter().rev().fold(0, fold)
+        }
+    }
+
+    /// Reads the [`u32`] at `offset` in `bytes`.
+    pub(crate) fn read_u32(self, bytes: &[u8], offset: usize) -> u32 {
+        self.read(bytes, offset, 4) as u32
+    }
+
+    /// Reads the class sized value at `offset` in `bytes`.
+    pub(crate) fn read_word(self, bytes: &[u8], offset: usize) -> u64 {
+        self.read(bytes, offset, self.word_size())
+    }
+
+    /// Writes `value` at `offset` in `bytes` as a value of `size` bytes, truncating it.
+    pub(crate) fn write(self, bytes: &mut [u8], offset: usize, size: usize, value: u64) {
+        let encoded = if self.big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        };
+        let encoded = if self.big_endian {
+            &encoded[8 - size..]
+        } else {
+            &encoded[..size]
+        };
+        bytes[offset..offset + size].copy_from_slice(encoded);
+    }
+
+    /// Writes `value` at `offset` in `bytes`.
+    pub(crate) fn write_u16(self, bytes: &mut [u8], offset: usize, value: u16) {
+        self.write(bytes, offset, 2, u64::from(value));
+    }
+
+    /// Writes `value` at `offset` in `bytes`.
+    pub(crate) fn write_u32(self, bytes: &mut [u8], offset: usize, value: u32) {
+        self.write(bytes, offset, 4, u64::from(value));
+    }
+
+    /// Writes `value` at `offset` in `bytes` as a class sized value, truncating it if the class
+    /// is 32-bit.
+    pub(crate) fn write_word(self, bytes: &mut [u8], offset: usize, value: u64) {
+        self.write(bytes, offset, self.word_size(), value);
+    }
+}
+
+/// Helper for writing class and encoding aware values into a buffer.
+pub(crate) struct Writer<'buffer> {
+    /// The buffer being written.
+    pub(crate) buffer: &'buffer mut [u8],
+    /// The [`ByteLayout`] of the values written.
+    pub(crate) layout: ByteLayout,
+}
+
+impl Writer<'_> {
+    /// Returns the size of a class sized value.
+    pub(crate) fn word_size(&self) -> usize {
+        self.layout.word_size()
+    }
+
+    /// Writes `value` at `offset`.
+    pub(crate) fn u16(&mut self, offset: usize, value: u16) {
+        self.layout.write_u16(self.buffer, offset, value);
+    }
+
+    /// Writes `value` at `offset`.
+    pub(crate) fn u32(&mut self, offset: usize, value: u32) {
+        self.layout.write_u32(self.buffer, offset, value);
+    }
+
+    /// Writes `value` at `offset` as a class sized value, truncating it if the class is 32-bit.
+    pub(crate) fn word(&mut self, offset: usize, value: u64) {
+        self.layout.write_word(self.buffer, offset, value);
+    }
+}
@@ -0,0 +1,185 @@
+//! Definitions for cross-referencing the relocations, symbols and sections of an ELF file.
+//!
+//! Each relocation of an ELF file is an edge from the section it applies to, through the symbol
+//! it refers to, to the section defining that symbol. [`ElfFile::cross_references()`] yields
+//! these edges one at a time without allocating, so that tools measuring which sections keep
+//! which others alive can build whatever graph representation they need from them.
+
+use core::iter::FusedIterator;
+
+use crate::{
+    class::ClassParse,
+    encoding::EncodingParse,
+    relocation::{RelTable, RelaTable},
+    section::{Section, Sections},
+    symbol::{Symbol, SymbolTable},
+    ElfFile,
+};
+
+impl<'slice, C: ClassParse, E: EncodingParse> ElfFile<'slice, C, E> {
+    /// Returns a [`CrossReferences`] over the [`CrossReference`]s formed by the relocations of
+    /// every [`SectionType::REL`][rel] and [`SectionType::RELA`][rela] section of this
+    /// [`ElfFile`].
+    ///
+    /// Relocation sections located out of bounds are skipped.
+    ///
+    /// [rel]: crate::section_header::SectionType::REL
+    /// [rela]: crate::section_header::SectionType::RELA
+    pub fn cross_references(&self) -> CrossReferences<'slice, C, E> {
+        CrossReferences {
+            file: *self,
+            sections: self.sections(),
+            next_section: 0,
+            current: None,
+        }
+    }
+}
+
+/// An edge from the section a relocation applies to, through the symbol it refers to, to the
+/// section defining that symbol.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct CrossReference<'slice, C: ClassParse, E: EncodingParse> {
+    /// The index of the relocation section holding the relocation.
+    pub relocation_section: u16,
+    /// The index of the relocation within its relocation section.
+    pub relocation_index: usize,
+    /// The index of the section the relocation applies to, or `None` if the relocation applies
+    /// to virtual addresses, as dynamic relocations do.
+    pub source_section: Option<u32>,
+    /// The offset or virtual address at which the relocation applies.
+    pub offset: u64,
+    /// The processor specific type of the relocation.
+    pub relocation_type: u32,
+    /// The index of the symbol the relocation refers to, zero if it refers to none.
+    pub symbol_index: u32,
+    /// The [`Symbol`] the relocation refers to, or `None` if it refers to none or the symbol
+    /// table is missing.
+    pub symbol: Option<Symbol<'slice, C, E>>,
+    /// The index of the section defining the [`Symbol`], or `None` if the [`Symbol`] is
+    /// undefined, absolute or common.
+    pub target_section: Option<u32>,
+}
+
+/// The relocations of a relocation section.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+enum Relocations<'slice, C: ClassParse, E: EncodingParse> {
+    /// The relocations of a [`SectionType::REL`][rel] section.
+    ///
+    /// [rel]: crate::section_header::SectionType::REL
+    Rel(RelTable<'slice, C, E>),
+    /// The relocations of a [`SectionType::RELA`][rela] section.
+    ///
+    /// [rela]: crate::section_header::SectionType::RELA
+    Rela(RelaTable<'slice, C, E>),
+}
+
+impl<C: ClassParse, E: EncodingParse> Relocations<'_, C, E> {
+    /// Returns the offset and information of the relocation at `index`.
+    fn get(&self, index: usize) -> Option<(C::ClassUsize, C::ClassUsize)> {
+        match self {
+            Self::Rel(table) => table.get(index).map(|rel| (rel.offset, rel.info)),
+            Self::Rela(table) => table.get(index).map(|rela| (rela.offset, rela.info)),
+        }
+    }
+}
+
+/// The relocation section whose relocations a [`CrossReferences`] is visiting.
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+struct CurrentSection<'slice, C: ClassParse, E: EncodingParse> {
+    /// The index of the relocation section.
+    index: u16,
+    /// The index of the section the relocations apply to.
+    source_section: Option<u32>,
+    /// The relocations of the relocation section.
+    relocations: Relocations<'slice, C, E>,
+    /// The [`SymbolTable`] the relocations refer to.
+    symbols: Option<SymbolTable<'slice, C, E>>,
+    /// The index of the next relocation.
+    next: usize,
+}
+
+/// An [`Iterator`] over the [`CrossReference`]s of an [`ElfFile`].
+#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+pub struct CrossReferences<'slice, C: ClassParse, E: EncodingParse> {
+    /// The [`ElfFile`] being cross-referenced.
+    file: ElfFile<'slice, C, E>,
+    /// The [`Section`]s that have not yet been visited.
+    sections: Sections<'slice, C, E>,
+    /// The index of the next [`Section`].
+    next_section: u16,
+    /// The relocation section being visited.
+    current: Option<CurrentSection<'slice, C, E>>,
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> CrossReferences<'slice, C, E> {
+    /// Returns the next relocation section, or `None` if there are no more.
+    fn next_relocation_section(&mut self) -> Option<CurrentSection<'slice, C, E>> {
+        loop {
+            let section = self.sections.next()?;
+            let index = self.next_section;
+            self.next_section = self.next_section.wrapping_add(1);
+
+            let section_header = section.section_header();
+            let relocations = match section {
+                Section::Rel(section) => section.table().map(Relocations::Rel),
+                Section::Rela(section) => section.table().map(Relocations::Rela),
+                _ => continue,
+            };
+            let Some(relocations) = relocations else {
+                continue;
+            };
+            let symbols = u16::try_from(section_header.link())
+                .ok()
+                .filter(|&link| link != 0)
+                .and_then(|link| self.file.section_header_table()?.get(link))
+                .and_then(|symbols| self.file.symbol_table_from_section(symbols));
+
+            return Some(CurrentSection {
+                index,
+                source_section: Some(section_header.info()).filter(|&info| info != 0),
+                relocations,
+                symbols,
+                next: 0,
+            });
+        }
+    }
+}
+
+impl<'slice, C: ClassParse, E: EncodingParse> Iterator for CrossReferences<'slice, C, E> {
+    type Item = CrossReference<'slice, C, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.current.is_none() {
+                self.current = Some(self.next_relocation_section()?);
+            }
+            let current = self.current.as_mut()?;
+
+            let Some((offset, info)) = current.relocations.get(current.next) else {
+                self.current = None;
+                continue;
+            };
+            let relocation_index = current.next;
+            current.next += 1;
+
+            let symbol_index = self.file.class.symbol_raw(info);
+            let symbol = current
+                .symbols
+                .filter(|_| symbol_index != 0)
+                .and_then(|symbols| symbols.get(usize::try_from(symbol_index).ok()?));
+
+            return Some(CrossReference {
+                relocation_section: current.index,
+                relocation_index,
+                source_section: current.source_section,
+                offset: offset.into(),
+                relocation_type: self.file.class.relocation_type_raw(info),
+                symbol_index,
+                symbol,
+                target_section: symbol.and_then(|symbol| symbol.section_header_index()),
+            });
+        }
+    }
+}
+
+impl<C: ClassParse, E: EncodingParse> FusedIterator for CrossReferences<'_, C, E> {}